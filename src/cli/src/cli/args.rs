@@ -1,4 +1,4 @@
-use clap::{Parser, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 const LONG_VERSION: &str = concat!(
     env!("VERSION"),
@@ -21,7 +21,11 @@ pub enum ReportFormat {
 #[command(about = "HTTP File Runner - Execute HTTP requests from .http files", long_about = None)]
 #[command(version = LONG_VERSION)]
 #[command(long_version = LONG_VERSION)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// One or more .http files to process
     #[arg(
         value_name = "FILE",
@@ -41,6 +45,42 @@ pub struct Cli {
     #[arg(long, value_name = "ENVIRONMENT")]
     pub env: Option<String>,
 
+    /// Set a variable, overriding the environment, --vars-file and @name=value (repeatable)
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_variable)]
+    pub var: Vec<(String, String)>,
+
+    /// Load variables from a JSON object, overriding the environment and @name=value
+    #[arg(long, value_name = "FILE")]
+    pub vars_file: Option<String>,
+
+    /// Print the resolved variables of each file and where they come from, then exit
+    #[arg(long)]
+    pub print_variables: bool,
+
+    /// Print each request with its variables and functions resolved, without sending it
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Fail requests that use an undefined {{variable}} instead of sending them
+    #[arg(long)]
+    pub strict_variables: bool,
+
+    /// Command resolving {{$secret("name")}}, such as 'pass show {name}' (default: $HTTPRUNNER_SECRET_COMMAND)
+    #[arg(long, value_name = "COMMAND")]
+    pub secret_command: Option<String>,
+
+    /// Run only requests with a matching @tag and the requests they depend on (repeatable, globs allowed)
+    #[arg(long, value_name = "TAG", value_delimiter = ',')]
+    pub tag: Vec<String>,
+
+    /// Skip requests with a matching @tag unless a selected request depends on them (repeatable)
+    #[arg(long, value_name = "TAG", value_delimiter = ',')]
+    pub exclude_tag: Vec<String>,
+
+    /// Run only requests whose @name matches and the requests they depend on (repeatable, globs allowed)
+    #[arg(long, value_name = "NAME")]
+    pub name: Vec<String>,
+
     /// Allow insecure HTTPS connections (accept invalid certificates and hostnames)
     #[arg(long)]
     pub insecure: bool,
@@ -88,6 +128,90 @@ pub struct Cli {
     /// Stop immediately on the first failed request and show its full details
     #[arg(long)]
     pub fail_fast: bool,
+
+    /// Keep OAuth 2.0 tokens across runs in the user's data directory
+    #[arg(long)]
+    pub persist_tokens: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Encrypt, decrypt and rotate values in environment files
+    #[command(subcommand)]
+    Env(EnvCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EnvCommand {
+    /// Encrypt values of an environment file (default: those with secret-looking names)
+    Encrypt(EnvValueArgs),
+    /// Decrypt values of an environment file (default: all encrypted values)
+    Decrypt(EnvValueArgs),
+    /// Encrypt every encrypted value of an environment file again with a new key
+    Rotate(RotateArgs),
+    /// Write a new random key file
+    GenerateKey {
+        /// Where to write the key
+        #[arg(value_name = "KEY_FILE", default_value = "http-client.env.key")]
+        path: String,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct EnvValueArgs {
+    /// The environment file to update
+    #[arg(value_name = "ENV_FILE")]
+    pub file: String,
+
+    /// Only update values of this environment
+    #[arg(long = "env", value_name = "ENVIRONMENT")]
+    pub environment: Option<String>,
+
+    /// Only update this variable (repeatable)
+    #[arg(long = "var", value_name = "NAME")]
+    pub names: Vec<String>,
+
+    #[command(flatten)]
+    pub key: KeyArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct RotateArgs {
+    /// The environment file to update
+    #[arg(value_name = "ENV_FILE")]
+    pub file: String,
+
+    #[command(flatten)]
+    pub key: KeyArgs,
+
+    /// Key file holding the new key, created when it does not exist
+    #[arg(
+        long,
+        value_name = "PATH",
+        required_unless_present = "new_passphrase_env"
+    )]
+    pub new_key_file: Option<String>,
+
+    /// Environment variable holding the passphrase to derive the new key from
+    #[arg(long, value_name = "VARIABLE", conflicts_with = "new_key_file")]
+    pub new_passphrase_env: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct KeyArgs {
+    /// Key file to use (default: HTTPRUNNER_ENV_KEY_FILE, HTTPRUNNER_ENV_PASSPHRASE or http-client.env.key next to ENV_FILE)
+    #[arg(long, value_name = "PATH")]
+    pub key_file: Option<String>,
+}
+
+/// Parse a `--var` value of the form `name=value`.
+fn parse_variable(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected NAME=VALUE, got '{}'", value)),
+    }
 }
 
 impl Cli {
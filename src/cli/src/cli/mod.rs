@@ -1,7 +1,7 @@
 mod args;
 mod banner;
 
-pub use args::{Cli, ReportFormat};
+pub use args::{Cli, Command, EnvCommand, EnvValueArgs, KeyArgs, ReportFormat, RotateArgs};
 pub use banner::show_donation_banner;
 
 #[cfg(test)]
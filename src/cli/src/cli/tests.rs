@@ -2,10 +2,20 @@ use super::args::*;
 
 fn cli_with_log(log: Option<Option<&str>>) -> Cli {
     Cli {
+        command: None,
         files: vec![],
         verbose: false,
         log: log.map(|opt| opt.map(|s| s.to_string())),
         env: None,
+        var: vec![],
+        vars_file: None,
+        print_variables: false,
+        dry_run: false,
+        strict_variables: false,
+        secret_command: None,
+        tag: vec![],
+        exclude_tag: vec![],
+        name: vec![],
         insecure: false,
         discover: false,
         upgrade: false,
@@ -18,6 +28,7 @@ fn cli_with_log(log: Option<Option<&str>>) -> Cli {
         no_telemetry: false,
         delay: 0,
         fail_fast: false,
+        persist_tokens: false,
     }
 }
 
@@ -53,6 +64,91 @@ fn fail_fast_flag_parses_long_form() {
     assert!(cli.fail_fast);
 }
 
+#[test]
+fn persist_tokens_flag_parses_long_form() {
+    use clap::Parser;
+    let cli = Cli::try_parse_from(["httprunner", "test.http"]).unwrap();
+    assert!(!cli.persist_tokens);
+    let cli = Cli::try_parse_from(["httprunner", "--persist-tokens", "test.http"]).unwrap();
+    assert!(cli.persist_tokens);
+}
+
+#[test]
+fn strict_variables_flag_parses_long_form() {
+    use clap::Parser;
+    let cli = Cli::try_parse_from(["httprunner", "test.http"]).unwrap();
+    assert!(!cli.strict_variables);
+    let cli = Cli::try_parse_from(["httprunner", "--strict-variables", "test.http"]).unwrap();
+    assert!(cli.strict_variables);
+}
+
+#[test]
+fn secret_command_flag_takes_the_whole_command() {
+    use clap::Parser;
+    let cli = Cli::try_parse_from(["httprunner", "test.http"]).unwrap();
+    assert_eq!(cli.secret_command, None);
+    let cli = Cli::try_parse_from([
+        "httprunner",
+        "--secret-command",
+        "pass show {name}",
+        "test.http",
+    ])
+    .unwrap();
+    assert_eq!(cli.secret_command.as_deref(), Some("pass show {name}"));
+}
+
+#[test]
+fn var_flag_is_repeatable_and_keeps_equals_in_value() {
+    use clap::Parser;
+    let cli = Cli::try_parse_from([
+        "httprunner",
+        "--var",
+        "baseUrl=http://localhost:8081",
+        "--var",
+        "filter=a=b",
+        "--vars-file",
+        "vars.json",
+        "test.http",
+    ])
+    .unwrap();
+    assert_eq!(
+        cli.var,
+        vec![
+            ("baseUrl".to_string(), "http://localhost:8081".to_string()),
+            ("filter".to_string(), "a=b".to_string()),
+        ]
+    );
+    assert_eq!(cli.vars_file.as_deref(), Some("vars.json"));
+}
+
+#[test]
+fn selection_flags_are_repeatable_and_split_tags_on_commas() {
+    use clap::Parser;
+    let cli = Cli::try_parse_from([
+        "httprunner",
+        "--tag",
+        "smoke,orders",
+        "--exclude-tag",
+        "slow",
+        "--name",
+        "create_*",
+        "--name",
+        "login",
+        "test.http",
+    ])
+    .unwrap();
+    assert_eq!(cli.tag, vec!["smoke", "orders"]);
+    assert_eq!(cli.exclude_tag, vec!["slow"]);
+    assert_eq!(cli.name, vec!["create_*", "login"]);
+}
+
+#[test]
+fn var_flag_rejects_missing_name() {
+    use clap::Parser;
+    assert!(Cli::try_parse_from(["httprunner", "--var", "novalue", "test.http"]).is_err());
+    assert!(Cli::try_parse_from(["httprunner", "--var", "=value", "test.http"]).is_err());
+}
+
 #[test]
 fn show_donation_banner_outputs_message() {
     // This test simply ensures show_donation_banner runs without panic
@@ -61,3 +157,50 @@ fn show_donation_banner_outputs_message() {
     use super::banner::show_donation_banner;
     show_donation_banner();
 }
+
+#[test]
+fn env_encrypt_subcommand_parses_selection_and_key_file() {
+    use clap::Parser;
+    let cli = Cli::try_parse_from([
+        "httprunner",
+        "env",
+        "encrypt",
+        "http-client.env.json",
+        "--env",
+        "dev",
+        "--var",
+        "apiKey",
+        "--key-file",
+        "team.key",
+    ])
+    .unwrap();
+    let Some(Command::Env(EnvCommand::Encrypt(args))) = cli.command else {
+        panic!("expected env encrypt");
+    };
+    assert_eq!(args.file, "http-client.env.json");
+    assert_eq!(args.environment.as_deref(), Some("dev"));
+    assert_eq!(args.names, vec!["apiKey"]);
+    assert_eq!(args.key.key_file.as_deref(), Some("team.key"));
+}
+
+#[test]
+fn env_rotate_subcommand_requires_a_new_key() {
+    use clap::Parser;
+    assert!(Cli::try_parse_from(["httprunner", "env", "rotate", "http-client.env.json"]).is_err());
+    let cli = Cli::try_parse_from([
+        "httprunner",
+        "env",
+        "rotate",
+        "http-client.env.json",
+        "--new-passphrase-env",
+        "NEW_PASSPHRASE",
+    ])
+    .unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Command::Env(EnvCommand::Rotate(RotateArgs {
+            new_passphrase_env: Some(_),
+            ..
+        })))
+    ));
+}
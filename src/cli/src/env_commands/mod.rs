@@ -0,0 +1,103 @@
+use crate::cli::{EnvCommand, EnvValueArgs, KeyArgs, RotateArgs};
+use anyhow::{Context, Result, anyhow, bail};
+use httprunner_core::colors;
+use httprunner_core::environment::{
+    ENVIRONMENT_KEY_FILE, EnvironmentKey, KEY_FILE_ENV_VAR, PASSPHRASE_ENV_VAR, ValueSelection,
+    decrypt_environment_file, encrypt_environment_file, rotate_environment_file,
+};
+use std::path::Path;
+
+/// Run an `httprunner env` subcommand.
+pub fn run(command: &EnvCommand) -> Result<()> {
+    match command {
+        EnvCommand::Encrypt(args) => {
+            let key = load_key(&args.file, &args.key)?;
+            let count = encrypt_environment_file(Path::new(&args.file), &key, &selection(args))?;
+            report(count, "Encrypted", &args.file);
+        }
+        EnvCommand::Decrypt(args) => {
+            let key = load_key(&args.file, &args.key)?;
+            let count = decrypt_environment_file(Path::new(&args.file), &key, &selection(args))?;
+            report(count, "Decrypted", &args.file);
+        }
+        EnvCommand::Rotate(args) => {
+            let old_key = load_key(&args.file, &args.key)?;
+            let new_key = new_key(args)?;
+            let count = rotate_environment_file(Path::new(&args.file), &old_key, &new_key)?;
+            report(count, "Re-encrypted", &args.file);
+        }
+        EnvCommand::GenerateKey { path } => {
+            let path = Path::new(path);
+            if path.exists() {
+                bail!("Key file already exists: {}", path.display());
+            }
+            EnvironmentKey::generate().write_key_file(path)?;
+            println!(
+                "{} Wrote a new key to {}. Keep it out of version control.",
+                colors::green("✅"),
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn selection(args: &EnvValueArgs) -> ValueSelection {
+    ValueSelection {
+        environment: args.environment.clone(),
+        names: args.names.clone(),
+    }
+}
+
+/// The key from `--key-file`, or else the one the environment file would be
+/// decrypted with when running requests.
+fn load_key(env_file: &str, args: &KeyArgs) -> Result<EnvironmentKey> {
+    if let Some(key_file) = &args.key_file {
+        return EnvironmentKey::from_key_file(Path::new(key_file));
+    }
+    EnvironmentKey::resolve(Path::new(env_file).parent())?.ok_or_else(|| {
+        anyhow!(
+            "No key available: pass --key-file, set {} or {}, or create {} with `httprunner env generate-key`",
+            KEY_FILE_ENV_VAR,
+            PASSPHRASE_ENV_VAR,
+            ENVIRONMENT_KEY_FILE
+        )
+    })
+}
+
+fn new_key(args: &RotateArgs) -> Result<EnvironmentKey> {
+    if let Some(variable) = &args.new_passphrase_env {
+        let passphrase = std::env::var(variable)
+            .with_context(|| format!("Environment variable {} is not set", variable))?;
+        return Ok(EnvironmentKey::from_passphrase(&passphrase));
+    }
+
+    let path = Path::new(
+        args.new_key_file
+            .as_deref()
+            .ok_or_else(|| anyhow!("Pass --new-key-file or --new-passphrase-env"))?,
+    );
+    if path.exists() {
+        return EnvironmentKey::from_key_file(path);
+    }
+    let key = EnvironmentKey::generate();
+    key.write_key_file(path)?;
+    println!(
+        "{} Wrote the new key to {}",
+        colors::green("✅"),
+        path.display()
+    );
+    Ok(key)
+}
+
+fn report(count: usize, action: &str, file: &str) {
+    let noun = if count == 1 { "value" } else { "values" };
+    println!(
+        "{} {} {} {} in {}",
+        colors::green("✅"),
+        action,
+        count,
+        noun,
+        file
+    );
+}
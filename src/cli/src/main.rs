@@ -1,14 +1,20 @@
 mod cli;
+mod env_commands;
 mod shutdown;
 mod upgrade;
 
 use crate::cli::ReportFormat;
 use anyhow::{Result, anyhow};
 use clap::{CommandFactory, Parser};
+use httprunner_core::project::ProjectConfig;
 use httprunner_core::report::{generate_html_with_options, generate_markdown_with_options};
+use httprunner_core::selection::RequestFilter;
 use httprunner_core::telemetry::{self, AppType, CliArgPatterns};
-use httprunner_core::types::ProcessorResults;
-use httprunner_core::{colors, discovery, export, logging, processor};
+use httprunner_core::types::{ProcessorResults, SourcedVariable, VariableSource};
+use httprunner_core::{
+    colors, discovery, environment, export, logging, parser, processor, project,
+};
+use std::path::Path;
 
 const VERSION: &str = env!("VERSION");
 const INSTRUMENTATION_KEY: &str = "a7a07a35-4869-4fa2-b852-03f44b35f418";
@@ -17,6 +23,9 @@ fn main() -> anyhow::Result<()> {
     shutdown::enable_forceful_shutdown();
 
     let cli_args = cli::Cli::parse();
+    if let Some(cli::Command::Env(command)) = &cli_args.command {
+        return env_commands::run(command);
+    }
     if cli_args.files.is_empty() && !cli_args.discover && !cli_args.upgrade {
         let mut cmd = cli::Cli::command();
         cmd.print_help()?;
@@ -69,6 +78,15 @@ fn track_cli_usage(cli_args: &cli::Cli) {
         file_count: cli_args.files.len(),
         delay: cli_args.delay,
         fail_fast: cli_args.fail_fast,
+        persist_tokens: cli_args.persist_tokens,
+        var: !cli_args.var.is_empty(),
+        vars_file: cli_args.vars_file.is_some(),
+        strict_variables: cli_args.strict_variables,
+        secret_command: cli_args.secret_command.is_some(),
+        tag: !cli_args.tag.is_empty(),
+        exclude_tag: !cli_args.exclude_tag.is_empty(),
+        name: !cli_args.name.is_empty(),
+        dry_run: cli_args.dry_run,
     };
     telemetry::track_cli_args(&patterns);
 }
@@ -78,7 +96,14 @@ fn run(cli_args: &cli::Cli) -> Result<()> {
     if files.is_empty() {
         return Ok(());
     }
-    let results = process_http_files(cli_args, files)?;
+    let variables = load_variable_overrides(cli_args)?;
+    if cli_args.print_variables {
+        return print_variables(cli_args, &files, &variables);
+    }
+    if cli_args.dry_run {
+        return dry_run(cli_args, &files, &variables);
+    }
+    let results = process_http_files(cli_args, files, &variables)?;
     generate_report(cli_args, &results)?;
     export_results(cli_args, &results)?;
     export_json_results(cli_args, &results)?;
@@ -106,17 +131,86 @@ fn load_files(cli_args: &cli::Cli) -> Result<Vec<String>> {
     Ok(files)
 }
 
-fn process_http_files(cli_args: &cli::Cli, files: Vec<String>) -> Result<ProcessorResults> {
+/// Variables from `--vars-file` followed by those from `--var`, lowest
+/// precedence first.
+fn load_variable_overrides(cli_args: &cli::Cli) -> Result<Vec<SourcedVariable>> {
+    let mut variables = Vec::new();
+    if let Some(vars_file) = &cli_args.vars_file {
+        variables.extend(
+            environment::load_variables_file(Path::new(vars_file))?
+                .into_iter()
+                .map(|variable| SourcedVariable {
+                    name: variable.name,
+                    value: variable.value,
+                    source: VariableSource::VarsFile,
+                }),
+        );
+    }
+    variables.extend(cli_args.var.iter().map(|(name, value)| SourcedVariable {
+        name: name.clone(),
+        value: value.clone(),
+        source: VariableSource::CommandLine,
+    }));
+    Ok(variables)
+}
+
+fn print_variables(
+    cli_args: &cli::Cli,
+    files: &[String],
+    overrides: &[SourcedVariable],
+) -> Result<()> {
+    for file in files {
+        let variables = parser::resolve_file_variables(file, cli_args.env.as_deref(), overrides)?;
+        println!("{} Variables for {}:", colors::blue("📋"), file);
+        if variables.is_empty() {
+            println!("   (none)");
+        }
+        for variable in variables {
+            let value =
+                if !cli_args.include_secrets && environment::is_secret_variable(&variable.name) {
+                    "***REDACTED***"
+                } else {
+                    variable.value.as_str()
+                };
+            println!("   {} = {} ({})", variable.name, value, variable.source);
+        }
+    }
+    Ok(())
+}
+
+fn dry_run(cli_args: &cli::Cli, files: &[String], variables: &[SourcedVariable]) -> Result<()> {
     let log_filename = cli_args.get_log_filename();
-    let config = processor::ProcessorConfig::new(&files)
-        .with_verbose(cli_args.verbose)
-        .with_log_filename(log_filename.as_deref())
-        .with_environment(cli_args.env.as_deref())
-        .with_insecure(cli_args.insecure)
-        .with_pretty_json(cli_args.pretty_json)
-        .with_delay(cli_args.delay)
-        .with_include_secrets(cli_args.include_secrets)
-        .with_fail_fast(cli_args.fail_fast);
+    // Setup and teardown files for the whole run, from the files' `httprunner.json`.
+    let project = project::load_project_config_for_files(files)?.unwrap_or_default();
+    let filter = request_filter(cli_args);
+    let config = processor_config(
+        cli_args,
+        files,
+        variables,
+        log_filename.as_deref(),
+        &project,
+        &filter,
+    );
+    processor::dry_run_http_files(&config)
+}
+
+fn process_http_files(
+    cli_args: &cli::Cli,
+    files: Vec<String>,
+    variables: &[SourcedVariable],
+) -> Result<ProcessorResults> {
+    let log_filename = cli_args.get_log_filename();
+    // Setup and teardown files for the whole run, from the files' `httprunner.json`.
+    let project = project::load_project_config_for_files(&files)?.unwrap_or_default();
+    let filter = request_filter(cli_args);
+    let config = processor_config(
+        cli_args,
+        &files,
+        variables,
+        log_filename.as_deref(),
+        &project,
+        &filter,
+    );
 
     let results = processor::process_http_files(&config, &processor::default_executor)?;
 
@@ -134,6 +228,41 @@ fn process_http_files(cli_args: &cli::Cli, files: Vec<String>) -> Result<Process
     Ok(results)
 }
 
+/// Only the requests selected with `--tag`, `--exclude-tag` and `--name`.
+fn request_filter(cli_args: &cli::Cli) -> RequestFilter {
+    RequestFilter {
+        tags: cli_args.tag.clone(),
+        exclude_tags: cli_args.exclude_tag.clone(),
+        names: cli_args.name.clone(),
+    }
+}
+
+fn processor_config<'a>(
+    cli_args: &'a cli::Cli,
+    files: &'a [String],
+    variables: &'a [SourcedVariable],
+    log_filename: Option<&'a str>,
+    project: &'a ProjectConfig,
+    filter: &'a RequestFilter,
+) -> processor::ProcessorConfig<'a> {
+    processor::ProcessorConfig::new(files)
+        .with_verbose(cli_args.verbose)
+        .with_log_filename(log_filename)
+        .with_environment(cli_args.env.as_deref())
+        .with_variables(variables)
+        .with_insecure(cli_args.insecure)
+        .with_pretty_json(cli_args.pretty_json)
+        .with_delay(cli_args.delay)
+        .with_include_secrets(cli_args.include_secrets)
+        .with_fail_fast(cli_args.fail_fast)
+        .with_persist_tokens(cli_args.persist_tokens)
+        .with_strict_variables(cli_args.strict_variables)
+        .with_secret_command(cli_args.secret_command.as_deref())
+        .with_setup_files(&project.setup)
+        .with_teardown_files(&project.teardown)
+        .with_filter(Some(filter))
+}
+
 fn generate_report(cli_args: &cli::Cli, results: &ProcessorResults) -> Result<()> {
     if let Some(format) = cli_args.report {
         let result = match format {
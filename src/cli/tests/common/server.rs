@@ -13,9 +13,12 @@ use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
 
 type HttpResponse = Response<Cursor<Vec<u8>>>;
 
+const DIGEST_NONCE: &str = "dcd98b7102dd2f0e8b11d0f600bfb0c093";
+
 #[derive(Default)]
 struct ServerState {
     next_user_id: u64,
+    issued_tokens: u64,
     users: HashMap<String, Value>,
 }
 
@@ -157,6 +160,98 @@ fn route_request(
             ));
             response
         }
+        (&Method::Get, "/api/items") => {
+            let args = parse_query(query);
+            let page = args
+                .get("page")
+                .or_else(|| args.get("cursor"))
+                .and_then(Value::as_str)
+                .and_then(|page| page.trim_start_matches("page-").parse::<u32>().ok())
+                .unwrap_or(1);
+            let items: Vec<Value> = (1..=2)
+                .map(|n| json!({ "id": format!("item-{}", (page - 1) * 2 + n) }))
+                .collect();
+            let next_cursor = (page < 3).then(|| format!("page-{}", page + 1));
+
+            let mut response = json_response(
+                200,
+                json!({ "data": items, "page": page, "next_cursor": next_cursor }),
+            );
+            if page < 3 {
+                response.add_header(make_header(
+                    "Link",
+                    &format!("</api/items?page={}>; rel=\"next\"", page + 1),
+                ));
+            }
+            response
+        }
+        (_, _) if path.starts_with("/basic-auth/") => {
+            let credentials = path
+                .trim_start_matches("/basic-auth/")
+                .replacen('/', ":", 1);
+            let expected = format!("Basic {}", base64_encode(&credentials));
+            if header_value(headers, "authorization") == Some(expected.as_str()) {
+                json_response(200, json!({ "authenticated": true }))
+            } else {
+                json_response(401, json!({ "authenticated": false }))
+            }
+        }
+        (_, _) if path.starts_with("/digest-auth/") => {
+            let username = path.trim_start_matches("/digest-auth/");
+            let username = username.split('/').next().unwrap_or_default();
+            let answered = header_value(headers, "authorization").is_some_and(|value| {
+                value.starts_with("Digest ")
+                    && value.contains(&format!("username=\"{username}\""))
+                    && value.contains(&format!("nonce=\"{DIGEST_NONCE}\""))
+                    && value.contains(&format!("uri=\"{path}\""))
+                    && value.contains("response=\"")
+            });
+            if answered {
+                json_response(200, json!({ "authenticated": true }))
+            } else {
+                let mut response = json_response(401, json!({ "authenticated": false }));
+                response.add_header(make_header(
+                    "WWW-Authenticate",
+                    &format!("Digest realm=\"httprunner\", qop=\"auth\", nonce=\"{DIGEST_NONCE}\""),
+                ));
+                response
+            }
+        }
+        (_, _) if path.starts_with("/aws/") => {
+            let signed = header_value(headers, "authorization").is_some_and(|value| {
+                value.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/")
+                    && value.contains("/eu-west-1/s3/aws4_request")
+                    && value
+                        .contains("SignedHeaders=content-type;host;x-amz-content-sha256;x-amz-date")
+            }) && header_value(headers, "x-amz-date").is_some();
+            if signed {
+                json_response(
+                    200,
+                    json!({
+                        "signed": true,
+                        "content_sha256": header_value(headers, "x-amz-content-sha256"),
+                    }),
+                )
+            } else {
+                json_response(403, json!({ "signed": false }))
+            }
+        }
+        (_, "/signed") => json_response(
+            200,
+            json!({
+                "content_digest": header_value(headers, "content-digest"),
+                "x_signature": header_value(headers, "x-signature"),
+                "signature_input": header_value(headers, "signature-input"),
+                "signature": header_value(headers, "signature"),
+            }),
+        ),
+        (_, "/bearer") => match header_value(headers, "authorization") {
+            Some(value) if value.starts_with("Bearer ") => json_response(
+                200,
+                json!({ "authenticated": true, "token": value.trim_start_matches("Bearer ") }),
+            ),
+            _ => json_response(401, json!({ "authenticated": false })),
+        },
         (_, _) if path.starts_with("/status/") => {
             let status = path.trim_start_matches("/status/").parse().unwrap_or(500);
             json_response(status, json!({ "status": status }))
@@ -186,6 +281,26 @@ fn route_request(
                 }),
             )
         }
+        (&Method::Post, "/oauth/token") => {
+            let form = parse_query(Some(body));
+            let client_authenticated = header_value(headers, "authorization")
+                == Some(format!("Basic {}", base64_encode("httprunner:client-secret")).as_str());
+            if !client_authenticated || form.get("grant_type") != Some(&json!("client_credentials"))
+            {
+                return json_response(401, json!({ "error": "invalid_client" }));
+            }
+
+            let mut state = state.lock().expect("server state lock poisoned");
+            state.issued_tokens += 1;
+            json_response(
+                200,
+                json!({
+                    "access_token": format!("token-{}", state.issued_tokens),
+                    "token_type": "Bearer",
+                    "expires_in": 3600
+                }),
+            )
+        }
         (&Method::Post, "/api/users") => {
             let mut state = state.lock().expect("server state lock poisoned");
             state.next_user_id += 1;
@@ -229,6 +344,34 @@ fn read_headers(request: &Request) -> Map<String, Value> {
     headers
 }
 
+fn header_value<'a>(headers: &'a Map<String, Value>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .and_then(|(_, value)| value.as_str())
+}
+
+fn base64_encode(input: &str) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in input.as_bytes().chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
 fn read_body(request: &mut Request) -> String {
     let mut body = String::new();
     let _ = request.as_reader().read_to_string(&mut body);
@@ -29,6 +29,194 @@ fn env_fixture_uses_http_client_env_file() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn print_variables_shows_cli_overrides_and_their_sources() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let env_fixture = workspace.arg("env/env.local.http");
+    let vars_file = workspace.path("vars.json");
+    std::fs::write(
+        &vars_file,
+        r#"{"DefaultHost": "vars-file.test", "Environment": "from-vars-file"}"#,
+    )?;
+
+    command_in(workspace.root())
+        .args([
+            env_fixture.as_str(),
+            "--env",
+            "local",
+            "--vars-file",
+            vars_file.to_str().unwrap(),
+            "--var",
+            "Environment=from-cli",
+            "--print-variables",
+            "--no-banner",
+            "--no-telemetry",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "DefaultHost = vars-file.test (--vars-file)",
+        ))
+        .stdout(predicate::str::contains("Environment = from-cli (--var)"))
+        .stdout(predicate::str::contains(
+            "ApiKey = ***REDACTED*** (environment)",
+        ))
+        .stdout(predicate::str::contains("env-secret").not());
+
+    Ok(())
+}
+
+#[test]
+fn env_encrypt_stores_encrypted_values_that_requests_decrypt() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let env_file = workspace.arg("env/http-client.env.json");
+    let key_file = workspace.arg("env/http-client.env.key");
+
+    command_in(workspace.root())
+        .args(["env", "generate-key", key_file.as_str()])
+        .assert()
+        .success();
+    command_in(workspace.root())
+        .args(["env", "encrypt", env_file.as_str(), "--env", "local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Encrypted 1 value"));
+
+    let saved = workspace.read_path(&workspace.path("env/http-client.env.json"))?;
+    assert!(saved.contains("\"ApiKey\": \"enc:v1:"));
+    assert!(!saved.contains("env-secret"));
+
+    command_in(workspace.root())
+        .args([
+            workspace.arg("env/env.local.http").as_str(),
+            "--env",
+            "local",
+            "--no-banner",
+            "--no-telemetry",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All discovered files processed successfully",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn strict_variables_fails_request_with_undefined_variable() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let http_file = workspace.path("strict.http");
+    std::fs::write(&http_file, "GET {{baseUrl}}/users\n")?;
+
+    command_in(workspace.root())
+        .args([
+            http_file.to_str().unwrap(),
+            "--strict-variables",
+            "--no-banner",
+            "--no-telemetry",
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Substitution error: undefined variable 'baseUrl' at line 1",
+        ));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn secret_command_resolves_and_redacts_secrets() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let http_file = workspace.path("secrets.http");
+    std::fs::write(
+        &http_file,
+        format!(
+            "GET {}/headers\nX-Api-Key: {{{{$secret(\"api-key\")}}}}\n\nEXPECTED_RESPONSE_BODY \"resolved-api-key\"\n",
+            server.base_url()
+        ),
+    )?;
+
+    command_in(workspace.root())
+        .args([
+            http_file.to_str().unwrap(),
+            "--secret-command",
+            "echo resolved-{name}",
+            "--verbose",
+            "--no-banner",
+            "--no-telemetry",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("***REDACTED***"))
+        .stdout(predicate::str::contains("resolved-api-key").not());
+
+    Ok(())
+}
+
+#[test]
+fn project_file_is_found_from_the_http_files_directory() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let project = workspace.path("project");
+    std::fs::create_dir_all(project.join("api"))?;
+    std::fs::write(
+        project.join("httprunner.json"),
+        r#"{ "setup": ["seed.http"] }"#,
+    )?;
+    std::fs::write(
+        project.join("seed.http"),
+        format!("GET {}/zen\n", server.base_url()),
+    )?;
+    let http_file = project.join("api/health.http");
+    std::fs::write(&http_file, format!("GET {}/health\n", server.base_url()))?;
+
+    // Run from a directory outside the project.
+    let elsewhere = tempfile::tempdir()?;
+    command_in(elsewhere.path())
+        .args([http_file.to_str().unwrap(), "--no-banner", "--no-telemetry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "GET {}/zen",
+            server.base_url()
+        )))
+        .stdout(predicate::str::contains(format!(
+            "GET {}/health",
+            server.base_url()
+        )));
+
+    Ok(())
+}
+
+#[test]
+fn oauth_fixture_acquires_and_caches_token() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let oauth_fixture = workspace.arg("env/oauth.local.http");
+
+    command_in(workspace.root())
+        .args([
+            oauth_fixture.as_str(),
+            "--env",
+            "local",
+            "--no-banner",
+            "--no-telemetry",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All discovered files processed successfully",
+        ));
+
+    Ok(())
+}
+
 #[test]
 fn feature_fixture_covers_chaining_conditionals_and_delay() -> Result<()> {
     let server = TestServer::start()?;
@@ -61,3 +249,126 @@ fn feature_fixture_covers_chaining_conditionals_and_delay() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn data_fixtures_repeat_requests_per_dataset_row() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let users_fixture = workspace.arg("data/users.local.http");
+    let flow_fixture = workspace.arg("data/flow.local.http");
+
+    command_in(workspace.root())
+        .args([
+            users_fixture.as_str(),
+            flow_fixture.as_str(),
+            "--no-banner",
+            "--no-telemetry",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All discovered files processed successfully",
+        ))
+        .stdout(predicate::str::contains("create_user[3]"))
+        .stdout(predicate::str::contains("login[2]"));
+
+    Ok(())
+}
+
+#[test]
+fn foreach_fixture_loops_over_response_array() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let foreach_fixture = workspace.arg("examples/foreach.local.http");
+
+    command_in(workspace.root())
+        .args([foreach_fixture.as_str(), "--no-banner", "--no-telemetry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All discovered files processed successfully",
+        ))
+        .stdout(predicate::str::contains("get_item[2]"))
+        .stdout(predicate::str::contains("after_loop"));
+
+    Ok(())
+}
+
+#[test]
+fn paginate_fixture_follows_pages_and_aggregates_items() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let paginate_fixture = workspace.arg("examples/paginate.local.http");
+
+    command_in(workspace.root())
+        .args([paginate_fixture.as_str(), "--no-banner", "--no-telemetry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All discovered files processed successfully",
+        ))
+        .stdout(predicate::str::contains("items[3]"))
+        .stdout(predicate::str::contains("cursor_items[2]"))
+        .stdout(predicate::str::contains("cursor_items[3]").not())
+        .stdout(predicate::str::contains("after_pages"));
+
+    Ok(())
+}
+
+#[test]
+fn sigv4_fixture_signs_request_without_leaking_secret() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let sigv4_fixture = workspace.arg("examples/sigv4.local.http");
+
+    command_in(workspace.root())
+        .args([
+            sigv4_fixture.as_str(),
+            "--verbose",
+            "--no-banner",
+            "--no-telemetry",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All discovered files processed successfully",
+        ))
+        .stdout(predicate::str::contains("wJalrXUtnFEMI").not());
+
+    Ok(())
+}
+
+#[test]
+fn signing_fixture_sends_hmac_message_signature_and_content_digest() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let signing_fixture = workspace.arg("examples/signing.local.http");
+
+    command_in(workspace.root())
+        .args([signing_fixture.as_str(), "--no-banner", "--no-telemetry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All discovered files processed successfully",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn auth_fixture_authenticates_with_basic_digest_and_bearer() -> Result<()> {
+    let server = TestServer::start()?;
+    let workspace = FixtureWorkspace::new(server.base_url())?;
+    let auth_fixture = workspace.arg("examples/auth.local.http");
+
+    command_in(workspace.root())
+        .args([auth_fixture.as_str(), "--no-banner", "--no-telemetry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All discovered files processed successfully",
+        ))
+        .stdout(predicate::str::contains("digest_shorthand"));
+
+    Ok(())
+}
@@ -12,6 +12,7 @@ fn build_result() -> HttpResult {
         response_headers: None,
         response_body: Some(r#"{"message":"ok"}"#.to_string()),
         assertion_results: Vec::new(),
+        captured_variables: Vec::new(),
     }
 }
 
@@ -0,0 +1,49 @@
+use crate::types::{Capture, CaptureSource, HttpResult, Variable};
+use crate::variables::extract_body_value;
+use anyhow::{Result, anyhow};
+
+/// The values the `@capture` directives of a request read from its result, in
+/// order. A value that cannot be found is an error naming the capture.
+pub fn capture_values(captures: &[Capture], result: &HttpResult) -> Result<Vec<Variable>> {
+    captures
+        .iter()
+        .map(|capture| {
+            let value = capture_value(&capture.source, result)?.ok_or_else(|| {
+                anyhow!(
+                    "Failed to capture '{}': {}",
+                    capture.name,
+                    describe_missing(&capture.source)
+                )
+            })?;
+            Ok(Variable {
+                name: capture.name.clone(),
+                value,
+            })
+        })
+        .collect()
+}
+
+fn capture_value(source: &CaptureSource, result: &HttpResult) -> Result<Option<String>> {
+    match source {
+        CaptureSource::BodyPath(path) => match &result.response_body {
+            Some(body) => extract_body_value(body, path),
+            None => Ok(None),
+        },
+        CaptureSource::Header(name) => Ok(result.response_headers.as_ref().and_then(|headers| {
+            headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.clone())
+        })),
+        CaptureSource::Body => Ok(result.response_body.clone()),
+        CaptureSource::Status => Ok(Some(result.status_code.to_string())),
+    }
+}
+
+fn describe_missing(source: &CaptureSource) -> String {
+    match source {
+        CaptureSource::BodyPath(path) => format!("{} was not found in the response body", path),
+        CaptureSource::Header(name) => format!("the response has no '{}' header", name),
+        CaptureSource::Body | CaptureSource::Status => "the response has no body".to_string(),
+    }
+}
@@ -0,0 +1,8 @@
+mod extractor;
+mod store;
+
+pub use extractor::capture_values;
+pub use store::CapturedVariables;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,41 @@
+use crate::redaction::is_sensitive_field_name;
+use crate::types::Variable;
+
+/// The variables stored by `@capture` directives, available as `{{name}}` to
+/// every later request and file of a run.
+#[derive(Debug, Clone, Default)]
+pub struct CapturedVariables {
+    variables: Vec<Variable>,
+}
+
+impl CapturedVariables {
+    /// Store `variable`, replacing an earlier value of the same name.
+    pub fn set(&mut self, variable: Variable) {
+        match self
+            .variables
+            .iter_mut()
+            .find(|stored| stored.name == variable.name)
+        {
+            Some(stored) => stored.value = variable.value,
+            None => self.variables.push(variable),
+        }
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    /// The values of the variables among `names` with secret-looking names,
+    /// such as `token`, which output redacts like other secrets.
+    pub fn secret_values<'a>(&'a self, names: &'a [String]) -> impl Iterator<Item = &'a str> {
+        self.variables
+            .iter()
+            .filter(|variable| is_sensitive_field_name(&variable.name))
+            .filter(|variable| names.contains(&variable.name))
+            .map(|variable| variable.value.as_str())
+    }
+}
@@ -0,0 +1,121 @@
+use super::*;
+use crate::redaction::sanitize_request_for_output;
+use crate::request_substitution::substitute_captured_variables_in_request;
+use crate::test_support::{request, response};
+use crate::types::{Capture, CaptureSource, Variable};
+
+fn capture(name: &str, source: CaptureSource) -> Capture {
+    Capture {
+        name: name.to_string(),
+        source,
+    }
+}
+
+#[test]
+fn capture_values_reads_json_path_header_body_and_status() {
+    let result = response(
+        201,
+        Some(r#"{"access_token":"abc","user":{"id":7}}"#),
+        &[("etag", "\"v1\"")],
+    );
+    let captures = [
+        capture(
+            "token",
+            CaptureSource::BodyPath("$.access_token".to_string()),
+        ),
+        capture("userId", CaptureSource::BodyPath("$.user.id".to_string())),
+        capture("etag", CaptureSource::Header("ETag".to_string())),
+        capture("raw", CaptureSource::Body),
+        capture("code", CaptureSource::Status),
+    ];
+
+    let values = capture_values(&captures, &result).unwrap();
+
+    let values: Vec<(&str, &str)> = values
+        .iter()
+        .map(|variable| (variable.name.as_str(), variable.value.as_str()))
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            ("token", "abc"),
+            ("userId", "7"),
+            ("etag", "\"v1\""),
+            ("raw", r#"{"access_token":"abc","user":{"id":7}}"#),
+            ("code", "201"),
+        ]
+    );
+}
+
+#[test]
+fn capture_values_fails_when_a_value_is_missing() {
+    let result = response(201, Some(r#"{"id":1}"#), &[]);
+
+    let error = capture_values(
+        &[capture(
+            "token",
+            CaptureSource::BodyPath("$.access_token".to_string()),
+        )],
+        &result,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Failed to capture 'token': $.access_token was not found in the response body"
+    );
+
+    let error = capture_values(
+        &[capture("etag", CaptureSource::Header("ETag".to_string()))],
+        &result,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Failed to capture 'etag': the response has no 'ETag' header"
+    );
+}
+
+#[test]
+fn captured_variables_replace_earlier_values() {
+    let mut captured = CapturedVariables::default();
+    assert!(captured.is_empty());
+
+    for value in ["first", "second"] {
+        captured.set(Variable {
+            name: "orderId".to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    assert_eq!(captured.variables().len(), 1);
+    assert_eq!(captured.variables()[0].value, "second");
+}
+
+#[test]
+fn captured_variables_with_secret_names_are_redacted() {
+    let mut captured = CapturedVariables::default();
+    captured.set(Variable {
+        name: "accessToken".to_string(),
+        value: "captured-access-token-value".to_string(),
+    });
+    captured.set(Variable {
+        name: "orderId".to_string(),
+        value: "captured-order-id-value".to_string(),
+    });
+    let mut request = request(
+        "GET",
+        "https://api.example.com/orders/{{orderId}}/{{accessToken}}",
+    );
+
+    substitute_captured_variables_in_request(&mut request, &captured).unwrap();
+
+    assert_eq!(
+        request.url,
+        "https://api.example.com/orders/captured-order-id-value/captured-access-token-value"
+    );
+    assert_eq!(request.secrets, vec!["captured-access-token-value"]);
+    assert_eq!(
+        sanitize_request_for_output(&request, false).url,
+        "https://api.example.com/orders/captured-order-id-value/***REDACTED***"
+    );
+}
@@ -1,16 +1,36 @@
+use crate::datasets::is_iteration_of;
+use crate::parser::import_alias;
 use crate::types::RequestContext;
 
 pub fn check_dependency(depends_on: &Option<String>, context: &[RequestContext]) -> bool {
     if let Some(dep_name) = depends_on {
-        let target_context = context.iter().find(|ctx| ctx.name == *dep_name);
+        let dep_name = &dependency_context_name(dep_name);
+        let target_context = context.iter().rev().find(|ctx| ctx.name == *dep_name);
 
-        if let Some(ctx) = target_context
-            && let Some(ref result) = ctx.result
-        {
-            return result.success;
+        if let Some(ctx) = target_context {
+            return ctx.result.as_ref().is_some_and(|result| result.success);
         }
-        return false;
+
+        // A dependency on a data-driven request is met once every iteration succeeded.
+        let mut iterations = context
+            .iter()
+            .filter(|ctx| is_iteration_of(&ctx.name, dep_name))
+            .peekable();
+        return iterations.peek().is_some()
+            && iterations.all(|ctx| ctx.result.as_ref().is_some_and(|result| result.success));
     }
 
     true
 }
+
+/// `<file>#<request>` refers to a request of an imported file, whose context
+/// is named `<alias>.<request>`.
+fn dependency_context_name(depends_on: &str) -> String {
+    match depends_on.split_once('#') {
+        Some((path, request)) => match import_alias(path) {
+            Some(alias) => format!("{alias}.{request}"),
+            None => request.to_string(),
+        },
+        None => depends_on.to_string(),
+    }
+}
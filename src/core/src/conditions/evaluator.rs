@@ -1,6 +1,6 @@
 use super::formatter::format_condition_type;
 use crate::types::{Condition, ConditionType, RequestContext};
-use crate::variables::extract_json_value;
+use crate::variables::extract_body_value;
 use anyhow::Result;
 
 #[derive(Debug)]
@@ -59,6 +59,7 @@ fn evaluate_single_condition_verbose(
 ) -> Result<ConditionEvaluationResult> {
     let target_context = context
         .iter()
+        .rev()
         .find(|ctx| ctx.name == condition.request_name);
 
     let Some(ctx) = target_context else {
@@ -93,7 +94,7 @@ fn evaluate_single_condition_verbose(
         }
         ConditionType::BodyJsonPath(json_path) => {
             if let Some(ref body) = result.response_body {
-                let extracted_value = extract_json_value(body, json_path)?;
+                let extracted_value = extract_body_value(body, json_path)?;
                 if let Some(value) = extracted_value {
                     let met = value.trim() == condition.expected_value.trim();
                     (Some(value), met)
@@ -32,6 +32,7 @@ fn test_evaluate_status_condition_success() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -43,6 +44,7 @@ fn test_evaluate_status_condition_success() {
         response_headers: Some(HashMap::new()),
         response_body: Some("test body".to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -77,6 +79,7 @@ fn test_evaluate_status_condition_failure() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -88,6 +91,7 @@ fn test_evaluate_status_condition_failure() {
         response_headers: Some(HashMap::new()),
         response_body: Some("test body".to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -115,6 +119,7 @@ fn test_check_dependency_success() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -126,6 +131,7 @@ fn test_check_dependency_success() {
         response_headers: Some(HashMap::new()),
         response_body: Some("test body".to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -153,6 +159,7 @@ fn test_check_dependency_not_200() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -164,6 +171,7 @@ fn test_check_dependency_not_200() {
         response_headers: Some(HashMap::new()),
         response_body: Some("not found".to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -191,6 +199,7 @@ fn test_check_dependency_201_created() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -202,6 +211,7 @@ fn test_check_dependency_201_created() {
         response_headers: Some(HashMap::new()),
         response_body: Some(r#"{"id": 1}"#.to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -219,6 +229,114 @@ fn test_check_dependency_none() {
     assert!(check_dependency(&None, &context));
 }
 
+#[test]
+fn test_check_dependency_on_data_iterations() {
+    let iteration = |name: &str, success: bool| RequestContext {
+        name: name.to_string(),
+        request: HttpRequest {
+            name: Some(name.to_string()),
+            method: "POST".to_string(),
+            url: "http://example.com/users".to_string(),
+            headers: vec![],
+            body: None,
+            assertions: vec![],
+            variables: vec![],
+            timeout: None,
+            connection_timeout: None,
+            depends_on: None,
+            conditions: vec![],
+            pre_delay_ms: None,
+            post_delay_ms: None,
+            ..Default::default()
+        },
+        result: Some(HttpResult {
+            request_name: Some(name.to_string()),
+            status_code: if success { 201 } else { 500 },
+            success,
+            error_message: None,
+            duration_ms: 100,
+            response_headers: None,
+            response_body: None,
+            assertion_results: vec![],
+            captured_variables: Vec::new(),
+        }),
+    };
+
+    let all_passed = vec![
+        iteration("create_user[1]", true),
+        iteration("create_user[2]", true),
+    ];
+    assert!(check_dependency(
+        &Some("create_user".to_string()),
+        &all_passed
+    ));
+    assert!(check_dependency(
+        &Some("create_user[2]".to_string()),
+        &all_passed
+    ));
+
+    let one_failed = vec![
+        iteration("create_user[1]", true),
+        iteration("create_user[2]", false),
+    ];
+    assert!(!check_dependency(
+        &Some("create_user".to_string()),
+        &one_failed
+    ));
+
+    let unrelated = vec![iteration("create_user_extra[1]", true)];
+    assert!(!check_dependency(
+        &Some("create_user".to_string()),
+        &unrelated
+    ));
+}
+
+#[test]
+fn test_check_dependency_on_imported_request() {
+    let request = HttpRequest {
+        name: Some("login".to_string()),
+        method: "POST".to_string(),
+        url: "http://example.com/login".to_string(),
+        headers: vec![],
+        body: None,
+        assertions: vec![],
+        variables: vec![],
+        timeout: None,
+        connection_timeout: None,
+        depends_on: None,
+        conditions: vec![],
+        pre_delay_ms: None,
+        post_delay_ms: None,
+        ..Default::default()
+    };
+    let result = HttpResult {
+        request_name: Some("login".to_string()),
+        status_code: 200,
+        success: true,
+        error_message: None,
+        duration_ms: 100,
+        response_headers: None,
+        response_body: None,
+        assertion_results: vec![],
+        captured_variables: Vec::new(),
+    };
+    let context = vec![RequestContext {
+        name: "auth.login".to_string(),
+        request,
+        result: Some(result),
+    }];
+
+    assert!(check_dependency(
+        &Some("./shared/auth.http#login".to_string()),
+        &context
+    ));
+    assert!(check_dependency(&Some("auth.login".to_string()), &context));
+    assert!(!check_dependency(
+        &Some("./orders.http#login".to_string()),
+        &context
+    ));
+}
+
 #[test]
 fn test_evaluate_body_jsonpath_condition_success() {
     let condition = Condition {
@@ -242,6 +360,7 @@ fn test_evaluate_body_jsonpath_condition_success() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -253,6 +372,7 @@ fn test_evaluate_body_jsonpath_condition_success() {
         response_headers: Some(HashMap::new()),
         response_body: Some(r#"{"username": "testuser", "id": 123}"#.to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -264,6 +384,55 @@ fn test_evaluate_body_jsonpath_condition_success() {
     assert!(evaluate_conditions(&[condition], &context).unwrap());
 }
 
+#[test]
+fn test_evaluate_body_regex_and_xpath_conditions() {
+    let request = HttpRequest {
+        name: Some("request1".to_string()),
+        method: "GET".to_string(),
+        url: "https://api.example.com/request1".to_string(),
+        headers: vec![],
+        body: None,
+        assertions: vec![],
+        variables: vec![],
+        timeout: None,
+        connection_timeout: None,
+        depends_on: None,
+        conditions: vec![],
+        pre_delay_ms: None,
+        post_delay_ms: None,
+        ..Default::default()
+    };
+
+    let result = HttpResult {
+        request_name: Some("request1".to_string()),
+        status_code: 200,
+        success: true,
+        error_message: None,
+        duration_ms: 100,
+        response_headers: Some(HashMap::new()),
+        response_body: Some("<status code=\"ok\">state=ready</status>".to_string()),
+        assertion_results: vec![],
+        captured_variables: Vec::new(),
+    };
+
+    let context = vec![RequestContext {
+        name: "request1".to_string(),
+        request,
+        result: Some(result),
+    }];
+
+    let condition = |path: &str, expected: &str| Condition {
+        request_name: "request1".to_string(),
+        condition_type: ConditionType::BodyJsonPath(path.to_string()),
+        expected_value: expected.to_string(),
+        negate: false,
+    };
+
+    assert!(evaluate_conditions(&[condition(r"regex(/state=(\w+)/)", "ready")], &context).unwrap());
+    assert!(evaluate_conditions(&[condition("/status/@code", "ok")], &context).unwrap());
+    assert!(!evaluate_conditions(&[condition("//status/@code", "failed")], &context).unwrap());
+}
+
 #[test]
 fn test_evaluate_body_jsonpath_condition_failure() {
     let condition = Condition {
@@ -287,6 +456,7 @@ fn test_evaluate_body_jsonpath_condition_failure() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -298,6 +468,7 @@ fn test_evaluate_body_jsonpath_condition_failure() {
         response_headers: Some(HashMap::new()),
         response_body: Some(r#"{"username": "testuser", "id": 123}"#.to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -340,6 +511,7 @@ fn test_evaluate_multiple_conditions_all_met() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -351,6 +523,7 @@ fn test_evaluate_multiple_conditions_all_met() {
         response_headers: Some(HashMap::new()),
         response_body: Some(r#"{"username": "testuser", "id": 123}"#.to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -393,6 +566,7 @@ fn test_evaluate_multiple_conditions_one_fails() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -404,6 +578,7 @@ fn test_evaluate_multiple_conditions_one_fails() {
         response_headers: Some(HashMap::new()),
         response_body: Some(r#"{"username": "testuser", "id": 123}"#.to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -438,6 +613,7 @@ fn test_evaluate_status_condition_negated_success() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -449,6 +625,7 @@ fn test_evaluate_status_condition_negated_success() {
         response_headers: Some(HashMap::new()),
         response_body: Some("test body".to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -483,6 +660,7 @@ fn test_evaluate_status_condition_negated_failure() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -494,6 +672,7 @@ fn test_evaluate_status_condition_negated_failure() {
         response_headers: Some(HashMap::new()),
         response_body: Some("test body".to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -528,6 +707,7 @@ fn test_evaluate_body_jsonpath_condition_negated_success() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -539,6 +719,7 @@ fn test_evaluate_body_jsonpath_condition_negated_success() {
         response_headers: Some(HashMap::new()),
         response_body: Some(r#"{"username": "testuser", "id": 123}"#.to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -573,6 +754,7 @@ fn test_evaluate_body_jsonpath_condition_negated_failure() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -584,6 +766,7 @@ fn test_evaluate_body_jsonpath_condition_negated_failure() {
         response_headers: Some(HashMap::new()),
         response_body: Some(r#"{"username": "testuser", "id": 123}"#.to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -645,6 +828,7 @@ fn test_evaluate_conditions_verbose_result_is_none() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -684,6 +868,7 @@ fn test_evaluate_conditions_verbose_no_response_body() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -695,6 +880,7 @@ fn test_evaluate_conditions_verbose_no_response_body() {
         response_headers: Some(HashMap::new()),
         response_body: None,
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -734,6 +920,7 @@ fn test_evaluate_conditions_verbose_json_path_not_found() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = HttpResult {
@@ -745,6 +932,7 @@ fn test_evaluate_conditions_verbose_json_path_not_found() {
         response_headers: Some(HashMap::new()),
         response_body: Some(r#"{"test": "data"}"#.to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
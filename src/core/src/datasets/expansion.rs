@@ -0,0 +1,136 @@
+use crate::parser::substitute_variables;
+use crate::types::{DataScope, HttpRequest, Variable};
+
+/// Expands `@data` requests into one request per dataset row.
+///
+/// Request-scoped datasets repeat the single request; file-scoped datasets
+/// repeat the whole run of file-scoped requests once per row. Each iteration
+/// is named `name[n]` (1-based), and within a file-scoped iteration references
+/// to sibling requests (`{{login.response...}}`, `@dependsOn`, `@if`) are
+/// rewritten to the sibling of the same iteration.
+pub fn expand_data_iterations(requests: Vec<HttpRequest>) -> Vec<HttpRequest> {
+    if requests.iter().all(|request| request.data.is_none()) {
+        return requests;
+    }
+
+    let mut expanded = Vec::with_capacity(requests.len());
+    let mut requests = requests.into_iter().peekable();
+
+    while let Some(request) = requests.next() {
+        let Some(data) = request.data.clone() else {
+            expanded.push(request);
+            continue;
+        };
+
+        let mut group = vec![request];
+        if data.scope == DataScope::File {
+            while let Some(next) = requests.next_if(|next| {
+                next.data
+                    .as_ref()
+                    .is_some_and(|next_data| next_data.scope == DataScope::File)
+            }) {
+                group.push(next);
+            }
+        }
+
+        let sibling_names: Vec<String> = if data.scope == DataScope::File {
+            group.iter().filter_map(|r| r.name.clone()).collect()
+        } else {
+            Vec::new()
+        };
+
+        for (row_idx, row) in data.rows.iter().enumerate() {
+            for request in &group {
+                expanded.push(bind_iteration(request, row, row_idx + 1, &sibling_names));
+            }
+        }
+    }
+
+    expanded
+}
+
+/// Returns the context name used for the `iteration`-th run of `name`.
+pub fn iteration_name(name: &str, iteration: usize) -> String {
+    format!("{}[{}]", name, iteration)
+}
+
+/// Returns true when `context_name` is an iteration (`name[n]`) of `name`.
+pub fn is_iteration_of(context_name: &str, name: &str) -> bool {
+    context_name
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix('['))
+        .and_then(|rest| rest.strip_suffix(']'))
+        .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn bind_iteration(
+    template: &HttpRequest,
+    row: &[Variable],
+    iteration: usize,
+    sibling_names: &[String],
+) -> HttpRequest {
+    let mut request = template.clone();
+    request.data = None;
+    request.name = template
+        .name
+        .as_deref()
+        .map(|name| iteration_name(name, iteration));
+
+    rewrite_request_text(&mut request, |value| substitute_variables(value, row));
+    for condition in &mut request.conditions {
+        condition.expected_value = substitute_variables(&condition.expected_value, row);
+    }
+
+    if sibling_names.is_empty() {
+        return request;
+    }
+
+    rewrite_request_text(&mut request, |value| {
+        rewrite_sibling_references(value, sibling_names, iteration)
+    });
+
+    if let Some(depends_on) = request.depends_on.as_mut()
+        && sibling_names.contains(depends_on)
+    {
+        *depends_on = iteration_name(depends_on, iteration);
+    }
+
+    for condition in &mut request.conditions {
+        if sibling_names.contains(&condition.request_name) {
+            condition.request_name = iteration_name(&condition.request_name, iteration);
+        }
+    }
+
+    request
+}
+
+pub(super) fn rewrite_request_text(request: &mut HttpRequest, rewrite: impl Fn(&str) -> String) {
+    request.url = rewrite(&request.url);
+
+    for header in &mut request.headers {
+        header.name = rewrite(&header.name);
+        header.value = rewrite(&header.value);
+    }
+
+    if let Some(body) = request.body.as_deref() {
+        request.body = Some(rewrite(body));
+    }
+
+    for assertion in &mut request.assertions {
+        assertion.expected_value = rewrite(&assertion.expected_value);
+    }
+}
+
+fn rewrite_sibling_references(value: &str, sibling_names: &[String], iteration: usize) -> String {
+    let mut result = value.to_string();
+    for name in sibling_names {
+        let indexed = iteration_name(name, iteration);
+        for source in ["request", "response"] {
+            result = result.replace(
+                &format!("{{{{{}.{}.", name, source),
+                &format!("{{{{{}.{}.", indexed, source),
+            );
+        }
+    }
+    result
+}
@@ -0,0 +1,73 @@
+use super::expansion::{iteration_name, rewrite_request_text};
+use crate::parser::substitute_variables;
+use crate::types::{ForEach, HttpRequest, RequestContext, Variable};
+use crate::variables::substitute_request_variables;
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+/// Resolves the `@foreach` source against earlier request contexts and returns
+/// one request per array item, named `name[n]` (1-based).
+///
+/// Each iteration binds `{{<variable>}}` to the item (JSON text for non-strings),
+/// `{{<variable>.<field>}}` for every (nested) object field and `{{index}}` to the
+/// zero-based position of the item in the array.
+pub fn expand_foreach(
+    template: &HttpRequest,
+    foreach: &ForEach,
+    context: &[RequestContext],
+) -> Result<Vec<HttpRequest>> {
+    let items = resolve_foreach_items(&foreach.source, context)?;
+
+    Ok(items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let mut bindings = Vec::new();
+            bind_item(&foreach.variable, item, &mut bindings);
+            bindings.push(Variable {
+                name: "index".to_string(),
+                value: index.to_string(),
+            });
+
+            let mut request = template.clone();
+            request.foreach = None;
+            request.name = template
+                .name
+                .as_deref()
+                .map(|name| iteration_name(name, index + 1));
+            rewrite_request_text(&mut request, |value| substitute_variables(value, &bindings));
+            for condition in &mut request.conditions {
+                condition.expected_value =
+                    substitute_variables(&condition.expected_value, &bindings);
+            }
+            request
+        })
+        .collect())
+}
+
+fn resolve_foreach_items(source: &str, context: &[RequestContext]) -> Result<Vec<Value>> {
+    let resolved = substitute_request_variables(source, context)?;
+    match serde_json::from_str::<Value>(&resolved) {
+        Ok(Value::Array(items)) => Ok(items),
+        _ => Err(anyhow!(
+            "@foreach source '{}' did not resolve to a JSON array",
+            source
+        )),
+    }
+}
+
+fn bind_item(name: &str, value: &Value, bindings: &mut Vec<Variable>) {
+    bindings.push(Variable {
+        name: name.to_string(),
+        value: match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        },
+    });
+
+    if let Value::Object(fields) = value {
+        for (field, field_value) in fields {
+            bind_item(&format!("{}.{}", name, field), field_value, bindings);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::types::Variable;
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Loads the rows of a `.csv` or `.json` dataset, resolving relative paths
+/// against `base_dir` (the directory of the `.http` file when known).
+pub fn load_dataset(path: &str, base_dir: Option<&Path>) -> Result<Vec<Vec<Variable>>> {
+    let resolved = resolve_dataset_path(path, base_dir);
+    let content = fs::read_to_string(&resolved)
+        .with_context(|| format!("Failed to read dataset: {}", resolved.display()))?;
+
+    let extension = resolved
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("csv") => parse_csv_dataset(&content),
+        Some("json") => parse_json_dataset(&content),
+        _ => bail!(
+            "Unsupported dataset format '{}': expected a .csv or .json file",
+            path
+        ),
+    }
+    .with_context(|| format!("Invalid dataset: {}", resolved.display()))
+}
+
+fn resolve_dataset_path(path: &str, base_dir: Option<&Path>) -> PathBuf {
+    let candidate = Path::new(path);
+    match base_dir {
+        Some(dir) if candidate.is_relative() => dir.join(candidate),
+        _ => candidate.to_path_buf(),
+    }
+}
+
+/// Parses CSV content where the first record holds the column names.
+/// Fields may be quoted with `"`; a doubled `""` inside quotes is a literal quote.
+pub fn parse_csv_dataset(content: &str) -> Result<Vec<Vec<Variable>>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = parse_csv_records(content)?.into_iter();
+
+    let Some(columns) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let columns: Vec<String> = columns.iter().map(|c| c.trim().to_string()).collect();
+
+    if columns.iter().any(|column| column.is_empty()) {
+        bail!("CSV header contains an empty column name");
+    }
+
+    records
+        .enumerate()
+        .map(|(idx, record)| {
+            if record.len() != columns.len() {
+                bail!(
+                    "CSV row {} has {} columns but the header has {}",
+                    idx + 1,
+                    record.len(),
+                    columns.len()
+                );
+            }
+
+            Ok(columns
+                .iter()
+                .zip(record)
+                .map(|(name, value)| Variable {
+                    name: name.clone(),
+                    value,
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn parse_csv_records(content: &str) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                push_record(&mut records, std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(anyhow!("CSV content ends inside a quoted field"));
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        push_record(&mut records, record);
+    }
+
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<Vec<String>>, record: Vec<String>) {
+    let is_blank = record.len() == 1 && record[0].trim().is_empty();
+    if !is_blank {
+        records.push(record);
+    }
+}
+
+/// Parses a JSON array of objects; each object becomes one row. String values
+/// are bound as-is, `null` as an empty string and anything else as JSON text.
+pub fn parse_json_dataset(content: &str) -> Result<Vec<Vec<Variable>>> {
+    let value: Value = serde_json::from_str(content).context("Dataset is not valid JSON")?;
+    let Value::Array(items) = value else {
+        bail!("JSON dataset must be an array of objects");
+    };
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let Value::Object(fields) = item else {
+                bail!("JSON dataset item {} is not an object", idx + 1);
+            };
+
+            Ok(fields
+                .into_iter()
+                .map(|(name, value)| Variable {
+                    name,
+                    value: match value {
+                        Value::String(text) => text,
+                        Value::Null => String::new(),
+                        other => other.to_string(),
+                    },
+                })
+                .collect())
+        })
+        .collect()
+}
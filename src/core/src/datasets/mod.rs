@@ -0,0 +1,10 @@
+mod expansion;
+mod foreach;
+mod loader;
+
+pub use expansion::{expand_data_iterations, is_iteration_of, iteration_name};
+pub use foreach::expand_foreach;
+pub use loader::{load_dataset, parse_csv_dataset, parse_json_dataset};
+
+#[cfg(test)]
+mod tests;
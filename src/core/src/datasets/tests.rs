@@ -0,0 +1,340 @@
+use super::*;
+use crate::test_support::{request, response};
+use crate::types::{
+    Assertion, AssertionType, Condition, ConditionType, DataScope, DataSource, Header, HttpRequest,
+    Variable,
+};
+use std::fs;
+use tempfile::tempdir;
+
+fn row(pairs: &[(&str, &str)]) -> Vec<Variable> {
+    pairs
+        .iter()
+        .map(|(name, value)| Variable {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+        .collect()
+}
+
+fn values(row: &[Variable]) -> Vec<(&str, &str)> {
+    row.iter()
+        .map(|v| (v.name.as_str(), v.value.as_str()))
+        .collect()
+}
+
+fn make_request(name: Option<&str>, url: &str) -> HttpRequest {
+    HttpRequest {
+        name: name.map(str::to_string),
+        ..request("POST", url)
+    }
+}
+
+fn data_source(scope: DataScope, rows: Vec<Vec<Variable>>) -> Option<DataSource> {
+    Some(DataSource {
+        path: "users.csv".to_string(),
+        scope,
+        rows,
+    })
+}
+
+#[test]
+fn parse_csv_dataset_binds_columns_by_header() {
+    let rows =
+        parse_csv_dataset("name,email\nalice,alice@example.com\nbob,bob@example.com\n").unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(
+        values(&rows[0]),
+        vec![("name", "alice"), ("email", "alice@example.com")]
+    );
+    assert_eq!(
+        values(&rows[1]),
+        vec![("name", "bob"), ("email", "bob@example.com")]
+    );
+}
+
+#[test]
+fn parse_csv_dataset_handles_quotes_crlf_and_bom() {
+    let content =
+        "\u{feff}id,note\r\n1,\"hello, world\"\r\n2,\"say \"\"hi\"\"\"\r\n3,\"multi\nline\"\r\n";
+    let rows = parse_csv_dataset(content).unwrap();
+
+    assert_eq!(rows.len(), 3);
+    assert_eq!(
+        values(&rows[0]),
+        vec![("id", "1"), ("note", "hello, world")]
+    );
+    assert_eq!(values(&rows[1]), vec![("id", "2"), ("note", "say \"hi\"")]);
+    assert_eq!(values(&rows[2]), vec![("id", "3"), ("note", "multi\nline")]);
+}
+
+#[test]
+fn parse_csv_dataset_skips_blank_lines_and_keeps_empty_fields() {
+    let rows = parse_csv_dataset("a,b\n\n1,\n\n,2").unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(values(&rows[0]), vec![("a", "1"), ("b", "")]);
+    assert_eq!(values(&rows[1]), vec![("a", ""), ("b", "2")]);
+}
+
+#[test]
+fn parse_csv_dataset_rejects_mismatched_rows() {
+    let error = parse_csv_dataset("a,b\n1,2,3\n").unwrap_err();
+    assert!(error.to_string().contains("CSV row 1 has 3 columns"));
+}
+
+#[test]
+fn parse_csv_dataset_rejects_unterminated_quotes() {
+    assert!(parse_csv_dataset("a\n\"open\n").is_err());
+}
+
+#[test]
+fn parse_csv_dataset_with_only_header_has_no_rows() {
+    assert!(parse_csv_dataset("a,b\n").unwrap().is_empty());
+    assert!(parse_csv_dataset("").unwrap().is_empty());
+}
+
+#[test]
+fn parse_json_dataset_stringifies_values() {
+    let rows =
+        parse_json_dataset(r#"[{"name":"alice","age":30,"admin":true,"tags":["a"],"note":null}]"#)
+            .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    let lookup = |key: &str| row.iter().find(|v| v.name == key).unwrap().value.clone();
+    assert_eq!(lookup("name"), "alice");
+    assert_eq!(lookup("age"), "30");
+    assert_eq!(lookup("admin"), "true");
+    assert_eq!(lookup("tags"), r#"["a"]"#);
+    assert_eq!(lookup("note"), "");
+}
+
+#[test]
+fn parse_json_dataset_requires_array_of_objects() {
+    assert!(parse_json_dataset(r#"{"name":"alice"}"#).is_err());
+
+    let error = parse_json_dataset(r#"[{"name":"alice"}, 2]"#).unwrap_err();
+    assert!(error.to_string().contains("item 2 is not an object"));
+}
+
+#[test]
+fn load_dataset_resolves_relative_to_base_dir() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("users.csv"), "name\nalice\n").unwrap();
+    fs::write(temp.path().join("users.json"), r#"[{"name":"bob"}]"#).unwrap();
+
+    let csv_rows = load_dataset("./users.csv", Some(temp.path())).unwrap();
+    assert_eq!(values(&csv_rows[0]), vec![("name", "alice")]);
+
+    let json_rows = load_dataset("users.json", Some(temp.path())).unwrap();
+    assert_eq!(values(&json_rows[0]), vec![("name", "bob")]);
+}
+
+#[test]
+fn load_dataset_reports_missing_and_unsupported_files() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("users.txt"), "name\nalice\n").unwrap();
+
+    let missing = load_dataset("missing.csv", Some(temp.path())).unwrap_err();
+    assert!(missing.to_string().contains("Failed to read dataset"));
+
+    let unsupported = load_dataset("users.txt", Some(temp.path())).unwrap_err();
+    assert!(format!("{unsupported:#}").contains("Unsupported dataset format"));
+}
+
+#[test]
+fn expand_data_iterations_leaves_plain_requests_untouched() {
+    let requests = vec![make_request(Some("a"), "http://example.com/a")];
+    let expanded = expand_data_iterations(requests);
+
+    assert_eq!(expanded.len(), 1);
+    assert_eq!(expanded[0].name.as_deref(), Some("a"));
+}
+
+#[test]
+fn expand_data_iterations_repeats_request_per_row() {
+    let mut request = make_request(Some("create_user"), "http://example.com/users/{{id}}");
+    request.headers.push(Header {
+        name: "X-User".to_string(),
+        value: "{{name}}".to_string(),
+    });
+    request.body = Some(r#"{"name":"{{name}}"}"#.to_string());
+    request.assertions.push(Assertion {
+        assertion_type: AssertionType::Body,
+        expected_value: "{{name}}".to_string(),
+    });
+    request.data = data_source(
+        DataScope::Request,
+        vec![
+            row(&[("id", "1"), ("name", "alice")]),
+            row(&[("id", "2"), ("name", "bob")]),
+        ],
+    );
+
+    let expanded = expand_data_iterations(vec![
+        request,
+        make_request(Some("after"), "http://example.com/{{id}}"),
+    ]);
+
+    assert_eq!(expanded.len(), 3);
+    assert_eq!(expanded[0].name.as_deref(), Some("create_user[1]"));
+    assert_eq!(expanded[0].url, "http://example.com/users/1");
+    assert_eq!(expanded[0].headers[0].value, "alice");
+    assert_eq!(expanded[0].body.as_deref(), Some(r#"{"name":"alice"}"#));
+    assert_eq!(expanded[0].assertions[0].expected_value, "alice");
+    assert!(expanded[0].data.is_none());
+
+    assert_eq!(expanded[1].name.as_deref(), Some("create_user[2]"));
+    assert_eq!(expanded[1].url, "http://example.com/users/2");
+
+    assert_eq!(expanded[2].name.as_deref(), Some("after"));
+    assert_eq!(expanded[2].url, "http://example.com/{{id}}");
+}
+
+#[test]
+fn expand_data_iterations_with_no_rows_drops_request() {
+    let mut request = make_request(Some("create_user"), "http://example.com/users");
+    request.data = data_source(DataScope::Request, vec![]);
+
+    assert!(expand_data_iterations(vec![request]).is_empty());
+}
+
+#[test]
+fn expand_data_iterations_repeats_file_scope_and_rewrites_siblings() {
+    let rows = vec![row(&[("user", "alice")]), row(&[("user", "bob")])];
+
+    let mut login = make_request(Some("login"), "http://example.com/login/{{user}}");
+    login.data = data_source(DataScope::File, rows.clone());
+
+    let mut profile = make_request(None, "http://example.com/profile");
+    profile.headers.push(Header {
+        name: "Authorization".to_string(),
+        value: "Bearer {{login.response.body.$.token}}".to_string(),
+    });
+    profile.depends_on = Some("login".to_string());
+    profile.conditions.push(Condition {
+        request_name: "login".to_string(),
+        condition_type: ConditionType::BodyJsonPath("$.user".to_string()),
+        expected_value: "{{user}}".to_string(),
+        negate: false,
+    });
+    profile.data = data_source(DataScope::File, rows);
+
+    let expanded = expand_data_iterations(vec![login, profile]);
+
+    let names: Vec<_> = expanded.iter().map(|r| r.name.clone()).collect();
+    assert_eq!(
+        names,
+        vec![
+            Some("login[1]".to_string()),
+            None,
+            Some("login[2]".to_string()),
+            None
+        ]
+    );
+    assert_eq!(expanded[0].url, "http://example.com/login/alice");
+    assert_eq!(expanded[2].url, "http://example.com/login/bob");
+
+    assert_eq!(
+        expanded[3].headers[0].value,
+        "Bearer {{login[2].response.body.$.token}}"
+    );
+    assert_eq!(expanded[3].depends_on.as_deref(), Some("login[2]"));
+    assert_eq!(expanded[3].conditions[0].request_name, "login[2]");
+    assert_eq!(expanded[3].conditions[0].expected_value, "bob");
+}
+
+#[test]
+fn iteration_names_round_trip() {
+    assert_eq!(iteration_name("create_user", 3), "create_user[3]");
+    assert!(is_iteration_of("create_user[3]", "create_user"));
+    assert!(!is_iteration_of("create_user", "create_user"));
+    assert!(!is_iteration_of("create_user[]", "create_user"));
+    assert!(!is_iteration_of("create_user[x]", "create_user"));
+    assert!(!is_iteration_of("create_users[1]", "create_user"));
+}
+
+fn context_with_body(name: &str, body: &str) -> crate::types::RequestContext {
+    crate::types::RequestContext {
+        name: name.to_string(),
+        request: make_request(Some(name), "http://example.com/list"),
+        result: Some(crate::types::HttpResult {
+            request_name: Some(name.to_string()),
+            ..response(200, Some(body), &[])
+        }),
+    }
+}
+
+#[test]
+fn expand_foreach_binds_item_fields_and_index() {
+    let context = vec![context_with_body(
+        "list",
+        r#"{"items":[{"id":1,"owner":{"name":"alice"}},{"id":2,"owner":{"name":"bob"}}]}"#,
+    )];
+    let mut template = make_request(Some("get_item"), "http://example.com/items/{{item.id}}");
+    template.body = Some("{{index}}:{{item.owner.name}}:{{item.owner}}".to_string());
+    let foreach = crate::types::ForEach {
+        variable: "item".to_string(),
+        source: "{{list.response.body.$.items}}".to_string(),
+    };
+
+    let iterations = expand_foreach(&template, &foreach, &context).unwrap();
+
+    assert_eq!(iterations.len(), 2);
+    assert_eq!(iterations[0].name.as_deref(), Some("get_item[1]"));
+    assert_eq!(iterations[0].url, "http://example.com/items/1");
+    assert_eq!(
+        iterations[0].body.as_deref(),
+        Some(r#"0:alice:{"name":"alice"}"#)
+    );
+    assert_eq!(iterations[1].name.as_deref(), Some("get_item[2]"));
+    assert_eq!(iterations[1].url, "http://example.com/items/2");
+    assert!(iterations.iter().all(|r| r.foreach.is_none()));
+}
+
+#[test]
+fn expand_foreach_binds_scalar_items() {
+    let context = vec![context_with_body("list", r#"{"ids":["a-1","b-2"]}"#)];
+    let template = make_request(None, "http://example.com/items/{{id}}");
+    let foreach = crate::types::ForEach {
+        variable: "id".to_string(),
+        source: "{{list.response.body.$.ids}}".to_string(),
+    };
+
+    let iterations = expand_foreach(&template, &foreach, &context).unwrap();
+
+    let urls: Vec<_> = iterations.iter().map(|r| r.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        vec![
+            "http://example.com/items/a-1",
+            "http://example.com/items/b-2"
+        ]
+    );
+    assert!(iterations.iter().all(|r| r.name.is_none()));
+}
+
+#[test]
+fn expand_foreach_rejects_non_array_sources() {
+    let context = vec![context_with_body("list", r#"{"items":{"id":1}}"#)];
+    let template = make_request(Some("get_item"), "http://example.com/items/{{item}}");
+    let foreach = crate::types::ForEach {
+        variable: "item".to_string(),
+        source: "{{list.response.body.$.items}}".to_string(),
+    };
+
+    let error = expand_foreach(&template, &foreach, &context).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("did not resolve to a JSON array")
+    );
+
+    let unresolved = crate::types::ForEach {
+        variable: "item".to_string(),
+        source: "{{missing.response.body.$.items}}".to_string(),
+    };
+    assert!(expand_foreach(&template, &unresolved, &context).is_err());
+}
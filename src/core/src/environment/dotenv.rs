@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Find the nearest `.env` file, starting in `dir` and walking up its parents.
+pub fn find_dotenv_file(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(".env"))
+        .find(|dotenv_file| dotenv_file.is_file())
+}
+
+/// Load the nearest `.env` file above `dir`, or nothing when there is none.
+pub fn load_dotenv_file(dir: &Path) -> Result<HashMap<String, String>> {
+    let Some(dotenv_file) = find_dotenv_file(dir) else {
+        return Ok(HashMap::new());
+    };
+
+    let content = fs::read_to_string(&dotenv_file)
+        .with_context(|| format!("Failed to read .env file: {}", dotenv_file.display()))?;
+    Ok(parse_dotenv(&content))
+}
+
+/// Parse `KEY=VALUE` lines. Supports `export` prefixes, `#` comments, and
+/// single- or double-quoted values; double quotes understand `\n`, `\t`,
+/// `\"` and `\\`. Lines without `=` are ignored.
+pub fn parse_dotenv(content: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        let key = key.trim();
+        if key.is_empty() {
+            continue;
+        }
+
+        values.insert(key.to_string(), parse_value(value.trim()));
+    }
+
+    values
+}
+
+fn parse_value(value: &str) -> String {
+    if let Some(quoted) = value.strip_prefix('"')
+        && let Some(end) = closing_quote(quoted)
+    {
+        return unescape(&quoted[..end]);
+    }
+
+    if let Some(quoted) = value.strip_prefix('\'')
+        && let Some(end) = quoted.find('\'')
+    {
+        return quoted[..end].to_string();
+    }
+
+    // An unquoted value ends at an inline ` #` comment.
+    match value.find(" #") {
+        Some(comment) => value[..comment].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+fn closing_quote(quoted: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (index, ch) in quoted.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
@@ -0,0 +1,564 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Context, Result, anyhow, bail};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Prefix of an encrypted environment value: `enc:v1:` followed by the
+/// base64 of a random nonce and the AES-256-GCM ciphertext.
+pub const ENCRYPTED_VALUE_PREFIX: &str = "enc:v1:";
+/// Key file looked up next to the environment files.
+pub const ENVIRONMENT_KEY_FILE: &str = "http-client.env.key";
+/// Environment variable naming a key file to use instead.
+pub const KEY_FILE_ENV_VAR: &str = "HTTPRUNNER_ENV_KEY_FILE";
+/// Environment variable holding a passphrase to derive the key from.
+pub const PASSPHRASE_ENV_VAR: &str = "HTTPRUNNER_ENV_PASSPHRASE";
+
+// The salt is fixed so that everyone sharing a passphrase derives the same key.
+const PASSPHRASE_SALT: &[u8] = b"httprunner-environment-v1";
+const PASSPHRASE_ITERATIONS: u32 = 100_000;
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// The AES-256 key encrypting environment values.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EnvironmentKey([u8; KEY_LEN]);
+
+impl fmt::Debug for EnvironmentKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EnvironmentKey(***)")
+    }
+}
+
+impl EnvironmentKey {
+    /// A new random key.
+    pub fn generate() -> Self {
+        use rand::RngExt;
+        let mut key = [0u8; KEY_LEN];
+        rand::rng().fill(&mut key);
+        Self(key)
+    }
+
+    /// Derive the key from a passphrase with PBKDF2-HMAC-SHA256.
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        let mut key = [0u8; KEY_LEN];
+        pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+            passphrase.as_bytes(),
+            PASSPHRASE_SALT,
+            PASSPHRASE_ITERATIONS,
+            &mut key,
+        );
+        Self(key)
+    }
+
+    /// Read a key file holding the base64 of a 32-byte key.
+    pub fn from_key_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read key file: {}", path.display()))?;
+        let bytes = STANDARD
+            .decode(content.trim())
+            .with_context(|| format!("Key file {} is not valid base64", path.display()))?;
+        let key = bytes.try_into().map_err(|_| {
+            anyhow!(
+                "Key file {} must contain a {}-byte key",
+                path.display(),
+                KEY_LEN
+            )
+        })?;
+        Ok(Self(key))
+    }
+
+    /// Write the key to a key file that only the current user can read.
+    pub fn write_key_file(&self, path: &Path) -> Result<()> {
+        use std::io::Write;
+        let mut file = create_private_file(path)
+            .with_context(|| format!("Failed to create key file: {}", path.display()))?;
+        file.write_all(format!("{}\n", STANDARD.encode(self.0)).as_bytes())
+            .with_context(|| format!("Failed to write key file: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// The key from the file named by `HTTPRUNNER_ENV_KEY_FILE`, the
+    /// passphrase in `HTTPRUNNER_ENV_PASSPHRASE`, or `http-client.env.key`
+    /// in `dir`, in that order.
+    pub fn resolve(dir: Option<&Path>) -> Result<Option<Self>> {
+        if let Some(path) = std::env::var_os(KEY_FILE_ENV_VAR).filter(|path| !path.is_empty()) {
+            return Self::from_key_file(Path::new(&path)).map(Some);
+        }
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR)
+            && !passphrase.is_empty()
+        {
+            return Ok(Some(Self::from_passphrase(&passphrase)));
+        }
+        match dir.map(|dir| dir.join(ENVIRONMENT_KEY_FILE)) {
+            Some(path) if path.is_file() => Self::from_key_file(&path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> String {
+        use rand::RngExt;
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rng().fill(&mut nonce);
+
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .expect("AES-GCM encryption does not fail for in-memory values");
+
+        let mut payload = nonce.to_vec();
+        payload.extend(ciphertext);
+        format!("{}{}", ENCRYPTED_VALUE_PREFIX, STANDARD.encode(payload))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        let encoded = value
+            .strip_prefix(ENCRYPTED_VALUE_PREFIX)
+            .ok_or_else(|| anyhow!("value is not encrypted"))?;
+        let payload = STANDARD
+            .decode(encoded)
+            .map_err(|_| anyhow!("encrypted value is not valid base64"))?;
+        if payload.len() <= NONCE_LEN {
+            bail!("encrypted value is too short");
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher()
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("wrong key or corrupted encrypted value"))?;
+        String::from_utf8(plaintext).map_err(|_| anyhow!("decrypted value is not valid UTF-8"))
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+    }
+}
+
+pub fn is_encrypted_value(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_VALUE_PREFIX)
+}
+
+/// Whether any string in `value` is encrypted.
+pub fn contains_encrypted_values(value: &Value) -> bool {
+    match value {
+        Value::String(s) => is_encrypted_value(s),
+        Value::Array(items) => items.iter().any(contains_encrypted_values),
+        Value::Object(map) => map.values().any(contains_encrypted_values),
+        _ => false,
+    }
+}
+
+/// Decrypt every encrypted string in `value` in place and return the
+/// decrypted values, so that output can redact them.
+pub fn decrypt_json(value: &mut Value, key: &EnvironmentKey) -> Result<Vec<String>> {
+    let mut decrypted = Vec::new();
+    decrypt_json_into(value, key, "", &mut decrypted)?;
+    Ok(decrypted)
+}
+
+fn decrypt_json_into(
+    value: &mut Value,
+    key: &EnvironmentKey,
+    path: &str,
+    decrypted: &mut Vec<String>,
+) -> Result<()> {
+    match value {
+        Value::String(s) if is_encrypted_value(s) => {
+            let plaintext = key
+                .decrypt(s)
+                .with_context(|| format!("Failed to decrypt environment value '{}'", path))?;
+            decrypted.push(plaintext.clone());
+            *s = plaintext;
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                decrypt_json_into(item, key, &join_path(path, &index.to_string()), decrypted)?;
+            }
+        }
+        Value::Object(map) => {
+            for (name, item) in map.iter_mut() {
+                decrypt_json_into(item, key, &join_path(path, name), decrypted)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// The encrypted values of the environment files loaded into an editor,
+/// including those nested in objects and arrays. They are decrypted for
+/// editing when a key is available, and encrypted again with
+/// [`EncryptedValues::encrypt`] before saving. Values that are unchanged keep
+/// their original ciphertext.
+#[derive(Debug, Default)]
+pub struct EncryptedValues {
+    key: Option<EnvironmentKey>,
+    /// (environment, path starting with the variable) -> (plaintext, original ciphertext)
+    values: HashMap<(String, Vec<String>), (Option<String>, String)>,
+}
+
+impl EncryptedValues {
+    pub fn new(key: Option<EnvironmentKey>) -> Self {
+        Self {
+            key,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Find the encrypted values of `config` and, with the right key, decrypt
+    /// them in place. Values that cannot be decrypted stay encrypted.
+    pub fn decrypt(&mut self, config: &mut HashMap<String, HashMap<String, Value>>) {
+        for (env_name, vars) in config.iter_mut() {
+            for (var_name, value) in vars.iter_mut() {
+                self.decrypt_value(env_name, &mut vec![var_name.clone()], value);
+            }
+        }
+    }
+
+    fn decrypt_value(&mut self, env_name: &str, path: &mut Vec<String>, value: &mut Value) {
+        match value {
+            Value::String(ciphertext) if is_encrypted_value(ciphertext) => {
+                let original = ciphertext.clone();
+                let plaintext = self
+                    .key
+                    .as_ref()
+                    .and_then(|key| key.decrypt(&original).ok());
+                if let Some(plaintext) = &plaintext {
+                    *ciphertext = plaintext.clone();
+                }
+                self.values
+                    .insert((env_name.to_string(), path.clone()), (plaintext, original));
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    path.push(index.to_string());
+                    self.decrypt_value(env_name, path, item);
+                    path.pop();
+                }
+            }
+            Value::Object(map) => {
+                for (name, item) in map.iter_mut() {
+                    path.push(name.clone());
+                    self.decrypt_value(env_name, path, item);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn values_of<'a>(
+        &'a self,
+        env_name: &'a str,
+        var_name: &'a str,
+    ) -> impl Iterator<Item = &'a (Option<String>, String)> {
+        self.values
+            .iter()
+            .filter(move |((env, path), _)| env == env_name && path[0] == var_name)
+            .map(|(_, value)| value)
+    }
+
+    /// Whether the value, or a value nested in it, was encrypted in the file.
+    pub fn is_encrypted(&self, env_name: &str, var_name: &str) -> bool {
+        self.values_of(env_name, var_name).next().is_some()
+    }
+
+    /// Whether the value, or a value nested in it, was encrypted in the file
+    /// but could not be decrypted, so the editor only has its ciphertext.
+    pub fn is_locked(&self, env_name: &str, var_name: &str) -> bool {
+        self.values_of(env_name, var_name)
+            .any(|(plaintext, _)| plaintext.is_none())
+    }
+
+    pub fn rename_variable(&mut self, env_name: &str, old_name: &str, new_name: &str) {
+        let renamed: Vec<_> = self
+            .values
+            .keys()
+            .filter(|(env, path)| env == env_name && path[0] == old_name)
+            .cloned()
+            .collect();
+        for key in renamed {
+            if let Some(value) = self.values.remove(&key) {
+                let (env, mut path) = key;
+                path[0] = new_name.to_string();
+                self.values.insert((env, path), value);
+            }
+        }
+    }
+
+    pub fn rename_environment(&mut self, old_name: &str, new_name: &str) {
+        let renamed: Vec<_> = self
+            .values
+            .keys()
+            .filter(|(env_name, _)| env_name == old_name)
+            .cloned()
+            .collect();
+        for key in renamed {
+            if let Some(value) = self.values.remove(&key) {
+                self.values.insert((new_name.to_string(), key.1), value);
+            }
+        }
+    }
+
+    /// `config` with the encrypted variables encrypted again. Fails when a
+    /// changed value has to be encrypted but there is no key.
+    pub fn encrypt(
+        &self,
+        config: &HashMap<String, HashMap<String, Value>>,
+    ) -> Result<HashMap<String, HashMap<String, Value>>> {
+        let mut encrypted = config.clone();
+        for ((env_name, path), (plaintext, original)) in &self.values {
+            let Some(Value::String(value)) = encrypted
+                .get_mut(env_name)
+                .and_then(|vars| vars.get_mut(&path[0]))
+                .and_then(|value| nested_value_mut(value, &path[1..]))
+            else {
+                continue;
+            };
+
+            if value == original || plaintext.as_deref() == Some(value.as_str()) {
+                *value = original.clone();
+            } else if !is_encrypted_value(value) {
+                let key = self.key.as_ref().ok_or_else(|| {
+                    anyhow!(
+                        "No key to encrypt '{}': set {} or {}, or create {}",
+                        path.join("."),
+                        KEY_FILE_ENV_VAR,
+                        PASSPHRASE_ENV_VAR,
+                        ENVIRONMENT_KEY_FILE
+                    )
+                })?;
+                *value = key.encrypt(value);
+            }
+        }
+        Ok(encrypted)
+    }
+}
+
+fn nested_value_mut<'a>(value: &'a mut Value, path: &[String]) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |value, segment| match value {
+        Value::Object(map) => map.get_mut(segment),
+        Value::Array(items) => items.get_mut(segment.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+/// Which values of an environment file to encrypt or decrypt: those of one
+/// environment or all of them, and the named variables or, with no names,
+/// those with secret-looking names.
+#[derive(Debug, Default, Clone)]
+pub struct ValueSelection {
+    pub environment: Option<String>,
+    pub names: Vec<String>,
+}
+
+impl ValueSelection {
+    fn matches_environment(&self, env_name: &str) -> bool {
+        self.environment
+            .as_deref()
+            .is_none_or(|environment| environment == env_name)
+    }
+
+    fn matches(&self, env_name: &str, var_name: &str) -> bool {
+        if !self.matches_environment(env_name) {
+            return false;
+        }
+        if self.names.is_empty() {
+            super::is_secret_variable(var_name)
+        } else {
+            self.names.iter().any(|name| name == var_name)
+        }
+    }
+
+    /// Keys nested in a variable's value are only selected by name, since
+    /// names such as `Token URL` look secret but are not.
+    fn matches_nested(&self, env_name: &str, key: &str) -> bool {
+        self.matches_environment(env_name) && self.names.iter().any(|name| name == key)
+    }
+}
+
+/// Encrypt the selected plain string values of an environment file in place
+/// and return how many were encrypted.
+pub fn encrypt_environment_file(
+    path: &Path,
+    key: &EnvironmentKey,
+    selection: &ValueSelection,
+) -> Result<usize> {
+    update_values(
+        path,
+        |env_name, var_name| selection.matches(env_name, var_name),
+        |env_name, key| selection.matches_nested(env_name, key),
+        |value| {
+            if is_encrypted_value(value) {
+                Ok(None)
+            } else {
+                Ok(Some(key.encrypt(value)))
+            }
+        },
+    )
+}
+
+/// Decrypt the selected values of an environment file in place and return
+/// how many were decrypted. With no names, every encrypted value is selected.
+pub fn decrypt_environment_file(
+    path: &Path,
+    key: &EnvironmentKey,
+    selection: &ValueSelection,
+) -> Result<usize> {
+    update_values(
+        path,
+        |env_name, var_name| {
+            if selection.names.is_empty() {
+                selection.matches_environment(env_name)
+            } else {
+                selection.matches(env_name, var_name)
+            }
+        },
+        |env_name, key| selection.matches_nested(env_name, key),
+        |value| {
+            if is_encrypted_value(value) {
+                key.decrypt(value).map(Some)
+            } else {
+                Ok(None)
+            }
+        },
+    )
+}
+
+/// Encrypt every encrypted value of an environment file again with a new key
+/// and return how many were rotated.
+pub fn rotate_environment_file(
+    path: &Path,
+    old_key: &EnvironmentKey,
+    new_key: &EnvironmentKey,
+) -> Result<usize> {
+    update_values(
+        path,
+        |_, _| true,
+        |_, _| true,
+        |value| {
+            if is_encrypted_value(value) {
+                Ok(Some(new_key.encrypt(&old_key.decrypt(value)?)))
+            } else {
+                Ok(None)
+            }
+        },
+    )
+}
+
+/// Replace the strings that `select` picks with what `update` returns for
+/// them, then save the file when anything changed. A string nested in objects
+/// and arrays is picked when `select` picks its variable or `select_nested`
+/// any key on the way to it. The rest of the file, including the order of its
+/// keys, is kept as it was.
+fn update_values(
+    path: &Path,
+    select: impl Fn(&str, &str) -> bool,
+    select_nested: impl Fn(&str, &str) -> bool,
+    update: impl Fn(&str) -> Result<Option<String>>,
+) -> Result<usize> {
+    let mut config: Value = fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| Ok(serde_json::from_str(&content)?))
+        .with_context(|| format!("Failed to read environment file: {}", path.display()))?;
+
+    let mut updated = 0;
+    let environments = config.as_object_mut().into_iter().flatten();
+    for (env_name, vars) in environments {
+        let Value::Object(vars) = vars else {
+            continue;
+        };
+        let select_nested = |key: &str| select_nested(env_name, key);
+        let update = |text: &str, path: &str| {
+            update(text).with_context(|| format!("Failed to update '{}' in '{}'", path, env_name))
+        };
+        for (var_name, value) in vars.iter_mut() {
+            update_value(
+                value,
+                select(env_name, var_name),
+                &select_nested,
+                &update,
+                var_name,
+                &mut updated,
+            )?;
+        }
+    }
+
+    if updated > 0 {
+        fs::write(path, serde_json::to_string_pretty(&config)?)
+            .with_context(|| format!("Failed to write environment file: {}", path.display()))?;
+    }
+    Ok(updated)
+}
+
+fn update_value(
+    value: &mut Value,
+    selected: bool,
+    select: &impl Fn(&str) -> bool,
+    update: &impl Fn(&str, &str) -> Result<Option<String>>,
+    path: &str,
+    updated: &mut usize,
+) -> Result<()> {
+    match value {
+        Value::String(text) if selected => {
+            if let Some(new_text) = update(text, path)? {
+                *text = new_text;
+                *updated += 1;
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                let path = join_path(path, &index.to_string());
+                update_value(item, selected, select, update, &path, updated)?;
+            }
+        }
+        Value::Object(map) => {
+            for (name, item) in map.iter_mut() {
+                let selected = selected || select(name);
+                update_value(
+                    item,
+                    selected,
+                    select,
+                    update,
+                    &join_path(path, name),
+                    updated,
+                )?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Create or truncate `path` so that only the current user can read it. The
+/// mode is also set on a file that already existed with wider permissions.
+#[cfg(unix)]
+pub(crate) fn create_private_file(path: &Path) -> std::io::Result<fs::File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn create_private_file(path: &Path) -> std::io::Result<fs::File> {
+    fs::File::create(path)
+}
@@ -1,65 +1,378 @@
-use crate::types::Variable;
-use anyhow::Result;
+use super::encryption::{
+    ENVIRONMENT_KEY_FILE, EnvironmentKey, KEY_FILE_ENV_VAR, PASSPHRASE_ENV_VAR,
+    contains_encrypted_values, decrypt_json,
+};
+use crate::parser::substitute_variables;
+use crate::types::{OAuthClientAuth, OAuthConfig, OAuthGrant, Variable};
+use anyhow::{Context, Result, anyhow, bail};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub const ENVIRONMENT_FILE: &str = "http-client.env.json";
+pub const PRIVATE_ENVIRONMENT_FILE: &str = "http-client.private.env.json";
+/// Environment whose values apply to every other environment.
+pub const SHARED_ENVIRONMENT: &str = "$shared";
+/// Key naming the environment an environment inherits from.
+pub const EXTENDS_KEY: &str = "$extends";
+
+/// The public environment file and the git-ignored private file next to it.
+/// Either may not exist yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentFiles {
+    pub public: PathBuf,
+    pub private: PathBuf,
+}
+
+impl EnvironmentFiles {
+    pub fn in_dir(dir: &Path) -> Self {
+        Self {
+            public: dir.join(ENVIRONMENT_FILE),
+            private: dir.join(PRIVATE_ENVIRONMENT_FILE),
+        }
+    }
+}
+
 pub fn load_environment_file(
     http_file_path: &str,
     environment_name: Option<&str>,
 ) -> Result<Vec<Variable>> {
-    let mut variables = Vec::new();
+    Ok(load_environment_with_secrets(http_file_path, environment_name)?.0)
+}
+
+/// The variables of the selected environment and the values among them that
+/// were decrypted, so that output can redact them.
+pub(crate) fn load_environment_with_secrets(
+    http_file_path: &str,
+    environment_name: Option<&str>,
+) -> Result<(Vec<Variable>, Vec<String>)> {
+    let Some(environment_name) = environment_name else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+    let Some((environment, decrypted)) = resolve_environment(http_file_path, environment_name)?
+    else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+
+    Ok((environment_variables(&environment), decrypted))
+}
+
+/// Load the OAuth 2.0 configurations from the `Security.Auth` section of the
+/// selected environment. Settings may reference the environment's variables.
+pub fn load_auth_configs(
+    http_file_path: &str,
+    environment_name: Option<&str>,
+) -> Result<HashMap<String, OAuthConfig>> {
+    let Some(environment_name) = environment_name else {
+        return Ok(HashMap::new());
+    };
+    let Some((environment, _)) = resolve_environment(http_file_path, environment_name)? else {
+        return Ok(HashMap::new());
+    };
+
+    let variables = environment_variables(&environment);
+    parse_auth_configs(&environment, &variables)
+}
+
+/// The names of the environments defined in the public and private files,
+/// sorted and without `$shared`.
+pub fn load_environment_names(http_file_path: &str) -> Result<Vec<String>> {
+    let Some(files) = find_environment_files(http_file_path)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut names = Vec::new();
+    for path in [&files.public, &files.private] {
+        if let Value::Object(root) = read_environment_json(path)? {
+            names.extend(
+                root.keys()
+                    .filter(|name| *name != SHARED_ENVIRONMENT)
+                    .cloned(),
+            );
+        }
+    }
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Load a JSON object of variables, such as the one given with `--vars-file`.
+/// Non-string values are converted the same way as environment values.
+pub fn load_variables_file(file_path: &Path) -> Result<Vec<Variable>> {
+    let content = fs::read_to_string(file_path)
+        .with_context(|| format!("Failed to read variables file: {}", file_path.display()))?;
+    let json: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse variables file: {}", file_path.display()))?;
+    if !json.is_object() {
+        bail!(
+            "Variables file {} must contain a JSON object",
+            file_path.display()
+        );
+    }
+
+    Ok(environment_variables(&json))
+}
+
+/// Whether a variable should be stored in the private environment file.
+pub fn is_secret_variable(name: &str) -> bool {
+    crate::redaction::is_sensitive_field_name(name)
+}
+
+/// Resolve an environment: `$shared` first, then the environments it
+/// `$extends`, then the environment itself, with the private file's values
+/// taking precedence over the public file's at each step. Encrypted values
+/// are decrypted and returned alongside the environment.
+fn resolve_environment(
+    http_file_path: &str,
+    environment_name: &str,
+) -> Result<Option<(Value, Vec<String>)>> {
+    let Some(files) = find_environment_files(http_file_path)? else {
+        return Ok(None);
+    };
+
+    let public = read_environment_json(&files.public)?;
+    let private = read_environment_json(&files.private)?;
+    let Some(mut environment) = merge_environment(&public, &private, environment_name)? else {
+        return Ok(None);
+    };
+
+    if !contains_encrypted_values(&environment) {
+        return Ok(Some((environment, Vec::new())));
+    }
+    let Some(key) = EnvironmentKey::resolve(files.public.parent())? else {
+        bail!(
+            "Environment '{}' has encrypted values but no key is available: set {} or {}, or create {}",
+            environment_name,
+            KEY_FILE_ENV_VAR,
+            PASSPHRASE_ENV_VAR,
+            ENVIRONMENT_KEY_FILE
+        );
+    };
+    let decrypted = decrypt_json(&mut environment, &key)?;
+    Ok(Some((environment, decrypted)))
+}
+
+fn merge_environment(public: &Value, private: &Value, name: &str) -> Result<Option<Value>> {
+    let lookup = |name: &str| -> Option<Value> {
+        match (public.get(name), private.get(name)) {
+            (None, None) => None,
+            (public, private) => {
+                let mut merged = Value::Object(serde_json::Map::new());
+                for environment in [public, private].into_iter().flatten() {
+                    merge_json(&mut merged, environment);
+                }
+                Some(merged)
+            }
+        }
+    };
 
-    if environment_name.is_none() {
-        return Ok(variables);
+    let mut chain: Vec<(String, Value)> = Vec::new();
+    let mut current = name.to_string();
+    loop {
+        let Some(environment) = lookup(&current) else {
+            return match chain.last() {
+                Some((child, _)) => Err(anyhow!(
+                    "Environment '{}' extends unknown environment '{}'",
+                    child,
+                    current
+                )),
+                None => Ok(None),
+            };
+        };
+        if chain.iter().any(|(seen, _)| *seen == current) {
+            bail!(
+                "Environment '{}' has a circular '{}' chain",
+                name,
+                EXTENDS_KEY
+            );
+        }
+
+        let parent = environment
+            .get(EXTENDS_KEY)
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        chain.push((current, environment));
+        match parent {
+            Some(parent) => current = parent,
+            None => break,
+        }
     }
 
-    let env_file_path = find_environment_file(http_file_path)?;
-    if env_file_path.is_none() {
-        return Ok(variables);
+    let mut merged =
+        lookup(SHARED_ENVIRONMENT).unwrap_or_else(|| Value::Object(Default::default()));
+    for (_, environment) in chain.iter().rev() {
+        merge_json(&mut merged, environment);
+    }
+    if let Value::Object(merged) = &mut merged {
+        merged.remove(EXTENDS_KEY);
     }
 
-    let env_config = parse_environment_file(&env_file_path.unwrap())?;
+    Ok(Some(merged))
+}
 
-    if let Some(env_vars) = env_config.get(environment_name.unwrap()) {
-        for (name, value) in env_vars {
-            variables.push(Variable {
-                name: name.clone(),
-                value: value.clone(),
-            });
+/// Merge `overlay` into `base`; objects are merged key by key, anything else
+/// is replaced.
+fn merge_json(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
         }
+        (base, overlay) => *base = overlay.clone(),
     }
+}
+
+fn environment_variables(environment: &Value) -> Vec<Variable> {
+    let Value::Object(vars) = environment else {
+        return Vec::new();
+    };
 
-    Ok(variables)
+    vars.iter()
+        .map(|(name, value)| Variable {
+            name: name.clone(),
+            value: environment_value_to_string(value),
+        })
+        .collect()
 }
 
-pub fn find_environment_file(http_file_path: &str) -> Result<Option<PathBuf>> {
-    let path = Path::new(http_file_path);
-    let mut current_dir = path
-        .parent()
-        .unwrap_or_else(|| Path::new("."))
-        .to_path_buf();
+/// The text substituted for an environment value. Objects and arrays become
+/// JSON, so `{{name}}` embeds them as-is and `{{name.key}}` can address them.
+pub fn environment_value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => String::new(),
+        // For objects and arrays, convert to JSON string
+        _ => value.to_string(),
+    }
+}
 
-    loop {
-        let env_file = current_dir.join("http-client.env.json");
-        if env_file.exists() {
-            return Ok(Some(env_file));
+/// The value to store for text entered in an editor. A value that was not a
+/// string keeps its type as long as the text is still valid JSON.
+pub fn environment_value_from_str(text: &str, previous: Option<&Value>) -> Value {
+    match previous {
+        Some(previous) if !previous.is_string() => {
+            serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
         }
+        _ => Value::String(text.to_string()),
+    }
+}
 
-        if let Some(parent) = current_dir.parent() {
-            current_dir = parent.to_path_buf();
-        } else {
-            break;
+/// An environment file as JSON, or an empty object when it does not exist.
+fn read_environment_json(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        return Ok(Value::Object(Default::default()));
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read environment file: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse environment file: {}", path.display()))
+}
+
+pub fn parse_auth_configs(
+    environment: &Value,
+    variables: &[Variable],
+) -> Result<HashMap<String, OAuthConfig>> {
+    let Some(Value::Object(auth)) = environment.pointer("/Security/Auth") else {
+        return Ok(HashMap::new());
+    };
+
+    let mut configs = HashMap::new();
+    for (name, settings) in auth {
+        let config = parse_auth_config(settings, variables)
+            .with_context(|| format!("Invalid auth configuration '{}'", name))?;
+        if let Some(config) = config {
+            configs.insert(name.clone(), config);
         }
     }
 
-    Ok(None)
+    Ok(configs)
 }
 
-pub fn parse_environment_file(
-    file_path: &Path,
-) -> Result<HashMap<String, HashMap<String, String>>> {
+/// Returns `None` for configurations that cannot run unattended, such as the
+/// browser-based Authorization Code, Implicit and Device Authorization grants.
+fn parse_auth_config(settings: &Value, variables: &[Variable]) -> Result<Option<OAuthConfig>> {
+    let setting = |key: &str| {
+        settings
+            .get(key)
+            .and_then(Value::as_str)
+            .map(|value| substitute_variables(value, variables))
+            .filter(|value| !value.is_empty())
+    };
+    let required = |key: &str| setting(key).ok_or_else(|| anyhow!("missing '{}'", key));
+
+    if let Some(auth_type) = setting("Type")
+        && !auth_type.eq_ignore_ascii_case("oauth2")
+    {
+        return Ok(None);
+    }
+
+    let grant_type = setting("Grant Type").unwrap_or_else(|| "Client Credentials".to_string());
+    let grant = match grant_type.to_ascii_lowercase().as_str() {
+        "client credentials" => OAuthGrant::ClientCredentials,
+        "password" => OAuthGrant::Password {
+            username: required("Username")?,
+            password: required("Password")?,
+        },
+        "refresh token" => OAuthGrant::RefreshToken {
+            refresh_token: required("Refresh Token")?,
+        },
+        _ => return Ok(None),
+    };
+
+    let client_auth = match setting("Client Credentials")
+        .map(|value| value.to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("basic") => OAuthClientAuth::Basic,
+        Some("in body") => OAuthClientAuth::InBody,
+        Some("none") => OAuthClientAuth::None,
+        Some(other) => bail!(
+            "unknown 'Client Credentials' value '{}', expected 'basic', 'in body' or 'none'",
+            other
+        ),
+    };
+
+    Ok(Some(OAuthConfig {
+        token_url: required("Token URL")?,
+        client_id: required("Client ID")?,
+        client_secret: setting("Client Secret"),
+        client_auth,
+        scope: setting("Scope"),
+        grant,
+    }))
+}
+
+pub fn find_environment_file(http_file_path: &str) -> Result<Option<PathBuf>> {
+    Ok(find_environment_files(http_file_path)?
+        .map(|files| files.public)
+        .filter(|public| public.exists()))
+}
+
+/// Find the nearest directory, starting at the `.http` file's, that holds a
+/// public or private environment file.
+pub fn find_environment_files(http_file_path: &str) -> Result<Option<EnvironmentFiles>> {
+    let path = Path::new(http_file_path);
+    let start = path.parent().unwrap_or_else(|| Path::new("."));
+
+    Ok(start
+        .ancestors()
+        .map(EnvironmentFiles::in_dir)
+        .find(|files| files.public.exists() || files.private.exists()))
+}
+
+/// Read an environment file as `environment -> variable -> value`, keeping
+/// the values' JSON types.
+pub fn parse_environment_file(file_path: &Path) -> Result<HashMap<String, HashMap<String, Value>>> {
     let content = fs::read_to_string(file_path)?;
     let json: Value = serde_json::from_str(&content)?;
 
@@ -67,23 +380,12 @@ pub fn parse_environment_file(
 
     if let Value::Object(root) = json {
         for (env_name, env_value) in root {
-            let mut env_vars = HashMap::new();
-
-            if let Value::Object(vars) = env_value {
-                for (var_name, var_value) in vars {
-                    let value_str = match var_value {
-                        Value::String(s) => s.clone(),
-                        Value::Number(n) => n.to_string(),
-                        Value::Bool(b) => b.to_string(),
-                        Value::Null => String::new(),
-                        // For objects and arrays, convert to JSON string
-                        _ => var_value.to_string(),
-                    };
-                    env_vars.insert(var_name.clone(), value_str);
-                }
-            }
+            let env_vars = match env_value {
+                Value::Object(vars) => vars.into_iter().collect(),
+                _ => HashMap::new(),
+            };
 
-            config.insert(env_name.clone(), env_vars);
+            config.insert(env_name, env_vars);
         }
     }
 
@@ -92,7 +394,7 @@ pub fn parse_environment_file(
 
 pub fn save_environment_file(
     file_path: &Path,
-    config: &HashMap<String, HashMap<String, String>>,
+    config: &HashMap<String, HashMap<String, Value>>,
 ) -> Result<()> {
     // Build a sorted JSON object for deterministic output
     let mut root = serde_json::Map::new();
@@ -105,7 +407,7 @@ pub fn save_environment_file(
         let mut var_names: Vec<&String> = vars.keys().collect();
         var_names.sort();
         for var_name in var_names {
-            env_obj.insert(var_name.clone(), Value::String(vars[var_name].clone()));
+            env_obj.insert(var_name.clone(), vars[var_name].clone());
         }
         root.insert(env_name.clone(), Value::Object(env_obj));
     }
@@ -1,10 +1,27 @@
+mod dotenv;
+mod encryption;
 mod loader;
 
-pub use loader::load_environment_file;
+pub use dotenv::{find_dotenv_file, load_dotenv_file, parse_dotenv};
+pub use loader::{
+    ENVIRONMENT_FILE, EXTENDS_KEY, EnvironmentFiles, PRIVATE_ENVIRONMENT_FILE, SHARED_ENVIRONMENT,
+    load_auth_configs, load_environment_file, load_variables_file, parse_auth_configs,
+};
+pub(crate) use encryption::create_private_file;
+pub(crate) use loader::load_environment_with_secrets;
+pub use encryption::{
+    ENCRYPTED_VALUE_PREFIX, ENVIRONMENT_KEY_FILE, EncryptedValues, EnvironmentKey,
+    KEY_FILE_ENV_VAR, PASSPHRASE_ENV_VAR, ValueSelection, decrypt_environment_file,
+    encrypt_environment_file, is_encrypted_value, rotate_environment_file,
+};
 
 // Export for GUI use
 #[allow(unused_imports)]
-pub use loader::{find_environment_file, parse_environment_file, save_environment_file};
+pub use loader::{
+    environment_value_from_str, environment_value_to_string, find_environment_file,
+    find_environment_files, is_secret_variable, load_environment_names, parse_environment_file,
+    save_environment_file,
+};
 
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,5 @@
 use super::*;
+use serde_json::json;
 use std::fs;
 use tempfile::tempdir;
 
@@ -42,7 +43,7 @@ fn find_environment_file_returns_none_when_absent() {
 }
 
 #[test]
-fn parse_environment_file_handles_non_string_values() {
+fn parse_environment_file_keeps_value_types() {
     let temp = tempdir().unwrap();
     let env_file = temp.path().join("http-client.env.json");
     fs::write(
@@ -53,9 +54,9 @@ fn parse_environment_file_handles_non_string_values() {
 
     let parsed = parse_environment_file(&env_file).unwrap();
     let dev = parsed.get("dev").unwrap();
-    assert_eq!(dev.get("TEXT").unwrap(), "value");
-    assert_eq!(dev.get("NUMBER").unwrap(), "123");
-    assert_eq!(dev.get("OBJECT").unwrap(), r#"{"foo":"bar"}"#);
+    assert_eq!(dev["TEXT"], json!("value"));
+    assert_eq!(dev["NUMBER"], json!(123));
+    assert_eq!(dev["OBJECT"], json!({"foo": "bar"}));
 }
 
 #[test]
@@ -95,8 +96,8 @@ fn save_environment_file_writes_valid_json() {
 
     let mut config = std::collections::HashMap::new();
     let mut dev_vars = std::collections::HashMap::new();
-    dev_vars.insert("API_URL".to_string(), "http://localhost".to_string());
-    dev_vars.insert("TOKEN".to_string(), "abc123".to_string());
+    dev_vars.insert("API_URL".to_string(), json!("http://localhost"));
+    dev_vars.insert("TOKEN".to_string(), json!("abc123"));
     config.insert("dev".to_string(), dev_vars);
 
     save_environment_file(&env_file, &config).unwrap();
@@ -104,8 +105,8 @@ fn save_environment_file_writes_valid_json() {
     // Read back and verify
     let parsed = parse_environment_file(&env_file).unwrap();
     let dev = parsed.get("dev").unwrap();
-    assert_eq!(dev.get("API_URL").unwrap(), "http://localhost");
-    assert_eq!(dev.get("TOKEN").unwrap(), "abc123");
+    assert_eq!(dev["API_URL"], json!("http://localhost"));
+    assert_eq!(dev["TOKEN"], json!("abc123"));
 }
 
 #[test]
@@ -116,23 +117,621 @@ fn save_environment_file_preserves_multiple_environments() {
     let mut config = std::collections::HashMap::new();
 
     let mut dev_vars = std::collections::HashMap::new();
-    dev_vars.insert("URL".to_string(), "http://dev.example.com".to_string());
+    dev_vars.insert("URL".to_string(), json!("http://dev.example.com"));
     config.insert("dev".to_string(), dev_vars);
 
     let mut prod_vars = std::collections::HashMap::new();
-    prod_vars.insert("URL".to_string(), "https://prod.example.com".to_string());
+    prod_vars.insert("URL".to_string(), json!("https://prod.example.com"));
     config.insert("prod".to_string(), prod_vars);
 
     save_environment_file(&env_file, &config).unwrap();
 
     let parsed = parse_environment_file(&env_file).unwrap();
     assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed["dev"]["URL"], json!("http://dev.example.com"));
+    assert_eq!(parsed["prod"]["URL"], json!("https://prod.example.com"));
+}
+
+#[test]
+fn load_auth_configs_reads_security_auth_with_variables() {
+    let temp = tempdir().unwrap();
+    let http_file = temp.path().join("request.http");
+    fs::write(&http_file, "GET http://example.com").unwrap();
+    fs::write(
+        temp.path().join("http-client.env.json"),
+        r#"{
+          "dev": {
+            "idp": "https://idp.example.com",
+            "Security": {
+              "Auth": {
+                "service": {
+                  "Type": "OAuth2",
+                  "Grant Type": "Client Credentials",
+                  "Token URL": "{{idp}}/token",
+                  "Client ID": "runner",
+                  "Client Secret": "s3cret",
+                  "Client Credentials": "in body",
+                  "Scope": "read write"
+                },
+                "user": {
+                  "Grant Type": "Password",
+                  "Token URL": "{{idp}}/token",
+                  "Client ID": "runner",
+                  "Username": "alice",
+                  "Password": "wonderland"
+                },
+                "browser": {
+                  "Type": "OAuth2",
+                  "Grant Type": "Authorization Code",
+                  "Auth URL": "{{idp}}/authorize",
+                  "Token URL": "{{idp}}/token",
+                  "Client ID": "runner"
+                }
+              }
+            }
+          }
+        }"#,
+    )
+    .unwrap();
+
+    let configs = load_auth_configs(http_file.to_str().unwrap(), Some("dev")).unwrap();
+
+    assert_eq!(configs.len(), 2);
+    let service = &configs["service"];
+    assert_eq!(service.token_url, "https://idp.example.com/token");
+    assert_eq!(service.client_secret.as_deref(), Some("s3cret"));
+    assert_eq!(service.client_auth, crate::types::OAuthClientAuth::InBody);
+    assert_eq!(service.scope.as_deref(), Some("read write"));
+    assert_eq!(service.grant, crate::types::OAuthGrant::ClientCredentials);
+    assert_eq!(
+        configs["user"].grant,
+        crate::types::OAuthGrant::Password {
+            username: "alice".to_string(),
+            password: "wonderland".to_string(),
+        }
+    );
     assert_eq!(
-        parsed.get("dev").unwrap().get("URL").unwrap(),
-        "http://dev.example.com"
+        configs["user"].client_auth,
+        crate::types::OAuthClientAuth::Basic
     );
+}
+
+#[test]
+fn parse_auth_configs_rejects_incomplete_configuration() {
+    let environment: serde_json::Value = serde_json::from_str(
+        r#"{"Security":{"Auth":{"service":{"Grant Type":"Password","Token URL":"https://idp/token","Client ID":"runner"}}}}"#,
+    )
+    .unwrap();
+
+    let error = parse_auth_configs(&environment, &[]).unwrap_err();
     assert_eq!(
-        parsed.get("prod").unwrap().get("URL").unwrap(),
-        "https://prod.example.com"
+        format!("{error:#}"),
+        "Invalid auth configuration 'service': missing 'Username'"
+    );
+}
+
+#[test]
+fn parse_dotenv_reads_plain_and_quoted_values() {
+    let values = parse_dotenv(
+        r#"
+# comment
+API_KEY=abc123
+export REGION = eu-west-1
+GREETING="hello\nworld"
+LITERAL='no \n escapes'
+PORT=8080 # inline comment
+HASH="a # b"
+not a pair
+"#,
+    );
+
+    assert_eq!(values.get("API_KEY"), Some(&"abc123".to_string()));
+    assert_eq!(values.get("REGION"), Some(&"eu-west-1".to_string()));
+    assert_eq!(values.get("GREETING"), Some(&"hello\nworld".to_string()));
+    assert_eq!(values.get("LITERAL"), Some(&"no \\n escapes".to_string()));
+    assert_eq!(values.get("PORT"), Some(&"8080".to_string()));
+    assert_eq!(values.get("HASH"), Some(&"a # b".to_string()));
+    assert_eq!(values.len(), 6);
+}
+
+#[test]
+fn load_dotenv_file_reads_nearest_dotenv_file() {
+    let temp = tempdir().unwrap();
+    let nested = temp.path().join("nested");
+    fs::create_dir(&nested).unwrap();
+    fs::write(temp.path().join(".env"), "API_KEY=from-parent").unwrap();
+
+    assert_eq!(find_dotenv_file(&nested), Some(temp.path().join(".env")));
+    let values = load_dotenv_file(&nested).unwrap();
+    assert_eq!(values.get("API_KEY"), Some(&"from-parent".to_string()));
+
+    fs::write(nested.join(".env"), "API_KEY=from-nested").unwrap();
+    let values = load_dotenv_file(&nested).unwrap();
+    assert_eq!(values.get("API_KEY"), Some(&"from-nested".to_string()));
+}
+
+fn write_environment_files(public: &str, private: Option<&str>) -> (tempfile::TempDir, String) {
+    let temp = tempdir().unwrap();
+    let http_file = temp.path().join("request.http");
+    fs::write(&http_file, "GET http://example.com").unwrap();
+    fs::write(temp.path().join("http-client.env.json"), public).unwrap();
+    if let Some(private) = private {
+        fs::write(temp.path().join("http-client.private.env.json"), private).unwrap();
+    }
+    let http_file = http_file.to_str().unwrap().to_string();
+    (temp, http_file)
+}
+
+fn load_map(http_file: &str, environment: &str) -> std::collections::HashMap<String, String> {
+    load_environment_file(http_file, Some(environment))
+        .unwrap()
+        .into_iter()
+        .map(|v| (v.name, v.value))
+        .collect()
+}
+
+#[test]
+fn load_environment_file_merges_private_file_over_public_file() {
+    let (_temp, http_file) = write_environment_files(
+        r#"{"dev":{"HOST":"https://dev","TOKEN":"placeholder"}}"#,
+        Some(r#"{"dev":{"TOKEN":"real-token"}}"#),
     );
+
+    let map = load_map(&http_file, "dev");
+    assert_eq!(map.get("HOST"), Some(&"https://dev".to_string()));
+    assert_eq!(map.get("TOKEN"), Some(&"real-token".to_string()));
+}
+
+#[test]
+fn load_environment_file_applies_shared_environment_and_extends() {
+    let (_temp, http_file) = write_environment_files(
+        r#"{
+            "$shared": {"VERSION": "v1", "HOST": "https://shared"},
+            "dev": {"HOST": "https://dev", "USER": "dev-user"},
+            "dev-eu": {"$extends": "dev", "REGION": "eu"}
+        }"#,
+        Some(r#"{"$shared": {"PASSWORD": "s3cret"}, "dev": {"USER": "private-user"}}"#),
+    );
+
+    let map = load_map(&http_file, "dev-eu");
+    assert_eq!(map.get("VERSION"), Some(&"v1".to_string()));
+    assert_eq!(map.get("HOST"), Some(&"https://dev".to_string()));
+    assert_eq!(map.get("USER"), Some(&"private-user".to_string()));
+    assert_eq!(map.get("REGION"), Some(&"eu".to_string()));
+    assert_eq!(map.get("PASSWORD"), Some(&"s3cret".to_string()));
+    assert!(!map.contains_key("$extends"));
+}
+
+#[test]
+fn load_environment_file_rejects_unknown_and_circular_extends() {
+    let (_temp, http_file) = write_environment_files(
+        r#"{
+            "a": {"$extends": "b"},
+            "b": {"$extends": "a"},
+            "c": {"$extends": "missing"}
+        }"#,
+        None,
+    );
+
+    let error = load_environment_file(&http_file, Some("a")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Environment 'a' has a circular '$extends' chain"
+    );
+    let error = load_environment_file(&http_file, Some("c")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Environment 'c' extends unknown environment 'missing'"
+    );
+}
+
+#[test]
+fn load_environment_file_reads_private_file_without_public_file() {
+    let temp = tempdir().unwrap();
+    let http_file = temp.path().join("request.http");
+    fs::write(
+        temp.path().join("http-client.private.env.json"),
+        r#"{"dev":{"TOKEN":"abc"}}"#,
+    )
+    .unwrap();
+    let http_file = http_file.to_str().unwrap();
+
+    assert_eq!(find_environment_file(http_file).unwrap(), None);
+    assert_eq!(
+        find_environment_files(http_file).unwrap(),
+        Some(EnvironmentFiles::in_dir(temp.path()))
+    );
+    assert_eq!(
+        load_map(http_file, "dev").get("TOKEN"),
+        Some(&"abc".to_string())
+    );
+}
+
+#[test]
+fn load_environment_names_lists_both_files_without_shared() {
+    let (_temp, http_file) = write_environment_files(
+        r#"{"$shared":{},"prod":{},"dev":{}}"#,
+        Some(r#"{"dev":{},"local":{}}"#),
+    );
+
+    assert_eq!(
+        load_environment_names(&http_file).unwrap(),
+        vec!["dev", "local", "prod"]
+    );
+}
+
+#[test]
+fn load_auth_configs_reads_secrets_from_private_file() {
+    let (_temp, http_file) = write_environment_files(
+        r#"{"dev":{"Security":{"Auth":{"service":{"Token URL":"https://idp/token","Client ID":"runner","Client Secret":"{{ClientSecret}}"}}}}}"#,
+        Some(r#"{"dev":{"ClientSecret":"from-private"}}"#),
+    );
+
+    let configs = load_auth_configs(&http_file, Some("dev")).unwrap();
+    assert_eq!(
+        configs["service"].client_secret.as_deref(),
+        Some("from-private")
+    );
+}
+
+#[test]
+fn is_secret_variable_matches_sensitive_names() {
+    assert!(is_secret_variable("ApiKey"));
+    assert!(is_secret_variable("client_secret"));
+    assert!(is_secret_variable("PASSWORD"));
+    assert!(!is_secret_variable("HostAddress"));
+}
+
+#[test]
+fn load_variables_file_reads_json_object_values() {
+    let temp = tempdir().unwrap();
+    let vars_file = temp.path().join("vars.json");
+    fs::write(
+        &vars_file,
+        r#"{"host":"example.com","port":8080,"debug":true}"#,
+    )
+    .unwrap();
+
+    let mut variables = load_variables_file(&vars_file).unwrap();
+    variables.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let pairs: Vec<(&str, &str)> = variables
+        .iter()
+        .map(|variable| (variable.name.as_str(), variable.value.as_str()))
+        .collect();
+    assert_eq!(
+        pairs,
+        vec![("debug", "true"), ("host", "example.com"), ("port", "8080")]
+    );
+}
+
+#[test]
+fn load_variables_file_rejects_non_object_json() {
+    let temp = tempdir().unwrap();
+    let vars_file = temp.path().join("vars.json");
+    fs::write(&vars_file, r#"["host"]"#).unwrap();
+
+    let error = load_variables_file(&vars_file).unwrap_err();
+    assert!(error.to_string().contains("must contain a JSON object"));
+}
+
+#[test]
+fn save_environment_file_preserves_value_types() {
+    let temp = tempdir().unwrap();
+    let env_file = temp.path().join("http-client.env.json");
+    let original = r#"{"dev":{"port":8080,"debug":true,"tenant":{"id":5,"tags":["a","b"]},"host":"localhost"}}"#;
+    fs::write(&env_file, original).unwrap();
+
+    let config = parse_environment_file(&env_file).unwrap();
+    save_environment_file(&env_file, &config).unwrap();
+
+    let saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&env_file).unwrap()).unwrap();
+    let original: serde_json::Value = serde_json::from_str(original).unwrap();
+    assert_eq!(saved, original);
+}
+
+#[test]
+fn environment_value_from_str_keeps_non_string_types() {
+    assert_eq!(
+        environment_value_from_str("8081", Some(&json!(8080))),
+        json!(8081)
+    );
+    assert_eq!(
+        environment_value_from_str(r#"{"id":6}"#, Some(&json!({"id": 5}))),
+        json!({"id": 6})
+    );
+    assert_eq!(
+        environment_value_from_str("not json", Some(&json!(8080))),
+        json!("not json")
+    );
+    assert_eq!(
+        environment_value_from_str("8081", Some(&json!("8080"))),
+        json!("8081")
+    );
+    assert_eq!(environment_value_from_str("true", None), json!("true"));
+}
+
+#[test]
+fn encrypted_values_round_trip_and_reject_the_wrong_key() {
+    let key = EnvironmentKey::from_passphrase("correct horse");
+    let encrypted = key.encrypt("s3cret");
+
+    assert!(is_encrypted_value(&encrypted));
+    assert_ne!(encrypted, key.encrypt("s3cret"));
+    assert_eq!(key.decrypt(&encrypted).unwrap(), "s3cret");
+    assert!(
+        EnvironmentKey::from_passphrase("wrong")
+            .decrypt(&encrypted)
+            .is_err()
+    );
+}
+
+#[test]
+fn load_environment_file_decrypts_values_with_key_file() {
+    let key = EnvironmentKey::generate();
+    let public = json!({"dev": {"host": "localhost", "apiKey": key.encrypt("s3cret")}});
+    let (temp, http_file) = write_environment_files(&public.to_string(), None);
+    key.write_key_file(&temp.path().join(ENVIRONMENT_KEY_FILE))
+        .unwrap();
+
+    let values = load_map(&http_file, "dev");
+    assert_eq!(values.get("apiKey"), Some(&"s3cret".to_string()));
+    assert_eq!(values.get("host"), Some(&"localhost".to_string()));
+
+    let (_, secrets) = load_environment_with_secrets(&http_file, Some("dev")).unwrap();
+    assert_eq!(secrets, vec!["s3cret"]);
+}
+
+#[test]
+fn load_environment_file_fails_for_encrypted_values_without_key() {
+    let key = EnvironmentKey::generate();
+    let public = json!({"dev": {"apiKey": key.encrypt("s3cret")}});
+    let (_temp, http_file) = write_environment_files(&public.to_string(), None);
+
+    let error = load_environment_file(&http_file, Some("dev")).unwrap_err();
+    assert!(error.to_string().contains("no key is available"));
+}
+
+#[test]
+fn encrypt_decrypt_and_rotate_environment_file() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join(ENVIRONMENT_FILE);
+    fs::write(
+        &path,
+        r#"{"dev":{"host":"localhost","apiKey":"a","password":"b"},"prod":{"apiKey":"c"}}"#,
+    )
+    .unwrap();
+    let key = EnvironmentKey::generate();
+    let dev = ValueSelection {
+        environment: Some("dev".to_string()),
+        names: Vec::new(),
+    };
+
+    assert_eq!(encrypt_environment_file(&path, &key, &dev).unwrap(), 2);
+    let config = parse_environment_file(&path).unwrap();
+    assert_eq!(config["dev"]["host"], json!("localhost"));
+    assert!(is_encrypted_value(config["dev"]["apiKey"].as_str().unwrap()));
+    assert_eq!(config["prod"]["apiKey"], json!("c"));
+
+    let new_key = EnvironmentKey::generate();
+    assert_eq!(rotate_environment_file(&path, &key, &new_key).unwrap(), 2);
+    let config = parse_environment_file(&path).unwrap();
+    let password = config["dev"]["password"].as_str().unwrap();
+    assert_eq!(new_key.decrypt(password).unwrap(), "b");
+    assert!(key.decrypt(password).is_err());
+
+    let password_only = ValueSelection {
+        environment: None,
+        names: vec!["password".to_string()],
+    };
+    assert_eq!(
+        decrypt_environment_file(&path, &new_key, &password_only).unwrap(),
+        1
+    );
+    let config = parse_environment_file(&path).unwrap();
+    assert_eq!(config["dev"]["password"], json!("b"));
+    assert!(is_encrypted_value(config["dev"]["apiKey"].as_str().unwrap()));
+}
+
+#[test]
+fn encrypt_decrypt_and_rotate_nested_environment_values() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join(ENVIRONMENT_FILE);
+    let key = EnvironmentKey::generate();
+    fs::write(
+        &path,
+        json!({
+            "dev": {
+                "db": {"host": "localhost", "password": "b"},
+                "keys": [key.encrypt("first"), "plain"],
+                "service": {"credentials": {"user": "u", "pass": "p"}}
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let all = ValueSelection {
+        environment: None,
+        names: Vec::new(),
+    };
+    let password = ValueSelection {
+        environment: None,
+        names: vec!["password".to_string()],
+    };
+
+    // Nested keys are not picked by their secret-looking names alone
+    assert_eq!(encrypt_environment_file(&path, &key, &all).unwrap(), 0);
+    assert_eq!(encrypt_environment_file(&path, &key, &password).unwrap(), 1);
+    let config = parse_environment_file(&path).unwrap();
+    assert_eq!(config["dev"]["db"]["host"], json!("localhost"));
+    assert!(is_encrypted_value(
+        config["dev"]["db"]["password"].as_str().unwrap()
+    ));
+
+    let credentials = ValueSelection {
+        environment: None,
+        names: vec!["credentials".to_string()],
+    };
+    assert_eq!(
+        encrypt_environment_file(&path, &key, &credentials).unwrap(),
+        2
+    );
+
+    let new_key = EnvironmentKey::generate();
+    assert_eq!(rotate_environment_file(&path, &key, &new_key).unwrap(), 4);
+    let config = parse_environment_file(&path).unwrap();
+    let first = config["dev"]["keys"][0].as_str().unwrap();
+    assert_eq!(new_key.decrypt(first).unwrap(), "first");
+    assert!(key.decrypt(first).is_err());
+    assert_eq!(config["dev"]["keys"][1], json!("plain"));
+
+    assert_eq!(decrypt_environment_file(&path, &new_key, &all).unwrap(), 4);
+    let config = parse_environment_file(&path).unwrap();
+    assert_eq!(config["dev"]["db"]["password"], json!("b"));
+    assert_eq!(config["dev"]["service"]["credentials"]["pass"], json!("p"));
+}
+
+#[test]
+fn rotate_environment_file_reports_nested_value_path() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join(ENVIRONMENT_FILE);
+    let other_key = EnvironmentKey::generate();
+    fs::write(
+        &path,
+        json!({"dev": {"db": {"password": other_key.encrypt("b")}}}).to_string(),
+    )
+    .unwrap();
+
+    let error = rotate_environment_file(
+        &path,
+        &EnvironmentKey::generate(),
+        &EnvironmentKey::generate(),
+    )
+    .unwrap_err();
+
+    assert!(format!("{error:#}").contains("Failed to update 'db.password' in 'dev'"));
+}
+
+#[test]
+fn encrypt_environment_file_keeps_key_order() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join(ENVIRONMENT_FILE);
+    fs::write(
+        &path,
+        r#"{"prod":{"zone":"eu","apiKey":"a"},"dev":{"token":"b","host":"localhost"}}"#,
+    )
+    .unwrap();
+    let all = ValueSelection {
+        environment: None,
+        names: vec!["apiKey".to_string(), "token".to_string()],
+    };
+
+    let key = EnvironmentKey::generate();
+    assert_eq!(encrypt_environment_file(&path, &key, &all).unwrap(), 2);
+
+    let content = fs::read_to_string(&path).unwrap();
+    let positions: Vec<usize> = ["prod", "zone", "apiKey", "dev", "token", "host"]
+        .iter()
+        .map(|name| content.find(&format!("\"{}\"", name)).unwrap())
+        .collect();
+    assert!(positions.is_sorted(), "{content}");
+}
+
+#[cfg(unix)]
+#[test]
+fn write_key_file_is_only_readable_by_owner() {
+    use std::os::unix::fs::PermissionsExt;
+    let temp = tempdir().unwrap();
+    let path = temp.path().join(ENVIRONMENT_KEY_FILE);
+    fs::write(&path, "old").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+    let key = EnvironmentKey::generate();
+    key.write_key_file(&path).unwrap();
+
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    assert_eq!(EnvironmentKey::from_key_file(&path).unwrap(), key);
+}
+
+#[test]
+fn encrypted_values_keep_unchanged_ciphertext_and_encrypt_edits() {
+    let key = EnvironmentKey::generate();
+    let original = key.encrypt("one");
+    let mut config: std::collections::HashMap<_, _> = [(
+        "dev".to_string(),
+        [
+            ("apiKey".to_string(), json!(original)),
+            ("token".to_string(), json!(key.encrypt("two"))),
+        ]
+        .into(),
+    )]
+    .into();
+
+    let mut values = EncryptedValues::new(Some(key.clone()));
+    values.decrypt(&mut config);
+    assert_eq!(config["dev"]["apiKey"], json!("one"));
+    assert!(values.is_encrypted("dev", "token"));
+    assert!(!values.is_locked("dev", "token"));
+
+    config
+        .get_mut("dev")
+        .unwrap()
+        .insert("token".to_string(), json!("changed"));
+    let saved = values.encrypt(&config).unwrap();
+    assert_eq!(saved["dev"]["apiKey"], json!(original));
+    assert_eq!(
+        key.decrypt(saved["dev"]["token"].as_str().unwrap()).unwrap(),
+        "changed"
+    );
+
+    let mut without_key = EncryptedValues::new(None);
+    without_key.decrypt(&mut saved.clone());
+    assert!(without_key.is_locked("dev", "token"));
+    let mut edited = saved.clone();
+    edited
+        .get_mut("dev")
+        .unwrap()
+        .insert("apiKey".to_string(), json!("plain"));
+    assert!(without_key.encrypt(&saved).is_ok());
+    assert!(without_key.encrypt(&edited).is_err());
+}
+
+#[test]
+fn encrypted_values_handle_nested_values() {
+    let key = EnvironmentKey::generate();
+    let original = key.encrypt("one");
+    let mut config: std::collections::HashMap<_, _> = [(
+        "dev".to_string(),
+        [(
+            "db".to_string(),
+            json!({"host": "localhost", "passwords": [original, key.encrypt("two")]}),
+        )]
+        .into(),
+    )]
+    .into();
+
+    let mut values = EncryptedValues::new(Some(key.clone()));
+    values.decrypt(&mut config);
+    assert_eq!(
+        config["dev"]["db"],
+        json!({"host": "localhost", "passwords": ["one", "two"]})
+    );
+    assert!(values.is_encrypted("dev", "db"));
+    assert!(!values.is_locked("dev", "db"));
+
+    config.get_mut("dev").unwrap().insert(
+        "db".to_string(),
+        json!({"host": "localhost", "passwords": ["one", "changed"]}),
+    );
+    let saved = values.encrypt(&config).unwrap();
+    assert_eq!(saved["dev"]["db"]["host"], json!("localhost"));
+    assert_eq!(saved["dev"]["db"]["passwords"][0], json!(original));
+    let changed = saved["dev"]["db"]["passwords"][1].as_str().unwrap();
+    assert_eq!(key.decrypt(changed).unwrap(), "changed");
+
+    values.rename_variable("dev", "db", "database");
+    assert!(values.is_encrypted("dev", "database"));
+    assert!(!values.is_encrypted("dev", "db"));
 }
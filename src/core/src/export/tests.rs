@@ -20,6 +20,7 @@ fn sample_request(name: &str, method: &str, url: &str) -> HttpRequest {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     }
 }
 
@@ -33,6 +34,7 @@ fn sample_result(status: u16, success: bool, duration: u64) -> HttpResult {
         response_headers: None,
         response_body: None,
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     }
 }
 
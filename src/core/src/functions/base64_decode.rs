@@ -0,0 +1,97 @@
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use base64::Engine;
+use base64::engine::general_purpose;
+
+const BASE64_DECODE_PATTERN: &str = r"\bbase64_decode\(\s*'((?:[^'\\]|\\.)*)'\s*\)";
+
+pub struct Base64DecodeSubstitutor {}
+impl FunctionSubstitutor for Base64DecodeSubstitutor {
+    fn get_regex(&self) -> &str {
+        r"(?!)"
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(BASE64_DECODE_PATTERN)?;
+        Ok(re.replace_all(input, decode_capture).to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(BASE64_DECODE_PATTERN, cache)?;
+        Ok(re.replace_all(input, decode_capture).to_string())
+    }
+}
+
+// Input that is not valid Base64 leaves the call as written.
+fn decode_capture(caps: &regex::Captures) -> String {
+    match general_purpose::STANDARD.decode(caps[1].trim()) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(_) => caps[0].to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_decode_basic() {
+        let sub = Base64DecodeSubstitutor {};
+        let result = sub
+            .replace("base64_decode('SGVsbG8sIFdvcmxkIQ==')")
+            .unwrap();
+
+        assert_eq!(result, "Hello, World!");
+    }
+
+    #[test]
+    fn test_base64_decode_case_insensitive_with_spaces() {
+        let sub = Base64DecodeSubstitutor {};
+        let result = sub
+            .replace("user: BASE64_DECODE( 'dXNlcm5hbWU=' )")
+            .unwrap();
+
+        assert_eq!(result, "user: username");
+    }
+
+    #[test]
+    fn test_base64_decode_empty_string() {
+        let sub = Base64DecodeSubstitutor {};
+        let result = sub.replace("base64_decode('')").unwrap();
+
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn test_base64_decode_invalid_input_is_left_unchanged() {
+        let sub = Base64DecodeSubstitutor {};
+        let input = "base64_decode('not base64!')";
+        let result = sub.replace(input).unwrap();
+
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn test_base64_decode_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = Base64DecodeSubstitutor {};
+        let result = sub
+            .replace_with_cache("base64_decode('YWJj')", &cache)
+            .unwrap();
+
+        assert_eq!(result, "abc");
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -1,9 +1,15 @@
-use super::substitution::FunctionSubstitutor;
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use chrono::TimeDelta;
 
 pub struct GetDateSubstitutor {}
 impl FunctionSubstitutor for GetDateSubstitutor {
     fn get_regex(&self) -> &str {
-        r"\bgetdate\(\)"
+        r"\bgetdate\(\s*(?:'((?:[^'\\]|\\.)*)'\s*(?:,\s*'((?:[^'\\]|\\.)*)'\s*)?)?\)"
     }
 
     fn generate(&self) -> String {
@@ -11,6 +17,80 @@ impl FunctionSubstitutor for GetDateSubstitutor {
         let local: DateTime<Local> = Local::now();
         local.format("%Y-%m-%d").to_string()
     }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(self.get_regex())?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                replace_date(caps, "%Y-%m-%d")
+            })
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(self.get_regex(), cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                replace_date(caps, "%Y-%m-%d")
+            })
+            .to_string())
+    }
+}
+
+/// Formats the local time for a date function called as `f()`, `f('<offset>')`,
+/// `f('<format>')` or `f('<format>', '<offset>')`. An unknown format specifier
+/// or offset leaves the call as written.
+pub(super) fn replace_date(caps: &regex::Captures, default_format: &str) -> String {
+    let (format, offset) = match (caps.get(1), caps.get(2)) {
+        (Some(format), Some(offset)) => (format.as_str(), Some(offset.as_str())),
+        (Some(argument), None) if parse_offset(argument.as_str()).is_some() => {
+            (default_format, Some(argument.as_str()))
+        }
+        (Some(format), None) => (format.as_str(), None),
+        _ => (default_format, None),
+    };
+    format_local_now(format, offset).unwrap_or_else(|| caps[0].to_string())
+}
+
+fn format_local_now(format: &str, offset: Option<&str>) -> Option<String> {
+    use chrono::format::{Item, StrftimeItems};
+    use chrono::prelude::*;
+
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return None;
+    }
+    let offset = match offset {
+        Some(offset) => parse_offset(offset)?,
+        None => TimeDelta::zero(),
+    };
+    let local = Local::now().checked_add_signed(offset)?;
+    Some(local.format(format).to_string())
+}
+
+/// Parses an offset such as `+7d`, `-1h` or `+30m`. Supported units are
+/// `s`, `m`, `h`, `d` and `w`.
+pub(super) fn parse_offset(offset: &str) -> Option<TimeDelta> {
+    let offset = offset.trim();
+    let (negative, rest) = match offset.as_bytes().first()? {
+        b'+' => (false, &offset[1..]),
+        b'-' => (true, &offset[1..]),
+        _ => return None,
+    };
+    let (unit_index, unit) = rest.char_indices().next_back()?;
+    let amount: i64 = rest[..unit_index].parse().ok()?;
+    let amount = if negative { -amount } else { amount };
+    match unit {
+        's' => TimeDelta::try_seconds(amount),
+        'm' => TimeDelta::try_minutes(amount),
+        'h' => TimeDelta::try_hours(amount),
+        'd' => TimeDelta::try_days(amount),
+        'w' => TimeDelta::try_weeks(amount),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -37,4 +117,87 @@ mod tests {
             date
         );
     }
+
+    fn today_plus(days: i64) -> String {
+        (chrono::Local::now() + TimeDelta::days(days))
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    #[test]
+    fn test_getdate_with_offset() {
+        let sub = GetDateSubstitutor {};
+        let result = sub.replace("getdate('+7d')").unwrap();
+
+        assert!(
+            result == today_plus(7) || result == today_plus(8),
+            "Unexpected date '{}'",
+            result
+        );
+    }
+
+    #[test]
+    fn test_getdate_with_format_and_offset() {
+        let sub = GetDateSubstitutor {};
+        let result = sub.replace("GETDATE( '%Y/%m/%d' , '-1w' )").unwrap();
+
+        let date_pattern = Regex::new(r"^\d{4}/\d{2}/\d{2}$").unwrap();
+        assert!(
+            date_pattern.is_match(&result),
+            "Unexpected date '{}'",
+            result
+        );
+    }
+
+    #[test]
+    fn test_getdate_with_invalid_offset_is_left_unchanged() {
+        let sub = GetDateSubstitutor {};
+        let input = "getdate('%Y', '+7x')";
+        let result = sub.replace(input).unwrap();
+
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn test_getdate_with_invalid_format_is_left_unchanged() {
+        let sub = GetDateSubstitutor {};
+        let input = "getdate('%Q')";
+        let result = sub.replace(input).unwrap();
+
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn test_getdate_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = GetDateSubstitutor {};
+        let result = sub.replace_with_cache("getdate('%Y')", &cache).unwrap();
+
+        assert_eq!(result, chrono::Local::now().format("%Y").to_string());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("+7d"), Some(TimeDelta::days(7)));
+        assert_eq!(parse_offset("-1h"), Some(TimeDelta::hours(-1)));
+        assert_eq!(parse_offset(" +30m "), Some(TimeDelta::minutes(30)));
+        assert_eq!(parse_offset("+2w"), Some(TimeDelta::weeks(2)));
+        assert_eq!(parse_offset("-15s"), Some(TimeDelta::seconds(-15)));
+        assert_eq!(parse_offset("7d"), None);
+        assert_eq!(parse_offset("+d"), None);
+        assert_eq!(parse_offset("+7y"), None);
+        assert_eq!(parse_offset(""), None);
+    }
+
+    #[test]
+    fn test_parse_offset_rejects_non_ascii_unit() {
+        assert_eq!(parse_offset("+7é"), None);
+        assert_eq!(parse_offset("+é"), None);
+        assert_eq!(parse_offset("-1日"), None);
+
+        // Not an offset, so the argument is used as a format.
+        let sub = GetDateSubstitutor {};
+        assert_eq!(sub.replace("getdate('+7é')").unwrap(), "+7é");
+    }
 }
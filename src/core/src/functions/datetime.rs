@@ -1,9 +1,15 @@
-use super::substitution::FunctionSubstitutor;
+use super::date::replace_date;
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
 
 pub struct GetDateTimeSubstitutor {}
 impl FunctionSubstitutor for GetDateTimeSubstitutor {
     fn get_regex(&self) -> &str {
-        r"\bgetdatetime\(\)"
+        r"\bgetdatetime\(\s*(?:'((?:[^'\\]|\\.)*)'\s*(?:,\s*'((?:[^'\\]|\\.)*)'\s*)?)?\)"
     }
 
     fn generate(&self) -> String {
@@ -11,6 +17,28 @@ impl FunctionSubstitutor for GetDateTimeSubstitutor {
         let local: DateTime<Local> = Local::now();
         local.format("%Y-%m-%d %H:%M:%S").to_string()
     }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(self.get_regex())?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                replace_date(caps, "%Y-%m-%d %H:%M:%S")
+            })
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(self.get_regex(), cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                replace_date(caps, "%Y-%m-%d %H:%M:%S")
+            })
+            .to_string())
+    }
 }
 
 #[cfg(test)]
@@ -37,4 +65,63 @@ mod tests {
             datetime
         );
     }
+
+    #[test]
+    fn test_getdatetime_with_format_and_offset() {
+        let sub = GetDateTimeSubstitutor {};
+        let result = sub
+            .replace("getdatetime('%Y-%m-%dT%H:%M:%S', '-1d')")
+            .unwrap();
+
+        use chrono::NaiveDateTime;
+        let parsed = NaiveDateTime::parse_from_str(&result, "%Y-%m-%dT%H:%M:%S")
+            .expect("DateTime should use the given format");
+        let yesterday = chrono::Local::now().naive_local() - chrono::TimeDelta::days(1);
+        assert!((parsed - yesterday).num_seconds().abs() <= 5);
+    }
+
+    #[test]
+    fn test_getdatetime_with_offset_only() {
+        let sub = GetDateTimeSubstitutor {};
+        let result = sub.replace("GetDateTime( '+1h' )").unwrap();
+
+        use chrono::NaiveDateTime;
+        let parsed = NaiveDateTime::parse_from_str(&result, "%Y-%m-%d %H:%M:%S")
+            .expect("DateTime should use the default format");
+        let in_an_hour = chrono::Local::now().naive_local() + chrono::TimeDelta::hours(1);
+        assert!((parsed - in_an_hour).num_seconds().abs() <= 5);
+    }
+
+    #[test]
+    fn test_getdatetime_with_format_only() {
+        let sub = GetDateTimeSubstitutor {};
+        let result = sub.replace("getdatetime('%H:%M')").unwrap();
+
+        let time_pattern = Regex::new(r"^\d{2}:\d{2}$").unwrap();
+        assert!(
+            time_pattern.is_match(&result),
+            "Unexpected time '{}'",
+            result
+        );
+    }
+
+    #[test]
+    fn test_getdatetime_is_not_matched_by_getdate() {
+        use crate::functions::date::GetDateSubstitutor;
+
+        let input = "getdatetime('%Y')";
+        let result = GetDateSubstitutor {}.replace(input).unwrap();
+
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn test_getdatetime_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = GetDateTimeSubstitutor {};
+        let result = sub.replace_with_cache("getdatetime()", &cache).unwrap();
+
+        assert_eq!(result.len(), "YYYY-MM-DD HH:MM:SS".len());
+        assert_eq!(cache.len(), 1);
+    }
 }
@@ -0,0 +1,451 @@
+use super::date::parse_offset;
+use super::email::EmailSubstitutor;
+use super::first_name::FirstNameSubstitutor;
+use super::guid::GuidSubstitutor;
+use super::last_name::LastNameSubstitutor;
+use super::number::random_number;
+use super::string_gen::{MAX_GENERATED_LENGTH, random_string, random_string_from};
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use chrono::{DateTime, Local, TimeDelta, TimeZone, Utc};
+use std::fmt::Display;
+
+const DYNAMIC_VARIABLE_PATTERN: &str =
+    r"\{\{\s*\$([a-z][\w.]*)\s*(?:\(([^()}]*)\))?\s*([^}]*?)\s*\}\}";
+
+const ALPHABETIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const HEXADECIMAL: &[u8] = b"0123456789abcdef";
+
+/// Maps the dynamic variables of the VS Code REST Client (`{{$guid}}`,
+/// `{{$randomInt 1 100}}`, `{{$datetime iso8601 1 d}}`, ...) and the IntelliJ
+/// HTTP Client (`{{$uuid}}`, `{{$isoTimestamp}}`, `{{$random.alphabetic(10)}}`,
+/// ...) onto the built-in functions. Unknown names and arguments are left as
+/// written, so `{{$auth.token("name")}}` still reaches the token provider.
+/// `{{$processEnv NAME}}` is resolved by the parser alongside `{{$env.NAME}}`.
+pub struct DynamicVariableSubstitutor {}
+impl FunctionSubstitutor for DynamicVariableSubstitutor {
+    fn get_regex(&self) -> &str {
+        DYNAMIC_VARIABLE_PATTERN
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        if !input.contains("{{") {
+            return Ok(input.to_string());
+        }
+        let re = get_case_insensitive_regex(self.get_regex())?;
+        Ok(re.replace_all(input, replace_dynamic_variable).to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        if !input.contains("{{") {
+            return Ok(input.to_string());
+        }
+        let re = get_case_insensitive_regex_with_cache(self.get_regex(), cache)?;
+        Ok(re.replace_all(input, replace_dynamic_variable).to_string())
+    }
+}
+
+fn replace_dynamic_variable(caps: &regex::Captures) -> String {
+    let name = caps[1].to_ascii_lowercase();
+    let rest = caps.get(3).map_or("", |rest| rest.as_str());
+    let resolved = match caps.get(2) {
+        // IntelliJ style: `$random.integer(1, 10)`
+        Some(args) if rest.is_empty() => {
+            let args: Vec<&str> = args
+                .as_str()
+                .split(',')
+                .map(str::trim)
+                .filter(|arg| !arg.is_empty())
+                .collect();
+            resolve(&name, &args)
+        }
+        Some(_) => None,
+        // VS Code style: `$randomInt 1 100`
+        None => resolve(&name, &split_arguments(rest)),
+    };
+    resolved.unwrap_or_else(|| caps[0].to_string())
+}
+
+/// Splits VS Code style arguments on whitespace, keeping a quoted custom
+/// format such as `"DD MMM YYYY"` in one piece, quotes included.
+fn split_arguments(rest: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut rest = rest.trim_start();
+    while let Some(first) = rest.chars().next() {
+        let end = match first {
+            '"' | '\'' => rest[1..].find(first).map_or(rest.len(), |end| end + 2),
+            _ => rest.find(char::is_whitespace).unwrap_or(rest.len()),
+        };
+        args.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    args
+}
+
+/// Parses the length of a `$random.*` string, refusing lengths above
+/// `MAX_GENERATED_LENGTH`.
+fn random_length(length: &str) -> Option<usize> {
+    length
+        .parse()
+        .ok()
+        .filter(|length| *length <= MAX_GENERATED_LENGTH)
+}
+
+fn resolve(name: &str, args: &[&str]) -> Option<String> {
+    match (name, args) {
+        ("guid" | "uuid" | "random.uuid", []) => Some(hyphenated_guid()),
+        ("timestamp", offset) => Some(now_with_offset(Utc::now(), offset)?.timestamp().to_string()),
+        ("isotimestamp", []) => Some(Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
+        ("randomint" | "random.integer", []) => Some(random_number(0, 1000)),
+        // Both clients exclude the upper bound.
+        ("randomint" | "random.integer", [min, max]) => {
+            let (min, max) = (min.parse::<i64>().ok()?, max.parse::<i64>().ok()?);
+            (min < max).then(|| random_number(min, max - 1))
+        }
+        ("random.alphabetic", [length]) => {
+            Some(random_string_from(ALPHABETIC, random_length(length)?))
+        }
+        ("random.alphanumeric", [length]) => Some(random_string(random_length(length)?)),
+        ("random.hexadecimal", [length]) => {
+            Some(random_string_from(HEXADECIMAL, random_length(length)?))
+        }
+        ("random.email" | "randomemail", []) => Some(EmailSubstitutor {}.generate()),
+        ("random.firstname", []) => Some(FirstNameSubstitutor {}.generate()),
+        ("random.lastname", []) => Some(LastNameSubstitutor {}.generate()),
+        ("datetime", [format, offset @ ..]) => {
+            format_datetime(now_with_offset(Utc::now(), offset)?, format, "Z", "GMT")
+        }
+        ("localdatetime", [format, offset @ ..]) => {
+            format_datetime(now_with_offset(Local::now(), offset)?, format, "%:z", "%z")
+        }
+        _ => None,
+    }
+}
+
+fn hyphenated_guid() -> String {
+    let guid = GuidSubstitutor {}.generate();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &guid[..8],
+        &guid[8..12],
+        &guid[12..16],
+        &guid[16..20],
+        &guid[20..]
+    )
+}
+
+/// Applies a VS Code offset such as `1 d` or `-3 h`.
+fn now_with_offset<Tz: TimeZone>(now: DateTime<Tz>, offset: &[&str]) -> Option<DateTime<Tz>> {
+    match offset {
+        [] => Some(now),
+        [amount, unit] => {
+            if !matches!(*unit, "s" | "m" | "h" | "d" | "w") {
+                return None;
+            }
+            let sign = if amount.starts_with(['+', '-']) {
+                ""
+            } else {
+                "+"
+            };
+            let offset: TimeDelta = parse_offset(&format!("{sign}{amount}{unit}"))?;
+            now.checked_add_signed(offset)
+        }
+        _ => None,
+    }
+}
+
+fn format_datetime<Tz: TimeZone>(
+    datetime: DateTime<Tz>,
+    format: &str,
+    iso_zone: &str,
+    rfc_zone: &str,
+) -> Option<String>
+where
+    Tz::Offset: Display,
+{
+    let format = match format.to_ascii_lowercase().as_str() {
+        "iso8601" => format!("%Y-%m-%dT%H:%M:%S%.3f{iso_zone}"),
+        "rfc1123" => format!("%a, %d %b %Y %H:%M:%S {rfc_zone}"),
+        _ => custom_format(format)?,
+    };
+    Some(datetime.format(&format).to_string())
+}
+
+/// Day.js tokens of VS Code custom formats and their chrono equivalents,
+/// longest first so that `YYYY` is not read as two `YY`.
+const CUSTOM_FORMAT_TOKENS: &[(&str, &str)] = &[
+    ("YYYY", "%Y"),
+    ("MMMM", "%B"),
+    ("dddd", "%A"),
+    ("DDDD", "%j"),
+    ("MMM", "%b"),
+    ("ddd", "%a"),
+    ("SSS", "%3f"),
+    ("YY", "%y"),
+    ("MM", "%m"),
+    ("DD", "%d"),
+    ("HH", "%H"),
+    ("hh", "%I"),
+    ("mm", "%M"),
+    ("ss", "%S"),
+    ("ZZ", "%z"),
+    ("M", "%-m"),
+    ("D", "%-d"),
+    ("d", "%w"),
+    ("H", "%-H"),
+    ("h", "%-I"),
+    ("m", "%-M"),
+    ("s", "%-S"),
+    ("A", "%p"),
+    ("a", "%P"),
+    ("Z", "%:z"),
+    ("X", "%s"),
+    ("x", "%s%3f"),
+];
+
+/// Translates a quoted VS Code custom format such as `"DD-MM-YYYY"` into a
+/// chrono format. Text in `[brackets]` and characters that are no token are
+/// kept as written; an unquoted format is not a custom format.
+fn custom_format(format: &str) -> Option<String> {
+    let quote = format
+        .chars()
+        .next()
+        .filter(|quote| matches!(quote, '"' | '\''))?;
+    let mut rest = format[1..].strip_suffix(quote)?;
+
+    let mut translated = String::new();
+    while let Some(c) = rest.chars().next() {
+        if c == '[' {
+            let end = rest.find(']')?;
+            translated.push_str(&rest[1..end].replace('%', "%%"));
+            rest = &rest[end + 1..];
+        } else if let Some((token, chrono)) = CUSTOM_FORMAT_TOKENS
+            .iter()
+            .find(|(token, _)| rest.starts_with(token))
+        {
+            translated.push_str(chrono);
+            rest = &rest[token.len()..];
+        } else {
+            translated.push_str(if c == '%' {
+                "%%"
+            } else {
+                &rest[..c.len_utf8()]
+            });
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    Some(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    fn replace(input: &str) -> String {
+        DynamicVariableSubstitutor {}.replace(input).unwrap()
+    }
+
+    #[test]
+    fn test_uuid_and_guid_are_hyphenated() {
+        let pattern =
+            Regex::new(r"^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$")
+                .unwrap();
+
+        for input in ["{{$uuid}}", "{{$guid}}", "{{ $random.uuid }}"] {
+            let result = replace(input);
+            assert!(pattern.is_match(&result), "{input} gave '{result}'");
+        }
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let before = Utc::now().timestamp();
+        let timestamp: i64 = replace("{{$timestamp}}").parse().unwrap();
+
+        assert!((before..=Utc::now().timestamp()).contains(&timestamp));
+    }
+
+    #[test]
+    fn test_timestamp_with_offset() {
+        let expected = Utc::now().timestamp() - 3 * 3600;
+        let timestamp: i64 = replace("{{$timestamp -3 h}}").parse().unwrap();
+
+        assert!((timestamp - expected).abs() <= 5);
+    }
+
+    #[test]
+    fn test_iso_timestamp() {
+        let pattern = Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}\.\d{3}Z$").unwrap();
+
+        assert!(pattern.is_match(&replace("{{$isoTimestamp}}")));
+    }
+
+    #[test]
+    fn test_random_int_excludes_upper_bound() {
+        for _ in 0..100 {
+            let value: i64 = replace("{{$randomInt 1 3}}").parse().unwrap();
+            assert!((1..3).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_random_integer_intellij_style() {
+        for _ in 0..100 {
+            let value: i64 = replace("{{$random.integer(-2, 2)}}").parse().unwrap();
+            assert!((-2..2).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_random_int_with_empty_range_is_left_unchanged() {
+        assert_eq!(replace("{{$randomInt 5 5}}"), "{{$randomInt 5 5}}");
+    }
+
+    #[test]
+    fn test_random_strings() {
+        let alphabetic = replace("{{$random.alphabetic(10)}}");
+        assert_eq!(alphabetic.len(), 10);
+        assert!(alphabetic.chars().all(|c| c.is_ascii_alphabetic()));
+
+        let alphanumeric = replace("{{$random.alphanumeric(12)}}");
+        assert_eq!(alphanumeric.len(), 12);
+        assert!(alphanumeric.chars().all(|c| c.is_ascii_alphanumeric()));
+
+        let hexadecimal = replace("{{$random.hexadecimal(8)}}");
+        assert_eq!(hexadecimal.len(), 8);
+        assert!(hexadecimal.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_random_strings_above_length_limit_are_left_unchanged() {
+        for name in ["alphabetic", "alphanumeric", "hexadecimal"] {
+            let input = format!("{{{{$random.{name}({})}}}}", MAX_GENERATED_LENGTH + 1);
+            assert_eq!(replace(&input), input);
+        }
+    }
+
+    #[test]
+    fn test_random_email() {
+        assert!(replace("{{$random.email}}").contains('@'));
+        assert!(replace("{{$randomEmail}}").contains('@'));
+    }
+
+    #[test]
+    fn test_datetime_iso8601_with_offset() {
+        let result = replace("{{$datetime iso8601 1 d}}");
+        let parsed = DateTime::parse_from_rfc3339(&result).expect("ISO 8601 date");
+        let tomorrow = Utc::now() + TimeDelta::days(1);
+
+        assert!((parsed.timestamp() - tomorrow.timestamp()).abs() <= 5);
+    }
+
+    #[test]
+    fn test_datetime_with_unknown_offset_unit_is_left_unchanged() {
+        for input in [
+            "{{$datetime iso8601 1 é}}",
+            "{{$localDatetime iso8601 -2 日}}",
+            "{{$datetime iso8601 1 y}}",
+        ] {
+            assert_eq!(replace(input), input);
+        }
+    }
+
+    #[test]
+    fn test_datetime_rfc1123() {
+        let result = replace("{{$datetime rfc1123}}");
+
+        assert!(DateTime::parse_from_rfc2822(&result).is_ok(), "'{result}'");
+        assert!(result.ends_with(" GMT"));
+    }
+
+    #[test]
+    fn test_local_datetime_iso8601() {
+        let result = replace("{{$localDatetime iso8601}}");
+
+        assert!(DateTime::parse_from_rfc3339(&result).is_ok(), "'{result}'");
+    }
+
+    #[test]
+    fn test_datetime_with_custom_format() {
+        let expected = (Utc::now() + TimeDelta::days(1))
+            .format("%d-%m-%Y")
+            .to_string();
+
+        assert_eq!(replace(r#"{{$datetime "DD-MM-YYYY" 1 d}}"#), expected);
+    }
+
+    #[test]
+    fn test_datetime_with_custom_format_tokens() {
+        let datetime = Utc.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap();
+        let format = |format: &str| format_datetime(datetime, format, "Z", "GMT").unwrap();
+
+        assert_eq!(format(r#""YYYY-MM-DD HH:mm:ss""#), "2024-03-05 14:07:09");
+        assert_eq!(format("'D/M/YY h:m:s A'"), "5/3/24 2:7:9 PM");
+        assert_eq!(format(r#""dddd, MMMM D""#), "Tuesday, March 5");
+        assert_eq!(
+            format(r#""ddd MMM DD [at] HH[h] 100%""#),
+            "Tue Mar 05 at 14h 100%"
+        );
+        assert_eq!(format(r#""X""#), "1709647629");
+        assert_eq!(format(r#""x""#), "1709647629000");
+        assert_eq!(
+            format(r#""YYYY-MM-DDTHH:mm:ssZ""#),
+            "2024-03-05T14:07:09+00:00"
+        );
+    }
+
+    #[test]
+    fn test_local_datetime_with_custom_format_containing_spaces() {
+        let expected = Local::now().format("%d %b %Y").to_string();
+
+        assert_eq!(replace(r#"{{$localDatetime 'DD MMM YYYY'}}"#), expected);
+    }
+
+    #[test]
+    fn test_datetime_with_unsupported_format_is_left_unchanged() {
+        for input in [
+            "{{$datetime DD-MM-YYYY}}",
+            r#"{{$datetime "DD-MM-YYYY}}"#,
+            r#"{{$datetime "[unclosed"}}"#,
+        ] {
+            assert_eq!(replace(input), input);
+        }
+    }
+
+    #[test]
+    fn test_unknown_names_are_left_unchanged() {
+        for input in [
+            r#"{{$auth.token("my-auth")}}"#,
+            "{{$dotenv API_KEY}}",
+            "{{$processEnv API_KEY}}",
+            "{{$uuid extra}}",
+            "{{name}}",
+        ] {
+            assert_eq!(replace(input), input);
+        }
+    }
+
+    #[test]
+    fn test_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let result = DynamicVariableSubstitutor {}
+            .replace_with_cache("id={{$randomInt 7 8}}", &cache)
+            .unwrap();
+
+        assert_eq!(result, "id=7");
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+
+const HEX_ENCODE_PATTERN: &str = r"\bhex_encode\(\s*'((?:[^'\\]|\\.)*)'\s*\)";
+
+pub struct HexEncodeSubstitutor {}
+impl FunctionSubstitutor for HexEncodeSubstitutor {
+    fn get_regex(&self) -> &str {
+        r"(?!)"
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(HEX_ENCODE_PATTERN)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| hex::encode(&caps[1]))
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(HEX_ENCODE_PATTERN, cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| hex::encode(&caps[1]))
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_encode_basic() {
+        let sub = HexEncodeSubstitutor {};
+        let result = sub.replace("hex_encode('Hello')").unwrap();
+
+        assert_eq!(result, "48656c6c6f");
+    }
+
+    #[test]
+    fn test_hex_encode_unicode() {
+        let sub = HexEncodeSubstitutor {};
+        let result = sub.replace("HEX_ENCODE( 'é' )").unwrap();
+
+        assert_eq!(result, "c3a9");
+    }
+
+    #[test]
+    fn test_hex_encode_empty_string() {
+        let sub = HexEncodeSubstitutor {};
+        let result = sub.replace("hex_encode('')").unwrap();
+
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn test_hex_encode_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = HexEncodeSubstitutor {};
+        let result = sub.replace_with_cache("hex_encode('abc')", &cache).unwrap();
+
+        assert_eq!(result, "616263");
+        assert_eq!(cache.len(), 1);
+    }
+}
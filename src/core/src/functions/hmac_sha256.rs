@@ -0,0 +1,119 @@
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+const HMAC_SHA256_PATTERN: &str =
+    r"\bhmac_sha256\(\s*'((?:[^'\\]|\\.)*)'\s*,\s*'((?:[^'\\]|\\.)*)'\s*\)";
+
+pub struct HmacSha256Substitutor {}
+impl FunctionSubstitutor for HmacSha256Substitutor {
+    fn get_regex(&self) -> &str {
+        r"(?!)"
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(HMAC_SHA256_PATTERN)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                hmac_sha256(&caps[1], &caps[2])
+            })
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(HMAC_SHA256_PATTERN, cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                hmac_sha256(&caps[1], &caps[2])
+            })
+            .to_string())
+    }
+}
+
+fn hmac_sha256(key: &str, message: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha256_basic() {
+        let sub = HmacSha256Substitutor {};
+        let result = sub
+            .replace("hmac_sha256('key', 'The quick brown fox jumps over the lazy dog')")
+            .unwrap();
+
+        assert_eq!(
+            result,
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha256_without_spaces() {
+        let sub = HmacSha256Substitutor {};
+        let result = sub.replace("HMAC_SHA256('','')").unwrap();
+
+        assert_eq!(
+            result,
+            "b613679a0814d9ec772f95d778c35fc5ff1697c493715653c6c712144292c5ad"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha256_single_argument_is_not_replaced() {
+        let sub = HmacSha256Substitutor {};
+        let input = "hmac_sha256('key')";
+        let result = sub.replace(input).unwrap();
+
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn test_hmac_sha256_in_header() {
+        let sub = HmacSha256Substitutor {};
+        let result = sub
+            .replace(
+                "X-Signature: hmac_sha256( 'key' , 'The quick brown fox jumps over the lazy dog' )",
+            )
+            .unwrap();
+
+        assert_eq!(
+            result,
+            "X-Signature: f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha256_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = HmacSha256Substitutor {};
+        let result = sub
+            .replace_with_cache("hmac_sha256('', '')", &cache)
+            .unwrap();
+
+        assert_eq!(
+            result,
+            "b613679a0814d9ec772f95d778c35fc5ff1697c493715653c6c712144292c5ad"
+        );
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use md5::{Digest, Md5};
+
+const MD5_PATTERN: &str = r"\bmd5\(\s*'((?:[^'\\]|\\.)*)'\s*\)";
+
+pub struct Md5Substitutor {}
+impl FunctionSubstitutor for Md5Substitutor {
+    fn get_regex(&self) -> &str {
+        r"(?!)"
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(MD5_PATTERN)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                hex::encode(Md5::digest(&caps[1]))
+            })
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(MD5_PATTERN, cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                hex::encode(Md5::digest(&caps[1]))
+            })
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_md5_basic() {
+        let sub = Md5Substitutor {};
+        let result = sub.replace("md5('abc')").unwrap();
+
+        assert_eq!(result, "900150983cd24fb0d6963f7d28e17f72");
+    }
+
+    #[test]
+    fn test_md5_empty_string() {
+        let sub = Md5Substitutor {};
+        let result = sub.replace("md5('')").unwrap();
+
+        assert_eq!(result, "d41d8cd98f00b204e9800998ecf8427e");
+    }
+
+    #[test]
+    fn test_md5_json_body() {
+        let sub = Md5Substitutor {};
+        let result = sub.replace(r#"{"etag": "MD5('abc')"}"#).unwrap();
+
+        assert_eq!(result, r#"{"etag": "900150983cd24fb0d6963f7d28e17f72"}"#);
+    }
+
+    #[test]
+    fn test_md5_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = Md5Substitutor {};
+        let result = sub.replace_with_cache("md5('abc')", &cache).unwrap();
+
+        assert_eq!(result, "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(cache.len(), 1);
+    }
+}
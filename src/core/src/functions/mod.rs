@@ -1,21 +1,34 @@
 mod address;
+mod base64_decode;
 mod base64_encode;
 mod date;
 mod datetime;
+mod dynamic_variables;
 mod email;
 mod first_name;
 mod guid;
+mod hex_encode;
+mod hmac_sha256;
 mod job_title;
+mod jwt_decode_claim;
 mod last_name;
 mod lorem_ipsum;
 mod lower;
+mod md5;
 mod name;
 mod number;
+mod sha1;
+mod sha256;
 mod string_gen;
 mod substitution;
 mod time;
+mod timestamp;
+mod ulid;
 mod upper;
+mod url_decode;
+mod url_encode;
 mod utc_datetime;
+mod uuid_v7;
 
 pub use substitution::{
     substitute_functions, substitute_functions_with_cache, HashMapRegexCache, RegexCache,
@@ -1,18 +1,52 @@
-use super::substitution::FunctionSubstitutor;
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
 
 pub struct NumberSubstitutor {}
 impl FunctionSubstitutor for NumberSubstitutor {
     fn get_regex(&self) -> &str {
-        r"\bnumber\(\)"
+        r"\bnumber\(\s*(?:(-?\d+)\s*,\s*(-?\d+)\s*)?\)"
     }
 
     fn generate(&self) -> String {
-        use rand::RngExt;
+        random_number(0, 100)
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(self.get_regex())?;
+        Ok(re.replace_all(input, replace_number).to_string())
+    }
 
-        rand::rng().random_range(0..=100).to_string()
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(self.get_regex(), cache)?;
+        Ok(re.replace_all(input, replace_number).to_string())
     }
 }
 
+// `number(min, max)` with an empty or out of range bound is left as written.
+fn replace_number(caps: &regex::Captures) -> String {
+    let (Some(min), Some(max)) = (caps.get(1), caps.get(2)) else {
+        return random_number(0, 100);
+    };
+    match (min.as_str().parse::<i64>(), max.as_str().parse::<i64>()) {
+        (Ok(min), Ok(max)) if min <= max => random_number(min, max),
+        _ => caps[0].to_string(),
+    }
+}
+
+pub(super) fn random_number(min: i64, max: i64) -> String {
+    use rand::RngExt;
+
+    rand::rng().random_range(min..=max).to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +196,58 @@ mod tests {
             assert!((0..=100).contains(&num));
         }
     }
+
+    #[test]
+    fn test_number_with_range() {
+        let sub = NumberSubstitutor {};
+
+        for _ in 0..100 {
+            let num: i64 = sub.replace("number(1, 6)").unwrap().parse().unwrap();
+            assert!((1..=6).contains(&num));
+        }
+    }
+
+    #[test]
+    fn test_number_with_negative_range() {
+        let sub = NumberSubstitutor {};
+
+        for _ in 0..100 {
+            let num: i64 = sub.replace("NUMBER( -10 ,-5 )").unwrap().parse().unwrap();
+            assert!((-10..=-5).contains(&num));
+        }
+    }
+
+    #[test]
+    fn test_number_with_single_value_range() {
+        let sub = NumberSubstitutor {};
+        let result = sub.replace("id=number(7,7)").unwrap();
+
+        assert_eq!(result, "id=7");
+    }
+
+    #[test]
+    fn test_number_with_reversed_range_is_left_unchanged() {
+        let sub = NumberSubstitutor {};
+        let result = sub.replace("number(10, 1)").unwrap();
+
+        assert_eq!(result, "number(10, 1)");
+    }
+
+    #[test]
+    fn test_number_with_single_argument_is_not_matched() {
+        let sub = NumberSubstitutor {};
+        let result = sub.replace("number(10)").unwrap();
+
+        assert_eq!(result, "number(10)");
+    }
+
+    #[test]
+    fn test_number_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = NumberSubstitutor {};
+        let result = sub.replace_with_cache("number(3, 3)", &cache).unwrap();
+
+        assert_eq!(result, "3");
+        assert_eq!(cache.len(), 1);
+    }
 }
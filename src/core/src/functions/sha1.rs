@@ -0,0 +1,84 @@
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use sha1::{Digest, Sha1};
+
+const SHA1_PATTERN: &str = r"\bsha1\(\s*'((?:[^'\\]|\\.)*)'\s*\)";
+
+pub struct Sha1Substitutor {}
+impl FunctionSubstitutor for Sha1Substitutor {
+    fn get_regex(&self) -> &str {
+        r"(?!)"
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(SHA1_PATTERN)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                hex::encode(Sha1::digest(&caps[1]))
+            })
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(SHA1_PATTERN, cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                hex::encode(Sha1::digest(&caps[1]))
+            })
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha1_basic() {
+        let sub = Sha1Substitutor {};
+        let result = sub.replace("sha1('abc')").unwrap();
+
+        assert_eq!(result, "a9993e364706816aba3e25717850c26c9cd0d89d");
+    }
+
+    #[test]
+    fn test_sha1_empty_string() {
+        let sub = Sha1Substitutor {};
+        let result = sub.replace("sha1('')").unwrap();
+
+        assert_eq!(result, "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+    }
+
+    #[test]
+    fn test_sha1_multiple() {
+        let sub = Sha1Substitutor {};
+        let result = sub.replace("SHA1('abc') and sha1( '' )").unwrap();
+
+        assert_eq!(
+            result,
+            "a9993e364706816aba3e25717850c26c9cd0d89d and da39a3ee5e6b4b0d3255bfef95601890afd80709"
+        );
+    }
+
+    #[test]
+    fn test_sha1_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = Sha1Substitutor {};
+        let result = sub.replace_with_cache("sha1('abc')", &cache).unwrap();
+
+        assert_eq!(result, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use sha2::{Digest, Sha256};
+
+const SHA256_PATTERN: &str = r"\bsha256\(\s*'((?:[^'\\]|\\.)*)'\s*\)";
+
+pub struct Sha256Substitutor {}
+impl FunctionSubstitutor for Sha256Substitutor {
+    fn get_regex(&self) -> &str {
+        r"(?!)"
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(SHA256_PATTERN)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                hex::encode(Sha256::digest(&caps[1]))
+            })
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(SHA256_PATTERN, cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                hex::encode(Sha256::digest(&caps[1]))
+            })
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_basic() {
+        let sub = Sha256Substitutor {};
+        let result = sub.replace("sha256('abc')").unwrap();
+
+        assert_eq!(
+            result,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_sha256_empty_string() {
+        let sub = Sha256Substitutor {};
+        let result = sub.replace("sha256('')").unwrap();
+
+        assert_eq!(
+            result,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_sha256_case_insensitive_with_spaces() {
+        let sub = Sha256Substitutor {};
+        let result = sub.replace("X-Hash: SHA256( 'abc' )").unwrap();
+
+        assert_eq!(
+            result,
+            "X-Hash: ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_sha256_does_not_match_hmac_sha256() {
+        let sub = Sha256Substitutor {};
+        let input = "hmac_sha256('key', 'abc')";
+        let result = sub.replace(input).unwrap();
+
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn test_sha256_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = Sha256Substitutor {};
+        let result = sub.replace_with_cache("sha256('abc')", &cache).unwrap();
+
+        assert_eq!(
+            result,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -1,21 +1,67 @@
-use super::substitution::FunctionSubstitutor;
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+
+/// The longest string `string(N)` and the `$random.*` variables generate, so
+/// that a typo such as `string(99999999999)` cannot exhaust memory.
+pub(super) const MAX_GENERATED_LENGTH: usize = 1024 * 1024;
 
 pub struct StringSubstitutor {}
 impl FunctionSubstitutor for StringSubstitutor {
     fn get_regex(&self) -> &str {
-        r"\bstring\(\)"
+        r"\bstring\(\s*(\d+)?\s*\)"
     }
 
     fn generate(&self) -> String {
-        use rand::RngExt;
-        use rand::distr::Alphanumeric;
+        random_string(20)
+    }
 
-        rand::rng()
-            .sample_iter(Alphanumeric)
-            .take(20)
-            .map(char::from)
-            .collect()
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(self.get_regex())?;
+        Ok(re.replace_all(input, replace_string).to_string())
     }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(self.get_regex(), cache)?;
+        Ok(re.replace_all(input, replace_string).to_string())
+    }
+}
+
+// `string(N)` with a length above `MAX_GENERATED_LENGTH` is left as written.
+fn replace_string(caps: &regex::Captures) -> String {
+    match caps.get(1).map(|length| length.as_str().parse::<usize>()) {
+        None => random_string(20),
+        Some(Ok(length)) if length <= MAX_GENERATED_LENGTH => random_string(length),
+        Some(_) => caps[0].to_string(),
+    }
+}
+
+pub(super) fn random_string(length: usize) -> String {
+    use rand::RngExt;
+    use rand::distr::Alphanumeric;
+
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+/// A random string of `length` characters picked from `charset`.
+pub(super) fn random_string_from(charset: &[u8], length: usize) -> String {
+    use rand::RngExt;
+
+    let mut rng = rand::rng();
+    (0..length)
+        .map(|_| charset[rng.random_range(0..charset.len())] as char)
+        .collect()
 }
 
 #[cfg(test)]
@@ -141,4 +187,58 @@ mod tests {
             "Should generate mostly unique strings in 200 iterations"
         );
     }
+
+    #[test]
+    fn test_string_with_length() {
+        let sub = StringSubstitutor {};
+        let s = sub.replace("string(32)").unwrap();
+
+        assert_eq!(s.len(), 32);
+        assert!(s.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    #[test]
+    fn test_string_with_length_and_spaces() {
+        let sub = StringSubstitutor {};
+        let s = sub.replace("key=STRING( 5 )").unwrap();
+
+        assert!(s.starts_with("key="));
+        assert_eq!(s.len(), "key=".len() + 5);
+    }
+
+    #[test]
+    fn test_string_with_zero_length() {
+        let sub = StringSubstitutor {};
+        let s = sub.replace("[string(0)]").unwrap();
+
+        assert_eq!(s, "[]");
+    }
+
+    #[test]
+    fn test_string_with_length_above_limit_is_left_unchanged() {
+        let sub = StringSubstitutor {};
+        let input = format!("string({})", MAX_GENERATED_LENGTH + 1);
+
+        assert_eq!(sub.replace(&input).unwrap(), input);
+        assert_eq!(
+            sub.replace("string(99999999999999999999)").unwrap(),
+            "string(99999999999999999999)"
+        );
+        assert_eq!(
+            sub.replace(&format!("string({})", MAX_GENERATED_LENGTH))
+                .unwrap()
+                .len(),
+            MAX_GENERATED_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_string_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = StringSubstitutor {};
+        let s = sub.replace_with_cache("string(8)", &cache).unwrap();
+
+        assert_eq!(s.len(), 8);
+        assert_eq!(cache.len(), 1);
+    }
 }
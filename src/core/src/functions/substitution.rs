@@ -1,20 +1,33 @@
 use crate::functions::address::AddressSubstitutor;
+use crate::functions::base64_decode::Base64DecodeSubstitutor;
 use crate::functions::base64_encode::Base64EncodeSubstitutor;
 use crate::functions::date::GetDateSubstitutor;
 use crate::functions::datetime::GetDateTimeSubstitutor;
+use crate::functions::dynamic_variables::DynamicVariableSubstitutor;
 use crate::functions::email::EmailSubstitutor;
 use crate::functions::first_name::FirstNameSubstitutor;
 use crate::functions::guid::GuidSubstitutor;
+use crate::functions::hex_encode::HexEncodeSubstitutor;
+use crate::functions::hmac_sha256::HmacSha256Substitutor;
 use crate::functions::job_title::JobTitleSubstitutor;
+use crate::functions::jwt_decode_claim::JwtDecodeClaimSubstitutor;
 use crate::functions::last_name::LastNameSubstitutor;
 use crate::functions::lorem_ipsum::LoremIpsumSubstitutor;
 use crate::functions::lower::LowerSubstitutor;
+use crate::functions::md5::Md5Substitutor;
 use crate::functions::name::NameSubstitutor;
 use crate::functions::number::NumberSubstitutor;
+use crate::functions::sha1::Sha1Substitutor;
+use crate::functions::sha256::Sha256Substitutor;
 use crate::functions::string_gen::StringSubstitutor;
 use crate::functions::time::GetTimeSubstitutor;
+use crate::functions::timestamp::{TimestampMsSubstitutor, TimestampSubstitutor};
+use crate::functions::ulid::UlidSubstitutor;
 use crate::functions::upper::UpperSubstitutor;
+use crate::functions::url_decode::UrlDecodeSubstitutor;
+use crate::functions::url_encode::UrlEncodeSubstitutor;
 use crate::functions::utc_datetime::GetUtcDateTimeSubstitutor;
+use crate::functions::uuid_v7::UuidV7Substitutor;
 use anyhow::Result;
 use regex::{Regex, RegexBuilder};
 use std::collections::HashMap;
@@ -126,12 +139,26 @@ pub trait FunctionSubstitutor: Sync {
 
 pub fn substitute_functions(input: &str) -> Result<String> {
     const SUBSTITUTORS: &[&dyn FunctionSubstitutor] = &[
+        &DynamicVariableSubstitutor {} as &dyn FunctionSubstitutor,
         &GuidSubstitutor {} as &dyn FunctionSubstitutor,
+        &UuidV7Substitutor {} as &dyn FunctionSubstitutor,
+        &UlidSubstitutor {} as &dyn FunctionSubstitutor,
+        &TimestampSubstitutor {} as &dyn FunctionSubstitutor,
+        &TimestampMsSubstitutor {} as &dyn FunctionSubstitutor,
         &StringSubstitutor {} as &dyn FunctionSubstitutor,
         &NumberSubstitutor {} as &dyn FunctionSubstitutor,
         &Base64EncodeSubstitutor {} as &dyn FunctionSubstitutor,
         &UpperSubstitutor {} as &dyn FunctionSubstitutor,
         &LowerSubstitutor {} as &dyn FunctionSubstitutor,
+        &Base64DecodeSubstitutor {} as &dyn FunctionSubstitutor,
+        &UrlEncodeSubstitutor {} as &dyn FunctionSubstitutor,
+        &UrlDecodeSubstitutor {} as &dyn FunctionSubstitutor,
+        &HexEncodeSubstitutor {} as &dyn FunctionSubstitutor,
+        &Sha256Substitutor {} as &dyn FunctionSubstitutor,
+        &Sha1Substitutor {} as &dyn FunctionSubstitutor,
+        &Md5Substitutor {} as &dyn FunctionSubstitutor,
+        &HmacSha256Substitutor {} as &dyn FunctionSubstitutor,
+        &JwtDecodeClaimSubstitutor {} as &dyn FunctionSubstitutor,
         &NameSubstitutor {} as &dyn FunctionSubstitutor,
         &FirstNameSubstitutor {} as &dyn FunctionSubstitutor,
         &LastNameSubstitutor {} as &dyn FunctionSubstitutor,
@@ -158,12 +185,26 @@ pub fn substitute_functions_with_cache(
     cache: &dyn RegexCache,
 ) -> Result<String> {
     const SUBSTITUTORS: &[&dyn FunctionSubstitutor] = &[
+        &DynamicVariableSubstitutor {} as &dyn FunctionSubstitutor,
         &GuidSubstitutor {} as &dyn FunctionSubstitutor,
+        &UuidV7Substitutor {} as &dyn FunctionSubstitutor,
+        &UlidSubstitutor {} as &dyn FunctionSubstitutor,
+        &TimestampSubstitutor {} as &dyn FunctionSubstitutor,
+        &TimestampMsSubstitutor {} as &dyn FunctionSubstitutor,
         &StringSubstitutor {} as &dyn FunctionSubstitutor,
         &NumberSubstitutor {} as &dyn FunctionSubstitutor,
         &Base64EncodeSubstitutor {} as &dyn FunctionSubstitutor,
         &UpperSubstitutor {} as &dyn FunctionSubstitutor,
         &LowerSubstitutor {} as &dyn FunctionSubstitutor,
+        &Base64DecodeSubstitutor {} as &dyn FunctionSubstitutor,
+        &UrlEncodeSubstitutor {} as &dyn FunctionSubstitutor,
+        &UrlDecodeSubstitutor {} as &dyn FunctionSubstitutor,
+        &HexEncodeSubstitutor {} as &dyn FunctionSubstitutor,
+        &Sha256Substitutor {} as &dyn FunctionSubstitutor,
+        &Sha1Substitutor {} as &dyn FunctionSubstitutor,
+        &Md5Substitutor {} as &dyn FunctionSubstitutor,
+        &HmacSha256Substitutor {} as &dyn FunctionSubstitutor,
+        &JwtDecodeClaimSubstitutor {} as &dyn FunctionSubstitutor,
         &NameSubstitutor {} as &dyn FunctionSubstitutor,
         &FirstNameSubstitutor {} as &dyn FunctionSubstitutor,
         &LastNameSubstitutor {} as &dyn FunctionSubstitutor,
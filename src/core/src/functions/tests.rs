@@ -1561,3 +1561,65 @@ Content-Type: application/json
     assert!(result.contains("POST"));
     assert!(result.contains("Content-Type:"));
 }
+
+#[test]
+fn test_hashing_functions_take_substituted_variables() {
+    use crate::functions::substitute_functions;
+    use crate::parser::parse_http_content;
+
+    let content = r#"@secret = key
+@message = The quick brown fox jumps over the lazy dog
+
+POST https://api.example.com/webhooks
+X-Signature: hmac_sha256('{{secret}}', '{{message}}')
+X-Digest: sha256('{{secret}}')
+"#;
+    let requests = parse_http_content(content, None).unwrap();
+    let headers = &requests[0].headers;
+
+    assert_eq!(
+        substitute_functions(&headers[0].value).unwrap(),
+        "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+    );
+    assert_eq!(
+        substitute_functions(&headers[1].value).unwrap(),
+        "2c70e12b7a0646f92279f427c7b38e7334d8e5389cff167a1dc30e73f826b683"
+    );
+}
+
+#[test]
+fn test_encoding_functions_round_trip() {
+    use crate::functions::substitute_functions;
+
+    let input =
+        "base64_decode('aGVsbG8gd29ybGQ=') url_decode('a%2Fb') url_encode('a/b') hex_encode('hi')";
+    let result = substitute_functions(input).unwrap();
+
+    assert_eq!(result, "hello world a/b a%2Fb 6869");
+}
+
+#[test]
+fn test_dynamic_variables_survive_parsing() {
+    use crate::functions::substitute_functions;
+    use crate::parser::parse_http_content;
+
+    let content = r#"@request_id = {{$uuid}}
+
+GET https://api.example.com/items?limit={{$randomInt 1 100}}
+X-Request-Id: {{request_id}}
+X-Name: {{$random.alphabetic(10)}}
+"#;
+    let requests = parse_http_content(content, None).unwrap();
+    let request = &requests[0];
+
+    let url = substitute_functions(&request.url).unwrap();
+    let limit: i64 = url.rsplit('=').next().unwrap().parse().unwrap();
+    assert!((1..100).contains(&limit));
+
+    let request_id = substitute_functions(&request.headers[0].value).unwrap();
+    assert_eq!(request_id.len(), 36);
+
+    let name = substitute_functions(&request.headers[1].value).unwrap();
+    assert_eq!(name.len(), 10);
+    assert!(name.chars().all(|c| c.is_ascii_alphabetic()));
+}
@@ -0,0 +1,62 @@
+use super::substitution::FunctionSubstitutor;
+
+pub struct TimestampSubstitutor {}
+impl FunctionSubstitutor for TimestampSubstitutor {
+    fn get_regex(&self) -> &str {
+        r"\btimestamp\(\)"
+    }
+
+    fn generate(&self) -> String {
+        chrono::Utc::now().timestamp().to_string()
+    }
+}
+
+pub struct TimestampMsSubstitutor {}
+impl FunctionSubstitutor for TimestampMsSubstitutor {
+    fn get_regex(&self) -> &str {
+        r"\btimestamp_ms\(\)"
+    }
+
+    fn generate(&self) -> String {
+        chrono::Utc::now().timestamp_millis().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_is_unix_seconds() {
+        let before = chrono::Utc::now().timestamp();
+        let timestamp: i64 = TimestampSubstitutor {}.generate().parse().unwrap();
+        let after = chrono::Utc::now().timestamp();
+
+        assert!((before..=after).contains(&timestamp));
+    }
+
+    #[test]
+    fn test_timestamp_ms_is_unix_milliseconds() {
+        let before = chrono::Utc::now().timestamp_millis();
+        let timestamp: i64 = TimestampMsSubstitutor {}.generate().parse().unwrap();
+        let after = chrono::Utc::now().timestamp_millis();
+
+        assert!((before..=after).contains(&timestamp));
+    }
+
+    #[test]
+    fn test_timestamp_does_not_match_timestamp_ms() {
+        let input = "timestamp_ms()";
+        let result = TimestampSubstitutor {}.replace(input).unwrap();
+
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn test_timestamp_case_insensitive() {
+        let result = TimestampMsSubstitutor {}.replace("TIMESTAMP_MS()").unwrap();
+
+        assert_eq!(result.len(), 13);
+        assert!(result.chars().all(|c| c.is_ascii_digit()));
+    }
+}
@@ -0,0 +1,76 @@
+use super::substitution::FunctionSubstitutor;
+
+const CROCKFORD_BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Generates a ULID: a 48-bit millisecond timestamp followed by 80 random bits,
+/// written as 26 Crockford Base32 characters.
+pub struct UlidSubstitutor {}
+impl FunctionSubstitutor for UlidSubstitutor {
+    fn get_regex(&self) -> &str {
+        r"\bulid\(\)"
+    }
+
+    fn generate(&self) -> String {
+        use rand::RngExt;
+        let mut rng = rand::rng();
+        let mut bytes = [0u8; 16];
+        rng.fill(&mut bytes[6..]);
+        let millis = chrono::Utc::now().timestamp_millis() as u64;
+        bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+
+        let value = u128::from_be_bytes(bytes);
+        (0..26)
+            .map(|index| {
+                let shift = 125 - 5 * index;
+                CROCKFORD_BASE32[((value >> shift) & 0x1f) as usize] as char
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    fn decode_time(ulid: &str) -> i64 {
+        ulid[..10].chars().fold(0, |value, ch| {
+            let digit = CROCKFORD_BASE32
+                .iter()
+                .position(|&b| b as char == ch)
+                .unwrap();
+            value * 32 + digit as i64
+        })
+    }
+
+    #[test]
+    fn test_ulid_format() {
+        let ulid = UlidSubstitutor {}.generate();
+        let pattern = Regex::new(r"^[0-7][0-9A-HJKMNP-TV-Z]{25}$").unwrap();
+
+        assert!(pattern.is_match(&ulid), "Invalid ULID '{}'", ulid);
+    }
+
+    #[test]
+    fn test_ulid_encodes_current_time() {
+        let before = chrono::Utc::now().timestamp_millis();
+        let ulid = UlidSubstitutor {}.generate();
+        let after = chrono::Utc::now().timestamp_millis();
+
+        assert!((before..=after).contains(&decode_time(&ulid)));
+    }
+
+    #[test]
+    fn test_ulid_generates_different_values() {
+        let sub = UlidSubstitutor {};
+
+        assert_ne!(sub.generate(), sub.generate());
+    }
+
+    #[test]
+    fn test_ulid_replace() {
+        let result = UlidSubstitutor {}.replace("ULID()").unwrap();
+
+        assert_eq!(result.len(), 26);
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use percent_encoding::percent_decode_str;
+
+const URL_DECODE_PATTERN: &str = r"\burl_decode\(\s*'((?:[^'\\]|\\.)*)'\s*\)";
+
+pub struct UrlDecodeSubstitutor {}
+impl FunctionSubstitutor for UrlDecodeSubstitutor {
+    fn get_regex(&self) -> &str {
+        r"(?!)"
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(URL_DECODE_PATTERN)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                percent_decode_str(&caps[1])
+                    .decode_utf8_lossy()
+                    .into_owned()
+            })
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(URL_DECODE_PATTERN, cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                percent_decode_str(&caps[1])
+                    .decode_utf8_lossy()
+                    .into_owned()
+            })
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_decode_basic() {
+        let sub = UrlDecodeSubstitutor {};
+        let result = sub.replace("url_decode('a%20b%26c%3Dd%2Fe')").unwrap();
+
+        assert_eq!(result, "a b&c=d/e");
+    }
+
+    #[test]
+    fn test_url_decode_unicode() {
+        let sub = UrlDecodeSubstitutor {};
+        let result = sub.replace("URL_DECODE( 'caf%C3%A9' )").unwrap();
+
+        assert_eq!(result, "café");
+    }
+
+    #[test]
+    fn test_url_decode_keeps_invalid_escapes() {
+        let sub = UrlDecodeSubstitutor {};
+        let result = sub.replace("url_decode('100%')").unwrap();
+
+        assert_eq!(result, "100%");
+    }
+
+    #[test]
+    fn test_url_decode_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = UrlDecodeSubstitutor {};
+        let result = sub
+            .replace_with_cache("url_decode('a%20b')", &cache)
+            .unwrap();
+
+        assert_eq!(result, "a b");
+        assert_eq!(cache.len(), 1);
+    }
+}
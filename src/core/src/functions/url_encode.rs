@@ -0,0 +1,98 @@
+#[cfg(test)]
+use super::substitution::HashMapRegexCache;
+use super::substitution::{
+    FunctionSubstitutor, RegexCache, get_case_insensitive_regex,
+    get_case_insensitive_regex_with_cache,
+};
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+
+const URL_ENCODE_PATTERN: &str = r"\burl_encode\(\s*'((?:[^'\\]|\\.)*)'\s*\)";
+
+/// Everything except the RFC 3986 unreserved characters is percent-encoded.
+const URL_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+pub struct UrlEncodeSubstitutor {}
+impl FunctionSubstitutor for UrlEncodeSubstitutor {
+    fn get_regex(&self) -> &str {
+        r"(?!)"
+    }
+
+    fn generate(&self) -> String {
+        String::new()
+    }
+
+    fn replace(&self, input: &str) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex(URL_ENCODE_PATTERN)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                utf8_percent_encode(&caps[1], URL_ENCODE_SET).to_string()
+            })
+            .to_string())
+    }
+
+    fn replace_with_cache(
+        &self,
+        input: &str,
+        cache: &dyn RegexCache,
+    ) -> Result<String, regex::Error> {
+        let re = get_case_insensitive_regex_with_cache(URL_ENCODE_PATTERN, cache)?;
+        Ok(re
+            .replace_all(input, |caps: &regex::Captures| {
+                utf8_percent_encode(&caps[1], URL_ENCODE_SET).to_string()
+            })
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_encode_basic() {
+        let sub = UrlEncodeSubstitutor {};
+        let result = sub.replace("url_encode('a b&c=d/e')").unwrap();
+
+        assert_eq!(result, "a%20b%26c%3Dd%2Fe");
+    }
+
+    #[test]
+    fn test_url_encode_keeps_unreserved_characters() {
+        let sub = UrlEncodeSubstitutor {};
+        let result = sub.replace("url_encode('AZaz09-_.~')").unwrap();
+
+        assert_eq!(result, "AZaz09-_.~");
+    }
+
+    #[test]
+    fn test_url_encode_unicode() {
+        let sub = UrlEncodeSubstitutor {};
+        let result = sub.replace("url_encode('café')").unwrap();
+
+        assert_eq!(result, "caf%C3%A9");
+    }
+
+    #[test]
+    fn test_url_encode_in_query_string() {
+        let sub = UrlEncodeSubstitutor {};
+        let result = sub
+            .replace("https://example.com/search?q=URL_ENCODE( 'rust & http' )")
+            .unwrap();
+
+        assert_eq!(result, "https://example.com/search?q=rust%20%26%20http");
+    }
+
+    #[test]
+    fn test_url_encode_replace_with_cache() {
+        let cache = HashMapRegexCache::new();
+        let sub = UrlEncodeSubstitutor {};
+        let result = sub.replace_with_cache("url_encode('a b')", &cache).unwrap();
+
+        assert_eq!(result, "a%20b");
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,68 @@
+use super::substitution::FunctionSubstitutor;
+
+/// Generates a time-ordered UUID version 7 in hyphenated format.
+pub struct UuidV7Substitutor {}
+impl FunctionSubstitutor for UuidV7Substitutor {
+    fn get_regex(&self) -> &str {
+        r"\buuid_v7\(\)"
+    }
+
+    fn generate(&self) -> String {
+        use rand::RngExt;
+        let mut rng = rand::rng();
+        let mut bytes = [0u8; 10];
+        rng.fill(&mut bytes);
+        let millis = chrono::Utc::now().timestamp_millis() as u64 & 0xffff_ffff_ffff;
+        format!(
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            millis >> 16,
+            millis & 0xffff,
+            (u16::from_be_bytes([bytes[0], bytes[1]]) & 0x0fff) | 0x7000,
+            (u16::from_be_bytes([bytes[2], bytes[3]]) & 0x3fff) | 0x8000,
+            u64::from_be_bytes([
+                0, 0, bytes[4], bytes[5], bytes[6], bytes[7], bytes[8], bytes[9]
+            ])
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn test_uuid_v7_format() {
+        let uuid = UuidV7Substitutor {}.generate();
+        let pattern =
+            Regex::new(r"^[0-9a-f]{8}-[0-9a-f]{4}-7[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$")
+                .unwrap();
+
+        assert!(pattern.is_match(&uuid), "Invalid UUID v7 '{}'", uuid);
+    }
+
+    #[test]
+    fn test_uuid_v7_encodes_current_time() {
+        let before = chrono::Utc::now().timestamp_millis();
+        let uuid = UuidV7Substitutor {}.generate();
+        let after = chrono::Utc::now().timestamp_millis();
+
+        let millis = i64::from_str_radix(&uuid[..13].replace('-', ""), 16).unwrap();
+        assert!((before..=after).contains(&millis));
+    }
+
+    #[test]
+    fn test_uuid_v7_generates_different_values() {
+        let sub = UuidV7Substitutor {};
+
+        assert_ne!(sub.generate(), sub.generate());
+    }
+
+    #[test]
+    fn test_uuid_v7_replace() {
+        let result = UuidV7Substitutor {}.replace("id=UUID_V7()").unwrap();
+
+        assert!(result.starts_with("id="));
+        assert_eq!(result.len(), "id=".len() + 36);
+    }
+}
@@ -1,15 +1,20 @@
 pub mod assertions;
+pub mod captures;
 pub mod colors;
 pub mod conditions;
+pub mod datasets;
 pub mod discovery;
 pub mod environment;
 pub mod functions;
 pub mod logging;
+pub mod pagination;
 pub mod parser;
+pub mod project;
 mod redaction;
 mod request_substitution;
 pub mod report;
 pub mod runner;
+pub mod selection;
 pub mod serializer;
 pub mod telemetry;
 pub mod types;
@@ -18,10 +23,19 @@ pub mod variables;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod export;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod oauth;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod processor;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod secrets;
+
 pub use types::{HttpRequest, HttpResult};
 
+#[cfg(test)]
+mod test_support;
+
 #[cfg(target_arch = "wasm32")]
 pub use runner::execute_http_request_async;
@@ -0,0 +1,147 @@
+use super::token::TokenResponse;
+use crate::environment::create_private_file;
+use crate::types::{OAuthConfig, OAuthGrant};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Tokens are renewed this many seconds before they expire, so a token does
+/// not run out while a request is in flight.
+const EXPIRY_MARGIN_SECS: u64 = 10;
+
+/// Tokens obtained in this process, shared by every file of a run.
+static TOKEN_CACHE: OnceLock<Mutex<HashMap<String, CachedToken>>> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct CachedToken {
+    pub(super) access_token: String,
+    pub(super) id_token: Option<String>,
+    pub(super) refresh_token: Option<String>,
+    pub(super) expires_at: Option<u64>, // Unix time in seconds
+}
+
+impl CachedToken {
+    pub(super) fn new(response: TokenResponse, now: u64) -> Self {
+        Self {
+            access_token: response.access_token,
+            id_token: response.id_token,
+            refresh_token: response.refresh_token,
+            expires_at: response.expires_in.map(|expires_in| now + expires_in),
+        }
+    }
+
+    /// Tokens without an expiry are reused for as long as they are cached.
+    pub(super) fn is_fresh(&self, now: u64) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| now + EXPIRY_MARGIN_SECS < expires_at)
+    }
+}
+
+/// The location of the token store used by `--persist-tokens`.
+pub fn default_token_store() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("httprunner").join("oauth-tokens.json"))
+}
+
+/// Identifies the tokens of one configuration. Secrets only enter it hashed,
+/// so a changed client secret or password never reuses an older token.
+pub(super) fn cache_key(config: &OAuthConfig) -> String {
+    let (grant, credentials) = match &config.grant {
+        OAuthGrant::ClientCredentials => ("client_credentials".to_string(), String::new()),
+        OAuthGrant::Password { username, password } => {
+            (format!("password:{}", username), password.clone())
+        }
+        OAuthGrant::RefreshToken { refresh_token } => {
+            ("refresh_token".to_string(), refresh_token.clone())
+        }
+    };
+
+    let mut hasher = Sha256::new();
+    for part in [
+        config.token_url.as_str(),
+        config.client_id.as_str(),
+        config.client_secret.as_deref().unwrap_or_default(),
+        &format!("{:?}", config.client_auth),
+        config.scope.as_deref().unwrap_or_default(),
+        &grant,
+        &credentials,
+    ] {
+        // Length-prefixed, so that moving text between parts changes the hash
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part.as_bytes());
+    }
+
+    format!(
+        "{} {} {} {} {}",
+        config.token_url,
+        config.client_id,
+        grant,
+        config.scope.as_deref().unwrap_or_default(),
+        hex::encode(hasher.finalize())
+    )
+}
+
+/// Forget the tokens obtained in this process, e.g. after the environment
+/// they were configured in was reloaded.
+pub fn clear_token_cache() {
+    memory_cache()
+        .lock()
+        .expect("oauth token cache mutex poisoned")
+        .clear();
+}
+
+pub(super) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+pub(super) fn cached(key: &str) -> Option<CachedToken> {
+    memory_cache()
+        .lock()
+        .expect("oauth token cache mutex poisoned")
+        .get(key)
+        .cloned()
+}
+
+pub(super) fn cache(key: &str, token: &CachedToken) {
+    memory_cache()
+        .lock()
+        .expect("oauth token cache mutex poisoned")
+        .insert(key.to_string(), token.clone());
+}
+
+pub(super) fn load_stored(store: &Path, key: &str) -> Option<CachedToken> {
+    read_store(store).remove(key)
+}
+
+/// Save a token to the store. Failing to persist a token never fails a request.
+pub(super) fn save_stored(store: &Path, key: &str, token: &CachedToken) {
+    let mut tokens = read_store(store);
+    tokens.insert(key.to_string(), token.clone());
+
+    if let Some(parent) = store.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&tokens)
+        && let Ok(mut file) = create_private_file(store)
+    {
+        let _ = file.write_all(json.as_bytes());
+    }
+}
+
+fn read_store(store: &Path) -> HashMap<String, CachedToken> {
+    fs::read_to_string(store)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn memory_cache() -> &'static Mutex<HashMap<String, CachedToken>> {
+    TOKEN_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
@@ -0,0 +1,11 @@
+mod cache;
+mod provider;
+mod substitution;
+mod token;
+
+pub use cache::{clear_token_cache, default_token_store};
+pub use provider::TokenProvider;
+pub use substitution::substitute_auth_tokens;
+
+#[cfg(test)]
+mod tests;
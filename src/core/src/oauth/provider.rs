@@ -0,0 +1,91 @@
+use super::cache::{self, CachedToken};
+use super::token::request_token;
+use crate::types::{OAuthConfig, OAuthGrant};
+use anyhow::{Context, Result, anyhow};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Resolves `$auth.token("name")` references to access tokens.
+///
+/// Tokens are cached until they expire, across requests and files of a run,
+/// and renewed with their refresh token when the endpoint issued one. With a
+/// store, tokens are also kept across runs.
+#[derive(Debug, Default)]
+pub struct TokenProvider {
+    configs: HashMap<String, OAuthConfig>,
+    store: Option<PathBuf>,
+}
+
+impl TokenProvider {
+    pub fn new(configs: HashMap<String, OAuthConfig>) -> Self {
+        Self {
+            configs,
+            store: None,
+        }
+    }
+
+    pub fn with_store(mut self, store: Option<PathBuf>) -> Self {
+        self.store = store;
+        self
+    }
+
+    pub fn access_token(&self, name: &str) -> Result<String> {
+        Ok(self.token(name)?.access_token)
+    }
+
+    pub fn id_token(&self, name: &str) -> Result<String> {
+        self.token(name)?
+            .id_token
+            .ok_or_else(|| anyhow!("The token response for '{}' has no 'id_token'", name))
+    }
+
+    fn token(&self, name: &str) -> Result<CachedToken> {
+        let config = self.configs.get(name).ok_or_else(|| {
+            anyhow!(
+                "No OAuth 2.0 configuration named '{}' in the environment's Security.Auth section (supported grant types: Client Credentials, Password, Refresh Token)",
+                name
+            )
+        })?;
+
+        let key = cache::cache_key(config);
+        let now = cache::now();
+        let cached = cache::cached(&key).or_else(|| {
+            self.store
+                .as_deref()
+                .and_then(|store| cache::load_stored(store, &key))
+        });
+
+        if let Some(token) = &cached
+            && token.is_fresh(now)
+        {
+            cache::cache(&key, token);
+            return Ok(token.clone());
+        }
+
+        // Prefer renewing an expired token; fall back to the configured grant.
+        let refresh_token = cached.and_then(|token| token.refresh_token);
+        let refreshed = refresh_token.as_ref().and_then(|refresh_token| {
+            let grant = OAuthGrant::RefreshToken {
+                refresh_token: refresh_token.clone(),
+            };
+            request_token(config, &grant).ok()
+        });
+        let response = match refreshed {
+            Some(response) => response,
+            None => request_token(config, &config.grant)
+                .with_context(|| format!("Failed to obtain a token for '{}'", name))?,
+        };
+
+        let mut token = CachedToken::new(response, now);
+        // Endpoints may omit the refresh token when it stays valid.
+        if token.refresh_token.is_none() {
+            token.refresh_token = refresh_token;
+        }
+
+        cache::cache(&key, &token);
+        if let Some(store) = &self.store {
+            cache::save_stored(store, &key, &token);
+        }
+        Ok(token)
+    }
+}
@@ -0,0 +1,37 @@
+use super::TokenProvider;
+use anyhow::Result;
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+static AUTH_TOKEN_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// Replace `{{$auth.token("name")}}` and `{{$auth.idToken("name")}}` with
+/// tokens from `provider`.
+pub fn substitute_auth_tokens(input: &str, provider: &TokenProvider) -> Result<String> {
+    if !input.contains("$auth.") {
+        return Ok(input.to_string());
+    }
+
+    let regex = AUTH_TOKEN_REGEX.get_or_init(|| {
+        Regex::new(r#"\{\{\s*\$auth\.(token|idToken)\(\s*"([^"]+)"\s*\)\s*\}\}"#)
+            .expect("valid $auth regex")
+    });
+
+    let mut error = None;
+    let substituted = regex.replace_all(input, |captures: &Captures| {
+        let name = &captures[2];
+        let token = match &captures[1] {
+            "token" => provider.access_token(name),
+            _ => provider.id_token(name),
+        };
+        token.unwrap_or_else(|err| {
+            error.get_or_insert(err);
+            String::new()
+        })
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(substituted.into_owned()),
+    }
+}
@@ -0,0 +1,260 @@
+use super::cache::{CachedToken, load_stored};
+use super::token::parse_token_response;
+use super::*;
+use crate::types::{OAuthClientAuth, OAuthConfig, OAuthGrant};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tempfile::tempdir;
+
+/// A token endpoint stand-in that answers every request with the next of
+/// `responses` and records the request bodies it received.
+fn spawn_token_server(responses: Vec<&'static str>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/token", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+
+    thread::spawn(move || {
+        for body in responses {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            recorded.lock().unwrap().push(read_request(&mut stream));
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    (url, requests)
+}
+
+fn read_request(stream: &mut std::net::TcpStream) -> String {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let read = stream.read(&mut buf).unwrap_or(0);
+        data.extend_from_slice(&buf[..read]);
+        let text = String::from_utf8_lossy(&data).to_string();
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if body.len() >= length || read == 0 {
+                return text;
+            }
+        } else if read == 0 {
+            return text;
+        }
+    }
+}
+
+fn config(token_url: &str, grant: OAuthGrant) -> OAuthConfig {
+    OAuthConfig {
+        token_url: token_url.to_string(),
+        client_id: "runner".to_string(),
+        client_secret: Some("s3cret".to_string()),
+        client_auth: OAuthClientAuth::Basic,
+        scope: Some("read".to_string()),
+        grant,
+    }
+}
+
+fn provider(name: &str, config: OAuthConfig) -> TokenProvider {
+    TokenProvider::new(HashMap::from([(name.to_string(), config)]))
+}
+
+#[test]
+fn parse_token_response_reads_token_fields() {
+    let response = parse_token_response(
+        r#"{"access_token":"abc","token_type":"Bearer","expires_in":"3600","refresh_token":"r1","id_token":"id"}"#,
+    )
+    .unwrap();
+    assert_eq!(response.access_token, "abc");
+    assert_eq!(response.expires_in, Some(3600));
+    assert_eq!(response.refresh_token.as_deref(), Some("r1"));
+    assert_eq!(response.id_token.as_deref(), Some("id"));
+
+    let error = parse_token_response(r#"{"error":"invalid_client"}"#).unwrap_err();
+    assert!(error.to_string().contains("no 'access_token'"));
+}
+
+#[test]
+fn cached_token_is_fresh_until_shortly_before_expiry() {
+    let token = CachedToken {
+        access_token: "abc".to_string(),
+        id_token: None,
+        refresh_token: None,
+        expires_at: Some(1_000),
+    };
+    assert!(token.is_fresh(900));
+    assert!(!token.is_fresh(995));
+    assert!(
+        CachedToken {
+            expires_at: None,
+            ..token
+        }
+        .is_fresh(u64::MAX - 100)
+    );
+}
+
+#[test]
+fn client_credentials_token_is_cached_across_requests() {
+    let (url, requests) =
+        spawn_token_server(vec![r#"{"access_token":"cc-token","expires_in":3600}"#]);
+    let provider = provider("service", config(&url, OAuthGrant::ClientCredentials));
+
+    assert_eq!(provider.access_token("service").unwrap(), "cc-token");
+    assert_eq!(provider.access_token("service").unwrap(), "cc-token");
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].contains("grant_type=client_credentials"));
+    assert!(requests[0].contains("scope=read"));
+    // "runner:s3cret" sent as Basic client authentication.
+    assert!(requests[0].contains("cnVubmVyOnMzY3JldA=="));
+}
+
+#[test]
+fn password_grant_sends_credentials_in_body() {
+    let (url, requests) = spawn_token_server(vec![r#"{"access_token":"pw-token"}"#]);
+    let mut config = config(
+        &url,
+        OAuthGrant::Password {
+            username: "alice".to_string(),
+            password: "wonder land".to_string(),
+        },
+    );
+    config.client_auth = OAuthClientAuth::InBody;
+
+    assert_eq!(
+        provider("user", config).access_token("user").unwrap(),
+        "pw-token"
+    );
+
+    let requests = requests.lock().unwrap();
+    assert!(requests[0].contains("grant_type=password&username=alice&password=wonder+land"));
+    assert!(requests[0].contains("client_id=runner&client_secret=s3cret"));
+}
+
+#[test]
+fn expired_token_is_renewed_with_refresh_token() {
+    let (url, requests) = spawn_token_server(vec![
+        r#"{"access_token":"first","expires_in":0,"refresh_token":"refresh-1"}"#,
+        r#"{"access_token":"second","expires_in":3600}"#,
+    ]);
+    let provider = provider("service", config(&url, OAuthGrant::ClientCredentials));
+
+    assert_eq!(provider.access_token("service").unwrap(), "first");
+    assert_eq!(provider.access_token("service").unwrap(), "second");
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].contains("grant_type=refresh_token&refresh_token=refresh-1"));
+}
+
+#[test]
+fn tokens_are_persisted_to_the_store() {
+    let (url, _requests) =
+        spawn_token_server(vec![r#"{"access_token":"stored-token","expires_in":3600}"#]);
+    let temp = tempdir().unwrap();
+    let store = temp.path().join("httprunner").join("oauth-tokens.json");
+    let config = config(&url, OAuthGrant::ClientCredentials);
+    let provider = provider("service", config.clone()).with_store(Some(store.clone()));
+
+    assert_eq!(provider.access_token("service").unwrap(), "stored-token");
+
+    let stored = load_stored(&store, &super::cache::cache_key(&config)).unwrap();
+    assert_eq!(stored.access_token, "stored-token");
+    assert!(!fs_contains(&store, "s3cret"));
+}
+
+#[cfg(unix)]
+#[test]
+fn token_store_is_only_readable_by_owner() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (url, _requests) =
+        spawn_token_server(vec![r#"{"access_token":"stored-token","expires_in":3600}"#]);
+    let temp = tempdir().unwrap();
+    let store = temp.path().join("oauth-tokens.json");
+    let provider = provider("service", config(&url, OAuthGrant::ClientCredentials))
+        .with_store(Some(store.clone()));
+
+    provider.access_token("service").unwrap();
+
+    let mode = std::fs::metadata(&store).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn cache_key_changes_with_secrets() {
+    let url = "https://auth.example.com/token";
+    let original = config(url, OAuthGrant::ClientCredentials);
+    let mut other_secret = original.clone();
+    other_secret.client_secret = Some("other".to_string());
+    let password = |password: &str| {
+        config(
+            url,
+            OAuthGrant::Password {
+                username: "alice".to_string(),
+                password: password.to_string(),
+            },
+        )
+    };
+
+    let key = super::cache::cache_key(&original);
+    assert_ne!(key, super::cache::cache_key(&other_secret));
+    assert_ne!(
+        super::cache::cache_key(&password("first")),
+        super::cache::cache_key(&password("second"))
+    );
+    assert!(!key.contains("s3cret"));
+}
+
+fn fs_contains(path: &std::path::Path, needle: &str) -> bool {
+    std::fs::read_to_string(path).unwrap().contains(needle)
+}
+
+#[test]
+fn substitute_auth_tokens_replaces_references() {
+    let (url, _requests) = spawn_token_server(vec![
+        r#"{"access_token":"access","id_token":"identity","expires_in":3600}"#,
+    ]);
+    let provider = provider("my-auth", config(&url, OAuthGrant::ClientCredentials));
+
+    assert_eq!(
+        substitute_auth_tokens(
+            r#"Bearer {{$auth.token("my-auth")}} / {{ $auth.idToken("my-auth") }}"#,
+            &provider
+        )
+        .unwrap(),
+        "Bearer access / identity"
+    );
+    assert_eq!(
+        substitute_auth_tokens("{{token}}", &provider).unwrap(),
+        "{{token}}"
+    );
+}
+
+#[test]
+fn substitute_auth_tokens_reports_unknown_configuration() {
+    let error = substitute_auth_tokens(r#"{{$auth.token("missing")}}"#, &TokenProvider::default())
+        .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("No OAuth 2.0 configuration named 'missing'")
+    );
+}
@@ -0,0 +1,89 @@
+use crate::types::{OAuthClientAuth, OAuthConfig, OAuthGrant};
+use anyhow::{Context, Result, anyhow, bail};
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::time::Duration;
+
+/// The fields of a token endpoint response that httprunner uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct TokenResponse {
+    pub(super) access_token: String,
+    pub(super) id_token: Option<String>,
+    pub(super) refresh_token: Option<String>,
+    pub(super) expires_in: Option<u64>,
+}
+
+/// Request a token from the configured token endpoint using `grant`.
+pub(super) fn request_token(config: &OAuthConfig, grant: &OAuthGrant) -> Result<TokenResponse> {
+    let mut form = form_urlencoded::Serializer::new(String::new());
+    match grant {
+        OAuthGrant::ClientCredentials => {
+            form.append_pair("grant_type", "client_credentials");
+        }
+        OAuthGrant::Password { username, password } => {
+            form.append_pair("grant_type", "password");
+            form.append_pair("username", username);
+            form.append_pair("password", password);
+        }
+        OAuthGrant::RefreshToken { refresh_token } => {
+            form.append_pair("grant_type", "refresh_token");
+            form.append_pair("refresh_token", refresh_token);
+        }
+    }
+    if let Some(scope) = &config.scope {
+        form.append_pair("scope", scope);
+    }
+
+    let client = Client::builder().timeout(Duration::from_secs(60)).build()?;
+    let mut request = client
+        .post(&config.token_url)
+        .header("Accept", "application/json");
+    match (config.client_auth, &config.client_secret) {
+        (OAuthClientAuth::Basic, Some(secret)) => {
+            request = request.basic_auth(&config.client_id, Some(secret));
+        }
+        (OAuthClientAuth::InBody, Some(secret)) => {
+            form.append_pair("client_id", &config.client_id);
+            form.append_pair("client_secret", secret);
+        }
+        _ => {
+            form.append_pair("client_id", &config.client_id);
+        }
+    }
+
+    let response = request
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(form.finish())
+        .send()
+        .with_context(|| format!("Token request to {} failed", config.token_url))?;
+    let status = response.status();
+    let body = response.text()?;
+    if !status.is_success() {
+        bail!(
+            "Token request to {} failed with status {}: {}",
+            config.token_url,
+            status.as_u16(),
+            body
+        );
+    }
+
+    parse_token_response(&body)
+}
+
+pub(super) fn parse_token_response(body: &str) -> Result<TokenResponse> {
+    let json: Value = serde_json::from_str(body).context("Token response is not a JSON object")?;
+    let text = |key: &str| json.get(key).and_then(Value::as_str).map(str::to_string);
+
+    Ok(TokenResponse {
+        access_token: text("access_token")
+            .ok_or_else(|| anyhow!("Token response has no 'access_token'"))?,
+        id_token: text("id_token"),
+        refresh_token: text("refresh_token"),
+        // Some providers send `expires_in` as a string.
+        expires_in: json.get("expires_in").and_then(|value| {
+            value
+                .as_u64()
+                .or_else(|| value.as_str().and_then(|value| value.parse().ok()))
+        }),
+    })
+}
@@ -0,0 +1,73 @@
+use crate::types::{HttpResult, Pagination};
+use crate::variables::{find_json_path, replace_json_path};
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::Value;
+
+/// Combines the fetched pages into a single result addressable as the
+/// paginated request.
+///
+/// With `items=`, the body is the first page with the array at that path
+/// replaced by the items of every page. Without it, array bodies are
+/// concatenated and any other bodies are collected into an array of pages.
+/// Status and headers come from the last page, durations are summed, and the
+/// aggregate only succeeds when every page did.
+pub fn aggregate_pages(
+    pagination: &Pagination,
+    name: Option<String>,
+    pages: &[HttpResult],
+) -> Result<HttpResult> {
+    let last = pages.last().context("No pages were fetched")?;
+
+    let bodies = pages
+        .iter()
+        .enumerate()
+        .map(|(index, page)| {
+            let body = page
+                .response_body
+                .as_deref()
+                .ok_or_else(|| anyhow!("Page {} has no response body", index + 1))?;
+            serde_json::from_str::<Value>(body)
+                .with_context(|| format!("Page {} is not a JSON response", index + 1))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let body = match &pagination.items {
+        Some(items_path) => concatenate_items(bodies, items_path)?,
+        None if bodies.iter().all(Value::is_array) => Value::Array(
+            bodies
+                .into_iter()
+                .flat_map(|body| match body {
+                    Value::Array(items) => items,
+                    _ => Vec::new(),
+                })
+                .collect(),
+        ),
+        None => Value::Array(bodies),
+    };
+
+    Ok(HttpResult {
+        request_name: name,
+        status_code: last.status_code,
+        success: pages.iter().all(|page| page.success),
+        error_message: pages.iter().find_map(|page| page.error_message.clone()),
+        duration_ms: pages.iter().map(|page| page.duration_ms).sum(),
+        response_headers: last.response_headers.clone(),
+        response_body: Some(body.to_string()),
+        assertion_results: Vec::new(),
+        captured_variables: Vec::new(),
+    })
+}
+
+fn concatenate_items(bodies: Vec<Value>, items_path: &str) -> Result<Value> {
+    let mut items = Vec::new();
+    for (index, body) in bodies.iter().enumerate() {
+        match find_json_path(body, items_path)? {
+            Some(Value::Array(page_items)) => items.extend(page_items.iter().cloned()),
+            _ => bail!("Page {} has no array at '{}'", index + 1, items_path),
+        }
+    }
+
+    let mut aggregate = bodies.into_iter().next().unwrap_or(Value::Null);
+    replace_json_path(&mut aggregate, items_path, Value::Array(items))?;
+    Ok(aggregate)
+}
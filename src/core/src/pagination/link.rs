@@ -0,0 +1,48 @@
+/// Returns the target of the `rel="next"` link in an RFC 8288 `Link` header
+/// value, e.g. `<https://api.example.com/items?page=2>; rel="next"`.
+pub fn parse_link_next(header: &str) -> Option<String> {
+    split_link_values(header)
+        .into_iter()
+        .find_map(|link_value| {
+            let link_value = link_value.trim();
+            let target_end = link_value.find('>')?;
+            let target = link_value.strip_prefix('<')?.get(..target_end - 1)?;
+            let is_next = link_value[target_end + 1..]
+                .split(';')
+                .filter_map(|param| param.split_once('='))
+                .any(|(name, value)| {
+                    name.trim().eq_ignore_ascii_case("rel")
+                        && value
+                            .trim()
+                            .trim_matches('"')
+                            .split_whitespace()
+                            .any(|rel| rel.eq_ignore_ascii_case("next"))
+                });
+            is_next.then(|| target.trim().to_string())
+        })
+}
+
+/// Splits a header value into link-values on the commas that are not part of a
+/// `<...>` target or a quoted parameter value.
+fn split_link_values(header: &str) -> Vec<&str> {
+    let mut values = Vec::new();
+    let mut start = 0;
+    let mut in_target = false;
+    let mut in_quotes = false;
+
+    for (index, character) in header.char_indices() {
+        match character {
+            '<' if !in_quotes => in_target = true,
+            '>' if !in_quotes => in_target = false,
+            '"' if !in_target => in_quotes = !in_quotes,
+            ',' if !in_target && !in_quotes => {
+                values.push(&header[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    values.push(&header[start..]);
+    values
+}
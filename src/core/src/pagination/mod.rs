@@ -0,0 +1,10 @@
+mod aggregate;
+mod link;
+mod next_page;
+
+pub use aggregate::aggregate_pages;
+pub use link::parse_link_next;
+pub use next_page::next_page_url;
+
+#[cfg(test)]
+mod tests;
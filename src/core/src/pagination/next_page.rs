@@ -0,0 +1,75 @@
+use super::link::parse_link_next;
+use crate::types::{HttpResult, Pagination, PaginationNext};
+use crate::variables::extract_json_value;
+use anyhow::{Context, Result};
+use reqwest::Url;
+
+/// Resolves the URL of the page after `result`, or `None` when pagination is
+/// exhausted.
+///
+/// `page_url` is the URL the page was fetched from and is used to resolve
+/// relative links; `first_url` is the URL of the first page, which cursors are
+/// added to as a query parameter.
+pub fn next_page_url(
+    pagination: &Pagination,
+    page_url: &str,
+    first_url: &str,
+    result: &HttpResult,
+) -> Result<Option<String>> {
+    let next = match &pagination.next {
+        PaginationNext::Link => result
+            .response_headers
+            .as_ref()
+            .and_then(|headers| {
+                headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case("link"))
+            })
+            .and_then(|(_, value)| parse_link_next(value))
+            .map(|target| resolve_url(page_url, &target))
+            .transpose()?,
+        PaginationNext::Url(path) => body_value(result, path)?
+            .map(|target| resolve_url(page_url, &target))
+            .transpose()?,
+        PaginationNext::Cursor { path, param } => body_value(result, path)?
+            .map(|cursor| with_query_param(first_url, param, &cursor))
+            .transpose()?,
+    };
+
+    // A server that keeps pointing at the same page would otherwise loop until `max`.
+    Ok(next.filter(|url| url != page_url))
+}
+
+/// Extracts a non-empty, non-null value from the JSON response body.
+fn body_value(result: &HttpResult, path: &str) -> Result<Option<String>> {
+    let Some(body) = result.response_body.as_deref() else {
+        return Ok(None);
+    };
+
+    let value = extract_json_value(body, path)
+        .with_context(|| format!("Failed to read next page from '{path}'"))?;
+    Ok(value.filter(|value| !value.is_empty() && value != "null"))
+}
+
+fn resolve_url(page_url: &str, target: &str) -> Result<String> {
+    let base = Url::parse(page_url).with_context(|| format!("Invalid page URL '{page_url}'"))?;
+    let next = base
+        .join(target)
+        .with_context(|| format!("Invalid next page URL '{target}'"))?;
+    Ok(next.to_string())
+}
+
+fn with_query_param(url: &str, param: &str, value: &str) -> Result<String> {
+    let mut url = Url::parse(url).with_context(|| format!("Invalid page URL '{url}'"))?;
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| name != param)
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair(param, value);
+    Ok(url.to_string())
+}
@@ -0,0 +1,207 @@
+use super::*;
+use crate::test_support::response;
+use crate::types::{
+    DEFAULT_MAX_PAGES, HttpResult, Pagination, PaginationAssertScope, PaginationNext,
+};
+
+fn pagination(next: PaginationNext, items: Option<&str>) -> Pagination {
+    Pagination {
+        next,
+        max_pages: DEFAULT_MAX_PAGES,
+        items: items.map(str::to_string),
+        assert_scope: PaginationAssertScope::Page,
+    }
+}
+
+fn page(body: &str, headers: &[(&str, &str)]) -> HttpResult {
+    response(200, Some(body), headers)
+}
+
+#[test]
+fn parse_link_next_finds_next_relation() {
+    let header = r#"<https://api.example.com/items?page=1>; rel="prev", <https://api.example.com/items?page=3>; rel="next", <https://api.example.com/items?page=9>; rel="last""#;
+    assert_eq!(
+        parse_link_next(header).as_deref(),
+        Some("https://api.example.com/items?page=3")
+    );
+}
+
+#[test]
+fn parse_link_next_handles_relation_lists_and_commas_in_targets() {
+    let header = r#"<https://api.example.com/items?ids=1,2>; title="a, b"; REL="last next""#;
+    assert_eq!(
+        parse_link_next(header).as_deref(),
+        Some("https://api.example.com/items?ids=1,2")
+    );
+    assert!(parse_link_next(r#"<https://api.example.com/items?page=1>; rel="prev""#).is_none());
+    assert!(parse_link_next("").is_none());
+}
+
+#[test]
+fn next_page_url_follows_relative_link_headers() {
+    let result = page("[]", &[("link", r#"</items?page=2>; rel="next""#)]);
+    let next = next_page_url(
+        &pagination(PaginationNext::Link, None),
+        "https://api.example.com/items?page=1",
+        "https://api.example.com/items?page=1",
+        &result,
+    )
+    .unwrap();
+    assert_eq!(
+        next.as_deref(),
+        Some("https://api.example.com/items?page=2")
+    );
+
+    let last = page("[]", &[]);
+    let next = next_page_url(
+        &pagination(PaginationNext::Link, None),
+        "https://api.example.com/items?page=2",
+        "https://api.example.com/items?page=1",
+        &last,
+    )
+    .unwrap();
+    assert!(next.is_none());
+}
+
+#[test]
+fn next_page_url_reads_next_url_from_body() {
+    let strategy = pagination(PaginationNext::Url("$.links.next".to_string()), None);
+    let result = page(
+        r#"{"links":{"next":"https://api.example.com/items?page=2"}}"#,
+        &[],
+    );
+    let next = next_page_url(
+        &strategy,
+        "https://api.example.com/items",
+        "https://api.example.com/items",
+        &result,
+    )
+    .unwrap();
+    assert_eq!(
+        next.as_deref(),
+        Some("https://api.example.com/items?page=2")
+    );
+
+    let exhausted = page(r#"{"links":{"next":null}}"#, &[]);
+    let next = next_page_url(
+        &strategy,
+        "https://api.example.com/items?page=2",
+        "https://api.example.com/items",
+        &exhausted,
+    )
+    .unwrap();
+    assert!(next.is_none());
+}
+
+#[test]
+fn next_page_url_sends_cursor_as_query_parameter() {
+    let strategy = pagination(
+        PaginationNext::Cursor {
+            path: "$.meta.cursor".to_string(),
+            param: "after".to_string(),
+        },
+        None,
+    );
+    let result = page(r#"{"meta":{"cursor":"abc 123"}}"#, &[]);
+    let next = next_page_url(
+        &strategy,
+        "https://api.example.com/items?limit=2&after=old",
+        "https://api.example.com/items?limit=2&after=old",
+        &result,
+    )
+    .unwrap();
+    assert_eq!(
+        next.as_deref(),
+        Some("https://api.example.com/items?limit=2&after=abc+123")
+    );
+
+    let exhausted = page(r#"{"meta":{"cursor":""}}"#, &[]);
+    let next = next_page_url(
+        &strategy,
+        "https://api.example.com/items?limit=2&after=abc+123",
+        "https://api.example.com/items?limit=2",
+        &exhausted,
+    )
+    .unwrap();
+    assert!(next.is_none());
+}
+
+#[test]
+fn next_page_url_stops_when_next_points_at_the_same_page() {
+    let strategy = pagination(PaginationNext::Url("$.next".to_string()), None);
+    let result = page(r#"{"next":"https://api.example.com/items?page=2"}"#, &[]);
+    let next = next_page_url(
+        &strategy,
+        "https://api.example.com/items?page=2",
+        "https://api.example.com/items",
+        &result,
+    )
+    .unwrap();
+    assert!(next.is_none());
+}
+
+#[test]
+fn aggregate_pages_splices_items_into_first_page() {
+    let pages = vec![
+        page(r#"{"data":[{"id":1},{"id":2}],"page":1}"#, &[]),
+        page(r#"{"data":[{"id":3}],"page":2}"#, &[("x-page", "2")]),
+    ];
+    let aggregate = aggregate_pages(
+        &pagination(PaginationNext::Link, Some("$.data")),
+        Some("users".to_string()),
+        &pages,
+    )
+    .unwrap();
+
+    let body: serde_json::Value =
+        serde_json::from_str(aggregate.response_body.as_deref().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({"data":[{"id":1},{"id":2},{"id":3}],"page":1})
+    );
+    assert_eq!(aggregate.request_name.as_deref(), Some("users"));
+    assert_eq!(aggregate.duration_ms, 20);
+    assert_eq!(
+        aggregate
+            .response_headers
+            .unwrap()
+            .get("x-page")
+            .map(String::as_str),
+        Some("2")
+    );
+    assert!(aggregate.success);
+}
+
+#[test]
+fn aggregate_pages_concatenates_array_bodies() {
+    let pages = vec![page("[1,2]", &[]), page("[3]", &[])];
+    let aggregate = aggregate_pages(&pagination(PaginationNext::Link, None), None, &pages).unwrap();
+    assert_eq!(aggregate.response_body.as_deref(), Some("[1,2,3]"));
+
+    let pages = vec![page(r#"{"page":1}"#, &[]), page(r#"{"page":2}"#, &[])];
+    let aggregate = aggregate_pages(&pagination(PaginationNext::Link, None), None, &pages).unwrap();
+    assert_eq!(
+        aggregate.response_body.as_deref(),
+        Some(r#"[{"page":1},{"page":2}]"#)
+    );
+}
+
+#[test]
+fn aggregate_pages_fails_when_a_page_is_missing_items() {
+    let mut failed = page(r#"{"error":"rate limited"}"#, &[]);
+    failed.success = false;
+    let pages = vec![page(r#"{"data":[1]}"#, &[]), failed];
+
+    let error = aggregate_pages(
+        &pagination(PaginationNext::Link, Some("$.data")),
+        None,
+        &pages,
+    )
+    .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Page 2 has no array at '$.data'")
+    );
+    assert!(aggregate_pages(&pagination(PaginationNext::Link, None), None, &[]).is_err());
+}
@@ -0,0 +1,26 @@
+use crate::types::Auth;
+
+/// Parses the value of an `@auth` directive: `basic <username> <password>`,
+/// `digest <username> <password>` or `bearer <token>`.
+///
+/// The password is everything after the username, so it may contain spaces.
+pub fn parse_auth(value: &str) -> Option<Auth> {
+    let (scheme, credentials) = value.trim().split_once(char::is_whitespace)?;
+    let credentials = credentials.trim();
+
+    match scheme.to_ascii_lowercase().as_str() {
+        "basic" | "digest" => {
+            let (username, password) = credentials.split_once(char::is_whitespace)?;
+            let (username, password) = (username.to_string(), password.trim().to_string());
+            if scheme.eq_ignore_ascii_case("basic") {
+                Some(Auth::Basic { username, password })
+            } else {
+                Some(Auth::Digest { username, password })
+            }
+        }
+        "bearer" if !credentials.contains(char::is_whitespace) => Some(Auth::Bearer {
+            token: credentials.to_string(),
+        }),
+        _ => None,
+    }
+}
@@ -0,0 +1,43 @@
+use super::auth_parser::*;
+use crate::types::Auth;
+
+#[test]
+fn test_parse_auth_basic() {
+    assert_eq!(
+        parse_auth("basic admin s3cret"),
+        Some(Auth::Basic {
+            username: "admin".to_string(),
+            password: "s3cret".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_parse_auth_digest_password_with_spaces() {
+    assert_eq!(
+        parse_auth("Digest Mufasa Circle Of Life"),
+        Some(Auth::Digest {
+            username: "Mufasa".to_string(),
+            password: "Circle Of Life".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_parse_auth_bearer() {
+    assert_eq!(
+        parse_auth("bearer  eyJhbGciOiJIUzI1NiJ9.e30.sig "),
+        Some(Auth::Bearer {
+            token: "eyJhbGciOiJIUzI1NiJ9.e30.sig".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_parse_auth_rejects_invalid_values() {
+    assert!(parse_auth("basic admin").is_none());
+    assert!(parse_auth("bearer").is_none());
+    assert!(parse_auth("bearer two tokens").is_none());
+    assert!(parse_auth("ntlm user password").is_none());
+    assert!(parse_auth("").is_none());
+}
@@ -0,0 +1,49 @@
+use crate::types::{Capture, CaptureSource};
+use anyhow::{Result, bail};
+
+/// Parses the value of a `@capture` directive: `<name> = <source>`, where the
+/// source is a `$.` JSONPath, `regex(/.../)` or `/`-prefixed XPath into the
+/// response body, `header <name>`, `body` or `status`, e.g.
+/// `token = $.access_token` or `etag = header ETag`.
+pub fn parse_capture(value: &str) -> Result<Capture> {
+    let Some((name, source)) = value.split_once('=') else {
+        bail!("expected '<name> = <source>'");
+    };
+
+    let name = name.trim();
+    if !is_capture_name(name) {
+        bail!("'{name}' is not a valid variable name");
+    }
+
+    let source = source.trim();
+    let source = if source.starts_with("$.")
+        || source.starts_with("regex(")
+        || source.starts_with('/')
+    {
+        CaptureSource::BodyPath(source.to_string())
+    } else if source.eq_ignore_ascii_case("body") {
+        CaptureSource::Body
+    } else if source.eq_ignore_ascii_case("status") {
+        CaptureSource::Status
+    } else if let Some((keyword, header)) = source.split_once(char::is_whitespace)
+        && keyword.eq_ignore_ascii_case("header")
+    {
+        CaptureSource::Header(header.trim().to_string())
+    } else {
+        bail!(
+            "expected '$.<jsonpath>', 'regex(/<pattern>/)', '/<xpath>', 'header <name>', 'body' or 'status' after '='"
+        );
+    };
+
+    Ok(Capture {
+        name: name.to_string(),
+        source,
+    })
+}
+
+fn is_capture_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
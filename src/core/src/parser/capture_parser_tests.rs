@@ -0,0 +1,54 @@
+use super::capture_parser::*;
+use crate::types::CaptureSource;
+
+#[test]
+fn test_parse_capture_json_path() {
+    let capture = parse_capture("token = $.access_token").unwrap();
+    assert_eq!(capture.name, "token");
+    assert_eq!(
+        capture.source,
+        CaptureSource::BodyPath("$.access_token".to_string())
+    );
+}
+
+#[test]
+fn test_parse_capture_regex_and_xpath() {
+    let capture = parse_capture(r"session = regex(/session=(\w+)/)").unwrap();
+    assert_eq!(
+        capture.source,
+        CaptureSource::BodyPath(r"regex(/session=(\w+)/)".to_string())
+    );
+
+    let capture = parse_capture("orderId = //order/id").unwrap();
+    assert_eq!(
+        capture.source,
+        CaptureSource::BodyPath("//order/id".to_string())
+    );
+}
+
+#[test]
+fn test_parse_capture_header_body_and_status() {
+    let capture = parse_capture("etag=header ETag").unwrap();
+    assert_eq!(capture.name, "etag");
+    assert_eq!(capture.source, CaptureSource::Header("ETag".to_string()));
+
+    let capture = parse_capture("page = body").unwrap();
+    assert_eq!(capture.source, CaptureSource::Body);
+
+    let capture = parse_capture("code = STATUS").unwrap();
+    assert_eq!(capture.source, CaptureSource::Status);
+}
+
+#[test]
+fn test_parse_capture_rejects_invalid_values() {
+    for value in [
+        "token",
+        "= $.token",
+        "my token = $.token",
+        "token = access_token",
+        "token = header",
+        "token =",
+    ] {
+        assert!(parse_capture(value).is_err(), "{value} should be rejected");
+    }
+}
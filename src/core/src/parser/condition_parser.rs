@@ -19,13 +19,21 @@ pub fn parse_condition(value: &str, negate: bool) -> Option<Condition> {
 
     let expected_value = strip_optional_quotes(&expected_parts.join(" ")).to_string();
 
-    let ref_parts: Vec<&str> = reference.split('.').collect();
+    let all_parts: Vec<&str> = reference.split('.').collect();
 
-    if ref_parts.len() < 3 {
+    // Requests of an imported file are named `<alias>.<request>`.
+    let name_len = if all_parts.get(1) != Some(&"response") && all_parts.get(2) == Some(&"response")
+    {
+        2
+    } else {
+        1
+    };
+    if all_parts.len() < name_len + 2 {
         return None;
     }
 
-    let request_name = ref_parts[0].to_string();
+    let request_name = all_parts[..name_len].join(".");
+    let ref_parts = &all_parts[name_len - 1..];
 
     if ref_parts.len() == 3 && ref_parts[1] == "response" && ref_parts[2] == "status" {
         return Some(Condition {
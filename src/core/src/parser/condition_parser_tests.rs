@@ -50,6 +50,20 @@ fn test_parse_condition_body_jsonpath_nested() {
     assert_eq!(result.expected_value, "test@example.com");
 }
 
+#[test]
+fn test_parse_condition_on_imported_request() {
+    let result = parse_condition("auth.login.response.status 200", false).unwrap();
+    assert_eq!(result.request_name, "auth.login");
+    assert!(matches!(result.condition_type, ConditionType::Status));
+
+    let result = parse_condition("auth.login.response.body.$.role admin", false).unwrap();
+    assert_eq!(result.request_name, "auth.login");
+    assert!(matches!(
+        &result.condition_type,
+        ConditionType::BodyJsonPath(path) if path == "$.role"
+    ));
+}
+
 #[test]
 fn test_parse_condition_expected_value_with_spaces() {
     let result = parse_condition("req.response.status 200 OK", false).unwrap();
@@ -0,0 +1,90 @@
+use crate::environment;
+use crate::redaction::is_sensitive_field_name;
+use anyhow::Result;
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static ENV_REFERENCE_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// Values of variables without a sensitive name are only redacted from this
+/// length on, so that short values such as `REGION=us` stay readable.
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// Resolves `{{$env.NAME}}` and `{{$processEnv NAME}}` from the process
+/// environment and `{{$dotenv NAME}}` from the nearest `.env` file, keeping
+/// the values that look like secrets so the requests can redact them.
+#[derive(Default)]
+pub(crate) struct EnvReferences {
+    dotenv: Option<HashMap<String, String>>,
+    secrets: Vec<String>,
+}
+
+impl EnvReferences {
+    /// Replace the references in `input`. Unknown names are left as written.
+    /// The `.env` file is looked up from `base_dir` on first use. The secrets
+    /// of a previous input that were not taken are dropped.
+    pub(crate) fn substitute<'a>(
+        &mut self,
+        input: &'a str,
+        base_dir: Option<&Path>,
+    ) -> Result<Cow<'a, str>> {
+        self.secrets.clear();
+        if !input.contains("{{") || !input.contains('$') {
+            return Ok(Cow::Borrowed(input));
+        }
+
+        let regex = ENV_REFERENCE_REGEX.get_or_init(|| {
+            Regex::new(
+                r"\{\{\s*\$(?:env\.([A-Za-z_][\w.-]*)|(?i:processEnv)\s+([A-Za-z_][\w.-]*)|(?i:dotenv)\s+([A-Za-z_][\w.-]*))\s*\}\}",
+            )
+            .expect("valid environment reference regex")
+        });
+        if !regex.is_match(input) {
+            return Ok(Cow::Borrowed(input));
+        }
+
+        if self.dotenv.is_none() && input.to_ascii_lowercase().contains("$dotenv") {
+            self.dotenv = Some(match base_dir {
+                Some(dir) => environment::load_dotenv_file(dir)?,
+                None => HashMap::new(),
+            });
+        }
+
+        let substituted = regex.replace_all(input, |captures: &Captures| {
+            let (name, value) = match (captures.get(1).or(captures.get(2)), captures.get(3)) {
+                (Some(name), _) => (name.as_str(), std::env::var(name.as_str()).ok()),
+                (None, Some(name)) => (
+                    name.as_str(),
+                    self.dotenv
+                        .as_ref()
+                        .and_then(|dotenv| dotenv.get(name.as_str()).cloned()),
+                ),
+                (None, None) => return captures[0].to_string(),
+            };
+
+            match value {
+                Some(value) => {
+                    if is_secret(name, &value) && !self.secrets.contains(&value) {
+                        self.secrets.push(value.clone());
+                    }
+                    value
+                }
+                None => captures[0].to_string(),
+            }
+        });
+
+        Ok(Cow::Owned(substituted.into_owned()))
+    }
+
+    /// The values of the last input that are treated as secrets.
+    pub(crate) fn take_secrets(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.secrets)
+    }
+}
+
+fn is_secret(name: &str, value: &str) -> bool {
+    !value.is_empty() && (is_sensitive_field_name(name) || value.len() >= MIN_SECRET_LENGTH)
+}
@@ -0,0 +1,93 @@
+use super::env_references::EnvReferences;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_env_and_process_env_read_the_process_environment() {
+    unsafe {
+        std::env::set_var("HTTPRUNNER_ENV_REFERENCES_TOKEN", "from-process");
+    }
+    let mut references = EnvReferences::default();
+
+    let result = references
+        .substitute(
+            "a={{$env.HTTPRUNNER_ENV_REFERENCES_TOKEN}}&b={{ $processEnv HTTPRUNNER_ENV_REFERENCES_TOKEN }}",
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(result, "a=from-process&b=from-process");
+    assert_eq!(references.take_secrets(), ["from-process".to_string()]);
+    unsafe {
+        std::env::remove_var("HTTPRUNNER_ENV_REFERENCES_TOKEN");
+    }
+}
+
+#[test]
+fn test_dotenv_reads_the_nearest_dotenv_file() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join(".env"), "API_KEY=dotenv-secret\n").unwrap();
+    let mut references = EnvReferences::default();
+
+    let result = references
+        .substitute("X-Api-Key: {{$dotenv API_KEY}}", Some(temp.path()))
+        .unwrap();
+
+    assert_eq!(result, "X-Api-Key: dotenv-secret");
+    assert_eq!(references.take_secrets(), ["dotenv-secret".to_string()]);
+}
+
+#[test]
+fn test_only_sensitive_names_and_long_values_are_secrets() {
+    let temp = tempdir().unwrap();
+    fs::write(
+        temp.path().join(".env"),
+        "REGION=us\nDEPLOY_ID=0123456789abcdef\nDB_PASSWORD=pw\n",
+    )
+    .unwrap();
+    let mut references = EnvReferences::default();
+
+    let result = references
+        .substitute(
+            "{{$dotenv REGION}} {{$dotenv DEPLOY_ID}} {{$dotenv DB_PASSWORD}}",
+            Some(temp.path()),
+        )
+        .unwrap();
+
+    assert_eq!(result, "us 0123456789abcdef pw");
+    assert_eq!(references.take_secrets(), ["0123456789abcdef", "pw"]);
+}
+
+#[test]
+fn test_secrets_are_kept_per_input() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join(".env"), "API_KEY=dotenv-secret\n").unwrap();
+    let mut references = EnvReferences::default();
+
+    references
+        .substitute("{{$dotenv API_KEY}}", Some(temp.path()))
+        .unwrap();
+    references.substitute("no references", None).unwrap();
+
+    assert!(references.take_secrets().is_empty());
+}
+
+#[test]
+fn test_missing_values_are_left_unchanged() {
+    let temp = tempdir().unwrap();
+    let mut references = EnvReferences::default();
+    let input = "{{$env.HTTPRUNNER_SURELY_NOT_SET}} {{$processEnv HTTPRUNNER_SURELY_NOT_SET}} {{$dotenv MISSING}}";
+
+    let result = references.substitute(input, Some(temp.path())).unwrap();
+
+    assert_eq!(result, input);
+    assert!(references.take_secrets().is_empty());
+}
+
+#[test]
+fn test_other_placeholders_are_left_unchanged() {
+    let mut references = EnvReferences::default();
+    let input = r#"{{name}} {{$uuid}} {{$auth.token("my-auth")}}"#;
+
+    assert_eq!(references.substitute(input, None).unwrap(), input);
+}
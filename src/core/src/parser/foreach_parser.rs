@@ -0,0 +1,27 @@
+use crate::types::ForEach;
+
+/// Parses the value of a `@foreach` directive: `<variable> in <reference>`.
+pub fn parse_foreach(value: &str) -> Option<ForEach> {
+    let (variable, rest) = value.trim().split_once(char::is_whitespace)?;
+    let source = rest.trim_start().strip_prefix("in")?;
+    if !source.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let source = source.trim();
+    if source.is_empty() || !is_loop_variable_name(variable) {
+        return None;
+    }
+
+    Some(ForEach {
+        variable: variable.to_string(),
+        source: source.to_string(),
+    })
+}
+
+fn is_loop_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
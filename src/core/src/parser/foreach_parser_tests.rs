@@ -0,0 +1,35 @@
+use super::foreach_parser::*;
+
+#[test]
+fn test_parse_foreach_reference() {
+    let foreach = parse_foreach("item in {{list.response.body.$.items}}").unwrap();
+    assert_eq!(foreach.variable, "item");
+    assert_eq!(foreach.source, "{{list.response.body.$.items}}");
+}
+
+#[test]
+fn test_parse_foreach_tolerates_extra_whitespace() {
+    let foreach = parse_foreach("  user_id   in   {{ids}}  ").unwrap();
+    assert_eq!(foreach.variable, "user_id");
+    assert_eq!(foreach.source, "{{ids}}");
+}
+
+#[test]
+fn test_parse_foreach_requires_in_keyword() {
+    assert!(parse_foreach("item {{list.response.body.$.items}}").is_none());
+    assert!(parse_foreach("item of {{list.response.body.$.items}}").is_none());
+    assert!(parse_foreach("item inside {{list.response.body.$.items}}").is_none());
+}
+
+#[test]
+fn test_parse_foreach_requires_source() {
+    assert!(parse_foreach("item in").is_none());
+    assert!(parse_foreach("item in   ").is_none());
+    assert!(parse_foreach("item").is_none());
+}
+
+#[test]
+fn test_parse_foreach_rejects_invalid_variable_names() {
+    assert!(parse_foreach("item.id in {{ids}}").is_none());
+    assert!(parse_foreach("{{item}} in {{ids}}").is_none());
+}
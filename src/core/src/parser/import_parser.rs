@@ -0,0 +1,63 @@
+use crate::types::Import;
+use anyhow::{Result, bail};
+use std::path::{Component, Path, PathBuf};
+
+/// Parses the value of an `@import` directive: the path of another `.http`
+/// file, resolved against `base_dir`. Its requests are referenced with the file
+/// name without extension, e.g. `auth.login` for `./auth.http`.
+pub fn parse_import(path: &str, base_dir: Option<&Path>) -> Result<Import> {
+    let path = path.trim();
+    if path.is_empty() {
+        bail!("expected the path of a .http file");
+    }
+
+    let Some(alias) = import_alias(path) else {
+        bail!("'{path}' has no file name");
+    };
+    if !is_import_alias(alias) {
+        bail!("'{alias}' is not a valid import name; use letters, digits, '_' or '-'");
+    }
+
+    Ok(Import {
+        alias: alias.to_string(),
+        path: path.to_string(),
+        file: resolve_file_path(path, base_dir)
+            .to_string_lossy()
+            .into_owned(),
+    })
+}
+
+/// `path` resolved against `base_dir` when it is relative, so that
+/// `./auth.http` and `auth.http` name the same file.
+pub fn resolve_file_path(path: &str, base_dir: Option<&Path>) -> PathBuf {
+    let candidate = Path::new(path);
+    let resolved = match base_dir {
+        Some(dir) if candidate.is_relative() => dir.join(candidate),
+        _ => candidate.to_path_buf(),
+    };
+    resolved
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+/// The import a `@dependsOn <file>#<request>` refers to, if it names a file.
+pub fn parse_dependency_import(
+    depends_on: &str,
+    base_dir: Option<&Path>,
+) -> Option<Result<Import>> {
+    let (path, _) = depends_on.split_once('#')?;
+    Some(parse_import(path, base_dir))
+}
+
+/// The name under which the imported requests of `path` are referenced.
+pub fn import_alias(path: &str) -> Option<&str> {
+    Path::new(path.trim()).file_stem()?.to_str()
+}
+
+fn is_import_alias(alias: &str) -> bool {
+    !alias.is_empty()
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
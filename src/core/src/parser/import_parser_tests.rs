@@ -0,0 +1,51 @@
+use super::import_parser::*;
+use std::path::Path;
+
+#[test]
+fn test_parse_import_uses_file_stem_as_alias() {
+    let import = parse_import("./auth.http", None).unwrap();
+    assert_eq!(import.alias, "auth");
+    assert_eq!(import.path, "./auth.http");
+    assert_eq!(import.file, "auth.http");
+}
+
+#[test]
+fn test_parse_import_resolves_relative_paths() {
+    let import = parse_import("shared/auth.http", Some(Path::new("/api"))).unwrap();
+    assert_eq!(import.alias, "auth");
+    assert_eq!(import.path, "shared/auth.http");
+    assert_eq!(
+        Path::new(&import.file),
+        Path::new("/api").join("shared/auth.http")
+    );
+
+    let import = parse_import("/etc/http/auth.http", Some(Path::new("/api"))).unwrap();
+    assert_eq!(import.file, "/etc/http/auth.http");
+}
+
+#[test]
+fn test_parse_import_rejects_invalid_paths() {
+    assert!(parse_import("  ", None).is_err());
+    assert!(parse_import("./", None).is_err());
+
+    let error = parse_import("./my auth.http", None).unwrap_err();
+    assert!(error.to_string().contains("not a valid import name"));
+}
+
+#[test]
+fn test_parse_dependency_import() {
+    let import = parse_dependency_import("auth.http#login", None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(import.alias, "auth");
+    assert_eq!(import.path, "auth.http");
+
+    assert!(parse_dependency_import("login", None).is_none());
+}
+
+#[test]
+fn test_import_alias() {
+    assert_eq!(import_alias("./shared/auth.http"), Some("auth"));
+    assert_eq!(import_alias("orders"), Some("orders"));
+    assert_eq!(import_alias(""), None);
+}
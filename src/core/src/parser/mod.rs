@@ -1,5 +1,14 @@
+mod auth_parser;
+mod capture_parser;
 mod condition_parser;
+mod env_references;
+mod foreach_parser;
+mod import_parser;
+mod pagination_parser;
+mod run_parser;
+mod signing_parser;
 mod substitution;
+mod tag_parser;
 mod timeout_parser;
 mod utils;
 
@@ -7,7 +16,11 @@ mod pest_parse_tree;
 mod pest_parser;
 mod pest_semantic_assembler;
 
-pub use pest_semantic_assembler::{parse_http_content, parse_http_file};
+pub use pest_semantic_assembler::{
+    parse_http_content, parse_http_file, parse_http_file_with_variables, resolve_file_variables,
+};
+pub(crate) use import_parser::import_alias;
+pub(crate) use substitution::{find_placeholders, substitute_variables};
 
 #[cfg(test)]
 mod tests;
@@ -21,5 +34,32 @@ mod utils_tests;
 #[cfg(test)]
 mod condition_parser_tests;
 
+#[cfg(test)]
+mod env_references_tests;
+
+#[cfg(test)]
+mod auth_parser_tests;
+
+#[cfg(test)]
+mod capture_parser_tests;
+
+#[cfg(test)]
+mod foreach_parser_tests;
+
+#[cfg(test)]
+mod import_parser_tests;
+
+#[cfg(test)]
+mod pagination_parser_tests;
+
+#[cfg(test)]
+mod run_parser_tests;
+
+#[cfg(test)]
+mod signing_parser_tests;
+
 #[cfg(test)]
 mod substitution_tests;
+
+#[cfg(test)]
+mod tag_parser_tests;
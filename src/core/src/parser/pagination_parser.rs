@@ -0,0 +1,90 @@
+use crate::types::{DEFAULT_MAX_PAGES, Pagination, PaginationAssertScope, PaginationNext};
+use anyhow::{Result, anyhow, bail};
+
+/// Parses the value of a `@paginate` directive.
+///
+/// The value names exactly one way of finding the next page — `link`,
+/// `next=<jsonpath>` or `cursor=<jsonpath>` — followed by optional
+/// `param=<name>`, `max=<pages>`, `items=<jsonpath>` and `assert=page|aggregate`
+/// settings, e.g. `cursor=$.meta.next param=after max=5 items=$.data`.
+pub fn parse_paginate(value: &str) -> Result<Pagination> {
+    let mut next = None;
+    let mut cursor_param = None;
+    let mut max_pages = DEFAULT_MAX_PAGES;
+    let mut items = None;
+    let mut assert_scope = PaginationAssertScope::Page;
+
+    for token in value.split_whitespace() {
+        if token.eq_ignore_ascii_case("link") {
+            set_next(&mut next, PaginationNext::Link)?;
+            continue;
+        }
+
+        let Some((key, setting)) = token.split_once('=') else {
+            bail!("unknown setting '{token}'");
+        };
+        if setting.is_empty() {
+            bail!("'{key}' requires a value");
+        }
+
+        match key {
+            "next" => set_next(&mut next, PaginationNext::Url(json_path(key, setting)?))?,
+            "cursor" => set_next(
+                &mut next,
+                PaginationNext::Cursor {
+                    path: json_path(key, setting)?,
+                    param: String::new(),
+                },
+            )?,
+            "param" => cursor_param = Some(setting.to_string()),
+            "max" => {
+                max_pages = setting
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|pages| *pages > 0)
+                    .ok_or_else(|| anyhow!("'max' must be a positive number of pages"))?;
+            }
+            "items" => items = Some(json_path(key, setting)?),
+            "assert" => {
+                assert_scope = match setting {
+                    "page" => PaginationAssertScope::Page,
+                    "aggregate" => PaginationAssertScope::Aggregate,
+                    _ => bail!("'assert' must be 'page' or 'aggregate'"),
+                };
+            }
+            _ => bail!("unknown setting '{key}'"),
+        }
+    }
+
+    let next = match (next, cursor_param) {
+        (None, _) => bail!("expected 'link', 'next=<jsonpath>' or 'cursor=<jsonpath>'"),
+        (Some(PaginationNext::Cursor { path, .. }), param) => PaginationNext::Cursor {
+            path,
+            param: param.unwrap_or_else(|| "cursor".to_string()),
+        },
+        (Some(_), Some(_)) => bail!("'param' can only be used with 'cursor'"),
+        (Some(next), None) => next,
+    };
+
+    Ok(Pagination {
+        next,
+        max_pages,
+        items,
+        assert_scope,
+    })
+}
+
+fn set_next(next: &mut Option<PaginationNext>, value: PaginationNext) -> Result<()> {
+    if next.is_some() {
+        bail!("only one of 'link', 'next' or 'cursor' can be used");
+    }
+    *next = Some(value);
+    Ok(())
+}
+
+fn json_path(key: &str, path: &str) -> Result<String> {
+    if !path.starts_with("$.") {
+        bail!("'{key}' must be a JSONPath starting with '$.'");
+    }
+    Ok(path.to_string())
+}
@@ -0,0 +1,58 @@
+use super::pagination_parser::*;
+use crate::types::{DEFAULT_MAX_PAGES, PaginationAssertScope, PaginationNext};
+
+#[test]
+fn test_parse_paginate_link_defaults() {
+    let pagination = parse_paginate("link").unwrap();
+    assert_eq!(pagination.next, PaginationNext::Link);
+    assert_eq!(pagination.max_pages, DEFAULT_MAX_PAGES);
+    assert!(pagination.items.is_none());
+    assert_eq!(pagination.assert_scope, PaginationAssertScope::Page);
+}
+
+#[test]
+fn test_parse_paginate_next_url_with_settings() {
+    let pagination =
+        parse_paginate("next=$.links.next max=5 items=$.data assert=aggregate").unwrap();
+    assert_eq!(
+        pagination.next,
+        PaginationNext::Url("$.links.next".to_string())
+    );
+    assert_eq!(pagination.max_pages, 5);
+    assert_eq!(pagination.items.as_deref(), Some("$.data"));
+    assert_eq!(pagination.assert_scope, PaginationAssertScope::Aggregate);
+}
+
+#[test]
+fn test_parse_paginate_cursor_param() {
+    let pagination = parse_paginate("cursor=$.meta.next_cursor param=after").unwrap();
+    assert_eq!(
+        pagination.next,
+        PaginationNext::Cursor {
+            path: "$.meta.next_cursor".to_string(),
+            param: "after".to_string(),
+        }
+    );
+
+    let pagination = parse_paginate("cursor=$.next").unwrap();
+    assert_eq!(
+        pagination.next,
+        PaginationNext::Cursor {
+            path: "$.next".to_string(),
+            param: "cursor".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_parse_paginate_rejects_invalid_values() {
+    assert!(parse_paginate("").is_err());
+    assert!(parse_paginate("max=5").is_err());
+    assert!(parse_paginate("link next=$.next").is_err());
+    assert!(parse_paginate("link param=page").is_err());
+    assert!(parse_paginate("link max=0").is_err());
+    assert!(parse_paginate("link max=many").is_err());
+    assert!(parse_paginate("link assert=sometimes").is_err());
+    assert!(parse_paginate("next=links.next").is_err());
+    assert!(parse_paginate("link pages=3").is_err());
+}
@@ -18,6 +18,7 @@ pub(crate) enum PestLineKind {
     Variable(PestVariableLine),
     Assertion(PestAssertionLine),
     Request(PestRequestLine),
+    Run(PestRunLine),
     Header(PestHeaderLine),
     Body(PestBodyLine),
     IgnoredScriptBlock(PestScriptBlock),
@@ -50,6 +51,15 @@ pub(crate) enum PestDirectiveKind {
     IfNot(PestConditionExpression),
     PreDelay(String),
     PostDelay(String),
+    Data(String),
+    ForEach { variable: String, source: String },
+    Paginate(String),
+    Auth { scheme: String, credentials: String },
+    Sign(String),
+    ContentDigest(String),
+    Capture(String),
+    Import(String),
+    Tag(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -105,6 +115,12 @@ pub(crate) enum PestAssertionValue {
     DoubleQuoted(String),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PestRunLine {
+    pub target: String,
+    pub variables: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct PestRequestLine {
     pub method: String,
@@ -1,9 +1,10 @@
 use super::pest_parse_tree::{
     CommentPrefix, PestAssertionKind, PestAssertionLine, PestAssertionValue, PestBlankLine,
     PestBodyLine, PestCommentLine, PestConditionExpression, PestDirectiveKind, PestDirectiveLine,
-    PestHeaderLine, PestHttpFile, PestLine, PestLineKind, PestRequestLine, PestScriptBlock,
-    PestTimeoutLiteral, PestVariableLine,
+    PestHeaderLine, PestHttpFile, PestLine, PestLineKind, PestRequestLine, PestRunLine,
+    PestScriptBlock, PestTimeoutLiteral, PestVariableLine,
 };
+use super::utils::split_request_line;
 use anyhow::{Context, Result, anyhow, bail};
 use pest::Parser;
 use pest::iterators::{Pair, Pairs};
@@ -105,6 +106,7 @@ fn build_line(pair: Pair<'_, Rule>, line_number: usize) -> Result<PestLine> {
         Rule::CommentOrDirectiveLine => build_comment_or_directive_line(&raw)?,
         Rule::VariableLine => PestLineKind::Variable(build_variable_line(&raw)?),
         Rule::AssertionLine => PestLineKind::Assertion(build_assertion_line(&raw)?),
+        Rule::RunLine => PestLineKind::Run(build_run_line(&raw)?),
         Rule::RequestLine => PestLineKind::Request(build_request_line(&raw)?),
         Rule::HeaderLine => PestLineKind::Header(build_header_line(&raw)?),
         Rule::BodyLine => PestLineKind::Body(build_body_line(pair)?),
@@ -132,7 +134,8 @@ fn build_comment_or_directive_line(raw: &str) -> Result<PestLineKind> {
     let directive_name = &directive_body[..separator];
     match directive_name {
         "name" | "timeout" | "connection-timeout" | "dependsOn" | "if" | "if-not" | "pre-delay"
-        | "post-delay" => Ok(PestLineKind::Directive(build_directive_line(raw)?)),
+        | "post-delay" | "data" | "foreach" | "paginate" | "auth" | "sign" | "content-digest"
+        | "capture" | "import" | "tag" => Ok(PestLineKind::Directive(build_directive_line(raw)?)),
         _ => Ok(PestLineKind::Comment(build_comment_from_raw(raw)?)),
     }
 }
@@ -153,6 +156,15 @@ fn build_directive_line(raw: &str) -> Result<PestDirectiveLine> {
         "@if-not" => build_if_not_directive(raw),
         "@pre-delay" => build_pre_delay_directive(raw),
         "@post-delay" => build_post_delay_directive(raw),
+        "@data" => build_data_directive(raw),
+        "@foreach" => build_foreach_directive(raw),
+        "@paginate" => build_paginate_directive(raw),
+        "@auth" => build_auth_directive(raw),
+        "@sign" => build_sign_directive(raw),
+        "@content-digest" => build_content_digest_directive(raw),
+        "@capture" => build_capture_directive(raw),
+        "@import" => build_import_directive(raw),
+        "@tag" => build_tag_directive(raw),
         other => bail!("unexpected directive keyword: {other}"),
     }
 }
@@ -229,6 +241,92 @@ fn build_post_delay_directive(raw: &str) -> Result<PestDirectiveLine> {
     })
 }
 
+fn build_data_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@data")?;
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::Data(value.to_string()),
+    })
+}
+
+fn build_foreach_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@foreach")?;
+    let (variable, source) = value
+        .split_once(" in ")
+        .context("foreach expression did not contain ' in '")?;
+
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::ForEach {
+            variable: variable.trim().to_string(),
+            source: source.trim().to_string(),
+        },
+    })
+}
+
+fn build_paginate_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@paginate")?;
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::Paginate(value.to_string()),
+    })
+}
+
+fn build_auth_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@auth")?;
+    let (scheme, credentials) = value
+        .split_once(char::is_whitespace)
+        .context("auth directive did not contain credentials")?;
+
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::Auth {
+            scheme: scheme.to_string(),
+            credentials: credentials.trim().to_string(),
+        },
+    })
+}
+
+fn build_sign_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@sign")?;
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::Sign(value.to_string()),
+    })
+}
+
+fn build_content_digest_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@content-digest")?;
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::ContentDigest(value.to_string()),
+    })
+}
+
+fn build_capture_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@capture")?;
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::Capture(value.to_string()),
+    })
+}
+
+fn build_import_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@import")?;
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::Import(value.to_string()),
+    })
+}
+
+fn build_tag_directive(raw: &str) -> Result<PestDirectiveLine> {
+    let (prefix, value) = parse_directive_value(raw, "@tag")?;
+    Ok(PestDirectiveLine {
+        prefix,
+        kind: PestDirectiveKind::Tag(value.to_string()),
+    })
+}
+
 fn build_timeout_literal(value: &str) -> Result<PestTimeoutLiteral> {
     let value = value.trim();
     let amount_end = value
@@ -349,7 +447,7 @@ fn build_assertion_line(raw: &str) -> Result<PestAssertionLine> {
 }
 
 fn build_request_line(raw: &str) -> Result<PestRequestLine> {
-    let parts: Vec<&str> = raw.split_whitespace().collect();
+    let parts = split_request_line(raw);
     if parts.len() < 2 {
         bail!("request line did not contain a method and target");
     }
@@ -377,6 +475,23 @@ fn build_request_line(raw: &str) -> Result<PestRequestLine> {
     })
 }
 
+fn build_run_line(raw: &str) -> Result<PestRunLine> {
+    let rest = raw
+        .trim()
+        .strip_prefix("run")
+        .context("run line did not start with 'run'")?
+        .trim();
+    let (target, variables) = match rest.find('(') {
+        Some(open) => (&rest[..open], Some(rest[open..].to_string())),
+        None => (rest, None),
+    };
+
+    Ok(PestRunLine {
+        target: target.trim_end().to_string(),
+        variables,
+    })
+}
+
 fn build_header_line(raw: &str) -> Result<PestHeaderLine> {
     let separator = raw.find(':').context("header line did not contain ':'")?;
     let name = raw[..separator].to_string();
@@ -658,6 +773,31 @@ GET https://api.example.com/profile"#;
         assert_eq!(tree.lines[1].line_number, 4);
     }
 
+    #[test]
+    fn classifies_run_lines_before_request_lines() {
+        let input = r#"run #login
+run ./setup.http#create user (@host=localhost, @user=admin)
+run is not a run line"#;
+
+        let tree = parse_http_content_to_pest_tree(input).expect("parse tree should build");
+
+        assert_eq!(
+            tree.lines[0].kind,
+            PestLineKind::Run(PestRunLine {
+                target: "#login".to_string(),
+                variables: None,
+            })
+        );
+        assert_eq!(
+            tree.lines[1].kind,
+            PestLineKind::Run(PestRunLine {
+                target: "./setup.http#create user".to_string(),
+                variables: Some("(@host=localhost, @user=admin)".to_string()),
+            })
+        );
+        assert!(matches!(tree.lines[2].kind, PestLineKind::Body(_)));
+    }
+
     #[test]
     fn parses_request_variable_references() {
         let mut pairs = HttpFilePestParser::parse(
@@ -670,6 +810,16 @@ GET https://api.example.com/profile"#;
             pairs.next().expect("request variable pair").as_rule(),
             Rule::RequestVariableReference
         );
+
+        for reference in [
+            "{{login.response.status}}",
+            "{{login.response.duration}}",
+            "{{create.request.url}}",
+            "{{create.request.method}}",
+        ] {
+            HttpFilePestParser::parse(Rule::RequestVariableReference, reference)
+                .expect("scalar request variable syntax should parse");
+        }
     }
 
     #[test]
@@ -682,4 +832,50 @@ GET https://api.example.com/profile"#;
             Rule::FunctionCall
         );
     }
+
+    #[test]
+    fn parses_two_argument_function_calls() {
+        let pair = HttpFilePestParser::parse(Rule::FunctionCall, "hmac_sha256('key', 'message')")
+            .expect("function syntax should parse")
+            .next()
+            .expect("function pair");
+
+        assert_eq!(pair.as_str(), "hmac_sha256('key', 'message')");
+    }
+
+    #[test]
+    fn request_line_keeps_dynamic_variable_arguments_in_target() {
+        let line =
+            build_request_line("GET /items?limit={{$randomInt 1 100}}&q={{ term }} HTTP/1.1\n")
+                .expect("request line should parse");
+
+        assert_eq!(line.method, "GET");
+        assert_eq!(
+            line.target,
+            "/items?limit={{$randomInt 1 100}}&q={{ term }}"
+        );
+        assert_eq!(line.http_version.as_deref(), Some("HTTP/1.1"));
+        assert!(line.trailing_tokens.is_empty());
+    }
+
+    #[test]
+    fn parses_parameterized_function_calls() {
+        for call in [
+            "number(1, 100)",
+            "number(-5,5)",
+            "string(32)",
+            "getdate('+7d')",
+            "getdatetime('%Y-%m-%dT%H:%M:%S', '-1d')",
+            "timestamp_ms()",
+            "uuid_v7()",
+            "ulid()",
+        ] {
+            let pair = HttpFilePestParser::parse(Rule::FunctionCall, call)
+                .expect("function syntax should parse")
+                .next()
+                .expect("function pair");
+
+            assert_eq!(pair.as_str(), call);
+        }
+    }
 }
@@ -1,40 +1,138 @@
+use super::auth_parser::parse_auth;
+use super::capture_parser::parse_capture;
 use super::condition_parser::parse_condition;
+use super::env_references::EnvReferences;
+use super::foreach_parser::parse_foreach;
+use super::import_parser::{parse_dependency_import, parse_import, resolve_file_path};
+use super::pagination_parser::parse_paginate;
 use super::pest_parse_tree::{PestHttpFile, PestLine, PestLineKind, PestScriptBlock};
 use super::pest_parser::{PestRawLineKind, parse_http_content_to_pest_raw_file};
-use super::substitution::substitute_variables;
+use super::run_parser::parse_run_line;
+use super::signing_parser::{parse_content_digest, parse_sign};
+use super::substitution::{find_placeholders, substitute_variables};
+use super::tag_parser::parse_tags;
 use super::timeout_parser::parse_timeout_value;
-use super::utils::is_http_request_line;
+use super::utils::{is_http_request_line, split_request_line};
+use crate::datasets;
 use crate::environment;
-use crate::types::{Assertion, AssertionType, Condition, Header, HttpRequest, Variable};
-use anyhow::{Context, Result, anyhow};
+use crate::types::{
+    Assertion, AssertionType, Auth, Capture, Condition, ContentDigest, DataScope, DataSource,
+    ForEach, Header, HttpRequest, Import, Pagination, RequestHook, Run, Signing, SourcedVariable,
+    UnresolvedVariable, Variable, VariableSource,
+};
+use anyhow::{Context, Result, anyhow, bail};
+use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 pub fn parse_http_file(
     file_path: &str,
     environment_name: Option<&str>,
 ) -> Result<Vec<HttpRequest>> {
+    parse_http_file_with_variables(file_path, environment_name, &[])
+}
+
+/// Parse a file with variables that override the environment and the file's
+/// own `@name=value` declarations, such as those given on the command line.
+/// See [`VariableSource`] for the precedence order.
+pub fn parse_http_file_with_variables(
+    file_path: &str,
+    environment_name: Option<&str>,
+    overrides: &[SourcedVariable],
+) -> Result<Vec<HttpRequest>> {
+    Ok(assemble_http_file(file_path, environment_name, overrides)?.requests)
+}
+
+/// The variables in effect at the end of a file, in declaration order, and
+/// where each value comes from.
+pub fn resolve_file_variables(
+    file_path: &str,
+    environment_name: Option<&str>,
+    overrides: &[SourcedVariable],
+) -> Result<Vec<SourcedVariable>> {
+    let state = assemble_http_file(file_path, environment_name, overrides)?;
+    Ok(state
+        .variables
+        .iter()
+        .map(|variable| SourcedVariable {
+            name: variable.name.clone(),
+            value: variable.value.clone(),
+            source: state.variable_sources[&variable.name],
+        })
+        .collect())
+}
+
+fn assemble_http_file(
+    file_path: &str,
+    environment_name: Option<&str>,
+    overrides: &[SourcedVariable],
+) -> Result<SemanticAssemblerState> {
     let content = fs::read_to_string(file_path)
         .with_context(|| format!("Failed to read file: {}", file_path))?;
 
-    let env_variables = environment::load_environment_file(file_path, environment_name)?;
-    parse_http_content_with_pest_semantics(&content, env_variables)
+    let (env_variables, env_secrets) =
+        environment::load_environment_with_secrets(file_path, environment_name)?;
+    let base_dir = Path::new(file_path).parent();
+    let running = vec![run_key(Path::new(file_path))];
+    let mut state = assemble_content(&content, env_variables, overrides, base_dir, running, true)?;
+    for request in &mut state.requests {
+        request.secrets.extend(env_secrets.iter().cloned());
+    }
+    Ok(state)
 }
 
 pub fn parse_http_content(
     content: &str,
     _environment_name: Option<&str>,
 ) -> Result<Vec<HttpRequest>> {
-    parse_http_content_with_pest_semantics(content, Vec::new())
+    parse_http_content_with_pest_semantics(content, Vec::new(), None)
 }
 
 pub(crate) fn parse_http_content_with_pest_semantics(
     content: &str,
     env_variables: Vec<Variable>,
+    base_dir: Option<&Path>,
 ) -> Result<Vec<HttpRequest>> {
+    Ok(assemble_http_content(content, env_variables, &[], base_dir)?.requests)
+}
+
+fn assemble_http_content(
+    content: &str,
+    env_variables: Vec<Variable>,
+    overrides: &[SourcedVariable],
+    base_dir: Option<&Path>,
+) -> Result<SemanticAssemblerState> {
+    assemble_content(
+        content,
+        env_variables,
+        overrides,
+        base_dir,
+        Vec::new(),
+        true,
+    )
+}
+
+/// `running` holds the files whose `run` lines are being expanded, to detect
+/// files that run each other. With `expand_runs` off, `run` lines add no
+/// requests.
+fn assemble_content(
+    content: &str,
+    env_variables: Vec<Variable>,
+    overrides: &[SourcedVariable],
+    base_dir: Option<&Path>,
+    running: Vec<PathBuf>,
+    expand_runs: bool,
+) -> Result<SemanticAssemblerState> {
     let raw_file = parse_http_content_to_pest_raw_file(content)?;
     let mut state = SemanticAssemblerState::new(env_variables);
+    state.apply_overrides(overrides);
+    state.base_dir = base_dir.map(Path::to_path_buf);
+    state.content = content.to_string();
+    state.running = running;
+    state.expand_runs = expand_runs;
 
     for line in raw_file.lines {
+        state.line_number = line.line_number;
         assemble_raw_line(line.raw, line.kind, &mut state).with_context(|| {
             format!(
                 "Failed to parse line {}: {}",
@@ -44,8 +142,8 @@ pub(crate) fn parse_http_content_with_pest_semantics(
         })?;
     }
 
-    state.finalize_current_request();
-    Ok(state.requests)
+    state.finish()?;
+    Ok(state)
 }
 
 #[cfg_attr(not(test), allow(dead_code))]
@@ -56,6 +154,7 @@ pub(crate) fn assemble_http_requests_from_pest_tree(
     let mut state = SemanticAssemblerState::new(env_variables);
 
     for line in &tree.lines {
+        state.line_number = line.line_number;
         assemble_line(line, &mut state).with_context(|| {
             format!(
                 "Failed to parse line {}: {}",
@@ -65,15 +164,19 @@ pub(crate) fn assemble_http_requests_from_pest_tree(
         })?;
     }
 
-    state.finalize_current_request();
+    state.finish()?;
     Ok(state.requests)
 }
 
 struct SemanticAssemblerState {
     requests: Vec<HttpRequest>,
     variables: Vec<Variable>,
+    variable_sources: HashMap<String, VariableSource>,
     current_request: Option<HttpRequest>,
     in_body: bool,
+    /// A `###` separator was seen since the current request started, so a
+    /// `run` line is not part of its body.
+    after_separator: bool,
     body_content: String,
     pending_request_name: Option<String>,
     pending_timeout: Option<u64>,
@@ -82,16 +185,47 @@ struct SemanticAssemblerState {
     pending_conditions: Vec<Condition>,
     pending_pre_delay: Option<u64>,
     pending_post_delay: Option<u64>,
+    pending_data: Option<DataSource>,
+    pending_foreach: Option<ForEach>,
+    pending_paginate: Option<Pagination>,
+    pending_auth: Option<Auth>,
+    pending_sign: Option<Signing>,
+    pending_content_digest: Option<ContentDigest>,
+    pending_captures: Vec<Capture>,
+    pending_hook: Option<RequestHook>,
+    pending_tags: Vec<String>,
+    pending_unresolved_variables: Vec<UnresolvedVariable>,
+    pending_secrets: Vec<String>,
+    /// Secret `$env`/`$processEnv`/`$dotenv` values of variable definitions,
+    /// redacted from every request after them.
+    file_secrets: Vec<String>,
+    file_data: Option<DataSource>,
+    imports: Vec<Import>,
+    base_dir: Option<PathBuf>,
+    content: String,
+    environment: Vec<Variable>,
+    overrides: Vec<SourcedVariable>,
+    running: Vec<PathBuf>,
+    expand_runs: bool,
+    env_references: EnvReferences,
     in_intellij_script: bool,
+    strict_variables: bool,
+    line_number: usize,
 }
 
 impl SemanticAssemblerState {
     fn new(env_variables: Vec<Variable>) -> Self {
         Self {
             requests: Vec::new(),
+            variable_sources: env_variables
+                .iter()
+                .map(|variable| (variable.name.clone(), VariableSource::Environment))
+                .collect(),
+            environment: env_variables.clone(),
             variables: env_variables,
             current_request: None,
             in_body: false,
+            after_separator: false,
             body_content: String::new(),
             pending_request_name: None,
             pending_timeout: None,
@@ -100,7 +234,29 @@ impl SemanticAssemblerState {
             pending_conditions: Vec::new(),
             pending_pre_delay: None,
             pending_post_delay: None,
+            pending_data: None,
+            pending_foreach: None,
+            pending_paginate: None,
+            pending_auth: None,
+            pending_sign: None,
+            pending_content_digest: None,
+            pending_captures: Vec::new(),
+            pending_hook: None,
+            pending_tags: Vec::new(),
+            pending_unresolved_variables: Vec::new(),
+            pending_secrets: Vec::new(),
+            file_secrets: Vec::new(),
+            file_data: None,
+            imports: Vec::new(),
+            base_dir: None,
+            content: String::new(),
+            overrides: Vec::new(),
+            running: Vec::new(),
+            expand_runs: true,
+            env_references: EnvReferences::default(),
             in_intellij_script: false,
+            strict_variables: false,
+            line_number: 0,
         }
     }
 
@@ -109,12 +265,141 @@ impl SemanticAssemblerState {
             if !self.body_content.is_empty() {
                 req.body = Some(substitute_variables(&self.body_content, &self.variables));
             }
+            add_secrets(&mut req.secrets, self.file_secrets.clone());
             self.requests.push(req);
             self.body_content.clear();
         }
     }
 
+    /// `@strict-variables` and `@import` apply to every request of the file,
+    /// wherever they appear. Requests of a `run` line keep the imports of
+    /// their own file too, so both files must import the same file under the
+    /// same name.
+    fn finish(&mut self) -> Result<()> {
+        self.finalize_current_request();
+        if self.strict_variables {
+            for request in &mut self.requests {
+                request.strict_variables = true;
+            }
+        }
+        for request in &mut self.requests {
+            for import in &self.imports {
+                let Some(own) = request.imports.iter().find(|own| own.alias == import.alias) else {
+                    request.imports.push(import.clone());
+                    continue;
+                };
+                if run_key(Path::new(&own.file)) != run_key(Path::new(&import.file)) {
+                    bail!(
+                        "'{}' is imported from both {} and {}",
+                        import.alias,
+                        import.file,
+                        own.file
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_import(&mut self, import: Import) -> Result<()> {
+        match self
+            .imports
+            .iter()
+            .find(|existing| existing.alias == import.alias)
+        {
+            Some(existing) if Path::new(&existing.file) == Path::new(&import.file) => Ok(()),
+            Some(existing) => Err(anyhow!(
+                "'{}' is already imported from {}",
+                import.alias,
+                existing.path
+            )),
+            None => {
+                self.imports.push(import);
+                Ok(())
+            }
+        }
+    }
+
+    /// Add the requests of a `run` line in its place. They are assembled again
+    /// from their file, with the line's variables overriding the file's, and
+    /// run as setup or teardown only when the `run` line itself is marked.
+    fn add_run_requests(&mut self, run: Run) -> Result<()> {
+        self.finalize_current_request();
+        self.in_body = false;
+        if !self.expand_runs {
+            return Ok(());
+        }
+
+        let mut overrides = self.overrides.clone();
+        overrides.extend(run.variables.iter().map(|variable| SourcedVariable {
+            name: variable.name.clone(),
+            value: substitute_variables(&variable.value, &self.variables),
+            source: VariableSource::Run,
+        }));
+
+        let requests = match &run.path {
+            // The file's own `run` lines are not expanded again.
+            None => {
+                let content = std::mem::take(&mut self.content);
+                let assembled = assemble_content(
+                    &content,
+                    self.environment.clone(),
+                    &overrides,
+                    self.base_dir.as_deref(),
+                    self.running.clone(),
+                    false,
+                );
+                self.content = content;
+                assembled?.requests
+            }
+            Some(path) => {
+                let file = resolve_file_path(path, self.base_dir.as_deref());
+                let key = run_key(&file);
+                if self.running.contains(&key) {
+                    bail!("Circular run of {}", file.display());
+                }
+                let content = fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read file: {}", file.display()))?;
+                let mut running = self.running.clone();
+                running.push(key);
+                assemble_content(
+                    &content,
+                    self.environment.clone(),
+                    &overrides,
+                    file.parent(),
+                    running,
+                    true,
+                )?
+                .requests
+            }
+        };
+
+        let requests: Vec<HttpRequest> = match &run.request {
+            Some(name) => requests
+                .into_iter()
+                .filter(|request| request.name.as_deref() == Some(name.as_str()))
+                .collect(),
+            None => requests,
+        };
+        if requests.is_empty() {
+            match &run.request {
+                Some(name) => bail!("No request named '{}' to run", name),
+                None => bail!("No requests to run"),
+            }
+        }
+
+        let hook = self.pending_hook.take();
+        for mut request in requests {
+            request.run = Some(run.clone());
+            request.hook = hook;
+            self.requests.push(request);
+        }
+        Ok(())
+    }
+
     fn start_new_request(&mut self, method: String, url: String) {
+        let mut unresolved_variables = std::mem::take(&mut self.pending_unresolved_variables);
+        record_unresolved_variables(&mut unresolved_variables, &url, self.line_number);
         self.current_request = Some(HttpRequest {
             name: self.pending_request_name.take(),
             method,
@@ -129,16 +414,36 @@ impl SemanticAssemblerState {
             conditions: std::mem::take(&mut self.pending_conditions),
             pre_delay_ms: self.pending_pre_delay.take(),
             post_delay_ms: self.pending_post_delay.take(),
+            data: self.pending_data.take().or_else(|| self.file_data.clone()),
+            foreach: self.pending_foreach.take(),
+            paginate: self.pending_paginate.take(),
+            auth: self.pending_auth.take(),
+            sign: self.pending_sign.take(),
+            content_digest: self.pending_content_digest.take(),
+            captures: std::mem::take(&mut self.pending_captures),
+            imports: Vec::new(),
+            run: None,
+            hook: self.pending_hook.take(),
+            tags: std::mem::take(&mut self.pending_tags),
+            secrets: std::mem::take(&mut self.pending_secrets),
+            resolved_secrets: Vec::new(),
+            strict_variables: false,
+            unresolved_variables,
         });
         self.in_body = false;
+        self.after_separator = false;
     }
 
     fn add_header(&mut self, name: &str, value: &str) {
         if let Some(ref mut req) = self.current_request {
-            req.headers.push(Header {
+            let header = Header {
                 name: substitute_variables(name, &self.variables),
                 value: substitute_variables(value, &self.variables),
-            });
+            };
+            for text in [&header.name, &header.value] {
+                record_unresolved_variables(&mut req.unresolved_variables, text, self.line_number);
+            }
+            req.headers.push(header);
         }
     }
 
@@ -157,16 +462,113 @@ impl SemanticAssemblerState {
             self.body_content.push('\n');
         }
         self.body_content.push_str(content);
+
+        if let Some(ref mut req) = self.current_request
+            && content.contains("{{")
+        {
+            record_unresolved_variables(
+                &mut req.unresolved_variables,
+                &substitute_variables(content, &self.variables),
+                self.line_number,
+            );
+        }
+    }
+
+    fn load_data_source(&self, path: &str) -> Result<DataSource> {
+        let path = substitute_variables(strip_quotes(path), &self.variables);
+        let rows = datasets::load_dataset(&path, self.base_dir.as_deref())?;
+        Ok(DataSource {
+            path,
+            scope: DataScope::Request,
+            rows,
+        })
+    }
+
+    /// Keep the secret environment values of a line for the requests it
+    /// affects: every later request for a variable definition, the current
+    /// request for its own lines, and the next request otherwise.
+    fn add_env_secrets(&mut self, secrets: Vec<String>, defines_variable: bool, directive: bool) {
+        let target = if defines_variable {
+            &mut self.file_secrets
+        } else {
+            match &mut self.current_request {
+                Some(req) if !self.after_separator && !directive => &mut req.secrets,
+                _ => &mut self.pending_secrets,
+            }
+        };
+        add_secrets(target, secrets);
+    }
+
+    /// A `@data` directive in the file header followed by a `###` separator
+    /// (before any request) repeats the whole file once per row.
+    fn promote_pending_data_to_file_scope(&mut self) {
+        if self.current_request.is_some() || !self.requests.is_empty() {
+            return;
+        }
+
+        if let Some(mut data) = self.pending_data.take() {
+            data.scope = DataScope::File;
+            self.file_data = Some(data);
+        }
+    }
+
+    /// Overrides replace the environment's values and keep the file's own
+    /// declarations from replacing theirs.
+    fn apply_overrides(&mut self, overrides: &[SourcedVariable]) {
+        self.overrides = overrides.to_vec();
+        let mut overrides: Vec<&SourcedVariable> = overrides.iter().collect();
+        overrides.sort_by_key(|variable| variable.source);
+        for variable in overrides {
+            self.store_variable(&variable.name, variable.value.clone(), variable.source);
+        }
     }
 
     fn set_variable(&mut self, name: &str, value: &str) {
+        if self
+            .variable_sources
+            .get(name)
+            .is_some_and(|source| *source > VariableSource::File)
+        {
+            return;
+        }
+
         let substituted_value = substitute_variables(value, &self.variables);
+        self.store_variable(name, substituted_value, VariableSource::File);
+    }
+
+    fn store_variable(&mut self, name: &str, value: String, source: VariableSource) {
         if let Some(var) = self.variables.iter_mut().find(|v| v.name == name) {
-            var.value = substituted_value;
+            var.value = value;
         } else {
             self.variables.push(Variable {
                 name: name.to_string(),
-                value: substituted_value,
+                value,
+            });
+        }
+        self.variable_sources.insert(name.to_string(), source);
+    }
+}
+
+/// Files are compared by their canonical path when they exist.
+fn run_key(file: &Path) -> PathBuf {
+    fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf())
+}
+
+/// Remember the placeholders left in `text` so strict mode can say which line
+/// an undefined variable is used on.
+fn record_unresolved_variables(
+    unresolved_variables: &mut Vec<UnresolvedVariable>,
+    text: &str,
+    line: usize,
+) {
+    for name in find_placeholders(text) {
+        if !unresolved_variables
+            .iter()
+            .any(|variable| variable.name == name)
+        {
+            unresolved_variables.push(UnresolvedVariable {
+                name: name.to_string(),
+                line,
             });
         }
     }
@@ -186,6 +588,22 @@ fn try_parse_directive(trimmed: &str, state: &mut SemanticAssemblerState) -> Lin
         return LineParseResult::NotHandled;
     };
 
+    match after_prefix.trim_end() {
+        "strict-variables" => {
+            state.strict_variables = true;
+            return LineParseResult::Continue;
+        }
+        "setup" => {
+            state.pending_hook = Some(RequestHook::Setup);
+            return LineParseResult::Continue;
+        }
+        "teardown" => {
+            state.pending_hook = Some(RequestHook::Teardown);
+            return LineParseResult::Continue;
+        }
+        _ => {}
+    }
+
     let Some(separator) = after_prefix.find(char::is_whitespace) else {
         return LineParseResult::NotHandled;
     };
@@ -215,9 +633,25 @@ fn try_parse_directive(trimmed: &str, state: &mut SemanticAssemblerState) -> Lin
             }
         },
         "dependsOn" => {
+            // `<file>#<request>` depends on a request of another file, which
+            // is imported implicitly.
+            if let Some(import) = parse_dependency_import(value, state.base_dir.as_deref())
+                && let Err(error) = import.and_then(|import| state.add_import(import))
+            {
+                return LineParseResult::Error(format!("Invalid @dependsOn directive: {error}"));
+            }
             state.pending_depends_on = Some(value.to_string());
             LineParseResult::Continue
         }
+        "import" => {
+            let path = substitute_variables(strip_quotes(value), &state.variables);
+            match parse_import(&path, state.base_dir.as_deref())
+                .and_then(|import| state.add_import(import))
+            {
+                Ok(()) => LineParseResult::Continue,
+                Err(error) => LineParseResult::Error(format!("Invalid @import directive: {error}")),
+            }
+        }
         "if" => match parse_condition(value, false) {
             Some(condition) => {
                 state.pending_conditions.push(condition);
@@ -252,6 +686,89 @@ fn try_parse_directive(trimmed: &str, state: &mut SemanticAssemblerState) -> Lin
                 value
             )),
         },
+        "data" => {
+            if state.file_data.is_some() {
+                return LineParseResult::Error(
+                    "@data cannot be used on a request when the file already iterates over a dataset"
+                        .to_string(),
+                );
+            }
+            match state.load_data_source(value) {
+                Ok(data) => {
+                    state.pending_data = Some(data);
+                    LineParseResult::Continue
+                }
+                Err(error) => LineParseResult::Error(format!("Invalid @data directive: {error:#}")),
+            }
+        }
+        "foreach" => match parse_foreach(value) {
+            Some(mut foreach) => {
+                foreach.source = substitute_variables(&foreach.source, &state.variables);
+                state.pending_foreach = Some(foreach);
+                LineParseResult::Continue
+            }
+            None => LineParseResult::Error(format!(
+                "Invalid @foreach directive format: '{value}', expected '<variable> in <reference>'"
+            )),
+        },
+        "paginate" => match parse_paginate(&substitute_variables(value, &state.variables)) {
+            Ok(pagination) => {
+                state.pending_paginate = Some(pagination);
+                LineParseResult::Continue
+            }
+            Err(error) => LineParseResult::Error(format!("Invalid @paginate directive: {error}")),
+        },
+        "auth" => {
+            let substituted = substitute_variables(value, &state.variables);
+            match parse_auth(&substituted) {
+                Some(auth) => {
+                    record_unresolved_variables(
+                        &mut state.pending_unresolved_variables,
+                        &substituted,
+                        state.line_number,
+                    );
+                    state.pending_auth = Some(auth);
+                    LineParseResult::Continue
+                }
+                None => LineParseResult::Error(format!(
+                    "Invalid @auth directive format: '{value}', expected 'basic <username> <password>', 'digest <username> <password>' or 'bearer <token>'"
+                )),
+            }
+        }
+        "sign" => match parse_sign(value, &state.variables) {
+            Ok(signing) => {
+                state.pending_sign = Some(signing);
+                LineParseResult::Continue
+            }
+            Err(error) => LineParseResult::Error(format!("Invalid @sign directive: {error}")),
+        },
+        "content-digest" => match parse_content_digest(value) {
+            Ok(digest) => {
+                state.pending_content_digest = Some(digest);
+                LineParseResult::Continue
+            }
+            Err(error) => {
+                LineParseResult::Error(format!("Invalid @content-digest directive: {error}"))
+            }
+        },
+        "tag" => match parse_tags(value) {
+            Ok(tags) => {
+                for tag in tags {
+                    if !state.pending_tags.contains(&tag) {
+                        state.pending_tags.push(tag);
+                    }
+                }
+                LineParseResult::Continue
+            }
+            Err(error) => LineParseResult::Error(format!("Invalid @tag directive: {error}")),
+        },
+        "capture" => match parse_capture(value) {
+            Ok(capture) => {
+                state.pending_captures.push(capture);
+                LineParseResult::Continue
+            }
+            Err(error) => LineParseResult::Error(format!("Invalid @capture directive: {error}")),
+        },
         _ => LineParseResult::NotHandled,
     }
 }
@@ -350,6 +867,22 @@ fn assemble_raw_line(
         return Ok(());
     }
 
+    let substituted = state
+        .env_references
+        .substitute(raw, state.base_dir.as_deref())?;
+    let secrets = state.env_references.take_secrets();
+    let trimmed = substituted.trim();
+    let defines_variable = !state.in_body && trimmed.starts_with('@');
+    let directive = !state.in_body && (trimmed.starts_with('#') || trimmed.starts_with("//"));
+
+    assemble_substituted_line(&substituted, state)?;
+    if !secrets.is_empty() {
+        state.add_env_secrets(secrets, defines_variable, directive);
+    }
+    Ok(())
+}
+
+fn assemble_substituted_line(raw: &str, state: &mut SemanticAssemblerState) -> Result<()> {
     let trimmed = raw.trim();
 
     if raw.trim_start().starts_with("> {%") {
@@ -377,6 +910,12 @@ fn assemble_raw_line(
         return Ok(());
     }
 
+    if trimmed.starts_with("###") {
+        state.promote_pending_data_to_file_scope();
+        state.after_separator = true;
+        return Ok(());
+    }
+
     if trimmed.starts_with('#') || trimmed.starts_with("//") {
         return Ok(());
     }
@@ -389,10 +928,18 @@ fn assemble_raw_line(
         return Ok(());
     }
 
+    // Inside a body, a line such as `run #1 completed` is body text.
+    if (!state.in_body || state.after_separator)
+        && let Some(run) = parse_run_line(trimmed, state.line_number)
+    {
+        let run = run.map_err(|error| anyhow!("Invalid run line: {error}"))?;
+        return state.add_run_requests(run);
+    }
+
     if is_http_request_line(trimmed) {
         state.finalize_current_request();
 
-        let parts: Vec<&str> = trimmed.split_whitespace().collect();
+        let parts = split_request_line(trimmed);
         if parts.len() >= 2 {
             let method = substitute_variables(parts[0], &state.variables);
             let url = substitute_variables(parts[1], &state.variables);
@@ -416,6 +963,14 @@ fn assemble_raw_line(
     Ok(())
 }
 
+fn add_secrets(target: &mut Vec<String>, secrets: Vec<String>) {
+    for secret in secrets {
+        if !target.contains(&secret) {
+            target.push(secret);
+        }
+    }
+}
+
 fn assemble_line(line: &PestLine, state: &mut SemanticAssemblerState) -> Result<()> {
     if let PestLineKind::IgnoredScriptBlock(block) = &line.kind {
         return handle_grouped_script_block(block, state);
@@ -533,7 +1088,7 @@ Content-Type: application/json
     fn pest_semantic_assembler_preserves_invalid_directive_errors_with_line_context() {
         let content = "GET https://api.example.com/users\n  # @timeout nope";
 
-        let error = parse_http_content_with_pest_semantics(content, Vec::new()).unwrap_err();
+        let error = parse_http_content_with_pest_semantics(content, Vec::new(), None).unwrap_err();
         let message = format!("{error:#}");
 
         assert!(message.contains("Failed to parse line 2: # @timeout nope"));
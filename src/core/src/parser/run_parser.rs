@@ -0,0 +1,78 @@
+use crate::types::{Run, Variable};
+use anyhow::{Result, bail};
+
+/// Parses a `run` line: `run #<request>`, `run <file>.http` or
+/// `run <file>.http#<request>`, optionally followed by variables overriding
+/// the file's, as in `run ./setup.http (@host=localhost, @user=admin)`.
+/// Returns `None` for lines that are not `run` lines.
+pub fn parse_run_line(line: &str, line_number: usize) -> Option<Result<Run>> {
+    let rest = line.strip_prefix("run")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let rest = rest.trim();
+    let (target, variables) = match rest.find('(') {
+        Some(open) => (rest[..open].trim_end(), Some(&rest[open..])),
+        None => (rest, None),
+    };
+    let (path, request) = match target.split_once('#') {
+        Some((path, request)) => (path.trim(), Some(request.trim())),
+        None => (target, None),
+    };
+    let same_file = path.is_empty() && request.is_some();
+    if !same_file && !path.ends_with(".http") {
+        return None;
+    }
+
+    Some(build_run(path, request, variables, line_number))
+}
+
+fn build_run(
+    path: &str,
+    request: Option<&str>,
+    variables: Option<&str>,
+    line_number: usize,
+) -> Result<Run> {
+    if request == Some("") {
+        bail!("expected a request name after '#'");
+    }
+
+    Ok(Run {
+        path: (!path.is_empty()).then(|| path.to_string()),
+        request: request.map(str::to_string),
+        variables: match variables {
+            Some(list) => parse_run_variables(list)?,
+            None => Vec::new(),
+        },
+        line: line_number,
+    })
+}
+
+fn parse_run_variables(list: &str) -> Result<Vec<Variable>> {
+    let Some(inner) = list
+        .strip_prefix('(')
+        .and_then(|list| list.strip_suffix(')'))
+    else {
+        bail!("expected variables as (@name=value, ...)");
+    };
+
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|assignment| !assignment.is_empty())
+        .map(|assignment| {
+            let Some((name, value)) = assignment
+                .strip_prefix('@')
+                .and_then(|assignment| assignment.split_once('='))
+                .filter(|(name, _)| !name.trim().is_empty())
+            else {
+                bail!("expected @name=value, found '{assignment}'");
+            };
+            Ok(Variable {
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,53 @@
+use super::run_parser::*;
+
+#[test]
+fn test_parse_run_line_same_file_request() {
+    let run = parse_run_line("run #login", 3).unwrap().unwrap();
+    assert_eq!(run.path, None);
+    assert_eq!(run.request.as_deref(), Some("login"));
+    assert!(run.variables.is_empty());
+    assert_eq!(run.line, 3);
+}
+
+#[test]
+fn test_parse_run_line_other_file_with_variables() {
+    let run = parse_run_line("run ./setup.http (@host=localhost, @user = admin)", 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(run.path.as_deref(), Some("./setup.http"));
+    assert_eq!(run.request, None);
+    let variables: Vec<(&str, &str)> = run
+        .variables
+        .iter()
+        .map(|variable| (variable.name.as_str(), variable.value.as_str()))
+        .collect();
+    assert_eq!(variables, vec![("host", "localhost"), ("user", "admin")]);
+
+    let run = parse_run_line("run ../shared/auth.http#get token", 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(run.path.as_deref(), Some("../shared/auth.http"));
+    assert_eq!(run.request.as_deref(), Some("get token"));
+}
+
+#[test]
+fn test_parse_run_line_ignores_other_lines() {
+    assert!(parse_run_line("run fast", 1).is_none());
+    assert!(parse_run_line("running #login", 1).is_none());
+    assert!(parse_run_line("GET https://api.example.com/run", 1).is_none());
+}
+
+#[test]
+fn test_parse_run_line_rejects_invalid_targets_and_variables() {
+    assert!(parse_run_line("run #", 1).unwrap().is_err());
+    assert!(
+        parse_run_line("run #login (host=localhost)", 1)
+            .unwrap()
+            .is_err()
+    );
+    assert!(
+        parse_run_line("run #login (@host=localhost", 1)
+            .unwrap()
+            .is_err()
+    );
+}
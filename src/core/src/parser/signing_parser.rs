@@ -0,0 +1,163 @@
+use super::substitution::substitute_variables;
+use crate::types::{
+    AwsCredentials, ContentDigest, SignatureAlgorithm, SignatureEncoding, Signing, Variable,
+};
+use anyhow::{Result, anyhow, bail};
+use std::collections::HashMap;
+
+/// Derived components of RFC 9421 that can be covered by `http-signature`.
+const DERIVED_COMPONENTS: [&str; 7] = [
+    "@method",
+    "@target-uri",
+    "@authority",
+    "@scheme",
+    "@request-target",
+    "@path",
+    "@query",
+];
+
+/// Parses the value of a `@sign` directive:
+///
+/// - `aws-sigv4 service=<service> region=<region>`
+/// - `http-signature alg=hmac-sha256|ed25519 key=<key> [key-id=<id>] [label=<label>]
+///   [components=<component>,...]`
+/// - `hmac-sha256 key=<key> [header=<name>] [encoding=hex|base64]`
+///
+/// Variables are substituted in each setting on its own, so keys may contain
+/// spaces (e.g. PEM-encoded Ed25519 keys). AWS credentials are read from
+/// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and the optional
+/// `AWS_SESSION_TOKEN` variables, then the process environment. They are left
+/// unset when either key is missing so the runner can report it when sending.
+pub fn parse_sign(value: &str, variables: &[Variable]) -> Result<Signing> {
+    let mut tokens = value.split_whitespace();
+    let scheme = tokens
+        .next()
+        .ok_or_else(|| anyhow!("expected 'aws-sigv4', 'http-signature' or 'hmac-sha256'"))?
+        .to_ascii_lowercase();
+
+    let mut settings = HashMap::new();
+    for token in tokens {
+        let Some((key, setting)) = token.split_once('=') else {
+            bail!("unknown setting '{token}'");
+        };
+        let setting = substitute_variables(setting, variables);
+        if setting.is_empty() {
+            bail!("'{key}' requires a value");
+        }
+        settings.insert(key, setting);
+    }
+
+    let signing = match scheme.as_str() {
+        "aws-sigv4" => parse_aws_sigv4(&mut settings, variables)?,
+        "http-signature" => parse_http_signature(&mut settings)?,
+        "hmac-sha256" => parse_hmac(&mut settings)?,
+        _ => bail!(
+            "unsupported signing scheme '{scheme}', expected 'aws-sigv4', 'http-signature' or 'hmac-sha256'"
+        ),
+    };
+
+    if let Some(key) = settings.keys().next() {
+        bail!("unknown setting '{key}'");
+    }
+    Ok(signing)
+}
+
+/// Parses the value of a `@content-digest` directive: `sha-256` or `sha-512`.
+pub fn parse_content_digest(value: &str) -> Result<ContentDigest> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "sha-256" => Ok(ContentDigest::Sha256),
+        "sha-512" => Ok(ContentDigest::Sha512),
+        other => bail!("unsupported algorithm '{other}', expected 'sha-256' or 'sha-512'"),
+    }
+}
+
+fn parse_aws_sigv4(
+    settings: &mut HashMap<&str, String>,
+    variables: &[Variable],
+) -> Result<Signing> {
+    let (Some(service), Some(region)) = (settings.remove("service"), settings.remove("region"))
+    else {
+        bail!("'service' and 'region' are required");
+    };
+
+    let lookup = |name: &str| {
+        variables
+            .iter()
+            .find(|variable| variable.name == name)
+            .map(|variable| variable.value.clone())
+            .or_else(|| std::env::var(name).ok())
+    };
+    let credentials = match (lookup("AWS_ACCESS_KEY_ID"), lookup("AWS_SECRET_ACCESS_KEY")) {
+        (Some(access_key_id), Some(secret_access_key)) => Some(AwsCredentials {
+            access_key_id,
+            secret_access_key,
+            session_token: lookup("AWS_SESSION_TOKEN"),
+        }),
+        _ => None,
+    };
+
+    Ok(Signing::AwsSigV4 {
+        service,
+        region,
+        credentials,
+    })
+}
+
+fn parse_http_signature(settings: &mut HashMap<&str, String>) -> Result<Signing> {
+    let algorithm = match settings.remove("alg").as_deref() {
+        Some("hmac-sha256") => SignatureAlgorithm::HmacSha256,
+        Some("ed25519") => SignatureAlgorithm::Ed25519,
+        Some(other) => bail!("unsupported 'alg' '{other}', expected 'hmac-sha256' or 'ed25519'"),
+        None => bail!("'alg' is required"),
+    };
+    let key = settings
+        .remove("key")
+        .ok_or_else(|| anyhow!("'key' is required"))?;
+
+    let mut components = Vec::new();
+    if let Some(list) = settings.remove("components") {
+        for component in list.split(',').filter(|component| !component.is_empty()) {
+            let component = component.to_ascii_lowercase();
+            let valid = if component.starts_with('@') {
+                DERIVED_COMPONENTS.contains(&component.as_str())
+            } else {
+                component
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+            };
+            if !valid {
+                bail!("unsupported component '{component}'");
+            }
+            components.push(component);
+        }
+    }
+
+    Ok(Signing::HttpSignature {
+        label: settings
+            .remove("label")
+            .unwrap_or_else(|| "sig1".to_string()),
+        algorithm,
+        key_id: settings.remove("key-id"),
+        key,
+        components,
+    })
+}
+
+fn parse_hmac(settings: &mut HashMap<&str, String>) -> Result<Signing> {
+    let key = settings
+        .remove("key")
+        .ok_or_else(|| anyhow!("'key' is required"))?;
+    let encoding = match settings.remove("encoding").as_deref() {
+        None | Some("hex") => SignatureEncoding::Hex,
+        Some("base64") => SignatureEncoding::Base64,
+        Some(_) => bail!("'encoding' must be 'hex' or 'base64'"),
+    };
+
+    Ok(Signing::Hmac {
+        header: settings
+            .remove("header")
+            .unwrap_or_else(|| "X-Signature".to_string()),
+        key,
+        encoding,
+    })
+}
@@ -1,4 +1,7 @@
+use crate::environment::environment_value_to_string;
 use crate::types::Variable;
+use serde_json::Value;
+use std::borrow::Cow;
 
 pub fn substitute_variables(input: &str, variables: &[Variable]) -> String {
     let mut result = String::new();
@@ -27,8 +30,8 @@ pub fn substitute_variables(input: &str, variables: &[Variable]) -> String {
                 }
 
                 if found_closing {
-                    if let Some(var) = variables.iter().find(|v| v.name == var_name) {
-                        result.push_str(&var.value);
+                    if let Some(value) = lookup_variable(&var_name, variables) {
+                        result.push_str(&value);
                     } else {
                         result.push_str("{{");
                         result.push_str(&var_name);
@@ -48,3 +51,46 @@ pub fn substitute_variables(input: &str, variables: &[Variable]) -> String {
 
     result
 }
+
+/// The value of `name`, or of a dotted path such as `tenant.id` or
+/// `servers.0.url` into a variable holding a JSON object or array.
+fn lookup_variable<'a>(name: &str, variables: &'a [Variable]) -> Option<Cow<'a, str>> {
+    if let Some(var) = variables.iter().find(|v| v.name == name) {
+        return Some(Cow::Borrowed(&var.value));
+    }
+
+    // Prefer the longest variable name, so `api.v1.url` finds `api.v1` first.
+    name.rmatch_indices('.').find_map(|(dot, _)| {
+        let root = variables.iter().find(|v| v.name == name[..dot])?;
+        if !root.value.trim_start().starts_with(['{', '[']) {
+            return None;
+        }
+
+        let json: Value = serde_json::from_str(&root.value).ok()?;
+        let value = name[dot + 1..]
+            .split('.')
+            .try_fold(&json, |value, key| match value {
+                Value::Object(map) => map.get(key),
+                Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            })?;
+        Some(Cow::Owned(environment_value_to_string(value)))
+    })
+}
+
+/// The trimmed names of the `{{name}}` placeholders in `input`, in order.
+pub fn find_placeholders(input: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+        names.push(after_open[..end].trim());
+        rest = &after_open[end + 2..];
+    }
+
+    names
+}
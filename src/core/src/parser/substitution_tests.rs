@@ -217,3 +217,50 @@ fn test_substitute_variables_numeric_values() {
     let result = substitute_variables("Port: {{port}}, Timeout: {{timeout}}ms", &variables);
     assert_eq!(result, "Port: 8080, Timeout: 30000ms");
 }
+
+#[test]
+fn test_find_placeholders() {
+    assert_eq!(
+        find_placeholders("{{ baseUrl }}/users/{{id}}?q={{$uuid}}"),
+        vec!["baseUrl", "id", "$uuid"]
+    );
+    assert!(find_placeholders("no placeholders {{ unterminated").is_empty());
+}
+
+#[test]
+fn test_substitute_variables_dotted_access_into_json_values() {
+    let variables = vec![
+        Variable {
+            name: "tenant".to_string(),
+            value: r#"{"id":5,"region":{"name":"eu"},"servers":["a.example.com","b.example.com"]}"#
+                .to_string(),
+        },
+        Variable {
+            name: "api.v1".to_string(),
+            value: r#"{"url":"https://v1.example.com"}"#.to_string(),
+        },
+        Variable {
+            name: "host".to_string(),
+            value: "example.com".to_string(),
+        },
+    ];
+
+    assert_eq!(
+        substitute_variables(
+            "{{tenant.id}} {{tenant.region.name}} {{tenant.servers.1}} {{api.v1.url}}",
+            &variables
+        ),
+        "5 eu b.example.com https://v1.example.com"
+    );
+    assert_eq!(
+        substitute_variables("{{tenant.region}}", &variables),
+        r#"{"name":"eu"}"#
+    );
+    assert_eq!(
+        substitute_variables(
+            "{{tenant.missing}} {{host.name}} {{login.response.body.$.id}}",
+            &variables
+        ),
+        "{{tenant.missing}} {{host.name}} {{login.response.body.$.id}}"
+    );
+}
@@ -0,0 +1,23 @@
+use anyhow::{Result, bail};
+
+/// Parses the value of a `@tag` directive: one or more comma-separated tags,
+/// e.g. `smoke, orders`.
+pub fn parse_tags(value: &str) -> Result<Vec<String>> {
+    if value.trim().is_empty() {
+        bail!("expected one or more comma-separated tags");
+    }
+
+    value
+        .split(',')
+        .map(|tag| {
+            let tag = tag.trim();
+            if tag.is_empty() {
+                bail!("empty tag in '{}'", value.trim());
+            }
+            if tag.contains(char::is_whitespace) {
+                bail!("'{tag}' is not a valid tag; separate tags with commas");
+            }
+            Ok(tag.to_string())
+        })
+        .collect()
+}
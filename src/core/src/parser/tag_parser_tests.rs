@@ -0,0 +1,27 @@
+use super::tag_parser::*;
+
+#[test]
+fn test_parse_tags_splits_on_commas() {
+    assert_eq!(parse_tags("smoke").unwrap(), vec!["smoke"]);
+    assert_eq!(
+        parse_tags("smoke, orders ,slow").unwrap(),
+        vec!["smoke", "orders", "slow"]
+    );
+}
+
+#[test]
+fn test_parse_tags_rejects_empty_and_spaced_tags() {
+    assert!(parse_tags("  ").is_err());
+    assert!(
+        parse_tags("smoke,,orders")
+            .unwrap_err()
+            .to_string()
+            .contains("empty tag")
+    );
+    assert!(
+        parse_tags("smoke orders")
+            .unwrap_err()
+            .to_string()
+            .contains("separate tags with commas")
+    );
+}
@@ -1,5 +1,7 @@
 use super::*;
-use crate::types::{AssertionType, ConditionType};
+use crate::types::{
+    AssertionType, ConditionType, DataScope, RequestHook, SourcedVariable, VariableSource,
+};
 use std::fs;
 use std::io::Write;
 use std::path::Path;
@@ -946,6 +948,436 @@ GET https://api.example.com/users"#;
     assert_eq!(requests[0].depends_on, Some("previousRequest".to_string()));
 }
 
+#[test]
+fn test_parse_data_directive_loads_rows_relative_to_file() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir(temp_dir.path().join("data")).unwrap();
+    fs::write(
+        temp_dir.path().join("data").join("users.csv"),
+        "name,email\nalice,alice@example.com\nbob,bob@example.com\n",
+    )
+    .unwrap();
+    let content = r#"@dataDir = ./data
+
+# @name create_user
+# @data {{dataDir}}/users.csv
+POST https://api.example.com/users
+Content-Type: application/json
+
+{"name": "{{name}}", "email": "{{email}}"}
+
+###
+
+GET https://api.example.com/users"#;
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+
+    let requests = parse_http_file(&file_path, None).unwrap();
+
+    assert_eq!(requests.len(), 2);
+    let data = requests[0].data.as_ref().unwrap();
+    assert_eq!(data.path, "./data/users.csv");
+    assert_eq!(data.scope, DataScope::Request);
+    assert_eq!(data.rows.len(), 2);
+    assert_eq!(data.rows[1][0].value, "bob");
+    assert!(
+        requests[0]
+            .body
+            .as_deref()
+            .unwrap()
+            .starts_with(r#"{"name": "{{name}}", "email": "{{email}}"}"#)
+    );
+    assert!(requests[1].data.is_none());
+}
+
+#[test]
+fn test_parse_data_directive_before_separator_applies_to_whole_file() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("accounts.json"),
+        r#"[{"user": "alice"}, {"user": "bob"}]"#,
+    )
+    .unwrap();
+    let content = r#"# @data ./accounts.json
+
+###
+# @name login
+POST https://api.example.com/login
+
+###
+GET https://api.example.com/profile"#;
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+
+    let requests = parse_http_file(&file_path, None).unwrap();
+
+    assert_eq!(requests.len(), 2);
+    for request in &requests {
+        let data = request.data.as_ref().unwrap();
+        assert_eq!(data.scope, DataScope::File);
+        assert_eq!(data.rows.len(), 2);
+    }
+}
+
+#[test]
+fn test_parse_data_directive_rejects_request_dataset_inside_file_dataset() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("a.csv"), "id\n1\n").unwrap();
+    let content = "# @data a.csv\n\n###\n# @data a.csv\nGET https://api.example.com/users";
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+
+    let error = parse_http_file(&file_path, None).unwrap_err();
+    assert!(format!("{error:#}").contains("@data cannot be used on a request"));
+}
+
+#[test]
+fn test_parse_data_directive_reports_missing_dataset() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "# @data ./missing.csv\nGET https://api.example.com/users";
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+
+    let error = parse_http_file(&file_path, None).unwrap_err();
+    let message = format!("{error:#}");
+    assert!(message.contains("Failed to parse line 1"));
+    assert!(message.contains("Invalid @data directive"));
+}
+
+#[test]
+fn test_parse_foreach_directive() {
+    let content = r#"# @name get_item
+// @foreach item in {{list.response.body.$.items}}
+GET https://api.example.com/items/{{item.id}}"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(requests.len(), 1);
+    let foreach = requests[0].foreach.as_ref().unwrap();
+    assert_eq!(foreach.variable, "item");
+    assert_eq!(foreach.source, "{{list.response.body.$.items}}");
+    assert_eq!(requests[0].url, "https://api.example.com/items/{{item.id}}");
+}
+
+#[test]
+fn test_parse_foreach_directive_rejects_invalid_format() {
+    let content = "# @foreach {{list.response.body.$.items}}\nGET https://api.example.com/items";
+
+    let error = parse_http_content(content, None).unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid @foreach directive format"));
+}
+
+#[test]
+fn test_parse_paginate_directive() {
+    let content = r#"@pageSize=50
+# @name users
+# @paginate next=$.links.next max={{pageSize}} items=$.data
+GET https://api.example.com/users"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    let pagination = requests[0].paginate.as_ref().unwrap();
+    assert_eq!(
+        pagination.next,
+        crate::types::PaginationNext::Url("$.links.next".to_string())
+    );
+    assert_eq!(pagination.max_pages, 50);
+    assert_eq!(pagination.items.as_deref(), Some("$.data"));
+    assert_eq!(requests[0].name.as_deref(), Some("users"));
+}
+
+#[test]
+fn test_parse_paginate_directive_rejects_invalid_settings() {
+    let content = "# @paginate link pages=5\nGET https://api.example.com/users";
+
+    let error = parse_http_content(content, None).unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid @paginate directive: unknown setting 'pages'"));
+}
+
+#[test]
+fn test_parse_auth_directive() {
+    let content = r#"@password=s3cret
+@token=abc.def
+# @auth basic admin {{password}}
+GET https://api.example.com/admin
+
+###
+# @auth bearer {{token}}
+GET https://api.example.com/me"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(
+        requests[0].auth,
+        Some(crate::types::Auth::Basic {
+            username: "admin".to_string(),
+            password: "s3cret".to_string(),
+        })
+    );
+    assert_eq!(
+        requests[1].auth,
+        Some(crate::types::Auth::Bearer {
+            token: "abc.def".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_parse_auth_directive_rejects_unknown_scheme() {
+    let content = "# @auth ntlm admin s3cret\nGET https://api.example.com/admin";
+
+    let error = parse_http_content(content, None).unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid @auth directive format"));
+}
+
+#[test]
+fn test_parse_sign_directive_reads_credentials_from_variables() {
+    let content = r#"@AWS_ACCESS_KEY_ID=AKIDEXAMPLE
+@AWS_SECRET_ACCESS_KEY=wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY
+@region=eu-west-1
+# @sign aws-sigv4 service=s3 region={{region}}
+GET https://s3.eu-west-1.amazonaws.com/bucket/key"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(
+        requests[0].sign,
+        Some(crate::types::Signing::AwsSigV4 {
+            service: "s3".to_string(),
+            region: "eu-west-1".to_string(),
+            credentials: Some(crate::types::AwsCredentials {
+                access_key_id: "AKIDEXAMPLE".to_string(),
+                secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+                session_token: None,
+            }),
+        })
+    );
+}
+
+#[test]
+fn test_parse_sign_directive_rejects_missing_region() {
+    let content = "# @sign aws-sigv4 service=s3\nGET https://s3.amazonaws.com/bucket/key";
+
+    let error = parse_http_content(content, None).unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid @sign directive: 'service' and 'region' are required"));
+}
+
+#[test]
+fn test_parse_http_signature_and_content_digest_directives() {
+    let content = r#"@partnerSecret=s3cret
+# @content-digest sha-512
+# @sign http-signature alg=hmac-sha256 key={{partnerSecret}} key-id=partner components=@method,@path,content-digest
+POST https://api.example.com/orders
+Content-Type: application/json
+
+{"id": 1}"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(
+        requests[0].content_digest,
+        Some(crate::types::ContentDigest::Sha512)
+    );
+    assert_eq!(
+        requests[0].sign,
+        Some(crate::types::Signing::HttpSignature {
+            label: "sig1".to_string(),
+            algorithm: crate::types::SignatureAlgorithm::HmacSha256,
+            key_id: Some("partner".to_string()),
+            key: "s3cret".to_string(),
+            components: vec![
+                "@method".to_string(),
+                "@path".to_string(),
+                "content-digest".to_string(),
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_parse_content_digest_directive_rejects_unknown_algorithm() {
+    let content = "# @content-digest md5\nPOST https://api.example.com/orders";
+
+    let error = parse_http_content(content, None).unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid @content-digest directive"));
+}
+
+#[test]
+fn test_parse_capture_directives_apply_to_next_request() {
+    let content = r#"# @name login
+# @capture token = $.access_token
+# @capture etag = header ETag
+POST https://api.example.com/login
+
+###
+GET https://api.example.com/orders
+Authorization: Bearer {{token}}"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(
+        requests[0].captures,
+        vec![
+            crate::types::Capture {
+                name: "token".to_string(),
+                source: crate::types::CaptureSource::BodyPath("$.access_token".to_string()),
+            },
+            crate::types::Capture {
+                name: "etag".to_string(),
+                source: crate::types::CaptureSource::Header("ETag".to_string()),
+            },
+        ]
+    );
+    assert!(requests[1].captures.is_empty());
+    assert_eq!(requests[1].headers[0].value, "Bearer {{token}}");
+}
+
+#[test]
+fn test_parse_capture_directive_rejects_unknown_source() {
+    let content = "# @capture token = access_token\nPOST https://api.example.com/login";
+
+    let error = parse_http_content(content, None).unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid @capture directive"));
+}
+
+#[test]
+fn test_parse_dotenv_references_are_resolved_and_kept_as_secrets() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_file(&temp_dir, ".env", "API_KEY=dotenv-key\nTENANT=acme\n");
+    let content = r#"@tenant = {{$dotenv TENANT}}
+
+POST https://api.example.com/{{tenant}}/orders
+X-Api-Key: {{$dotenv API_KEY}}
+
+{"missing": "{{$dotenv MISSING}}"}"#;
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+
+    let requests = parse_http_file(&file_path, None).unwrap();
+
+    assert_eq!(requests[0].url, "https://api.example.com/acme/orders");
+    assert_eq!(requests[0].headers[0].value, "dotenv-key");
+    assert_eq!(
+        requests[0].body.as_deref(),
+        Some(r#"{"missing": "{{$dotenv MISSING}}"}"#)
+    );
+    assert_eq!(requests[0].secrets, vec!["dotenv-key"]);
+}
+
+#[test]
+fn test_parse_short_env_values_are_not_secrets() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_file(&temp_dir, ".env", "REGION=us\n");
+    let content = "GET https://{{$dotenv REGION}}.api.example.com/users?region={{$dotenv REGION}}";
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+
+    let requests = parse_http_file(&file_path, None).unwrap();
+    let sanitized = crate::redaction::sanitize_request_for_output(&requests[0], false);
+
+    assert!(requests[0].secrets.is_empty());
+    assert_eq!(sanitized.url, "https://us.api.example.com/users?region=us");
+}
+
+#[test]
+fn test_parse_env_secrets_belong_to_the_requests_using_them() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_file(
+        &temp_dir,
+        ".env",
+        "SHARED_TOKEN=shared-token\nFIRST_TOKEN=first-token\nSECOND_TOKEN=second-token\n",
+    );
+    let content = r#"@shared = {{$dotenv SHARED_TOKEN}}
+
+# @auth bearer {{$dotenv FIRST_TOKEN}}
+GET https://api.example.com/first
+X-Shared: {{shared}}
+
+###
+
+GET https://api.example.com/second
+X-Token: {{$dotenv SECOND_TOKEN}}"#;
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+
+    let requests = parse_http_file(&file_path, None).unwrap();
+
+    assert_eq!(requests[0].secrets, vec!["first-token", "shared-token"]);
+    assert_eq!(requests[1].secrets, vec!["second-token", "shared-token"]);
+}
+
+#[test]
+fn test_parse_with_variables_applies_override_precedence() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_file(
+        &temp_dir,
+        "http-client.env.json",
+        r#"{"dev": {"host": "env.example.com", "version": "v1", "token": "env-token"}}"#,
+    );
+    let content = r#"@host = file.example.com
+@version = v2
+@token = file-token
+
+GET https://{{host}}/{{version}}/items
+Authorization: Bearer {{token}}"#;
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+    let overrides = vec![
+        SourcedVariable {
+            name: "token".to_string(),
+            value: "cli-token".to_string(),
+            source: VariableSource::CommandLine,
+        },
+        SourcedVariable {
+            name: "token".to_string(),
+            value: "vars-file-token".to_string(),
+            source: VariableSource::VarsFile,
+        },
+        SourcedVariable {
+            name: "host".to_string(),
+            value: "vars-file.example.com".to_string(),
+            source: VariableSource::VarsFile,
+        },
+    ];
+
+    let requests = parse_http_file_with_variables(&file_path, Some("dev"), &overrides).unwrap();
+
+    assert_eq!(requests[0].url, "https://vars-file.example.com/v2/items");
+    assert_eq!(requests[0].headers[0].value, "Bearer cli-token");
+
+    let variables = resolve_file_variables(&file_path, Some("dev"), &overrides).unwrap();
+    let source_of = |name: &str| {
+        variables
+            .iter()
+            .find(|variable| variable.name == name)
+            .map(|variable| (variable.value.as_str(), variable.source))
+            .unwrap()
+    };
+    assert_eq!(
+        source_of("host"),
+        ("vars-file.example.com", VariableSource::VarsFile)
+    );
+    assert_eq!(source_of("version"), ("v2", VariableSource::File));
+    assert_eq!(
+        source_of("token"),
+        ("cli-token", VariableSource::CommandLine)
+    );
+}
+
+#[test]
+fn test_parse_dotted_access_into_environment_objects() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_file(
+        &temp_dir,
+        "http-client.env.json",
+        r#"{"dev": {"tenant": {"id": 5, "hosts": ["eu.example.com"]}, "limit": 10}}"#,
+    );
+    let content = r#"GET https://{{tenant.hosts.0}}/tenants/{{tenant.id}}?limit={{limit}}
+
+{"tenant": {{tenant}}}"#;
+    let file_path = create_test_file(&temp_dir, "test.http", content);
+
+    let requests = parse_http_file(&file_path, Some("dev")).unwrap();
+
+    assert_eq!(requests[0].url, "https://eu.example.com/tenants/5?limit=10");
+    assert_eq!(
+        requests[0].body.as_deref(),
+        Some(r#"{"tenant": {"id":5,"hosts":["eu.example.com"]}}"#)
+    );
+}
+
 #[test]
 fn test_parse_readme_authentication_flow_example() {
     let content = r#"# @name authenticate
@@ -1237,3 +1669,265 @@ mod golden_tests {
         assert!(tested > 0, "No fixture files found in {:?}", fixtures);
     }
 }
+
+#[test]
+fn test_parse_strict_variables_directive_and_unresolved_lines() {
+    let content = r#"@host = api.example.com
+
+GET https://{{host}}/{{version}}/users
+Authorization: Bearer {{token}}
+
+{"id": "{{userId}}", "trace": "{{$uuid}}"}
+
+###
+# @strict-variables
+GET https://{{host}}/health"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert!(requests.iter().all(|request| request.strict_variables));
+    let unresolved: Vec<(&str, usize)> = requests[0]
+        .unresolved_variables
+        .iter()
+        .map(|variable| (variable.name.as_str(), variable.line))
+        .collect();
+    assert_eq!(
+        unresolved,
+        vec![("version", 3), ("token", 4), ("userId", 6), ("$uuid", 6)]
+    );
+    assert!(requests[1].unresolved_variables.is_empty());
+}
+
+#[test]
+fn test_parse_setup_and_teardown_directives() {
+    let content = r#"# @setup
+# @name create_user
+POST https://api.example.com/users
+
+###
+GET https://api.example.com/users/1
+
+###
+// @teardown
+run #create_user
+
+###
+# @teardown
+DELETE https://api.example.com/users/1"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    let hooks: Vec<Option<RequestHook>> = requests.iter().map(|request| request.hook).collect();
+    assert_eq!(
+        hooks,
+        vec![
+            Some(RequestHook::Setup),
+            None,
+            Some(RequestHook::Teardown),
+            Some(RequestHook::Teardown)
+        ]
+    );
+    assert_eq!(requests[0].name.as_deref(), Some("create_user"));
+}
+
+#[test]
+fn test_parse_tag_directive() {
+    let content = r#"# @name list_orders
+# @tag smoke, orders
+// @tag smoke
+GET https://api.example.com/orders
+
+###
+GET https://api.example.com/health"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(requests[0].tags, vec!["smoke", "orders"]);
+    assert!(requests[1].tags.is_empty());
+}
+
+#[test]
+fn test_parse_tag_directive_rejects_invalid_tags() {
+    let content = r#"# @tag smoke slow
+GET https://api.example.com"#;
+
+    let error = parse_http_content(content, None).unwrap_err();
+
+    assert!(format!("{error:#}").contains("Invalid @tag directive"));
+}
+
+#[test]
+fn test_parse_import_directive_applies_to_every_request() {
+    let content = r#"@shared = ./shared
+# @import {{shared}}/auth.http
+
+GET https://api.example.com/orders
+Authorization: Bearer {{auth.login.response.body.$.token}}
+
+###
+# @dependsOn ./users.http#list_users
+GET https://api.example.com/users/{{users.list_users.response.body.$[0].id}}"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(requests.len(), 2);
+    for request in &requests {
+        let imports: Vec<(&str, &str)> = request
+            .imports
+            .iter()
+            .map(|import| (import.alias.as_str(), import.path.as_str()))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![("auth", "./shared/auth.http"), ("users", "./users.http")]
+        );
+    }
+    assert_eq!(
+        requests[1].depends_on.as_deref(),
+        Some("./users.http#list_users")
+    );
+}
+
+#[test]
+fn test_parse_import_directive_rejects_conflicting_aliases() {
+    let content =
+        "# @import ./auth.http\n# @import ./legacy/auth.http\nGET https://api.example.com/orders";
+
+    let error = parse_http_content(content, None).unwrap_err();
+    assert!(format!("{error:#}").contains("'auth' is already imported"));
+
+    let content = "# @import ./auth.http\n# @import auth.http\nGET https://api.example.com/orders";
+    assert_eq!(
+        parse_http_content(content, None).unwrap()[0].imports.len(),
+        1
+    );
+}
+
+#[test]
+fn test_parse_import_directive_rejects_aliases_conflicting_with_run_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir(temp_dir.path().join("legacy")).unwrap();
+    create_test_file(&temp_dir, "auth.http", "GET https://api.example.com/login\n");
+    create_test_file(
+        &temp_dir,
+        "legacy/auth.http",
+        "GET https://legacy.example.com/login\n",
+    );
+    create_test_file(
+        &temp_dir,
+        "legacy/setup.http",
+        "# @import ./auth.http\nPOST https://legacy.example.com/seed\n",
+    );
+
+    let main = create_test_file(
+        &temp_dir,
+        "main.http",
+        "# @import ./auth.http\nrun ./legacy/setup.http\n\n###\nGET https://api.example.com/orders\n",
+    );
+    let error = parse_http_file(&main, None).unwrap_err();
+    assert!(format!("{error:#}").contains("'auth' is imported from both"));
+
+    let main = create_test_file(
+        &temp_dir,
+        "main.http",
+        "# @import ./legacy/auth.http\nrun ./legacy/setup.http\n\n###\nGET https://api.example.com/orders\n",
+    );
+    let requests = parse_http_file(&main, None).unwrap();
+    assert!(requests.iter().all(|request| request.imports.len() == 1));
+}
+
+#[test]
+fn test_parse_run_line_inlines_requests_with_variable_overrides() {
+    let content = r#"@user = alice
+
+# @name login
+POST https://api.example.com/login
+
+{"user": "{{user}}"}
+
+###
+run #login (@user=bob)
+
+###
+GET https://api.example.com/profile"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].body.as_ref().unwrap().contains(r#""alice""#));
+    assert!(requests[0].run.is_none());
+    assert_eq!(requests[1].name.as_deref(), Some("login"));
+    assert!(requests[1].body.as_ref().unwrap().contains(r#""bob""#));
+    let run = requests[1].run.as_ref().unwrap();
+    assert_eq!(run.request.as_deref(), Some("login"));
+    assert_eq!(run.line, 9);
+    assert_eq!(requests[2].url, "https://api.example.com/profile");
+}
+
+#[test]
+fn test_parse_run_line_of_another_file() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_file(
+        &temp_dir,
+        "setup.http",
+        "@host = https://staging.example.com\n\n# @name seed\nPOST {{host}}/seed\n\n###\n# @name reset\nPOST {{host}}/reset\n",
+    );
+    let main = create_test_file(
+        &temp_dir,
+        "main.http",
+        "run ./setup.http (@host=http://localhost:8080)\n\n###\nrun ./setup.http#reset\n\n###\nGET https://api.example.com/items\n",
+    );
+
+    let requests = parse_http_file(&main, None).unwrap();
+
+    let urls: Vec<&str> = requests.iter().map(|r| r.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        vec![
+            "http://localhost:8080/seed",
+            "http://localhost:8080/reset",
+            "https://staging.example.com/reset",
+            "https://api.example.com/items",
+        ]
+    );
+}
+
+#[test]
+fn test_parse_run_line_in_body_is_body_text() {
+    let content = r#"# @name notify
+POST https://api.example.com/messages
+Content-Type: text/plain
+
+Deploy finished
+run #1 completed
+
+###
+run #notify"#;
+
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0].body.as_deref(),
+        Some("Deploy finished\nrun #1 completed\n")
+    );
+    assert!(requests[0].run.is_none());
+    assert_eq!(
+        requests[1].run.as_ref().unwrap().request.as_deref(),
+        Some("notify")
+    );
+}
+
+#[test]
+fn test_parse_run_line_errors() {
+    let error = parse_http_content("run #missing\n", None).unwrap_err();
+    assert!(format!("{error:#}").contains("No request named 'missing' to run"));
+
+    let temp_dir = TempDir::new().unwrap();
+    create_test_file(&temp_dir, "first.http", "run ./second.http\n");
+    create_test_file(&temp_dir, "second.http", "run ./first.http\n");
+    let first = temp_dir.path().join("first.http");
+
+    let error = parse_http_file(first.to_str().unwrap(), None).unwrap_err();
+    assert!(format!("{error:#}").contains("Circular run of"));
+}
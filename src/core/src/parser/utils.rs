@@ -10,3 +10,41 @@ pub fn is_http_request_line(line: &str) -> bool {
         || line.starts_with("TRACE ")
         || line.starts_with("CONNECT ")
 }
+
+/// Splits a request line on whitespace outside `{{...}}`, so dynamic variables
+/// with arguments such as `{{$randomInt 1 100}}` stay part of the target.
+pub(crate) fn split_request_line(raw: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+    let mut chars = raw.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        if ch.is_whitespace() && depth == 0 {
+            if let Some(begin) = start.take() {
+                parts.push(&raw[begin..index]);
+            }
+            continue;
+        }
+        start.get_or_insert(index);
+        match (ch, chars.peek()) {
+            ('{', Some((_, '{'))) => {
+                depth += 1;
+                chars.next();
+            }
+            ('}', Some((_, '}'))) if depth > 0 => {
+                depth -= 1;
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    if depth > 0 {
+        // An unclosed `{{` is not a variable, so split the line as usual.
+        return raw.split_whitespace().collect();
+    }
+    if let Some(begin) = start {
+        parts.push(&raw[begin..]);
+    }
+    parts
+}
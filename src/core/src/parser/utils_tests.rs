@@ -94,3 +94,31 @@ fn test_is_http_request_line_partial_method_match() {
     assert!(!is_http_request_line("GETTING /api"));
     assert!(!is_http_request_line("POSTS /api"));
 }
+
+#[test]
+fn test_split_request_line_on_whitespace() {
+    assert_eq!(
+        split_request_line("GET  https://example.com\tHTTP/1.1\n"),
+        vec!["GET", "https://example.com", "HTTP/1.1"]
+    );
+}
+
+#[test]
+fn test_split_request_line_keeps_variables_with_spaces() {
+    assert_eq!(
+        split_request_line("GET /items?limit={{$randomInt 1 100}}&q={{ term }} HTTP/1.1"),
+        vec![
+            "GET",
+            "/items?limit={{$randomInt 1 100}}&q={{ term }}",
+            "HTTP/1.1"
+        ]
+    );
+}
+
+#[test]
+fn test_split_request_line_with_unclosed_variable() {
+    assert_eq!(
+        split_request_line("GET /items?q={{term HTTP/1.1"),
+        vec!["GET", "/items?q={{term", "HTTP/1.1"]
+    );
+}
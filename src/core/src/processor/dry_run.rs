@@ -0,0 +1,146 @@
+use super::executor::{ProcessorConfig, is_teardown_file};
+use super::incremental_loop::split_teardown;
+use super::output;
+use crate::colors;
+use crate::datasets;
+use crate::logging::Log;
+use crate::parser;
+use crate::redaction::sanitize_request_for_output;
+use crate::request_substitution::{
+    check_undefined_variables, mark_pending_values_in_request, mark_secrets_in_request,
+    substitute_functions_in_request,
+};
+use crate::types::{HttpRequest, RequestHook};
+use anyhow::Result;
+
+/// Print each request of the run as it would be sent, with the environment,
+/// file and command-line variables and functions applied, without sending
+/// anything. Values taken from responses are shown as
+/// `<pending: login.response.body.$.token>`, and `{{$secret("name")}}` as
+/// `<secret: name>` without running the secret command.
+///
+/// Fails when a file cannot be parsed or a request cannot be resolved.
+pub fn dry_run_http_files(config: &ProcessorConfig) -> Result<()> {
+    let mut log = Log::new_with_silent(config.log_filename, config.silent)?;
+    let mut failed = 0;
+
+    let files = config
+        .setup_files
+        .iter()
+        .map(|file| (file, Some(RequestHook::Setup)))
+        .chain(
+            config
+                .files
+                .iter()
+                .filter(|file| !is_teardown_file(config, file))
+                .map(|file| (file, None)),
+        )
+        .chain(
+            config
+                .teardown_files
+                .iter()
+                .map(|file| (file, Some(RequestHook::Teardown))),
+        );
+    // Like `@capture` in a run, variables captured by a file, including the
+    // setup files, are available to the files after it.
+    let mut captured: Vec<String> = Vec::new();
+    for (http_file, hook) in files {
+        failed += dry_run_file(http_file, hook, config, &mut captured, &mut log);
+    }
+
+    log.writeln(&format!(
+        "{} Dry run complete, no requests were sent",
+        colors::blue("🔍")
+    ));
+    if failed > 0 {
+        anyhow::bail!("{} request(s) or file(s) could not be resolved", failed);
+    }
+    Ok(())
+}
+
+/// Print the requests of one file, returning the number of failures. Like a
+/// run, a file with no request selected by the filter is skipped.
+fn dry_run_file(
+    http_file: &str,
+    hook: Option<RequestHook>,
+    config: &ProcessorConfig,
+    captured: &mut Vec<String>,
+    log: &mut Log,
+) -> usize {
+    let mut parsed =
+        parser::parse_http_file_with_variables(http_file, config.environment, config.variables);
+    if let (Some(filter), Ok(requests)) = (config.filter, &mut parsed)
+        && !filter.is_empty()
+        && hook.is_none()
+    {
+        *requests = filter.select(std::mem::take(requests));
+        if requests.is_empty() {
+            return 0;
+        }
+    }
+
+    log.writeln(&format!(
+        "{} HTTP File Runner - Dry run of file: {}",
+        colors::blue("🔍"),
+        http_file
+    ));
+    log.writeln(&"=".repeat(50));
+
+    let mut requests = match parsed {
+        Ok(requests) => requests,
+        Err(e) => {
+            log.writeln(&format!("{} Error parsing file: {}", colors::red("❌"), e));
+            return 1;
+        }
+    };
+    for request in &mut requests {
+        request.strict_variables |= config.strict_variables;
+        if hook.is_some() {
+            request.hook = hook;
+        }
+    }
+
+    let mut failed = 0;
+    for (request, error) in dry_run_requests(requests, captured, config.include_secrets) {
+        output::log_request_details(&request, log, config.pretty_json);
+        if let Some(error) = error {
+            log.writeln(&format!(
+                "{} Cannot resolve request: {:#}",
+                colors::red("❌"),
+                error
+            ));
+            failed += 1;
+        }
+    }
+    log.writeln("");
+    failed
+}
+
+/// The requests of a file in the order they would run, resolved as far as
+/// possible without responses, with the error of each that cannot be sent.
+/// `captured` holds the names of the variables stored by `@capture` so far,
+/// which only exist once a response arrives, and gains those of the file.
+pub(crate) fn dry_run_requests(
+    requests: Vec<HttpRequest>,
+    captured: &mut Vec<String>,
+    include_secrets: bool,
+) -> Vec<(HttpRequest, Option<anyhow::Error>)> {
+    let (mut requests, teardown) = split_teardown(datasets::expand_data_iterations(requests));
+    requests.extend(teardown);
+
+    requests
+        .into_iter()
+        .map(|mut request| {
+            let error = mark_pending_values_in_request(&mut request, captured)
+                .and_then(|()| mark_secrets_in_request(&mut request))
+                .and_then(|()| substitute_functions_in_request(&mut request))
+                .and_then(|()| check_undefined_variables(&request))
+                .err();
+            captured.extend(request.captures.iter().map(|capture| capture.name.clone()));
+            (
+                sanitize_request_for_output(&request, include_secrets),
+                error,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,173 @@
+use super::dry_run::{dry_run_http_files, dry_run_requests};
+use super::executor::ProcessorConfig;
+use crate::parser::parse_http_content;
+use crate::types::HttpRequest;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+fn dry_run(content: &str, include_secrets: bool) -> Vec<HttpRequest> {
+    let requests = parse_http_content(content, None).unwrap();
+    dry_run_requests(requests, &mut Vec::new(), include_secrets)
+        .into_iter()
+        .map(|(request, error)| {
+            assert!(error.is_none(), "unexpected error: {error:?}");
+            request
+        })
+        .collect()
+}
+
+fn create_temp_http_file(content: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file.flush().unwrap();
+    file
+}
+
+#[test]
+fn test_dry_run_resolves_variables_and_functions() {
+    let content = r#"@host = https://api.example.com
+@version = v2
+
+# @name create_user
+POST {{host}}/{{version}}/users
+Content-Type: application/json
+
+{"id": "guid()", "role": "upper('admin')"}"#;
+
+    let requests = dry_run(content, false);
+
+    assert_eq!(requests[0].url, "https://api.example.com/v2/users");
+    let body = requests[0].body.as_deref().unwrap();
+    assert!(body.contains(r#""role": "ADMIN""#), "{body}");
+    assert!(!body.contains("guid()"), "{body}");
+}
+
+#[test]
+fn test_dry_run_marks_values_from_responses_as_pending() {
+    let content = r#"# @name login
+# @capture session = $.session
+POST https://api.example.com/login
+
+###
+GET https://api.example.com/orders?session={{session}}
+X-Token: {{ login.response.body.$.token }}
+X-Auth: {{$auth.token("api")}}"#;
+
+    let requests = dry_run(content, true);
+
+    assert_eq!(
+        requests[1].url,
+        "https://api.example.com/orders?session=<pending: session>"
+    );
+    assert_eq!(
+        requests[1].headers[0].value,
+        "<pending: login.response.body.$.token>"
+    );
+    assert_eq!(
+        requests[1].headers[1].value,
+        r#"<pending: $auth.token("api")>"#
+    );
+}
+
+#[test]
+fn test_dry_run_shows_secret_references_without_resolving_them() {
+    let content = r#"GET https://api.example.com/orders
+X-Api-Key: {{$secret("api/orders-key")}}"#;
+
+    // Without a secret command, resolving the secret would fail.
+    let requests = dry_run(content, true);
+
+    assert_eq!(requests[0].headers[0].value, "<secret: api/orders-key>");
+}
+
+#[test]
+fn test_dry_run_redacts_secrets_unless_included() {
+    let content = r#"POST https://api.example.com/login
+Authorization: Bearer abc123
+Content-Type: application/json
+
+{"username": "admin", "password": "hunter2"}"#;
+
+    let redacted = dry_run(content, false);
+    assert_eq!(redacted[0].headers[0].value, "***REDACTED***");
+    assert!(!redacted[0].body.as_deref().unwrap().contains("hunter2"));
+
+    let included = dry_run(content, true);
+    assert_eq!(included[0].headers[0].value, "Bearer abc123");
+    assert!(included[0].body.as_deref().unwrap().contains("hunter2"));
+}
+
+#[test]
+fn test_dry_run_orders_setup_and_teardown_requests() {
+    let content = r#"# @teardown
+DELETE https://api.example.com/seed
+
+###
+GET https://api.example.com/health
+
+###
+# @setup
+POST https://api.example.com/seed"#;
+
+    let methods: Vec<String> = dry_run(content, false)
+        .into_iter()
+        .map(|request| request.method)
+        .collect();
+
+    assert_eq!(methods, vec!["POST", "GET", "DELETE"]);
+}
+
+#[test]
+fn test_dry_run_fails_for_undefined_variables_in_strict_mode() {
+    let temp_file = create_temp_http_file("GET https://api.example.com/{{missing}}\n");
+    let files = [temp_file.path().to_str().unwrap().to_string()];
+
+    let lenient = dry_run_http_files(&ProcessorConfig::new(&files).with_silent(true));
+    assert!(lenient.is_ok());
+
+    let strict = dry_run_http_files(
+        &ProcessorConfig::new(&files)
+            .with_silent(true)
+            .with_strict_variables(true),
+    );
+    assert!(strict.is_err());
+}
+
+#[test]
+fn test_dry_run_keeps_captured_variables_across_files() {
+    let setup = parse_http_content(
+        "# @capture token = $.access_token\nPOST https://api.example.com/login\n",
+        None,
+    )
+    .unwrap();
+    let orders = parse_http_content(
+        "GET https://api.example.com/orders\nAuthorization: Bearer {{token}}\n",
+        None,
+    )
+    .unwrap();
+
+    let mut captured = Vec::new();
+    dry_run_requests(setup, &mut captured, false);
+    let requests = dry_run_requests(orders, &mut captured, true);
+
+    assert!(requests[0].1.is_none());
+    assert_eq!(requests[0].0.headers[0].value, "Bearer <pending: token>");
+}
+
+#[test]
+fn test_dry_run_of_setup_captures_passes_in_strict_mode() {
+    let setup = create_temp_http_file(
+        "# @capture token = $.access_token\nPOST https://api.example.com/login\n",
+    );
+    let orders = create_temp_http_file("GET https://api.example.com/orders?token={{token}}\n");
+    let setup_files = [setup.path().to_str().unwrap().to_string()];
+    let files = [orders.path().to_str().unwrap().to_string()];
+
+    let result = dry_run_http_files(
+        &ProcessorConfig::new(&files)
+            .with_setup_files(&setup_files)
+            .with_silent(true)
+            .with_strict_variables(true),
+    );
+    assert!(result.is_ok());
+}
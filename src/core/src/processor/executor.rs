@@ -1,25 +1,41 @@
 use super::formatter::format_request_name;
-use super::incremental_loop::{RequestReporter, SyncSleep, block_on, run_requests};
+use super::imports::ImportCache;
+use super::incremental_loop::{Providers, RequestReporter, SyncSleep, block_on, run_requests};
 use super::output;
+use crate::captures::CapturedVariables;
 use crate::colors;
+use crate::environment;
 use crate::logging::Log;
+use crate::oauth::{self, TokenProvider};
 use crate::parser;
-use crate::redaction::{sanitize_request_for_output, sanitize_result_for_output};
+use crate::redaction::{sanitize_request_for_output, sanitize_result_for_request_output};
 use crate::runner;
-use crate::types::{HttpFileResults, HttpRequest, HttpResult, ProcessorResults};
+use crate::secrets::SecretProvider;
+use crate::selection::RequestFilter;
+use crate::types::{
+    HttpFileResults, HttpRequest, HttpResult, ProcessorResults, RequestHook, SourcedVariable,
+};
 use anyhow::Result;
+use std::path::PathBuf;
 
 pub struct ProcessorConfig<'a> {
     pub files: &'a [String],
     pub verbose: bool,
     pub log_filename: Option<&'a str>,
     pub environment: Option<&'a str>,
+    pub variables: &'a [SourcedVariable],
     pub insecure: bool,
     pub pretty_json: bool,
     pub silent: bool,
     pub delay_ms: u64,
     pub include_secrets: bool,
     pub fail_fast: bool,
+    pub persist_tokens: bool,
+    pub strict_variables: bool,
+    pub secret_command: Option<&'a str>,
+    pub setup_files: &'a [String],
+    pub teardown_files: &'a [String],
+    pub filter: Option<&'a RequestFilter>,
 }
 
 impl<'a> ProcessorConfig<'a> {
@@ -29,12 +45,19 @@ impl<'a> ProcessorConfig<'a> {
             verbose: false,
             log_filename: None,
             environment: None,
+            variables: &[],
             insecure: false,
             pretty_json: false,
             silent: false,
             delay_ms: 0,
             include_secrets: false,
             fail_fast: false,
+            persist_tokens: false,
+            strict_variables: false,
+            secret_command: None,
+            setup_files: &[],
+            teardown_files: &[],
+            filter: None,
         }
     }
 
@@ -53,6 +76,12 @@ impl<'a> ProcessorConfig<'a> {
         self
     }
 
+    /// Variables overriding the environment and the files' own declarations
+    pub fn with_variables(mut self, variables: &'a [SourcedVariable]) -> Self {
+        self.variables = variables;
+        self
+    }
+
     pub fn with_insecure(mut self, insecure: bool) -> Self {
         self.insecure = insecure;
         self
@@ -82,6 +111,46 @@ impl<'a> ProcessorConfig<'a> {
         self.fail_fast = fail_fast;
         self
     }
+
+    pub fn with_persist_tokens(mut self, persist_tokens: bool) -> Self {
+        self.persist_tokens = persist_tokens;
+        self
+    }
+
+    /// Fail requests that still contain undefined `{{variables}}`, as if every
+    /// file declared `@strict-variables`
+    pub fn with_strict_variables(mut self, strict_variables: bool) -> Self {
+        self.strict_variables = strict_variables;
+        self
+    }
+
+    /// The command resolving `{{$secret("name")}}`, such as `pass show {name}`.
+    /// Without one, the command in `HTTPRUNNER_SECRET_COMMAND` is used
+    pub fn with_secret_command(mut self, secret_command: Option<&'a str>) -> Self {
+        self.secret_command = secret_command;
+        self
+    }
+
+    /// Files run before `files`, as if each of their requests were marked
+    /// `@setup`
+    pub fn with_setup_files(mut self, setup_files: &'a [String]) -> Self {
+        self.setup_files = setup_files;
+        self
+    }
+
+    /// Files run after `files` even when fail-fast halted them, as if each of
+    /// their requests were marked `@teardown`
+    pub fn with_teardown_files(mut self, teardown_files: &'a [String]) -> Self {
+        self.teardown_files = teardown_files;
+        self
+    }
+
+    /// Run only the requests selected by tag or name, with the requests they
+    /// depend on. Imported, setup and teardown files are not filtered
+    pub fn with_filter(mut self, filter: Option<&'a RequestFilter>) -> Self {
+        self.filter = filter;
+        self
+    }
 }
 
 /// Reporter adapter for the CLI batch path: logs each outcome (reusing the
@@ -121,7 +190,9 @@ impl RequestReporter for BatchReporter<'_, '_> {
         request: &HttpRequest,
         dep_name: &str,
     ) -> bool {
-        self.counters.record_skip();
+        if request.hook.is_none() {
+            self.counters.record_skip();
+        }
         let sanitized_request = sanitize_request_for_output(request, self.config.include_secrets);
         let name_str = format_request_name(&sanitized_request.name);
         self.log.writeln(&format!(
@@ -136,7 +207,9 @@ impl RequestReporter for BatchReporter<'_, '_> {
     }
 
     fn conditions_skipped(&mut self, _idx: usize, _total: usize, request: &HttpRequest) -> bool {
-        self.counters.record_skip();
+        if request.hook.is_none() {
+            self.counters.record_skip();
+        }
         let sanitized_request = sanitize_request_for_output(request, self.config.include_secrets);
         output::log_conditions_not_met(&sanitized_request, self.log);
         true
@@ -172,7 +245,7 @@ impl RequestReporter for BatchReporter<'_, '_> {
         let sanitized_request = sanitize_request_for_output(request, self.config.include_secrets);
         let name_str = format_request_name(&sanitized_request.name);
         self.log.writeln(&format!(
-            "{} {} {} {} - Internal error: {}",
+            "{} {} {} {} - Substitution error: {}",
             colors::red("❌"),
             name_str,
             sanitized_request.method,
@@ -193,8 +266,11 @@ impl RequestReporter for BatchReporter<'_, '_> {
         request: &HttpRequest,
         result: &HttpResult,
     ) -> bool {
+        // `@setup` and `@teardown` requests only count when they fail.
         if result.success {
-            self.counters.record_success();
+            if request.hook.is_none() {
+                self.counters.record_success();
+            }
         } else {
             self.counters.record_failure();
         }
@@ -203,19 +279,24 @@ impl RequestReporter for BatchReporter<'_, '_> {
         output::log_execution_result(result, &sanitized_request, self.log);
 
         if self.config.verbose {
-            let sanitized_result = sanitize_result_for_output(result, self.config.include_secrets);
+            let sanitized_result =
+                sanitize_result_for_request_output(result, request, self.config.include_secrets);
             output::log_response_details(&sanitized_result, self.log, self.config.pretty_json);
+            if !sanitized_result.captured_variables.is_empty() {
+                output::log_captured_variables(&sanitized_result, self.log);
+            }
         }
 
         if !request.assertions.is_empty() {
-            let sanitized_result = sanitize_result_for_output(result, self.config.include_secrets);
+            let sanitized_result =
+                sanitize_result_for_request_output(result, request, self.config.include_secrets);
             output::log_assertion_results(&sanitized_result, self.log);
         }
 
         let failed = !result.success;
 
         output::log_fail_fast_verbose(
-            &sanitized_request,
+            request,
             Some(result),
             self.config.include_secrets,
             self.config.pretty_json,
@@ -257,18 +338,135 @@ impl RequestReporter for BatchReporter<'_, '_> {
     }
 }
 
+/// The state shared by the files of a run: the variables stored by `@capture`,
+/// the files already run for `@import`, and the results and totals so far.
+/// `hook` marks every request while the setup or teardown files run.
+struct FileRun {
+    captures: CapturedVariables,
+    imports: ImportCache,
+    results: Vec<HttpFileResults>,
+    totals: output::TotalCounters,
+    halted: bool,
+    hook: Option<RequestHook>,
+}
+
+impl FileRun {
+    fn new() -> Self {
+        Self {
+            captures: CapturedVariables::default(),
+            imports: ImportCache::default(),
+            results: Vec::new(),
+            totals: output::TotalCounters::new(),
+            halted: false,
+            hook: None,
+        }
+    }
+}
+
+/// Run one file and record its outcome in `run`. The files it `@import`s run
+/// first, unless an earlier file already ran them.
+fn run_file<F>(
+    http_file: &str,
+    config: &ProcessorConfig,
+    executor: &F,
+    run: &mut FileRun,
+    log: &mut Log,
+) where
+    F: Fn(&HttpRequest, bool, bool) -> Result<HttpResult>,
+{
+    if let Err(e) = run.imports.start(http_file) {
+        log.writeln(&format!(
+            "{} Error importing file: {}",
+            colors::red("❌"),
+            e
+        ));
+        run.totals.increment_files_failed();
+        run.halted = config.fail_fast;
+        return;
+    }
+
+    match process_single_file(http_file, config, executor, run, log) {
+        // No request of the file is selected. An imported file still counts
+        // as run, with no requests to reference.
+        Ok(None) => {
+            let contexts = run.imports.is_importing().then_some(&[][..]);
+            run.imports.finish(http_file, contexts);
+        }
+        Ok(Some((file_results, file_halted))) => {
+            run.imports
+                .finish(http_file, Some(&file_results.result_contexts));
+            run.totals.add_file_results(&output::RequestCounters {
+                success: file_results.success_count,
+                failed: file_results.failed_count,
+                skipped: file_results.skipped_count,
+            });
+            run.results.push(file_results);
+            // Fail-fast halt: skip all remaining files.
+            run.halted = file_halted;
+        }
+        Err(_) => {
+            run.imports.finish(http_file, None);
+            // A fail-fast halt while running the imports was already counted.
+            if !run.halted {
+                // Parse error - count the entire file as failed
+                run.totals.increment_files_failed();
+                // Parse/processing errors also trigger a fail-fast halt.
+                run.halted = config.fail_fast;
+            }
+        }
+    }
+}
+
 fn process_single_file<F>(
     http_file: &str,
     config: &ProcessorConfig,
     executor: &F,
+    run: &mut FileRun,
     log: &mut Log,
-) -> Result<(HttpFileResults, bool)>
+) -> Result<Option<(HttpFileResults, bool)>>
 where
     F: Fn(&HttpRequest, bool, bool) -> Result<HttpResult>,
 {
+    let mut parsed =
+        parser::parse_http_file_with_variables(http_file, config.environment, config.variables);
+
+    if let (Some(filter), Ok(requests)) = (config.filter, &mut parsed)
+        && !filter.is_empty()
+        && run.hook.is_none()
+    {
+        if run.imports.is_importing() {
+            // An imported file runs only the requests the importing files use.
+            let names = run.imports.referenced_requests(http_file);
+            *requests = if names.is_empty() {
+                Vec::new()
+            } else {
+                let filter = RequestFilter {
+                    names,
+                    ..Default::default()
+                };
+                filter.select(std::mem::take(requests))
+            };
+        } else {
+            *requests = filter.select(std::mem::take(requests));
+        }
+        if requests.is_empty() {
+            return Ok(None);
+        }
+        run.imports.reference(requests);
+    }
+
+    if let Ok(requests) = &parsed {
+        for import in run.imports.pending_imports(requests) {
+            run_file(&import, config, executor, run, log);
+            if run.halted {
+                anyhow::bail!("Halted while importing {}", import);
+            }
+        }
+    }
+
     output::log_file_header(http_file, log);
 
-    let requests = match parser::parse_http_file(http_file, config.environment) {
+    let mut requests = match parsed {
         Ok(reqs) => reqs,
         Err(e) => {
             log.writeln(&format!("{} Error parsing file: {}", colors::red("❌"), e));
@@ -286,6 +484,53 @@ where
 
     log.writeln(&format!("Found {} HTTP request(s)\n", requests.len()));
 
+    if config.strict_variables {
+        for request in &mut requests {
+            request.strict_variables = true;
+        }
+    }
+    if let Some(hook) = run.hook {
+        for request in &mut requests {
+            request.hook = Some(hook);
+        }
+    }
+
+    let auth_configs = match environment::load_auth_configs(http_file, config.environment) {
+        Ok(configs) => configs,
+        Err(e) => {
+            log.writeln(&format!(
+                "{} Error loading auth configuration: {:#}",
+                colors::red("❌"),
+                e
+            ));
+            return Err(e);
+        }
+    };
+    let imported = match run.imports.imported_contexts(&requests) {
+        Ok(imported) => imported,
+        Err(e) => {
+            log.writeln(&format!(
+                "{} Error importing file: {:#}",
+                colors::red("❌"),
+                e
+            ));
+            return Err(e);
+        }
+    };
+    let token_store = config
+        .persist_tokens
+        .then(oauth::default_token_store)
+        .flatten();
+    let mut providers = Providers {
+        tokens: TokenProvider::new(auth_configs).with_store(token_store),
+        secrets: match config.secret_command {
+            Some(command) => SecretProvider::new(Some(command.to_string())),
+            None => SecretProvider::from_env(),
+        },
+        captures: std::mem::take(&mut run.captures),
+        imported,
+    };
+
     // When fail_fast is enabled we force full response capture for every request
     // (verbose || fail_fast) so the failed request always has body/headers
     // available, even though we only print verbose detail for the failing request.
@@ -298,11 +543,14 @@ where
     let result_contexts = block_on(run_requests(
         &mut reporter,
         requests,
+        &mut providers,
         config.insecure,
         config.delay_ms,
         &wrapped,
         SyncSleep,
-    ))?;
+    ));
+    run.captures = providers.captures;
+    let result_contexts = result_contexts?;
 
     let BatchReporter {
         counters, halted, ..
@@ -314,7 +562,7 @@ where
         output::log_file_summary(&counters, log);
     }
 
-    Ok((
+    Ok(Some((
         HttpFileResults {
             filename: http_file.to_string(),
             success_count: counters.success,
@@ -323,7 +571,15 @@ where
             result_contexts,
         },
         halted,
-    ))
+    )))
+}
+
+pub(super) fn is_teardown_file(config: &ProcessorConfig, http_file: &str) -> bool {
+    let key = |file: &str| std::fs::canonicalize(file).unwrap_or_else(|_| PathBuf::from(file));
+    config
+        .teardown_files
+        .iter()
+        .any(|file| key(file) == key(http_file))
 }
 
 /// The default executor: performs real blocking HTTP requests.
@@ -343,9 +599,7 @@ where
     F: Fn(&HttpRequest, bool, bool) -> Result<HttpResult>,
 {
     let mut log = Log::new_with_silent(config.log_filename, config.silent)?;
-    let mut http_file_results = Vec::<HttpFileResults>::new();
-    let mut totals = output::TotalCounters::new();
-    let mut halted = false;
+    let mut run = FileRun::new();
 
     if config.insecure {
         log.writeln(&format!(
@@ -354,40 +608,43 @@ where
         ));
     }
 
+    run.hook = Some(RequestHook::Setup);
+    for http_file in config.setup_files {
+        run_file(http_file, config, executor, &mut run, &mut log);
+        if run.halted {
+            break;
+        }
+    }
+    run.hook = None;
+
     for http_file in config.files {
-        match process_single_file(http_file, config, executor, &mut log) {
-            Ok((file_results, file_halted)) => {
-                totals.add_file_results(&output::RequestCounters {
-                    success: file_results.success_count,
-                    failed: file_results.failed_count,
-                    skipped: file_results.skipped_count,
-                });
-                http_file_results.push(file_results);
-                if file_halted {
-                    // Fail-fast halt: skip all remaining files.
-                    halted = true;
-                    break;
-                }
-            }
-            Err(_) => {
-                // Parse error - count the entire file as failed
-                totals.increment_files_failed();
-                if config.fail_fast {
-                    // Parse/processing errors also trigger a fail-fast halt.
-                    halted = true;
-                    break;
-                }
-            }
+        if run.halted {
+            break;
+        }
+        // Already run because an earlier file imported it, or to run last.
+        if run.imports.has_run(http_file) || is_teardown_file(config, http_file) {
+            continue;
         }
+        run_file(http_file, config, executor, &mut run, &mut log);
     }
 
+    // Every teardown file runs, even after a fail-fast halt.
+    let mut halted = run.halted;
+    run.hook = Some(RequestHook::Teardown);
+    for http_file in config.teardown_files {
+        run.halted = false;
+        run_file(http_file, config, executor, &mut run, &mut log);
+        halted |= run.halted;
+    }
+    run.halted = halted;
+
     // Suppress the overall summary when halting due to fail-fast.
-    if !halted {
-        output::log_overall_summary(&totals, &mut log);
+    if !run.halted {
+        output::log_overall_summary(&run.totals, &mut log);
     }
 
     Ok(ProcessorResults {
-        success: totals.failed == 0,
-        files: http_file_results,
+        success: run.totals.failed == 0,
+        files: run.results,
     })
 }
@@ -6,7 +6,8 @@ mod tests {
     use super::super::executor::{ProcessorConfig, process_http_files};
     use super::super::mock_executor::MockHttpExecutor;
     use super::*;
-    use crate::types::{HttpRequest, HttpResult};
+    use crate::selection::RequestFilter;
+    use crate::types::{HttpRequest, HttpResult, RequestHook};
 
     fn create_success_response(name: Option<String>) -> HttpResult {
         HttpResult {
@@ -18,6 +19,7 @@ mod tests {
             response_headers: None,
             response_body: Some(r#"{"result":"ok"}"#.to_string()),
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         }
     }
 
@@ -103,6 +105,7 @@ DELETE https://api.example.com/3
             response_headers: None,
             response_body: None,
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -385,6 +388,7 @@ GET https://api.example.com/data
             response_headers: None,
             response_body: None,
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -557,6 +561,7 @@ GET https://api.example.com/success2
                 response_headers: None,
                 response_body: None,
                 assertion_results: Vec::new(),
+                captured_variables: Vec::new(),
             },
             create_success_response(None),
         ]);
@@ -727,6 +732,7 @@ Content-Type: application/json
             response_headers: None,
             response_body: None,
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         };
         let mock = MockHttpExecutor::new(vec![failed_first]);
         let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i));
@@ -884,6 +890,7 @@ GET https://api.example.com/third
                 response_headers: None,
                 response_body: None,
                 assertion_results: Vec::new(),
+                captured_variables: Vec::new(),
             },
         ]);
 
@@ -947,6 +954,7 @@ GET https://api.example.com/third
             response_headers: Some(headers),
             response_body: Some(r#"{"status":"ok"}"#.to_string()),
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         };
 
         let mock = MockHttpExecutor::new(vec![response]);
@@ -1023,6 +1031,7 @@ Content-Type: application/json
             response_headers: Some(headers),
             response_body: Some(r#"{"result":"success"}"#.to_string()),
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         };
 
         let mock = MockHttpExecutor::new(vec![response]);
@@ -1060,6 +1069,7 @@ Content-Type: application/json
             response_headers: None,
             response_body: Some(r#"{"status":"ok","data":{"id":123}}"#.to_string()),
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         };
 
         let mock = MockHttpExecutor::new(vec![response]);
@@ -1142,6 +1152,7 @@ GET https://api.example.com/test
                 actual_value: Some("404".to_string()),
                 error_message: Some("Expected 200, got 404".to_string()),
             }],
+            captured_variables: Vec::new(),
         };
 
         let mock = MockHttpExecutor::new(vec![response]);
@@ -1294,6 +1305,7 @@ GET https://api.example.com/data
             response_headers: None,
             response_body: None,
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         };
 
         let mock = MockHttpExecutor::new(vec![response]);
@@ -1329,6 +1341,7 @@ GET https://api.example.com/data
             response_headers: None,
             response_body: None,
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         };
 
         let mock = MockHttpExecutor::new(vec![response]);
@@ -1471,6 +1484,7 @@ Authorization: Bearer token123
             response_headers: None,
             response_body: None,
             assertion_results: Vec::new(),
+            captured_variables: Vec::new(),
         };
 
         let mock = MockHttpExecutor::new(vec![response]);
@@ -1545,6 +1559,7 @@ Content-Type: application/json
                 actual_value: Some("400".to_string()),
                 error_message: None,
             }],
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -1587,6 +1602,7 @@ Content-Type: application/json
                 actual_value: Some("404".to_string()),
                 error_message: None,
             }],
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -1629,6 +1645,7 @@ Content-Type: application/json
                 actual_value: Some("500".to_string()),
                 error_message: None,
             }],
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -1671,6 +1688,7 @@ Content-Type: application/json
                 actual_value: Some("500".to_string()),
                 error_message: Some("Expected status 400, got 500".to_string()),
             }],
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -1724,6 +1742,7 @@ Content-Type: application/json
                     error_message: None,
                 },
             ],
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -1778,6 +1797,7 @@ Content-Type: application/json
                     error_message: Some("Expected body to contain 'validation failed'".to_string()),
                 },
             ],
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -1821,6 +1841,7 @@ Content-Type: application/json
                 actual_value: Some("200".to_string()),
                 error_message: Some("Expected status 201, got 200".to_string()),
             }],
+            captured_variables: Vec::new(),
         }]);
 
         let result = process_http_files(
@@ -1871,6 +1892,7 @@ GET https://api.example.com/not-found
                     actual_value: Some("200".to_string()),
                     error_message: None,
                 }],
+                captured_variables: Vec::new(),
             },
             // Second request: 400 with passing assertion (expected bad request)
             HttpResult {
@@ -1890,6 +1912,7 @@ GET https://api.example.com/not-found
                     actual_value: Some("400".to_string()),
                     error_message: None,
                 }],
+                captured_variables: Vec::new(),
             },
             // Third request: 404 with failing assertion (expected 200)
             HttpResult {
@@ -1909,6 +1932,7 @@ GET https://api.example.com/not-found
                     actual_value: Some("404".to_string()),
                     error_message: Some("Expected status 200, got 404".to_string()),
                 }],
+                captured_variables: Vec::new(),
             },
         ]);
 
@@ -1952,6 +1976,7 @@ GET https://api.example.com/3
                 response_headers: None,
                 response_body: Some(r#"{"error":"boom"}"#.to_string()),
                 assertion_results: Vec::new(),
+                captured_variables: Vec::new(),
             },
             create_success_response(None),
             create_success_response(None),
@@ -1993,6 +2018,7 @@ GET https://api.example.com/3
                 response_headers: None,
                 response_body: Some("body".to_string()),
                 assertion_results: Vec::new(),
+                captured_variables: Vec::new(),
             })
         });
 
@@ -2032,6 +2058,7 @@ GET https://api.example.com/never
                     actual_value: Some("404".to_string()),
                     error_message: Some("Expected status 200, got 404".to_string()),
                 }],
+                captured_variables: Vec::new(),
             },
             create_success_response(None),
         ]);
@@ -2128,6 +2155,7 @@ GET https://api.example.com/final
                 response_headers: None,
                 response_body: Some("body".to_string()),
                 assertion_results: Vec::new(),
+                captured_variables: Vec::new(),
             },
             create_success_response(None),
         ]);
@@ -2167,6 +2195,7 @@ GET https://api.example.com/3
                 response_headers: None,
                 response_body: None,
                 assertion_results: Vec::new(),
+                captured_variables: Vec::new(),
             },
             create_success_response(None),
             create_success_response(None),
@@ -2184,4 +2213,449 @@ GET https://api.example.com/3
         assert_eq!(res.files[0].failed_count, 1);
         assert_eq!(res.files[0].success_count, 2);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secret_references_are_resolved_by_the_secret_command() {
+        let file_content = "GET https://api.example.com/test\nAuthorization: Bearer {{$secret(\"api/key\")}}\n";
+        let temp_file = create_temp_http_file(file_content);
+        let files = [temp_file.path().to_str().unwrap().to_string()];
+
+        let mock = MockHttpExecutor::new(vec![create_success_response(None)]);
+        let config = ProcessorConfig::new(&files)
+            .with_silent(true)
+            .with_secret_command(Some("echo executor-secret-{name}"));
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(result.success);
+        assert_eq!(
+            mock.get_executed_requests()[0].headers[0].value,
+            "Bearer executor-secret-api/key"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failing_secret_command_fails_the_request() {
+        let file_content = "GET https://api.example.com/test\nAuthorization: Bearer {{$secret(\"api/key\")}}\n";
+        let temp_file = create_temp_http_file(file_content);
+        let files = [temp_file.path().to_str().unwrap().to_string()];
+
+        let mock = MockHttpExecutor::new(vec![create_success_response(None)]);
+        let config = ProcessorConfig::new(&files)
+            .with_silent(true)
+            .with_secret_command(Some("false"));
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.files[0].failed_count, 1);
+        assert_eq!(mock.get_call_count(), 0);
+    }
+
+    #[test]
+    fn test_captured_variables_are_available_to_later_files() {
+        let login = create_temp_http_file(
+            "# @capture token = $.access_token\n# @capture etag = header ETag\nPOST https://api.example.com/login\n",
+        );
+        let orders = create_temp_http_file(
+            "GET https://api.example.com/orders\nAuthorization: Bearer {{token}}\nIf-None-Match: {{etag}}\n",
+        );
+        let files = [
+            login.path().to_str().unwrap().to_string(),
+            orders.path().to_str().unwrap().to_string(),
+        ];
+
+        let mut login_response = create_success_response(None);
+        login_response.response_body = Some(r#"{"access_token":"captured-token"}"#.to_string());
+        login_response.response_headers = Some(
+            [("etag".to_string(), "\"v1\"".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        let mock = MockHttpExecutor::new(vec![login_response, create_success_response(None)]);
+        let config = ProcessorConfig::new(&files).with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(result.success);
+        let captured = &result.files[0].result_contexts[0]
+            .result
+            .as_ref()
+            .unwrap()
+            .captured_variables;
+        assert_eq!(captured.len(), 2);
+        let orders_request = &mock.get_executed_requests()[1];
+        assert_eq!(orders_request.headers[0].value, "Bearer captured-token");
+        assert_eq!(orders_request.headers[1].value, "\"v1\"");
+    }
+
+    #[test]
+    fn test_missing_captured_value_fails_the_request() {
+        let file_content = "# @capture token = $.access_token\nPOST https://api.example.com/login\n";
+        let temp_file = create_temp_http_file(file_content);
+        let files = [temp_file.path().to_str().unwrap().to_string()];
+
+        let mock = MockHttpExecutor::new(vec![create_success_response(None)]);
+        let config = ProcessorConfig::new(&files).with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(!result.success);
+        let failed = result.files[0].result_contexts[0].result.as_ref().unwrap();
+        assert_eq!(
+            failed.error_message.as_deref(),
+            Some("Failed to capture 'token': $.access_token was not found in the response body")
+        );
+    }
+
+    #[test]
+    fn test_regex_and_xpath_body_paths_chain_requests() {
+        let file_content = r#"# @name legacy
+# @capture session = regex(/session=(\w+)/)
+POST https://api.example.com/soap
+
+###
+
+# @if legacy.response.body.//order/@status shipped
+GET https://api.example.com/orders/{{legacy.response.body.//order/id}}
+X-Session: {{session}}
+"#;
+        let temp_file = create_temp_http_file(file_content);
+        let files = [temp_file.path().to_str().unwrap().to_string()];
+
+        let mut legacy_response = create_success_response(Some("legacy".to_string()));
+        legacy_response.response_body = Some(
+            r#"<result session="x"><order status="shipped"><id>42</id></order><!-- session=abc123 --></result>"#
+                .to_string(),
+        );
+        let mock = MockHttpExecutor::new(vec![legacy_response, create_success_response(None)]);
+        let config = ProcessorConfig::new(&files).with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(result.success);
+        let orders_request = &mock.get_executed_requests()[1];
+        assert_eq!(orders_request.url, "https://api.example.com/orders/42");
+        assert_eq!(orders_request.headers[0].value, "abc123");
+    }
+
+    #[test]
+    fn test_imported_requests_can_be_referenced_and_run_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let auth = dir.path().join("auth.http");
+        std::fs::write(&auth, "# @name login\nPOST https://api.example.com/login\n").unwrap();
+        let orders = dir.path().join("orders.http");
+        std::fs::write(
+            &orders,
+            "# @import ./auth.http\n\n# @dependsOn auth.http#login\nGET https://api.example.com/orders\nAuthorization: Bearer {{auth.login.response.body.$.token}}\n",
+        )
+        .unwrap();
+        let files = [
+            orders.to_str().unwrap().to_string(),
+            auth.to_str().unwrap().to_string(),
+        ];
+
+        let mut login_response = create_success_response(Some("login".to_string()));
+        login_response.response_body = Some(r#"{"token":"imported-token"}"#.to_string());
+        let mock = MockHttpExecutor::new(vec![login_response, create_success_response(None)]);
+        let config = ProcessorConfig::new(&files).with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(result.success);
+        assert_eq!(mock.get_call_count(), 2);
+        let filenames: Vec<&str> = result.files.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(filenames, vec![files[1].as_str(), files[0].as_str()]);
+        assert_eq!(result.files[1].success_count, 1);
+        assert_eq!(result.files[1].result_contexts.len(), 1);
+        let orders_request = &mock.get_executed_requests()[1];
+        assert_eq!(orders_request.headers[0].value, "Bearer imported-token");
+    }
+
+    #[test]
+    fn test_circular_import_fails_without_running_requests() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let first = dir.path().join("first.http");
+        std::fs::write(
+            &first,
+            "# @import ./second.http\nGET https://api.example.com/first\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("second.http"),
+            "# @import ./first.http\nGET https://api.example.com/second\n",
+        )
+        .unwrap();
+        let files = [first.to_str().unwrap().to_string()];
+
+        let mock = MockHttpExecutor::new(vec![]);
+        let config = ProcessorConfig::new(&files).with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(!result.success);
+        assert_eq!(mock.get_call_count(), 0);
+        assert!(result.files.is_empty());
+    }
+
+    #[test]
+    fn test_run_line_results_are_referenced_by_later_requests() {
+        let file_content = r#"# @name login
+POST https://api.example.com/login?user={{user}}
+
+###
+run #login (@user=admin)
+
+###
+GET https://api.example.com/admin
+Authorization: Bearer {{login.response.body.$.token}}
+"#;
+        let temp_file = create_temp_http_file(file_content);
+        let files = [temp_file.path().to_str().unwrap().to_string()];
+
+        let mut user_login = create_success_response(Some("login".to_string()));
+        user_login.response_body = Some(r#"{"token":"user-token"}"#.to_string());
+        let mut admin_login = create_success_response(Some("login".to_string()));
+        admin_login.response_body = Some(r#"{"token":"admin-token"}"#.to_string());
+        let mock = MockHttpExecutor::new(vec![user_login, admin_login]);
+        let config = ProcessorConfig::new(&files).with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.files[0].success_count, 3);
+        let executed = mock.get_executed_requests();
+        assert_eq!(executed[1].url, "https://api.example.com/login?user=admin");
+        assert_eq!(executed[2].headers[0].value, "Bearer admin-token");
+    }
+
+    #[test]
+    fn test_teardown_requests_run_after_a_fail_fast_halt() {
+        let file_content = r#"# @teardown
+DELETE https://api.example.com/users/1
+
+###
+# @setup
+POST https://api.example.com/users
+
+###
+GET https://api.example.com/users/1
+
+###
+GET https://api.example.com/users/2
+"#;
+        let temp_file = create_temp_http_file(file_content);
+        let files = [temp_file.path().to_str().unwrap().to_string()];
+
+        let mut not_found = create_success_response(None);
+        not_found.status_code = 404;
+        not_found.success = false;
+        let mock = MockHttpExecutor::new(vec![
+            create_success_response(None),
+            not_found,
+            create_success_response(None),
+        ]);
+        let config = ProcessorConfig::new(&files)
+            .with_fail_fast(true)
+            .with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        let executed: Vec<String> = mock
+            .get_executed_requests()
+            .iter()
+            .map(|request| format!("{} {}", request.method, request.url))
+            .collect();
+        assert_eq!(
+            executed,
+            vec![
+                "POST https://api.example.com/users",
+                "GET https://api.example.com/users/1",
+                "DELETE https://api.example.com/users/1",
+            ]
+        );
+        // The successful setup and teardown requests are not counted.
+        assert!(!result.success);
+        assert_eq!(result.files[0].success_count, 0);
+        assert_eq!(result.files[0].failed_count, 1);
+        assert_eq!(result.files[0].result_contexts.len(), 3);
+    }
+
+    #[test]
+    fn test_failed_setup_request_counts_as_failure() {
+        let file_content = "# @setup\nPOST https://api.example.com/users\n\n###\nGET https://api.example.com/users/1\n";
+        let temp_file = create_temp_http_file(file_content);
+        let files = [temp_file.path().to_str().unwrap().to_string()];
+
+        let mut conflict = create_success_response(None);
+        conflict.status_code = 409;
+        conflict.success = false;
+        let mock = MockHttpExecutor::new(vec![conflict, create_success_response(None)]);
+        let config = ProcessorConfig::new(&files).with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.files[0].success_count, 1);
+        assert_eq!(result.files[0].failed_count, 1);
+    }
+
+    #[test]
+    fn test_suite_setup_and_teardown_files_run_around_the_files() {
+        let setup = create_temp_http_file("POST https://api.example.com/seed\n");
+        let first = create_temp_http_file("GET https://api.example.com/first\n");
+        let second = create_temp_http_file("GET https://api.example.com/second\n");
+        let teardown = create_temp_http_file("DELETE https://api.example.com/seed\n");
+        let setup_files = [setup.path().to_str().unwrap().to_string()];
+        let teardown_files = [teardown.path().to_str().unwrap().to_string()];
+        let files = [
+            first.path().to_str().unwrap().to_string(),
+            second.path().to_str().unwrap().to_string(),
+        ];
+
+        let mut server_error = create_success_response(None);
+        server_error.status_code = 500;
+        server_error.success = false;
+        let mock = MockHttpExecutor::new(vec![
+            create_success_response(None),
+            server_error,
+            create_success_response(None),
+        ]);
+        let config = ProcessorConfig::new(&files)
+            .with_setup_files(&setup_files)
+            .with_teardown_files(&teardown_files)
+            .with_fail_fast(true)
+            .with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        let urls: Vec<String> = mock
+            .get_executed_requests()
+            .iter()
+            .map(|request| request.url.clone())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://api.example.com/seed",
+                "https://api.example.com/first",
+                "https://api.example.com/seed",
+            ]
+        );
+        assert!(!result.success);
+        let filenames: Vec<&str> = result.files.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(
+            filenames,
+            vec![
+                setup_files[0].as_str(),
+                files[0].as_str(),
+                teardown_files[0].as_str()
+            ]
+        );
+        assert_eq!(result.files[0].success_count, 0);
+        assert_eq!(
+            result.files[0].result_contexts[0].request.hook,
+            Some(RequestHook::Setup)
+        );
+        assert_eq!(result.files[2].success_count, 0);
+        assert_eq!(
+            result.files[2].result_contexts[0].request.hook,
+            Some(RequestHook::Teardown)
+        );
+    }
+
+    #[test]
+    fn test_filter_runs_selected_requests_with_their_dependencies() {
+        let orders = create_temp_http_file(
+            r#"# @name login
+POST https://api.example.com/login
+
+###
+# @name list_orders
+# @tag smoke
+GET https://api.example.com/orders
+Authorization: Bearer {{login.response.body.$.token}}
+
+###
+# @name create_order
+GET https://api.example.com/orders/new
+"#,
+        );
+        let health = create_temp_http_file("# @name health\nGET https://api.example.com/health\n");
+        let files = [
+            orders.path().to_str().unwrap().to_string(),
+            health.path().to_str().unwrap().to_string(),
+        ];
+
+        let mut login_response = create_success_response(Some("login".to_string()));
+        login_response.response_body = Some(r#"{"token":"abc"}"#.to_string());
+        let mock = MockHttpExecutor::new(vec![login_response, create_success_response(None)]);
+        let filter = RequestFilter {
+            tags: vec!["smoke".to_string()],
+            ..Default::default()
+        };
+        let config = ProcessorConfig::new(&files)
+            .with_filter(Some(&filter))
+            .with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(result.success);
+        let urls: Vec<String> = mock
+            .get_executed_requests()
+            .iter()
+            .map(|request| request.url.clone())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://api.example.com/login",
+                "https://api.example.com/orders"
+            ]
+        );
+        // The file without a selected request is not run or reported.
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].success_count, 2);
+    }
+
+    #[test]
+    fn test_filter_runs_only_the_imported_requests_that_are_used() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("auth.http"),
+            "# @name login\nPOST https://api.example.com/login\n\n###\n# @name logout\nPOST https://api.example.com/logout\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("admin.http"),
+            "# @name ping\nGET https://api.example.com/ping\n",
+        )
+        .unwrap();
+        let orders = dir.path().join("orders.http");
+        std::fs::write(
+            &orders,
+            "# @import ./auth.http\n# @import ./admin.http\n\n# @name list_orders\n# @tag smoke\n# @auth bearer {{auth.login.response.body.$.token}}\nGET https://api.example.com/orders\n\n###\n# @name create_order\n# @dependsOn admin.http#ping\nPOST https://api.example.com/orders\n",
+        )
+        .unwrap();
+        let files = [orders.to_str().unwrap().to_string()];
+
+        let mut login_response = create_success_response(Some("login".to_string()));
+        login_response.response_body = Some(r#"{"token":"abc"}"#.to_string());
+        let mock = MockHttpExecutor::new(vec![login_response, create_success_response(None)]);
+        let filter = RequestFilter {
+            tags: vec!["smoke".to_string()],
+            ..Default::default()
+        };
+        let config = ProcessorConfig::new(&files)
+            .with_filter(Some(&filter))
+            .with_silent(true);
+        let result = process_http_files(&config, &|req, v, i| mock.execute(req, v, i)).unwrap();
+
+        assert!(result.success);
+        let urls: Vec<String> = mock
+            .get_executed_requests()
+            .iter()
+            .map(|request| request.url.clone())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://api.example.com/login",
+                "https://api.example.com/orders"
+            ]
+        );
+        let filenames: Vec<&str> = result.files.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(filenames.len(), 2);
+        assert!(filenames[0].ends_with("auth.http"));
+    }
 }
@@ -0,0 +1,132 @@
+use crate::selection::imported_request_names;
+use crate::types::{HttpRequest, Import, RequestContext};
+use anyhow::{Result, anyhow, bail};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// The request contexts of every file run so far, so that a file imported with
+/// `@import` runs once per run however many files import it. With a request
+/// filter, it records the requests of each imported file that the importing
+/// files use.
+#[derive(Debug, Default)]
+pub(crate) struct ImportCache {
+    contexts: HashMap<PathBuf, Vec<RequestContext>>,
+    running: Vec<PathBuf>,
+    referenced: HashMap<PathBuf, Vec<String>>,
+}
+
+impl ImportCache {
+    pub(crate) fn has_run(&self, file: &str) -> bool {
+        self.contexts.contains_key(&file_key(file))
+    }
+
+    /// Whether the file running now runs because another file imports it.
+    pub(crate) fn is_importing(&self) -> bool {
+        self.running.len() > 1
+    }
+
+    /// Mark `file` as running, failing when it is already running because it
+    /// imports itself, directly or through other files.
+    pub(crate) fn start(&mut self, file: &str) -> Result<()> {
+        let key = file_key(file);
+        if self.running.contains(&key) {
+            bail!("Circular @import of {}", file);
+        }
+        self.running.push(key);
+        Ok(())
+    }
+
+    /// Record the contexts of a file that has run, or `None` when it could not
+    /// be run.
+    pub(crate) fn finish(&mut self, file: &str, contexts: Option<&[RequestContext]>) {
+        let key = file_key(file);
+        self.running.retain(|running| *running != key);
+        if let Some(contexts) = contexts {
+            self.contexts.insert(key, contexts.to_vec());
+        }
+    }
+
+    /// Record the requests of the imported files that `requests` use.
+    pub(crate) fn reference(&mut self, requests: &[HttpRequest]) {
+        for import in imports_of(requests) {
+            let names = self.referenced.entry(file_key(&import.file)).or_default();
+            names.extend(imported_request_names(requests, &import.alias));
+            names.sort();
+            names.dedup();
+        }
+    }
+
+    /// The requests of `file` that the files importing it use.
+    pub(crate) fn referenced_requests(&self, file: &str) -> Vec<String> {
+        self.referenced
+            .get(&file_key(file))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The imported files of `requests` that have not run yet.
+    pub(crate) fn pending_imports(&self, requests: &[HttpRequest]) -> Vec<String> {
+        imports_of(requests)
+            .iter()
+            .filter(|import| !self.has_run(&import.file))
+            .map(|import| import.file.clone())
+            .collect()
+    }
+
+    /// The contexts of the files imported by `requests`, named
+    /// `<alias>.<request>` so that they can be referenced as
+    /// `{{auth.login.response.body.$.token}}` or `@dependsOn auth.http#login`.
+    /// Fails when two of the files are imported under the same name.
+    pub(crate) fn imported_contexts(
+        &self,
+        requests: &[HttpRequest],
+    ) -> Result<Vec<RequestContext>> {
+        let imports = imports_of(requests);
+        for (index, import) in imports.iter().enumerate() {
+            if let Some(other) = imports[..index]
+                .iter()
+                .find(|other| other.alias == import.alias)
+            {
+                bail!(
+                    "'{}' is imported from both {} and {}",
+                    import.alias,
+                    other.file,
+                    import.file
+                );
+            }
+        }
+
+        let mut imported = Vec::new();
+        for import in imports {
+            let contexts = self
+                .contexts
+                .get(&file_key(&import.file))
+                .ok_or_else(|| anyhow!("Failed to import {}", import.file))?;
+            imported.extend(contexts.iter().map(|context| RequestContext {
+                name: format!("{}.{}", import.alias, context.name),
+                request: context.request.clone(),
+                result: context.result.clone(),
+            }));
+        }
+        Ok(imported)
+    }
+}
+
+/// `@import` applies to the whole file, but the requests of a `run` line also
+/// carry the imports of the file they come from.
+fn imports_of(requests: &[HttpRequest]) -> Vec<&Import> {
+    let mut imports: Vec<&Import> = Vec::new();
+    for import in requests.iter().flat_map(|request| &request.imports) {
+        if !imports
+            .iter()
+            .any(|existing| file_key(&existing.file) == file_key(&import.file))
+        {
+            imports.push(import);
+        }
+    }
+    imports
+}
+
+fn file_key(file: &str) -> PathBuf {
+    std::fs::canonicalize(file).unwrap_or_else(|_| PathBuf::from(file))
+}
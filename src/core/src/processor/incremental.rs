@@ -1,8 +1,13 @@
-use super::incremental_loop::{SyncSleep, block_on, process_requests_incremental};
+use super::imports::ImportCache;
+use super::incremental_loop::{CallbackReporter, Providers, SyncSleep, block_on, run_requests};
+use crate::captures::CapturedVariables;
+use crate::environment;
+use crate::oauth::TokenProvider;
 use crate::parser;
 use crate::runner;
-use crate::types::{HttpRequest, HttpResult};
-use anyhow::Result;
+use crate::secrets::SecretProvider;
+use crate::types::{HttpRequest, HttpResult, RequestContext};
+use anyhow::{Context, Result};
 
 pub use super::incremental_loop::RequestProcessingResult;
 
@@ -46,12 +51,76 @@ where
         async move { executor(&request, verbose, insecure) }
     };
 
-    block_on(process_requests_incremental(
+    let mut imports = ImportCache::default();
+    imports.start(file_path)?;
+    let imported = run_imports(&requests, &mut imports, environment, insecure, &wrapped)?;
+
+    let mut providers = Providers {
+        tokens: TokenProvider::new(environment::load_auth_configs(file_path, environment)?),
+        secrets: SecretProvider::from_env(),
+        captures: CapturedVariables::default(),
+        imported,
+    };
+
+    block_on(run_requests(
+        &mut CallbackReporter::new(callback),
         requests,
+        &mut providers,
         insecure,
         delay_ms,
-        callback,
         &wrapped,
         SyncSleep,
+    ))?;
+    Ok(())
+}
+
+/// Run the files `requests` `@import` without reporting their requests, so
+/// that the importing file can reference them, and return their contexts.
+fn run_imports<Fut>(
+    requests: &[HttpRequest],
+    imports: &mut ImportCache,
+    environment: Option<&str>,
+    insecure: bool,
+    executor: &impl Fn(HttpRequest, bool, bool) -> Fut,
+) -> Result<Vec<RequestContext>>
+where
+    Fut: Future<Output = Result<HttpResult>>,
+{
+    for file in imports.pending_imports(requests) {
+        imports.start(&file)?;
+        let contexts = run_imported_file(&file, imports, environment, insecure, executor);
+        imports.finish(&file, contexts.as_deref().ok());
+        contexts.with_context(|| format!("Failed to import {}", file))?;
+    }
+    imports.imported_contexts(requests)
+}
+
+fn run_imported_file<Fut>(
+    file: &str,
+    imports: &mut ImportCache,
+    environment: Option<&str>,
+    insecure: bool,
+    executor: &impl Fn(HttpRequest, bool, bool) -> Fut,
+) -> Result<Vec<RequestContext>>
+where
+    Fut: Future<Output = Result<HttpResult>>,
+{
+    let requests = parser::parse_http_file(file, environment)?;
+    let imported = run_imports(&requests, imports, environment, insecure, executor)?;
+    let mut providers = Providers {
+        tokens: TokenProvider::new(environment::load_auth_configs(file, environment)?),
+        secrets: SecretProvider::from_env(),
+        captures: CapturedVariables::default(),
+        imported,
+    };
+
+    block_on(run_requests(
+        &mut CallbackReporter::new(|_, _, _| true),
+        requests,
+        &mut providers,
+        insecure,
+        0,
+        executor,
+        SyncSleep,
     ))
 }
@@ -1,10 +1,18 @@
+use super::page_run::PageRun;
 use crate::assertions;
+use crate::captures::{self, CapturedVariables};
 use crate::conditions;
+use crate::datasets;
+use crate::oauth::TokenProvider;
 use crate::request_substitution::{
-    substitute_functions_in_request, substitute_request_variables_in_request,
+    check_undefined_variables, substitute_auth_tokens_in_request,
+    substitute_captured_variables_in_request, substitute_functions_in_request,
+    substitute_request_variables_in_request, substitute_secrets_in_request,
 };
-use crate::types::{HttpRequest, HttpResult, RequestContext};
+use crate::secrets::SecretProvider;
+use crate::types::{HttpRequest, HttpResult, RequestContext, RequestHook};
 use anyhow::Result;
+use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
 #[cfg(not(target_arch = "wasm32"))]
@@ -308,6 +316,18 @@ impl Future for NativeSleep {
     }
 }
 
+/// Resolve the references that are only known at run time:
+/// `$auth.token()` with `tokens`, `$secret()` with `secrets`, the
+/// variables stored by `@capture` with `captures` and the requests of the
+/// `@import`ed files with `imported`.
+#[derive(Debug, Default)]
+pub(crate) struct Providers {
+    pub(crate) tokens: TokenProvider,
+    pub(crate) secrets: SecretProvider,
+    pub(crate) captures: CapturedVariables,
+    pub(crate) imported: Vec<RequestContext>,
+}
+
 pub(crate) fn add_request_context(
     contexts: &mut Vec<RequestContext>,
     request: HttpRequest,
@@ -345,21 +365,42 @@ where
     S: Sleep,
 {
     let mut reporter = CallbackReporter::new(callback);
-    run_requests(&mut reporter, requests, insecure, delay_ms, executor, sleep).await?;
+    let mut providers = Providers {
+        tokens: TokenProvider::default(),
+        secrets: SecretProvider::from_env(),
+        captures: CapturedVariables::default(),
+        imported: Vec::new(),
+    };
+    run_requests(
+        &mut reporter,
+        requests,
+        &mut providers,
+        insecure,
+        delay_ms,
+        executor,
+        sleep,
+    )
+    .await?;
     Ok(())
 }
 
-/// The single request-processing orchestration: dependency checking, condition
-/// evaluation, variable/function substitution, pre/post delays, execution and
-/// assertions. Outcomes are reported through `reporter`, which also controls
-/// fail-fast (returning `false` halts the loop). Returns the accumulated request
-/// contexts so callers can aggregate per-file results.
+/// The single request-processing orchestration: `@data` and `@foreach`
+/// expansion, dependency checking, condition evaluation, variable/function
+/// substitution (including the captured variables, `$auth.token()` and
+/// `$secret()` references resolved by `providers`), pre/post delays,
+/// execution, `@paginate` page following, assertions and `@capture`, with
+/// `@setup` requests first and `@teardown` requests last. Outcomes are reported
+/// through `reporter`, which also controls fail-fast (returning `false` halts
+/// the loop, except for the `@teardown` requests). Returns the accumulated
+/// request contexts so callers can aggregate per-file results; the imported
+/// contexts taken from `providers` are only referenced, not returned.
 ///
 /// The executor is called with an owned `HttpRequest` (the loop clones it before
 /// dispatching), so the original remains available for reporting and context tracking.
 pub(crate) async fn run_requests<R, Fut, S>(
     reporter: &mut R,
     requests: Vec<HttpRequest>,
+    providers: &mut Providers,
     insecure: bool,
     delay_ms: u64,
     executor: &impl Fn(HttpRequest, bool, bool) -> Fut,
@@ -370,116 +411,279 @@ where
     Fut: Future<Output = Result<HttpResult>>,
     S: Sleep,
 {
-    let total = requests.len();
-    let mut request_contexts: Vec<RequestContext> = Vec::new();
+    let mut request_contexts: Vec<RequestContext> = std::mem::take(&mut providers.imported);
+    let imported_count = request_contexts.len();
+    let mut next_idx = 0;
+
+    // `@teardown` requests run after the others even when a failure halted
+    // them, and a failing teardown request does not stop the ones after it.
+    let (requests, teardown) = split_teardown(requests);
+    let teardown_count = teardown.len();
+    for (in_teardown, requests) in [(false, requests), (true, teardown)] {
+        let queued = if in_teardown { 0 } else { teardown_count };
+        let mut pending: VecDeque<HttpRequest> = datasets::expand_data_iterations(requests).into();
+        let mut pagination_run: Option<PageRun> = None;
+
+        // `@foreach` iterations and `@paginate` pages are queued at run time, so
+        // `total` grows as loops expand and further pages are found.
+        while let Some(mut request) = pending.pop_front() {
+            let idx = next_idx;
+            next_idx += 1;
+            let total = idx + 1 + pending.len() + queued;
+            let request_count = (idx + 1) as u32;
+            // Set only when `request` is the next page of a paginated request.
+            let mut page_run = pagination_run.take();
+
+            if idx > 0 && delay_ms > 0 && request.foreach.is_none() {
+                sleep.sleep(Duration::from_millis(delay_ms)).await;
+            }
 
-    if requests.is_empty() {
-        return Ok(request_contexts);
-    }
+            if let Some(dep_name) = request.depends_on.clone()
+                && !conditions::check_dependency(&Some(dep_name.clone()), &request_contexts)
+            {
+                let should_continue = reporter.dependency_skipped(idx, total, &request, &dep_name);
+                add_request_context(&mut request_contexts, request, None, request_count);
+                if !should_continue && !in_teardown {
+                    break;
+                }
+                continue;
+            }
 
-    for (idx, mut request) in requests.into_iter().enumerate() {
-        let request_count = (idx + 1) as u32;
+            if !request.conditions.is_empty() {
+                match conditions::evaluate_conditions(&request.conditions, &request_contexts) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        let should_continue = reporter.conditions_skipped(idx, total, &request);
+                        add_request_context(&mut request_contexts, request, None, request_count);
+                        if !should_continue && !in_teardown {
+                            break;
+                        }
+                        continue;
+                    }
+                    Err(error) => {
+                        let should_continue =
+                            reporter.condition_error(idx, total, &request, &error);
+                        add_request_context(&mut request_contexts, request, None, request_count);
+                        if !should_continue && !in_teardown {
+                            break;
+                        }
+                        continue;
+                    }
+                }
+            }
 
-        if idx > 0 && delay_ms > 0 {
-            sleep.sleep(Duration::from_millis(delay_ms)).await;
-        }
+            if let Some(foreach) = request.foreach.take() {
+                match datasets::expand_foreach(&request, &foreach, &request_contexts) {
+                    Ok(iterations) => {
+                        // The loop itself is not executed; its iterations take its place.
+                        next_idx -= 1;
+                        for iteration in iterations.into_iter().rev() {
+                            pending.push_front(iteration);
+                        }
+                    }
+                    Err(error) => {
+                        let should_continue =
+                            reporter.substitution_error(idx, total, &request, &error);
+                        add_request_context(&mut request_contexts, request, None, request_count);
+                        if !should_continue && !in_teardown {
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
 
-        if let Some(dep_name) = request.depends_on.clone()
-            && !conditions::check_dependency(&Some(dep_name.clone()), &request_contexts)
-        {
-            let should_continue = reporter.dependency_skipped(idx, total, &request, &dep_name);
-            add_request_context(&mut request_contexts, request, None, request_count);
-            if !should_continue {
-                break;
+            if let Err(error) =
+                substitute_captured_variables_in_request(&mut request, &providers.captures)
+            {
+                let should_continue = reporter.substitution_error(idx, total, &request, &error);
+                add_request_context(&mut request_contexts, request, None, request_count);
+                if !should_continue && !in_teardown {
+                    break;
+                }
+                continue;
             }
-            continue;
-        }
 
-        if !request.conditions.is_empty() {
-            match conditions::evaluate_conditions(&request.conditions, &request_contexts) {
-                Ok(true) => {}
-                Ok(false) => {
-                    let should_continue = reporter.conditions_skipped(idx, total, &request);
-                    add_request_context(&mut request_contexts, request, None, request_count);
-                    if !should_continue {
+            if let Err(error) =
+                substitute_request_variables_in_request(&mut request, &request_contexts)
+            {
+                let should_continue = reporter.substitution_error(idx, total, &request, &error);
+                add_request_context(&mut request_contexts, request, None, request_count);
+                if !should_continue && !in_teardown {
+                    break;
+                }
+                continue;
+            }
+
+            if let Err(error) = substitute_auth_tokens_in_request(&mut request, &providers.tokens) {
+                let should_continue = reporter.substitution_error(idx, total, &request, &error);
+                add_request_context(&mut request_contexts, request, None, request_count);
+                if !should_continue && !in_teardown {
+                    break;
+                }
+                continue;
+            }
+
+            if let Err(error) = substitute_secrets_in_request(&mut request, &providers.secrets) {
+                let should_continue = reporter.substitution_error(idx, total, &request, &error);
+                add_request_context(&mut request_contexts, request, None, request_count);
+                if !should_continue && !in_teardown {
+                    break;
+                }
+                continue;
+            }
+
+            if let Err(error) = substitute_functions_in_request(&mut request) {
+                let should_continue = reporter.substitution_error(idx, total, &request, &error);
+                add_request_context(&mut request_contexts, request, None, request_count);
+                if !should_continue && !in_teardown {
+                    break;
+                }
+                continue;
+            }
+            if let Err(error) = check_undefined_variables(&request) {
+                let should_continue = reporter.substitution_error(idx, total, &request, &error);
+                add_request_context(&mut request_contexts, request, None, request_count);
+                if !should_continue && !in_teardown {
+                    break;
+                }
+                continue;
+            }
+
+            if let Some(pre_delay_ms) = request.pre_delay_ms
+                && pre_delay_ms > 0
+            {
+                sleep.sleep(Duration::from_millis(pre_delay_ms)).await;
+            }
+
+            let post_delay_ms = request.post_delay_ms;
+
+            if page_run.is_none() {
+                page_run = PageRun::start(&mut request);
+            }
+            let mut next_page = None;
+
+            reporter.request_started(idx, total, &request);
+
+            // Clone the request for the executor so the original remains available
+            // for reporting and context tracking.
+            match executor(request.clone(), false, insecure).await {
+                Ok(mut result) => {
+                    if !request.assertions.is_empty() {
+                        let assertion_results =
+                            assertions::evaluate_assertions(&request.assertions, &result);
+                        let all_passed = assertion_results.iter().all(|r| r.passed);
+                        result.success = all_passed;
+                        result.assertion_results = assertion_results;
+                    }
+                    if !request.captures.is_empty() {
+                        capture_variables(&request, &mut result, &mut providers.captures);
+                    }
+                    if let Some(run) = page_run.as_mut() {
+                        next_page = run.record(&request, &result);
+                    }
+                    let should_continue = reporter.executed(idx, total, &request, &result);
+                    add_request_context(
+                        &mut request_contexts,
+                        request,
+                        Some(result),
+                        request_count,
+                    );
+                    if !should_continue && !in_teardown {
                         break;
                     }
-                    continue;
                 }
                 Err(error) => {
-                    let should_continue = reporter.condition_error(idx, total, &request, &error);
+                    let should_continue = reporter.execution_error(idx, total, &request, &error);
                     add_request_context(&mut request_contexts, request, None, request_count);
-                    if !should_continue {
+                    if !should_continue && !in_teardown {
                         break;
                     }
-                    continue;
                 }
             }
-        }
-
-        if let Err(error) = substitute_request_variables_in_request(&mut request, &request_contexts)
-        {
-            let should_continue = reporter.substitution_error(idx, total, &request, &error);
-            add_request_context(&mut request_contexts, request, None, request_count);
-            if !should_continue {
-                break;
-            }
-            continue;
-        }
 
-        if let Err(error) = substitute_functions_in_request(&mut request) {
-            let should_continue = reporter.substitution_error(idx, total, &request, &error);
-            add_request_context(&mut request_contexts, request, None, request_count);
-            if !should_continue {
-                break;
+            if let Some(post_delay_ms) = post_delay_ms
+                && post_delay_ms > 0
+            {
+                sleep.sleep(Duration::from_millis(post_delay_ms)).await;
             }
-            continue;
-        }
-
-        if let Some(pre_delay_ms) = request.pre_delay_ms
-            && pre_delay_ms > 0
-        {
-            sleep.sleep(Duration::from_millis(pre_delay_ms)).await;
-        }
 
-        let post_delay_ms = request.post_delay_ms;
-
-        reporter.request_started(idx, total, &request);
-
-        // Clone the request for the executor so the original remains available
-        // for reporting and context tracking.
-        match executor(request.clone(), false, insecure).await {
-            Ok(mut result) => {
-                if !request.assertions.is_empty() {
-                    let assertion_results =
-                        assertions::evaluate_assertions(&request.assertions, &result);
-                    let all_passed = assertion_results.iter().all(|r| r.passed);
-                    result.success = all_passed;
-                    result.assertion_results = assertion_results;
+            if let Some(run) = page_run {
+                if let Some(next_page) = next_page {
+                    pending.push_front(next_page);
+                    pagination_run = Some(run);
+                    continue;
                 }
-                let should_continue = reporter.executed(idx, total, &request, &result);
-                add_request_context(&mut request_contexts, request, Some(result), request_count);
-                if !should_continue {
-                    break;
+
+                let reports_aggregate = run.reports_aggregate();
+                let Some((request, aggregate)) = run.finish() else {
+                    continue;
+                };
+                let idx = next_idx;
+                let total = idx + 1 + pending.len() + queued;
+                let should_continue = match &aggregate {
+                    Ok(result) if reports_aggregate => {
+                        next_idx += 1;
+                        reporter.executed(idx, total, &request, result)
+                    }
+                    Ok(_) => true,
+                    Err(error) => {
+                        next_idx += 1;
+                        reporter.execution_error(idx, total, &request, error)
+                    }
+                };
+                if request.name.is_some() || reports_aggregate {
+                    add_request_context(
+                        &mut request_contexts,
+                        request,
+                        aggregate.ok(),
+                        (idx + 1) as u32,
+                    );
                 }
-            }
-            Err(error) => {
-                let should_continue = reporter.execution_error(idx, total, &request, &error);
-                add_request_context(&mut request_contexts, request, None, request_count);
-                if !should_continue {
+                if !should_continue && !in_teardown {
                     break;
                 }
             }
         }
+    }
 
-        if let Some(post_delay_ms) = post_delay_ms
-            && post_delay_ms > 0
-        {
-            sleep.sleep(Duration::from_millis(post_delay_ms)).await;
+    Ok(request_contexts.split_off(imported_count))
+}
+
+/// Split `requests` into those run first, with the `@setup` requests ahead of
+/// the others, and the `@teardown` requests run last.
+pub(super) fn split_teardown(requests: Vec<HttpRequest>) -> (Vec<HttpRequest>, Vec<HttpRequest>) {
+    let (teardown, requests): (Vec<_>, Vec<_>) = requests
+        .into_iter()
+        .partition(|request| request.hook == Some(RequestHook::Teardown));
+    let (mut setup, others): (Vec<_>, Vec<_>) = requests
+        .into_iter()
+        .partition(|request| request.hook == Some(RequestHook::Setup));
+    setup.extend(others);
+    (setup, teardown)
+}
+
+/// Store the values of the request's `@capture` directives. A value that
+/// cannot be found fails the request.
+fn capture_variables(
+    request: &HttpRequest,
+    result: &mut HttpResult,
+    captured: &mut CapturedVariables,
+) {
+    match captures::capture_values(&request.captures, result) {
+        Ok(values) => {
+            for variable in &values {
+                captured.set(variable.clone());
+            }
+            result.captured_variables = values;
+        }
+        Err(error) => {
+            result.success = false;
+            result
+                .error_message
+                .get_or_insert_with(|| error.to_string());
         }
     }
-
-    Ok(request_contexts)
 }
 
 /// Block on a future using a no-op waker.
@@ -501,7 +705,11 @@ pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Assertion, AssertionType, Condition, ConditionType};
+    use crate::types::{
+        Assertion, AssertionType, Condition, ConditionType, Pagination, PaginationAssertScope,
+        PaginationNext,
+    };
+    use std::collections::HashMap;
     use std::pin::Pin;
     use std::sync::{
         Arc, Mutex,
@@ -537,6 +745,7 @@ mod tests {
             response_headers: None,
             response_body: None,
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }
     }
 
@@ -555,6 +764,7 @@ mod tests {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         }
     }
 
@@ -754,6 +964,358 @@ mod tests {
             if reason.contains("Dependency")));
     }
 
+    #[test]
+    fn test_process_requests_expands_data_iterations() {
+        let requests = vec![
+            HttpRequest {
+                url: "https://example.com/users/{{id}}".to_string(),
+                data: Some(crate::types::DataSource {
+                    path: "users.csv".to_string(),
+                    scope: crate::types::DataScope::Request,
+                    rows: vec![
+                        vec![crate::types::Variable {
+                            name: "id".to_string(),
+                            value: "1".to_string(),
+                        }],
+                        vec![crate::types::Variable {
+                            name: "id".to_string(),
+                            value: "2".to_string(),
+                        }],
+                    ],
+                }),
+                ..make_request("create_user")
+            },
+            HttpRequest {
+                depends_on: Some("create_user".to_string()),
+                ..make_request("list_users")
+            },
+        ];
+        let executor = ok_executor();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let e = Arc::clone(&events);
+
+        block_on(process_requests_incremental(
+            requests,
+            false,
+            0,
+            |idx, total, result| {
+                if let RequestProcessingResult::Executed { request, .. } = result {
+                    e.lock()
+                        .unwrap()
+                        .push((idx, total, request.name.unwrap(), request.url));
+                }
+                true
+            },
+            &executor,
+            MockSleep::new(),
+        ))
+        .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![
+                (
+                    0,
+                    3,
+                    "create_user[1]".to_string(),
+                    "https://example.com/users/1".to_string()
+                ),
+                (
+                    1,
+                    3,
+                    "create_user[2]".to_string(),
+                    "https://example.com/users/2".to_string()
+                ),
+                (
+                    2,
+                    3,
+                    "list_users".to_string(),
+                    "https://example.com/test".to_string()
+                ),
+            ]
+        );
+    }
+
+    fn list_executor()
+    -> impl Fn(HttpRequest, bool, bool) -> Pin<Box<dyn Future<Output = Result<HttpResult>>>> {
+        |request: HttpRequest, _verbose: bool, _insecure: bool| {
+            let mut result = make_result(request.name.clone());
+            if request.name.as_deref() == Some("list") {
+                result.response_body =
+                    Some(r#"{"items":[{"id":7,"tag":"a"},{"id":9,"tag":"b"}]}"#.to_string());
+            }
+            Box::pin(async move { Ok(result) })
+        }
+    }
+
+    #[test]
+    fn test_process_requests_expands_foreach_at_run_time() {
+        let requests = vec![
+            make_request("list"),
+            HttpRequest {
+                url: "https://example.com/items/{{item.id}}?pos={{index}}".to_string(),
+                foreach: Some(crate::types::ForEach {
+                    variable: "item".to_string(),
+                    source: "{{list.response.body.$.items}}".to_string(),
+                }),
+                ..make_request("get_item")
+            },
+            HttpRequest {
+                depends_on: Some("get_item".to_string()),
+                ..make_request("summary")
+            },
+        ];
+        let executor = list_executor();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let e = Arc::clone(&events);
+
+        let contexts = block_on(run_requests(
+            &mut CallbackReporter::new(|idx, total, result| {
+                if let RequestProcessingResult::Executed { request, .. } = result {
+                    e.lock()
+                        .unwrap()
+                        .push((idx, total, request.name.unwrap(), request.url));
+                }
+                true
+            }),
+            requests,
+            &mut Providers::default(),
+            false,
+            0,
+            &executor,
+            MockSleep::new(),
+        ))
+        .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].0, 0);
+        assert_eq!(events[0].1, 3);
+        assert_eq!(
+            events[1],
+            (
+                1,
+                4,
+                "get_item[1]".to_string(),
+                "https://example.com/items/7?pos=0".to_string()
+            )
+        );
+        assert_eq!(
+            events[2],
+            (
+                2,
+                4,
+                "get_item[2]".to_string(),
+                "https://example.com/items/9?pos=1".to_string()
+            )
+        );
+        assert_eq!(events[3].2, "summary");
+
+        let names: Vec<_> = contexts.iter().map(|ctx| ctx.name.as_str()).collect();
+        assert_eq!(names, vec!["list", "get_item[1]", "get_item[2]", "summary"]);
+    }
+
+    #[test]
+    fn test_process_requests_foreach_over_missing_array_fails() {
+        let requests = vec![
+            make_request("list"),
+            HttpRequest {
+                foreach: Some(crate::types::ForEach {
+                    variable: "item".to_string(),
+                    source: "{{list.response.body.$.missing}}".to_string(),
+                }),
+                ..make_request("get_item")
+            },
+            HttpRequest {
+                depends_on: Some("get_item".to_string()),
+                ..make_request("summary")
+            },
+        ];
+        let executor = list_executor();
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let r = Arc::clone(&results);
+
+        block_on(process_requests_incremental(
+            requests,
+            false,
+            0,
+            |_idx, _total, result| {
+                r.lock().unwrap().push(result);
+                true
+            },
+            &executor,
+            MockSleep::new(),
+        ))
+        .unwrap();
+
+        let results = results.lock().unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(&results[1], RequestProcessingResult::Failed { error, .. }
+            if error.contains("Substitution error")));
+        assert!(matches!(&results[2], RequestProcessingResult::Skipped { reason, .. }
+            if reason.contains("Dependency")));
+    }
+
+    #[test]
+    fn test_process_requests_foreach_respects_dependency_on_loop() {
+        let requests = vec![HttpRequest {
+            depends_on: Some("missing".to_string()),
+            foreach: Some(crate::types::ForEach {
+                variable: "item".to_string(),
+                source: "[1, 2, 3]".to_string(),
+            }),
+            ..make_request("get_item")
+        }];
+        let executor = ok_executor();
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let r = Arc::clone(&results);
+
+        block_on(process_requests_incremental(
+            requests,
+            false,
+            0,
+            |_idx, _total, result| {
+                r.lock().unwrap().push(result);
+                true
+            },
+            &executor,
+            MockSleep::new(),
+        ))
+        .unwrap();
+
+        let results = results.lock().unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(&results[0], RequestProcessingResult::Skipped { .. }));
+    }
+
+    fn paged_executor()
+    -> impl Fn(HttpRequest, bool, bool) -> Pin<Box<dyn Future<Output = Result<HttpResult>>>> {
+        |request: HttpRequest, _verbose: bool, _insecure: bool| {
+            let mut result = make_result(request.name.clone());
+            if let Some(page) = request.url.strip_prefix("https://example.com/users?page=") {
+                let page: u32 = page.parse().unwrap();
+                result.response_body = Some(format!(r#"{{"data":[{}],"page":{}}}"#, page, page));
+                if page < 3 {
+                    let link = format!(r#"</users?page={}>; rel="next""#, page + 1);
+                    result.response_headers = Some(HashMap::from([("link".to_string(), link)]));
+                }
+            }
+            Box::pin(async move { Ok(result) })
+        }
+    }
+
+    fn paginated_request(max_pages: u32, assert_scope: PaginationAssertScope) -> HttpRequest {
+        HttpRequest {
+            url: "https://example.com/users?page=1".to_string(),
+            paginate: Some(Pagination {
+                next: PaginationNext::Link,
+                max_pages,
+                items: Some("$.data".to_string()),
+                assert_scope,
+            }),
+            ..make_request("users")
+        }
+    }
+
+    fn run_paginated(
+        requests: Vec<HttpRequest>,
+    ) -> (Vec<(String, HttpResult)>, Vec<RequestContext>) {
+        let executor = paged_executor();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let e = Arc::clone(&events);
+
+        let contexts = block_on(run_requests(
+            &mut CallbackReporter::new(|_idx, _total, result| {
+                if let RequestProcessingResult::Executed { request, result } = result {
+                    e.lock().unwrap().push((request.name.unwrap(), result));
+                }
+                true
+            }),
+            requests,
+            &mut Providers::default(),
+            false,
+            0,
+            &executor,
+            MockSleep::new(),
+        ))
+        .unwrap();
+
+        let events = events.lock().unwrap().clone();
+        (events, contexts)
+    }
+
+    #[test]
+    fn test_process_requests_follows_pages_and_records_aggregate() {
+        let requests = vec![
+            paginated_request(100, PaginationAssertScope::Page),
+            HttpRequest {
+                url: "https://example.com/summary?count={{users.response.body.$.data[2]}}"
+                    .to_string(),
+                depends_on: Some("users".to_string()),
+                ..make_request("summary")
+            },
+        ];
+
+        let (events, contexts) = run_paginated(requests);
+
+        let names: Vec<_> = events.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["users[1]", "users[2]", "users[3]", "summary"]);
+
+        let context_names: Vec<_> = contexts.iter().map(|ctx| ctx.name.as_str()).collect();
+        assert_eq!(
+            context_names,
+            vec!["users[1]", "users[2]", "users[3]", "users", "summary"]
+        );
+        let aggregate = contexts[3].result.as_ref().unwrap();
+        assert_eq!(
+            aggregate.response_body.as_deref(),
+            Some(r#"{"data":[1,2,3],"page":1}"#)
+        );
+        assert_eq!(
+            contexts[4].request.url,
+            "https://example.com/summary?count=3"
+        );
+    }
+
+    #[test]
+    fn test_process_requests_paginate_stops_at_max_pages() {
+        let (events, contexts) =
+            run_paginated(vec![paginated_request(2, PaginationAssertScope::Page)]);
+
+        assert_eq!(events.len(), 2);
+        let aggregate = contexts.last().unwrap();
+        assert_eq!(aggregate.name, "users");
+        assert_eq!(
+            aggregate.result.as_ref().unwrap().response_body.as_deref(),
+            Some(r#"{"data":[1,2],"page":1}"#)
+        );
+    }
+
+    #[test]
+    fn test_process_requests_paginate_asserts_over_aggregate() {
+        let request = HttpRequest {
+            assertions: vec![Assertion {
+                assertion_type: AssertionType::Body,
+                expected_value: r#""data":[1,2,3]"#.to_string(),
+            }],
+            ..paginated_request(100, PaginationAssertScope::Aggregate)
+        };
+
+        let (events, _) = run_paginated(vec![request]);
+
+        let names: Vec<_> = events.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["users[1]", "users[2]", "users[3]", "users"]);
+        assert!(
+            events[..3]
+                .iter()
+                .all(|(_, result)| result.assertion_results.is_empty())
+        );
+        let aggregate = &events[3].1;
+        assert_eq!(aggregate.assertion_results.len(), 1);
+        assert!(aggregate.success);
+    }
+
     #[test]
     fn test_process_requests_condition_skip() {
         let requests = vec![HttpRequest {
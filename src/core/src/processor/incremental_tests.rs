@@ -16,6 +16,7 @@ fn create_response(status: u16) -> HttpResult {
         response_headers: None,
         response_body: Some(r#"{"status":"ok"}"#.to_string()),
         assertion_results: Vec::new(),
+        captured_variables: Vec::new(),
     }
 }
 
@@ -29,6 +30,7 @@ fn create_response_with_body(status: u16, body: &str) -> HttpResult {
         response_headers: None,
         response_body: Some(body.to_string()),
         assertion_results: Vec::new(),
+        captured_variables: Vec::new(),
     }
 }
 
@@ -556,3 +558,99 @@ fn test_functions_example_resolves_request_derived_variables() {
     assert!(second_body.contains("\"first_request_guid_base64\": \"YWJjLTEyMw==\""));
     assert!(second_body.contains("\"first_request_guid_variable_base64\": \"YWJjLTEyMw==\""));
 }
+
+#[test]
+fn test_strict_variables_fails_request_with_undefined_variable() {
+    let file_content = r#"# @strict-variables
+
+GET {{baseUrl}}/users
+X-Tenant: {{tenant}}
+
+###
+GET https://api.example.com/{{$randomInt 1 9}}
+"#;
+    let temp_file = create_temp_http_file(file_content);
+    let file_path = temp_file.path().to_str().unwrap();
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let results_clone = Arc::clone(&results);
+
+    let mock = MockHttpExecutor::new(vec![create_response(200)]);
+
+    process_http_file_incremental_with_executor(
+        file_path,
+        None,
+        false,
+        0,
+        move |_idx, _total, result| {
+            results_clone.lock().unwrap().push(result);
+            true
+        },
+        &|req, v, i| mock.execute(req, v, i),
+    )
+    .unwrap();
+
+    let results = results.lock().unwrap();
+    assert_eq!(results.len(), 2);
+    match &results[0] {
+        RequestProcessingResult::Failed { error, .. } => assert_eq!(
+            error,
+            "Substitution error: undefined variables 'baseUrl' at line 3, 'tenant' at line 4"
+        ),
+        other => panic!("expected a substitution failure, got {other:?}"),
+    }
+    assert!(matches!(
+        results[1],
+        RequestProcessingResult::Executed { .. }
+    ));
+    drop(results);
+
+    let executed_requests = mock.get_executed_requests();
+    assert_eq!(executed_requests.len(), 1);
+    assert!(!executed_requests[0].url.contains("{{"));
+}
+
+#[test]
+fn test_imported_requests_run_without_being_reported() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("auth.http"),
+        "# @name login\nPOST https://api.example.com/login\n",
+    )
+    .unwrap();
+    let orders = dir.path().join("orders.http");
+    std::fs::write(
+        &orders,
+        "# @import ./auth.http\n\nGET https://api.example.com/orders\nAuthorization: Bearer {{auth.login.response.body.$.token}}\n",
+    )
+    .unwrap();
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let results_clone = Arc::clone(&results);
+
+    let mock = MockHttpExecutor::new(vec![
+        create_response_with_body(200, r#"{"token":"imported-token"}"#),
+        create_response(200),
+    ]);
+
+    process_http_file_incremental_with_executor(
+        orders.to_str().unwrap(),
+        None,
+        false,
+        0,
+        move |_idx, _total, result| {
+            results_clone.lock().unwrap().push(result);
+            true
+        },
+        &|req, v, i| mock.execute(req, v, i),
+    )
+    .unwrap();
+
+    assert_eq!(results.lock().unwrap().len(), 1);
+    let executed_requests = mock.get_executed_requests();
+    assert_eq!(executed_requests.len(), 2);
+    assert_eq!(
+        executed_requests[1].headers[0].value,
+        "Bearer imported-token"
+    );
+}
@@ -48,6 +48,7 @@ impl MockHttpExecutor {
                 response_headers: None,
                 response_body: Some(r#"{"status":"ok"}"#.to_string()),
                 assertion_results: Vec::new(),
+                captured_variables: Vec::new(),
             })
         } else {
             Ok(responses.remove(0))
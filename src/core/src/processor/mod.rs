@@ -1,9 +1,13 @@
+mod dry_run;
 mod executor;
 mod formatter;
+mod imports;
 mod incremental;
 pub(crate) mod incremental_loop;
 mod output;
+mod page_run;
 
+pub use dry_run::dry_run_http_files;
 pub use executor::{ProcessorConfig, default_executor, process_http_files};
 
 pub use formatter::format_json_if_valid;
@@ -22,5 +26,8 @@ mod tests;
 #[cfg(test)]
 mod executor_tests;
 
+#[cfg(test)]
+mod dry_run_tests;
+
 #[cfg(test)]
 mod incremental_tests;
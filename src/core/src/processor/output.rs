@@ -1,7 +1,7 @@
 use super::formatter::{format_json_if_valid, format_request_name};
 use crate::colors;
 use crate::logging::Log;
-use crate::redaction::{sanitize_request_for_output, sanitize_result_for_output};
+use crate::redaction::{sanitize_request_for_output, sanitize_result_for_request_output};
 use crate::types::{AssertionType, HttpRequest, HttpResult};
 
 pub(super) struct RequestCounters {
@@ -180,6 +180,14 @@ pub fn log_response_details(result: &HttpResult, log: &mut Log, pretty_json: boo
     log.writeln(&"-".repeat(30));
 }
 
+pub fn log_captured_variables(result: &HttpResult, log: &mut Log) {
+    log.writeln(&format!("\n{} Captured Variables:", colors::blue("📌")));
+    for variable in &result.captured_variables {
+        log.writeln(&format!("  {} = {}", variable.name, variable.value));
+    }
+    log.writeln(&"-".repeat(30));
+}
+
 pub fn log_assertion_results(result: &HttpResult, log: &mut Log) {
     log.writeln(&format!("\n{} Assertion Results:", colors::blue("🔍")));
     for assertion_result in &result.assertion_results {
@@ -277,7 +285,8 @@ pub fn log_fail_fast_verbose(
         log_request_details(&sanitized_request, log, pretty_json);
 
         if let Some(result) = http_result {
-            let sanitized_result = sanitize_result_for_output(result, include_secrets);
+            let sanitized_result =
+                sanitize_result_for_request_output(result, processed_request, include_secrets);
             log_response_details(&sanitized_result, log, pretty_json);
         }
     }
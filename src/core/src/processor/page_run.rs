@@ -0,0 +1,101 @@
+use crate::assertions;
+use crate::datasets::iteration_name;
+use crate::pagination::{aggregate_pages, next_page_url};
+use crate::types::{HttpRequest, HttpResult, Pagination, PaginationAssertScope};
+use anyhow::Result;
+
+/// Tracks a `@paginate` request while its pages run through the request loop.
+///
+/// Pages are named `name[1]`, `name[2]`, … and the aggregate of all pages is
+/// recorded under the request's own name once pagination is exhausted.
+pub(crate) struct PageRun {
+    pagination: Pagination,
+    request: HttpRequest, // First page as written, keeping its name and assertions
+    pages: Vec<HttpResult>,
+    error: Option<anyhow::Error>,
+}
+
+impl PageRun {
+    /// Starts paginating `request` if it has a `@paginate` directive, turning it
+    /// into the first page.
+    pub(crate) fn start(request: &mut HttpRequest) -> Option<Self> {
+        let run = Self {
+            pagination: request.paginate.clone()?,
+            request: request.clone(),
+            pages: Vec::new(),
+            error: None,
+        };
+        run.prepare_page(request, 1);
+        Some(run)
+    }
+
+    /// Records a fetched page and returns the request for the next page, if
+    /// there is one to fetch.
+    pub(crate) fn record(
+        &mut self,
+        page: &HttpRequest,
+        result: &HttpResult,
+    ) -> Option<HttpRequest> {
+        self.pages.push(result.clone());
+        if !result.success || self.pages.len() >= self.pagination.max_pages as usize {
+            return None;
+        }
+
+        match next_page_url(&self.pagination, &page.url, &self.request.url, result) {
+            Ok(Some(url)) => {
+                let mut next = page.clone();
+                next.url = url;
+                next.depends_on = None;
+                next.conditions.clear();
+                self.prepare_page(&mut next, self.pages.len() + 1);
+                Some(next)
+            }
+            Ok(None) => None,
+            Err(error) => {
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+
+    /// Whether the aggregate is reported as a result of its own, because the
+    /// assertions run over it instead of over every page.
+    pub(crate) fn reports_aggregate(&self) -> bool {
+        self.pagination.assert_scope == PaginationAssertScope::Aggregate
+    }
+
+    /// Ends the run, returning the original request with the aggregate of its
+    /// pages, or `None` when no page was fetched.
+    pub(crate) fn finish(self) -> Option<(HttpRequest, Result<HttpResult>)> {
+        if self.pages.is_empty() {
+            return None;
+        }
+
+        let reports_aggregate = self.reports_aggregate();
+        let aggregate = match self.error {
+            Some(error) => Err(error),
+            None => aggregate_pages(&self.pagination, self.request.name.clone(), &self.pages),
+        };
+        let aggregate = aggregate.map(|mut result| {
+            if reports_aggregate && !self.request.assertions.is_empty() {
+                result.assertion_results =
+                    assertions::evaluate_assertions(&self.request.assertions, &result);
+                result.success &= result.assertion_results.iter().all(|r| r.passed);
+            }
+            result
+        });
+
+        Some((self.request, aggregate))
+    }
+
+    fn prepare_page(&self, page: &mut HttpRequest, number: usize) {
+        page.name = self
+            .request
+            .name
+            .as_deref()
+            .map(|name| iteration_name(name, number));
+        if self.reports_aggregate() {
+            page.assertions.clear();
+        }
+    }
+}
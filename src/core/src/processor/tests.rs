@@ -61,6 +61,7 @@ fn test_substitute_request_variables_in_url() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -79,6 +80,7 @@ fn test_substitute_request_variables_in_url() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("login".to_string()),
@@ -89,6 +91,7 @@ fn test_substitute_request_variables_in_url() {
             response_headers: None,
             response_body: Some(r#"{"id":"123","token":"abc"}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -117,6 +120,7 @@ fn test_substitute_request_variables_in_headers() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -135,6 +139,7 @@ fn test_substitute_request_variables_in_headers() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("login".to_string()),
@@ -145,6 +150,7 @@ fn test_substitute_request_variables_in_headers() {
             response_headers: None,
             response_body: Some(r#"{"token":"secret123"}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -170,6 +176,7 @@ fn test_substitute_request_variables_in_body() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -188,6 +195,7 @@ fn test_substitute_request_variables_in_body() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("getUser".to_string()),
@@ -198,6 +206,7 @@ fn test_substitute_request_variables_in_body() {
             response_headers: None,
             response_body: Some(r#"{"id":"456","name":"Jane"}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -223,6 +232,7 @@ fn test_substitute_request_variables_with_no_context() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![];
@@ -250,6 +260,7 @@ fn test_substitute_multiple_variables() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![
@@ -269,6 +280,7 @@ fn test_substitute_multiple_variables() {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
             result: Some(HttpResult {
                 request_name: Some("host".to_string()),
@@ -279,6 +291,7 @@ fn test_substitute_multiple_variables() {
                 response_headers: None,
                 response_body: Some(r#"{"domain":"api.example.com"}"#.to_string()),
                 assertion_results: vec![],
+                captured_variables: Vec::new(),
             }),
         },
         RequestContext {
@@ -297,6 +310,7 @@ fn test_substitute_multiple_variables() {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
             result: Some(HttpResult {
                 request_name: Some("user".to_string()),
@@ -307,6 +321,7 @@ fn test_substitute_multiple_variables() {
                 response_headers: None,
                 response_body: Some(r#"{"id":"789"}"#.to_string()),
                 assertion_results: vec![],
+                captured_variables: Vec::new(),
             }),
         },
     ];
@@ -333,6 +348,7 @@ fn test_substitute_functions_in_request_with_body() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = substitute_functions_in_request(&mut request);
@@ -380,6 +396,7 @@ fn test_substitute_functions_in_request_with_headers() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = substitute_functions_in_request(&mut request);
@@ -423,6 +440,7 @@ fn test_substitute_functions_and_variables_together() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("Setup Request".to_string()),
@@ -433,6 +451,7 @@ fn test_substitute_functions_and_variables_together() {
             response_headers: Some(response_headers),
             response_body: Some(r#"{"user_id": "12345"}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -454,6 +473,7 @@ fn test_substitute_functions_and_variables_together() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = substitute_request_variables_in_request(&mut request, &context);
@@ -496,6 +516,7 @@ fn test_substitute_request_variables_with_missing_result() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -514,6 +535,7 @@ fn test_substitute_request_variables_with_missing_result() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: None,
     }];
@@ -545,6 +567,7 @@ fn test_substitute_request_variables_in_assertions() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -563,6 +586,7 @@ fn test_substitute_request_variables_in_assertions() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("setup".to_string()),
@@ -573,6 +597,7 @@ fn test_substitute_request_variables_in_assertions() {
             response_headers: None,
             response_body: Some(r#"{"expected_status":"200"}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -598,6 +623,7 @@ fn test_substitute_functions_with_url() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = substitute_functions_in_request(&mut request);
@@ -631,6 +657,7 @@ fn test_substitute_functions_with_multiple_functions() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = substitute_functions_in_request(&mut request);
@@ -663,6 +690,7 @@ fn test_substitute_request_variables_with_header_reference() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -681,6 +709,7 @@ fn test_substitute_request_variables_with_header_reference() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("login".to_string()),
@@ -691,6 +720,7 @@ fn test_substitute_request_variables_with_header_reference() {
             response_headers: Some(response_headers),
             response_body: None,
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -715,6 +745,7 @@ fn test_substitute_request_variables_with_complex_jsonpath() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -733,6 +764,7 @@ fn test_substitute_request_variables_with_complex_jsonpath() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("data".to_string()),
@@ -746,6 +778,7 @@ fn test_substitute_request_variables_with_complex_jsonpath() {
                     .to_string(),
             ),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -792,6 +825,7 @@ fn test_substitute_request_variables_empty_body() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -810,6 +844,7 @@ fn test_substitute_request_variables_empty_body() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("login".to_string()),
@@ -820,6 +855,7 @@ fn test_substitute_request_variables_empty_body() {
             response_headers: None,
             response_body: None,
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -844,6 +880,7 @@ fn test_substitute_request_variables_with_special_chars_in_json() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -862,6 +899,7 @@ fn test_substitute_request_variables_with_special_chars_in_json() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("prev".to_string()),
@@ -872,6 +910,7 @@ fn test_substitute_request_variables_with_special_chars_in_json() {
             response_headers: None,
             response_body: Some(r#"{"text":"Hello, World!"}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -901,6 +940,7 @@ fn test_substitute_functions_in_assertions() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result = substitute_functions_in_request(&mut request);
@@ -932,6 +972,7 @@ fn test_substitute_request_variables_preserve_unmatched_patterns() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![];
@@ -941,3 +982,100 @@ fn test_substitute_request_variables_preserve_unmatched_patterns() {
     // Unknown variables should be preserved
     assert!(request.url.contains("{{unknown.var}}"));
 }
+
+#[test]
+fn test_substitute_request_variables_in_auth() {
+    use crate::types::Auth;
+
+    let mut request = HttpRequest {
+        name: Some("profile".to_string()),
+        method: "GET".to_string(),
+        url: "https://api.example.com/me".to_string(),
+        headers: vec![],
+        body: None,
+        assertions: vec![],
+        variables: vec![],
+        timeout: None,
+        connection_timeout: None,
+        depends_on: None,
+        conditions: vec![],
+        pre_delay_ms: None,
+        post_delay_ms: None,
+        auth: Some(Auth::Bearer {
+            token: "{{login.response.body.$.token}}".to_string(),
+        }),
+        ..Default::default()
+    };
+
+    let context = vec![RequestContext {
+        name: "login".to_string(),
+        request: HttpRequest {
+            name: Some("login".to_string()),
+            method: "POST".to_string(),
+            url: "https://api.example.com/login".to_string(),
+            headers: vec![],
+            body: None,
+            assertions: vec![],
+            variables: vec![],
+            timeout: None,
+            connection_timeout: None,
+            depends_on: None,
+            conditions: vec![],
+            pre_delay_ms: None,
+            post_delay_ms: None,
+            ..Default::default()
+        },
+        result: Some(HttpResult {
+            request_name: Some("login".to_string()),
+            status_code: 200,
+            success: true,
+            error_message: None,
+            duration_ms: 100,
+            response_headers: None,
+            response_body: Some(r#"{"token":"abc123"}"#.to_string()),
+            assertion_results: vec![],
+            captured_variables: Vec::new(),
+        }),
+    }];
+
+    let result = substitute_request_variables_in_request(&mut request, &context);
+
+    assert!(result.is_ok());
+    assert_eq!(
+        request.auth,
+        Some(Auth::Bearer {
+            token: "abc123".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_substitute_auth_tokens_leaves_requests_without_references() {
+    let mut request = HttpRequest {
+        name: None,
+        method: "GET".to_string(),
+        url: "https://api.example.com/{{id}}".to_string(),
+        headers: vec![Header {
+            name: "Authorization".to_string(),
+            value: "Bearer {{token}}".to_string(),
+        }],
+        body: None,
+        assertions: vec![],
+        variables: vec![],
+        timeout: None,
+        connection_timeout: None,
+        depends_on: None,
+        conditions: vec![],
+        pre_delay_ms: None,
+        post_delay_ms: None,
+        ..Default::default()
+    };
+
+    let tokens = crate::oauth::TokenProvider::default();
+    substitute_auth_tokens_in_request(&mut request, &tokens).unwrap();
+    assert_eq!(request.url, "https://api.example.com/{{id}}");
+    assert_eq!(request.headers[0].value, "Bearer {{token}}");
+
+    request.headers[0].value = r#"Bearer {{$auth.token("missing")}}"#.to_string();
+    assert!(substitute_auth_tokens_in_request(&mut request, &tokens).is_err());
+}
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const PROJECT_FILE: &str = "httprunner.json";
+
+/// Settings for every run in a directory tree, read from `httprunner.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ProjectConfig {
+    /// Files run before all others, as if each request were marked `@setup`
+    #[serde(default)]
+    pub setup: Vec<String>,
+    /// Files run after all others, as if each request were marked `@teardown`
+    #[serde(default)]
+    pub teardown: Vec<String>,
+}
+
+impl ProjectConfig {
+    /// Read a project file. The files it lists are resolved against its
+    /// directory.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut config: ProjectConfig = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid project file {}", path.display()))?;
+
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        for file in config.setup.iter_mut().chain(config.teardown.iter_mut()) {
+            *file = resolve(dir, file);
+        }
+        Ok(config)
+    }
+}
+
+/// Find the project file in `dir` or the nearest directory above it.
+pub fn find_project_file(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .find(|path| path.is_file())
+}
+
+/// The project config applying to `dir`, if there is one.
+pub fn load_project_config(dir: &Path) -> Result<Option<ProjectConfig>> {
+    find_project_file(dir)
+        .map(|path| ProjectConfig::load(&path))
+        .transpose()
+}
+
+/// The project config applying to `files`, found from the nearest directory
+/// holding all of them rather than the current directory, so that a run
+/// started elsewhere uses the files' project. Without files, the current
+/// directory's applies.
+pub fn load_project_config_for_files(files: &[String]) -> Result<Option<ProjectConfig>> {
+    load_project_config(&common_dir(files)?)
+}
+
+fn common_dir(files: &[String]) -> Result<PathBuf> {
+    let mut common: Option<PathBuf> = None;
+    for file in files {
+        let path = std::path::absolute(file)
+            .with_context(|| format!("Failed to resolve the directory of {}", file))?;
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or(path);
+        match common.as_mut() {
+            None => common = Some(dir),
+            Some(shared) => {
+                // Files on different Windows drives share no directory.
+                while !dir.starts_with(&*shared) {
+                    if !shared.pop() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    match common {
+        Some(dir) => Ok(dir),
+        None => Ok(std::env::current_dir()?),
+    }
+}
+
+fn resolve(dir: &Path, file: &str) -> String {
+    let path = Path::new(file);
+    if path.is_relative() && dir != Path::new("") {
+        dir.join(path).to_string_lossy().into_owned()
+    } else {
+        file.to_string()
+    }
+}
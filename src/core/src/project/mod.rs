@@ -0,0 +1,9 @@
+mod config;
+
+pub use config::{
+    PROJECT_FILE, ProjectConfig, find_project_file, load_project_config,
+    load_project_config_for_files,
+};
+
+#[cfg(test)]
+mod tests;
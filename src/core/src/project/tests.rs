@@ -0,0 +1,88 @@
+use super::*;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn load_project_config_resolves_files_against_the_project_dir() {
+    let temp = tempdir().unwrap();
+    fs::write(
+        temp.path().join(PROJECT_FILE),
+        r#"{ "setup": ["seed.http"], "teardown": ["cleanup/delete.http"] }"#,
+    )
+    .unwrap();
+    let nested = temp.path().join("api");
+    fs::create_dir(&nested).unwrap();
+
+    let config = load_project_config(&nested).unwrap().unwrap();
+
+    assert_eq!(
+        config.setup,
+        vec![temp.path().join("seed.http").to_string_lossy().into_owned()]
+    );
+    assert_eq!(
+        config.teardown,
+        vec![
+            temp.path()
+                .join("cleanup/delete.http")
+                .to_string_lossy()
+                .into_owned()
+        ]
+    );
+}
+
+#[test]
+fn load_project_config_defaults_missing_lists() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join(PROJECT_FILE), r#"{ "teardown": [] }"#).unwrap();
+
+    let config = load_project_config(temp.path()).unwrap().unwrap();
+
+    assert_eq!(config, ProjectConfig::default());
+}
+
+#[test]
+fn load_project_config_without_a_project_file() {
+    let temp = tempdir().unwrap();
+
+    assert!(find_project_file(temp.path()).is_none());
+    assert!(load_project_config(temp.path()).unwrap().is_none());
+}
+
+#[test]
+fn load_project_config_reports_invalid_json() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join(PROJECT_FILE), r#"{ "setup": "seed.http" }"#).unwrap();
+
+    let error = load_project_config(temp.path()).unwrap_err();
+
+    assert!(format!("{error:#}").contains("Invalid project file"));
+}
+
+#[test]
+fn load_project_config_for_files_uses_the_directory_holding_them() {
+    let temp = tempdir().unwrap();
+    fs::write(
+        temp.path().join(PROJECT_FILE),
+        r#"{ "setup": ["seed.http"] }"#,
+    )
+    .unwrap();
+    for dir in ["api/orders", "api/users", "other"] {
+        fs::create_dir_all(temp.path().join(dir)).unwrap();
+    }
+    // A project file next to one of the files does not apply to the others.
+    fs::write(temp.path().join("api/orders").join(PROJECT_FILE), "{}").unwrap();
+    let files = [
+        temp.path().join("api/orders/list.http"),
+        temp.path().join("api/users/list.http"),
+    ]
+    .map(|file| file.to_string_lossy().into_owned());
+
+    let config = load_project_config_for_files(&files).unwrap().unwrap();
+    assert_eq!(
+        config.setup,
+        vec![temp.path().join("seed.http").to_string_lossy().into_owned()]
+    );
+
+    let config = load_project_config_for_files(&files[..1]).unwrap().unwrap();
+    assert_eq!(config, ProjectConfig::default());
+}
@@ -1,6 +1,9 @@
 use crate::types::{
-    Header, HttpFileResults, HttpRequest, HttpResult, ProcessorResults, RequestContext,
+    Auth, AwsCredentials, Header, HttpFileResults, HttpRequest, HttpResult, ProcessorResults,
+    RequestContext, Signing,
 };
+use base64::Engine;
+use base64::engine::general_purpose;
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -10,10 +13,6 @@ pub fn sanitize_processor_results(
     results: &ProcessorResults,
     include_secrets: bool,
 ) -> ProcessorResults {
-    if include_secrets {
-        return results.clone();
-    }
-
     ProcessorResults {
         success: results.success,
         files: results
@@ -24,9 +23,13 @@ pub fn sanitize_processor_results(
     }
 }
 
+/// Sanitize a request for output. The values resolved for `$secret()` are
+/// redacted even when secrets are included.
 pub fn sanitize_request_for_output(request: &HttpRequest, include_secrets: bool) -> HttpRequest {
     if include_secrets {
-        return request.clone();
+        let mut sanitized = request.clone();
+        redact_request_secrets(&mut sanitized, &request.resolved_secrets);
+        return sanitized;
     }
 
     let mut sanitized = request.clone();
@@ -52,9 +55,37 @@ pub fn sanitize_request_for_output(request: &HttpRequest, include_secrets: bool)
         condition.expected_value = sanitize_text(&condition.expected_value, include_secrets);
     }
 
+    redact_request_secrets(&mut sanitized, &request_secrets(request));
+    sanitized.auth = request.auth.as_ref().map(redact_auth);
+    sanitized.sign = request.sign.as_ref().map(redact_signing);
+
     sanitized
 }
 
+fn redact_request_secrets(request: &mut HttpRequest, secrets: &[String]) {
+    if secrets.is_empty() {
+        return;
+    }
+    request.url = redact_secrets(&request.url, secrets);
+    for header in &mut request.headers {
+        header.value = redact_secrets(&header.value, secrets);
+    }
+    request.body = request
+        .body
+        .take()
+        .map(|body| redact_secrets(&body, secrets));
+    for assertion in &mut request.assertions {
+        assertion.expected_value = redact_secrets(&assertion.expected_value, secrets);
+    }
+    match &mut request.auth {
+        Some(Auth::Basic { password, .. } | Auth::Digest { password, .. }) => {
+            *password = redact_secrets(password, secrets);
+        }
+        Some(Auth::Bearer { token }) => *token = redact_secrets(token, secrets),
+        None => {}
+    }
+}
+
 pub fn sanitize_result_for_output(result: &HttpResult, include_secrets: bool) -> HttpResult {
     if include_secrets {
         return result.clone();
@@ -77,6 +108,48 @@ pub fn sanitize_result_for_output(result: &HttpResult, include_secrets: bool) ->
             .map(|actual| sanitize_text(actual, include_secrets));
     }
 
+    for variable in &mut sanitized.captured_variables {
+        variable.value = if is_sensitive_field_name(&variable.name) {
+            REDACTED_VALUE.to_string()
+        } else {
+            sanitize_text(&variable.value, include_secrets)
+        };
+    }
+
+    sanitized
+}
+
+/// Sanitize a result for output, also redacting the request's `@auth` and
+/// `@sign` credentials wherever the response echoes them back. The values
+/// resolved for `$secret()` are redacted even when secrets are included.
+pub fn sanitize_result_for_request_output(
+    result: &HttpResult,
+    request: &HttpRequest,
+    include_secrets: bool,
+) -> HttpResult {
+    let mut sanitized = sanitize_result_for_output(result, include_secrets);
+    let secrets = if include_secrets {
+        request.resolved_secrets.clone()
+    } else {
+        request_secrets(request)
+    };
+    if !secrets.is_empty() {
+        sanitized.response_body = sanitized
+            .response_body
+            .map(|body| redact_secrets(&body, &secrets));
+        for variable in &mut sanitized.captured_variables {
+            variable.value = redact_secrets(&variable.value, &secrets);
+        }
+        for assertion_result in &mut sanitized.assertion_results {
+            assertion_result.assertion.expected_value =
+                redact_secrets(&assertion_result.assertion.expected_value, &secrets);
+            assertion_result.actual_value = assertion_result
+                .actual_value
+                .as_deref()
+                .map(|actual| redact_secrets(actual, &secrets));
+        }
+    }
+
     sanitized
 }
 
@@ -126,10 +199,91 @@ fn sanitize_request_context(context: &RequestContext, include_secrets: bool) ->
     RequestContext {
         name: context.name.clone(),
         request: sanitize_request_for_output(&context.request, include_secrets),
-        result: context
-            .result
-            .as_ref()
-            .map(|result| sanitize_result_for_output(result, include_secrets)),
+        result: context.result.as_ref().map(|result| {
+            sanitize_result_for_request_output(result, &context.request, include_secrets)
+        }),
+    }
+}
+
+/// The `@auth` and `@sign` secrets as they may appear on the wire, including
+/// the base64-encoded Basic credentials, plus the values read from the
+/// process environment or a `.env` file and those resolved for `$secret()`.
+fn request_secrets(request: &HttpRequest) -> Vec<String> {
+    let mut secrets = request.secrets.clone();
+    secrets.extend(request.resolved_secrets.iter().cloned());
+    if let Some(auth) = &request.auth {
+        secrets.extend(auth.secrets().into_iter().map(str::to_string));
+        if let Auth::Basic { username, password } = auth {
+            secrets.push(general_purpose::STANDARD.encode(format!("{username}:{password}")));
+        }
+    }
+    if let Some(sign) = &request.sign {
+        secrets.extend(sign.secrets().into_iter().map(str::to_string));
+    }
+    secrets.retain(|secret| !secret.is_empty());
+    secrets
+}
+
+fn redact_secrets(value: &str, secrets: &[String]) -> String {
+    secrets.iter().fold(value.to_string(), |value, secret| {
+        value.replace(secret.as_str(), REDACTED_VALUE)
+    })
+}
+
+fn redact_auth(auth: &Auth) -> Auth {
+    match auth {
+        Auth::Basic { username, .. } => Auth::Basic {
+            username: username.clone(),
+            password: REDACTED_VALUE.to_string(),
+        },
+        Auth::Digest { username, .. } => Auth::Digest {
+            username: username.clone(),
+            password: REDACTED_VALUE.to_string(),
+        },
+        Auth::Bearer { .. } => Auth::Bearer {
+            token: REDACTED_VALUE.to_string(),
+        },
+    }
+}
+
+fn redact_signing(sign: &Signing) -> Signing {
+    match sign {
+        Signing::AwsSigV4 {
+            service,
+            region,
+            credentials,
+        } => Signing::AwsSigV4 {
+            service: service.clone(),
+            region: region.clone(),
+            credentials: credentials.as_ref().map(|credentials| AwsCredentials {
+                access_key_id: credentials.access_key_id.clone(),
+                secret_access_key: REDACTED_VALUE.to_string(),
+                session_token: credentials
+                    .session_token
+                    .as_ref()
+                    .map(|_| REDACTED_VALUE.to_string()),
+            }),
+        },
+        Signing::HttpSignature {
+            label,
+            algorithm,
+            key_id,
+            components,
+            ..
+        } => Signing::HttpSignature {
+            label: label.clone(),
+            algorithm: *algorithm,
+            key_id: key_id.clone(),
+            key: REDACTED_VALUE.to_string(),
+            components: components.clone(),
+        },
+        Signing::Hmac {
+            header, encoding, ..
+        } => Signing::Hmac {
+            header: header.clone(),
+            key: REDACTED_VALUE.to_string(),
+            encoding: *encoding,
+        },
     }
 }
 
@@ -262,10 +416,11 @@ fn is_sensitive_header(name: &str) -> bool {
             | "xrefreshtoken"
             | "xcsrftoken"
             | "xxsrftoken"
+            | "xamzsecuritytoken"
     )
 }
 
-fn is_sensitive_field_name(name: &str) -> bool {
+pub(crate) fn is_sensitive_field_name(name: &str) -> bool {
     let normalized = normalize_name(name);
     normalized.contains("token")
         || normalized.contains("secret")
@@ -286,7 +441,9 @@ fn normalize_name(name: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{HttpFileResults, ProcessorResults, RequestContext};
+    use crate::types::{
+        HttpFileResults, ProcessorResults, RequestContext, SignatureEncoding, Variable,
+    };
     use serde_json::Value;
 
     fn sample_request() -> HttpRequest {
@@ -313,6 +470,7 @@ mod tests {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         }
     }
 
@@ -330,6 +488,7 @@ mod tests {
             response_headers: Some(headers),
             response_body: Some(r#"{"password":"secret","status":"ok"}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }
     }
 
@@ -369,6 +528,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sanitize_result_for_output_redacts_captured_secrets() {
+        let mut result = sample_result();
+        result.captured_variables = vec![
+            Variable {
+                name: "token".to_string(),
+                value: "abc".to_string(),
+            },
+            Variable {
+                name: "orderId".to_string(),
+                value: "42".to_string(),
+            },
+        ];
+
+        let sanitized = sanitize_result_for_output(&result, false);
+        assert_eq!(sanitized.captured_variables[0].value, "***REDACTED***");
+        assert_eq!(sanitized.captured_variables[1].value, "42");
+
+        let included = sanitize_result_for_output(&result, true);
+        assert_eq!(included.captured_variables[0].value, "abc");
+    }
+
     #[test]
     fn sanitize_text_redacts_form_encoded_values() {
         assert_eq!(
@@ -377,6 +558,183 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sanitize_request_for_output_redacts_auth_credentials() {
+        let mut request = sample_request();
+        request.url = "https://example.com/users/hunter2-secret".to_string();
+        request.auth = Some(Auth::Basic {
+            username: "admin".to_string(),
+            password: "hunter2-secret".to_string(),
+        });
+
+        let sanitized = sanitize_request_for_output(&request, false);
+
+        assert_eq!(sanitized.url, "https://example.com/users/***REDACTED***");
+        assert_eq!(
+            sanitized.auth,
+            Some(Auth::Basic {
+                username: "admin".to_string(),
+                password: "***REDACTED***".to_string(),
+            })
+        );
+        assert_eq!(
+            sanitize_request_for_output(&request, true).auth,
+            request.auth
+        );
+    }
+
+    #[test]
+    fn sanitize_request_for_output_redacts_signing_credentials() {
+        let mut request = sample_request();
+        request.headers.push(Header {
+            name: "X-Amz-Security-Token".to_string(),
+            value: "session-token".to_string(),
+        });
+        request.body = Some("signed with aws-secret-key".to_string());
+        request.sign = Some(Signing::AwsSigV4 {
+            service: "s3".to_string(),
+            region: "eu-west-1".to_string(),
+            credentials: Some(AwsCredentials {
+                access_key_id: "AKIDEXAMPLE".to_string(),
+                secret_access_key: "aws-secret-key".to_string(),
+                session_token: Some("session-token".to_string()),
+            }),
+        });
+
+        let sanitized = sanitize_request_for_output(&request, false);
+
+        assert_eq!(sanitized.headers[2].value, "***REDACTED***");
+        assert_eq!(
+            sanitized.body.as_deref(),
+            Some("signed with ***REDACTED***")
+        );
+        assert_eq!(
+            sanitized.sign,
+            Some(Signing::AwsSigV4 {
+                service: "s3".to_string(),
+                region: "eu-west-1".to_string(),
+                credentials: Some(AwsCredentials {
+                    access_key_id: "AKIDEXAMPLE".to_string(),
+                    secret_access_key: "***REDACTED***".to_string(),
+                    session_token: Some("***REDACTED***".to_string()),
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn sanitize_request_for_output_redacts_signing_keys() {
+        let mut request = sample_request();
+        request.sign = Some(Signing::Hmac {
+            header: "X-Signature".to_string(),
+            key: "hmac-signing-key".to_string(),
+            encoding: SignatureEncoding::Hex,
+        });
+        request.body = Some("key=hmac-signing-key".to_string());
+
+        let sanitized = sanitize_request_for_output(&request, false);
+
+        assert_eq!(sanitized.body.as_deref(), Some("key=***REDACTED***"));
+        assert_eq!(
+            sanitized.sign,
+            Some(Signing::Hmac {
+                header: "X-Signature".to_string(),
+                key: "***REDACTED***".to_string(),
+                encoding: SignatureEncoding::Hex,
+            })
+        );
+    }
+
+    #[test]
+    fn sanitize_request_for_output_redacts_environment_values() {
+        let mut request = sample_request();
+        request.url = "https://example.com/orders?key=env-api-key".to_string();
+        request.headers[1].value = "env-api-key".to_string();
+        request.secrets = vec!["env-api-key".to_string()];
+        let mut result = sample_result();
+        result.response_body = Some("echo env-api-key".to_string());
+
+        let sanitized = sanitize_request_for_output(&request, false);
+        let sanitized_result = sanitize_result_for_request_output(&result, &request, false);
+
+        assert_eq!(sanitized.url, "https://example.com/orders?key=***REDACTED***");
+        assert_eq!(sanitized.headers[1].value, "***REDACTED***");
+        assert_eq!(
+            sanitized_result.response_body.as_deref(),
+            Some("echo ***REDACTED***")
+        );
+        assert_eq!(
+            sanitize_request_for_output(&request, true).headers[1].value,
+            "env-api-key"
+        );
+    }
+
+    #[test]
+    fn resolved_secrets_are_redacted_even_when_secrets_are_included() {
+        let mut request = sample_request();
+        request.url = "https://api.example.com/?q=resolved-secret-value".to_string();
+        request.resolved_secrets = vec!["resolved-secret-value".to_string()];
+        let mut result = sample_result();
+        result.response_body = Some("echo: resolved-secret-value".to_string());
+
+        for include_secrets in [false, true] {
+            let sanitized = sanitize_request_for_output(&request, include_secrets);
+            assert_eq!(sanitized.url, "https://api.example.com/?q=***REDACTED***");
+
+            let sanitized = sanitize_result_for_request_output(&result, &request, include_secrets);
+            assert_eq!(
+                sanitized.response_body.as_deref(),
+                Some("echo: ***REDACTED***")
+            );
+        }
+
+        assert_eq!(
+            sanitize_request_for_output(&request, true).headers[0].value,
+            "Bearer secret-token"
+        );
+        assert_eq!(
+            sanitize_text("resolved-secret-value", false),
+            "resolved-secret-value"
+        );
+    }
+
+    #[test]
+    fn sanitize_processor_results_redacts_echoed_auth_credentials() {
+        let mut request = sample_request();
+        request.auth = Some(Auth::Basic {
+            username: "admin".to_string(),
+            password: "hunter2-secret".to_string(),
+        });
+        let mut result = sample_result();
+        result.response_body = Some("Basic YWRtaW46aHVudGVyMi1zZWNyZXQ= was sent".to_string());
+
+        let results = ProcessorResults {
+            success: true,
+            files: vec![HttpFileResults {
+                filename: "test.http".to_string(),
+                success_count: 1,
+                failed_count: 0,
+                skipped_count: 0,
+                result_contexts: vec![RequestContext {
+                    name: "login".to_string(),
+                    request,
+                    result: Some(result),
+                }],
+            }],
+        };
+
+        let sanitized = sanitize_processor_results(&results, false);
+        assert_eq!(
+            sanitized.files[0].result_contexts[0]
+                .result
+                .as_ref()
+                .unwrap()
+                .response_body
+                .as_deref(),
+            Some("Basic ***REDACTED*** was sent")
+        );
+    }
+
     #[test]
     fn sanitize_processor_results_preserves_values_when_opted_in() {
         let results = ProcessorResults {
@@ -97,9 +97,20 @@ fn append_file_results(html: &mut String, results: &ProcessorResults) {
             file_results.success_count, file_results.failed_count, file_results.skipped_count
         ));
 
-        for context in &file_results.result_contexts {
+        // `@setup` and `@teardown` requests are listed after the others.
+        let (hooks, requests): (Vec<_>, Vec<_>) = file_results
+            .result_contexts
+            .iter()
+            .partition(|context| context.request.hook.is_some());
+        for context in requests {
             append_request_section(html, context);
         }
+        if !hooks.is_empty() {
+            html.push_str("            <h3>Setup and Teardown</h3>\n");
+            for context in hooks {
+                append_request_section(html, context);
+            }
+        }
 
         html.push_str("        </section>\n");
     }
@@ -107,9 +118,13 @@ fn append_file_results(html: &mut String, results: &ProcessorResults) {
 
 fn append_request_section(html: &mut String, context: &crate::types::RequestContext) {
     html.push_str("            <div class=\"request-card\">\n");
+    let title = match context.request.hook {
+        Some(hook) => format!("{}: {}", hook, context.name),
+        None => context.name.clone(),
+    };
     html.push_str(&format!(
         "                <h3>{}</h3>\n",
-        escape_html(&context.name)
+        escape_html(&title)
     ));
 
     append_request_details(html, context);
@@ -236,6 +251,7 @@ fn append_response_details(html: &mut String, context: &crate::types::RequestCon
 
         append_response_headers(html, &result.response_headers);
         append_response_body(html, &result.response_body);
+        append_captured_variables(html, &result.captured_variables);
         append_assertions(html, &result.assertion_results);
     } else {
         html.push_str("                    <p class=\"skipped\">⏭️ Request was skipped</p>\n");
@@ -269,6 +285,26 @@ fn append_response_headers(
     }
 }
 
+fn append_captured_variables(html: &mut String, variables: &[crate::types::Variable]) {
+    if !variables.is_empty() {
+        html.push_str("                    <h5>Captured Variables</h5>\n");
+        html.push_str("                    <table class=\"data-table\">\n");
+        html.push_str(
+            "                        <thead><tr><th>Variable</th><th>Value</th></tr></thead>\n",
+        );
+        html.push_str("                        <tbody>\n");
+        for variable in variables {
+            html.push_str(&format!(
+                "                            <tr><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&variable.name),
+                escape_html(&variable.value)
+            ));
+        }
+        html.push_str("                        </tbody>\n");
+        html.push_str("                    </table>\n");
+    }
+}
+
 fn append_response_body(html: &mut String, body: &Option<String>) {
     if let Some(body) = body {
         html.push_str("                    <h5>Response Body</h5>\n");
@@ -1,7 +1,7 @@
 use super::*;
 use crate::types::{
     Assertion, AssertionResult, AssertionType, Condition, ConditionType, Header, HttpFileResults,
-    HttpRequest, HttpResult, ProcessorResults, RequestContext,
+    HttpRequest, HttpResult, ProcessorResults, RequestContext, RequestHook,
 };
 use std::collections::HashMap;
 use std::fs;
@@ -21,6 +21,7 @@ fn sample_request(name: &str, method: &str, url: &str) -> HttpRequest {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     }
 }
 
@@ -34,6 +35,7 @@ fn sample_result(status: u16, success: bool, duration: u64) -> HttpResult {
         response_headers: None,
         response_body: None,
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     }
 }
 
@@ -574,6 +576,46 @@ fn generate_html_includes_timeouts() {
     fs::remove_file(filename).ok();
 }
 
+#[test]
+fn generate_html_lists_setup_and_teardown_separately() {
+    let context = |name: &str, hook: Option<RequestHook>| {
+        let mut request = sample_request(name, "POST", "https://example.com");
+        request.hook = hook;
+        RequestContext {
+            name: name.to_string(),
+            request,
+            result: Some(sample_result(200, true, 100)),
+        }
+    };
+
+    let file_results = HttpFileResults {
+        filename: "test.http".to_string(),
+        success_count: 1,
+        failed_count: 0,
+        skipped_count: 0,
+        result_contexts: vec![
+            context("create_user", Some(RequestHook::Setup)),
+            context("get_user", None),
+            context("delete_user", Some(RequestHook::Teardown)),
+        ],
+    };
+
+    let results = ProcessorResults {
+        success: true,
+        files: vec![file_results],
+    };
+
+    let filename = generate_html(&results).unwrap();
+    let content = fs::read_to_string(&filename).unwrap();
+
+    let setup_section = content.find("<h3>Setup and Teardown</h3>").unwrap();
+    assert!(content.find("<h3>get_user</h3>").unwrap() < setup_section);
+    assert!(content.find("<h3>Setup: create_user</h3>").unwrap() > setup_section);
+    assert!(content.find("<h3>Teardown: delete_user</h3>").unwrap() > setup_section);
+
+    fs::remove_file(filename).ok();
+}
+
 #[test]
 fn generate_html_includes_dependencies() {
     let mut request = sample_request("dependent", "POST", "https://example.com");
@@ -60,15 +60,31 @@ fn append_file_results(report: &mut String, results: &ProcessorResults) {
             file_results.success_count, file_results.failed_count, file_results.skipped_count
         ));
 
-        for context in &file_results.result_contexts {
+        // `@setup` and `@teardown` requests are listed after the others.
+        let (hooks, requests): (Vec<_>, Vec<_>) = file_results
+            .result_contexts
+            .iter()
+            .partition(|context| context.request.hook.is_some());
+        for context in requests {
             append_request_section(report, context);
         }
+        if !hooks.is_empty() {
+            report.push_str("### Setup and Teardown\n\n");
+            for context in hooks {
+                append_request_section(report, context);
+            }
+        }
     }
 }
 
 fn append_request_section(report: &mut String, context: &crate::types::RequestContext) {
+    let kind = match context.request.hook {
+        Some(hook) => hook.to_string(),
+        None => "Request".to_string(),
+    };
     report.push_str(&format!(
-        "### Request: {}\n\n",
+        "### {}: {}\n\n",
+        kind,
         escape_markdown(&context.name)
     ));
 
@@ -162,6 +178,7 @@ fn append_response_details(report: &mut String, context: &crate::types::RequestC
 
         append_response_headers(report, &result.response_headers);
         append_response_body(report, &result.response_body);
+        append_captured_variables(report, &result.captured_variables);
         append_assertions(report, &result.assertion_results);
     } else {
         report.push_str("#### Response Details\n\n");
@@ -199,6 +216,22 @@ fn append_response_body(report: &mut String, body: &Option<String>) {
     }
 }
 
+fn append_captured_variables(report: &mut String, variables: &[crate::types::Variable]) {
+    if !variables.is_empty() {
+        report.push_str("**Captured Variables:**\n\n");
+        report.push_str("| Variable | Value |\n");
+        report.push_str("|----------|-------|\n");
+        for variable in variables {
+            report.push_str(&format!(
+                "| {} | {} |\n",
+                escape_markdown(&variable.name),
+                escape_markdown(&variable.value)
+            ));
+        }
+        report.push('\n');
+    }
+}
+
 fn append_assertions(report: &mut String, assertions: &[crate::types::AssertionResult]) {
     if !assertions.is_empty() {
         report.push_str("#### Assertion Results\n\n");
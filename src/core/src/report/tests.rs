@@ -2,7 +2,7 @@ use super::formatter::*;
 use super::*;
 use crate::types::{
     Assertion, AssertionResult, AssertionType, Condition, ConditionType, Header, HttpFileResults,
-    HttpRequest, HttpResult, ProcessorResults, RequestContext,
+    HttpRequest, HttpResult, ProcessorResults, RequestContext, RequestHook, Variable,
 };
 use std::collections::HashMap;
 use std::fs;
@@ -29,6 +29,7 @@ fn sample_request(name: &str, method: &str, url: &str) -> HttpRequest {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     }
 }
 
@@ -42,6 +43,7 @@ fn sample_result(status: u16, success: bool, duration: u64) -> HttpResult {
         response_headers: None,
         response_body: None,
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     }
 }
 
@@ -312,6 +314,46 @@ fn generate_markdown_includes_response_details() {
     fs::remove_file(filename).ok();
 }
 
+#[test]
+fn generate_markdown_lists_captured_variables_redacted() {
+    let mut result = sample_result(200, true, 10);
+    result.captured_variables = vec![
+        Variable {
+            name: "token".to_string(),
+            value: "abc".to_string(),
+        },
+        Variable {
+            name: "orderId".to_string(),
+            value: "42".to_string(),
+        },
+    ];
+
+    let results = ProcessorResults {
+        success: true,
+        files: vec![HttpFileResults {
+            filename: "test.http".to_string(),
+            success_count: 1,
+            failed_count: 0,
+            skipped_count: 0,
+            result_contexts: vec![RequestContext {
+                name: "login".to_string(),
+                request: sample_request("login", "POST", "https://api.example.com/login"),
+                result: Some(result),
+            }],
+        }],
+    };
+
+    let filename = generate_markdown(&results).unwrap();
+    let content = fs::read_to_string(&filename).unwrap();
+
+    assert!(content.contains("**Captured Variables:**"));
+    assert!(content.contains("| token | ***REDACTED*** |"));
+    assert!(content.contains("| orderId | 42 |"));
+    assert!(!content.contains("| token | abc |"));
+
+    fs::remove_file(filename).ok();
+}
+
 #[test]
 fn generate_markdown_includes_assertions() {
     let assertion = Assertion {
@@ -474,6 +516,46 @@ fn generate_markdown_includes_timeouts() {
     fs::remove_file(filename).ok();
 }
 
+#[test]
+fn generate_markdown_lists_setup_and_teardown_separately() {
+    let context = |name: &str, hook: Option<RequestHook>| {
+        let mut request = sample_request(name, "POST", "https://example.com");
+        request.hook = hook;
+        RequestContext {
+            name: name.to_string(),
+            request,
+            result: Some(sample_result(200, true, 100)),
+        }
+    };
+
+    let file_results = HttpFileResults {
+        filename: "test.http".to_string(),
+        success_count: 1,
+        failed_count: 0,
+        skipped_count: 0,
+        result_contexts: vec![
+            context("create_user", Some(RequestHook::Setup)),
+            context("get_user", None),
+            context("delete_user", Some(RequestHook::Teardown)),
+        ],
+    };
+
+    let results = ProcessorResults {
+        success: true,
+        files: vec![file_results],
+    };
+
+    let filename = generate_markdown(&results).unwrap();
+    let content = fs::read_to_string(&filename).unwrap();
+
+    let setup_section = content.find("### Setup and Teardown").unwrap();
+    assert!(content.find("### Request: get_user").unwrap() < setup_section);
+    assert!(content.find("### Setup: create_user").unwrap() > setup_section);
+    assert!(content.find("### Teardown: delete_user").unwrap() > setup_section);
+
+    fs::remove_file(filename).ok();
+}
+
 #[test]
 fn generate_markdown_includes_dependencies() {
     let mut request = sample_request("dependent", "POST", "https://example.com");
@@ -1,11 +1,17 @@
+use crate::captures::CapturedVariables;
 use crate::functions;
-use crate::types::{HttpRequest, RequestContext};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::oauth::{self, TokenProvider};
+use crate::parser::{find_placeholders, substitute_variables};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::secrets::{self, SecretProvider};
+use crate::types::{Auth, HttpRequest, RequestContext};
 use crate::variables;
-use anyhow::Result;
+use anyhow::{Result, bail};
 
-fn apply_substitution<F>(request: &mut HttpRequest, substitutor: F) -> Result<()>
+fn apply_substitution<F>(request: &mut HttpRequest, mut substitutor: F) -> Result<()>
 where
-    F: Fn(&str) -> Result<String>,
+    F: FnMut(&str) -> Result<String>,
 {
     request.url = substitutor(&request.url)?;
 
@@ -22,6 +28,15 @@ where
         assertion.expected_value = substitutor(&assertion.expected_value)?;
     }
 
+    match &mut request.auth {
+        Some(Auth::Basic { username, password } | Auth::Digest { username, password }) => {
+            *username = substitutor(username)?;
+            *password = substitutor(password)?;
+        }
+        Some(Auth::Bearer { token }) => *token = substitutor(token)?,
+        None => {}
+    }
+
     Ok(())
 }
 
@@ -34,6 +49,140 @@ pub(crate) fn substitute_request_variables_in_request(
     })
 }
 
+/// Substitute the `{{name}}` placeholders of variables stored by `@capture`
+/// directives of earlier requests. The values of those with secret-looking
+/// names are added to the request's secrets, so that output redacts them.
+pub(crate) fn substitute_captured_variables_in_request(
+    request: &mut HttpRequest,
+    captures: &CapturedVariables,
+) -> Result<()> {
+    if captures.is_empty() {
+        return Ok(());
+    }
+    let mut referenced = Vec::new();
+    apply_substitution(request, |value| {
+        referenced.extend(find_placeholders(value).into_iter().map(str::to_string));
+        Ok(substitute_variables(value, captures.variables()))
+    })?;
+    request
+        .secrets
+        .extend(captures.secret_values(&referenced).map(str::to_string));
+    Ok(())
+}
+
+/// Write the values a dry run has no response for as `<pending: …>`: request
+/// variables, `{{$auth.token("name")}}` and the `@capture` variables named in
+/// `captured`.
+pub(crate) fn mark_pending_values_in_request(
+    request: &mut HttpRequest,
+    captured: &[String],
+) -> Result<()> {
+    apply_substitution(request, |value| {
+        Ok(mark_pending(value, |reference| {
+            variables::looks_like_request_variable(reference)
+                || reference.starts_with("$auth.")
+                || captured.iter().any(|name| name == reference)
+        }))
+    })
+}
+
+fn mark_pending(input: &str, is_pending: impl Fn(&str) -> bool) -> String {
+    let mut result = String::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+        let reference = after_open[..end].trim();
+        if is_pending(reference) {
+            result.push_str(&rest[..start]);
+            result.push_str(&format!("<pending: {reference}>"));
+        } else {
+            result.push_str(&rest[..start + 2 + end + 2]);
+        }
+        rest = &after_open[end + 2..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
 pub(crate) fn substitute_functions_in_request(request: &mut HttpRequest) -> Result<()> {
     apply_substitution(request, functions::substitute_functions)
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn substitute_auth_tokens_in_request(
+    request: &mut HttpRequest,
+    tokens: &TokenProvider,
+) -> Result<()> {
+    apply_substitution(request, |value| {
+        oauth::substitute_auth_tokens(value, tokens)
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn substitute_secrets_in_request(
+    request: &mut HttpRequest,
+    provider: &SecretProvider,
+) -> Result<()> {
+    let mut resolved = Vec::new();
+    let substituted = apply_substitution(request, |value| {
+        secrets::substitute_secrets(value, provider, &mut resolved)
+    });
+    request.resolved_secrets.extend(resolved);
+    substituted
+}
+
+/// Write `{{$secret("name")}}` as `<secret: name>`, so that a dry run shows
+/// which secret a request uses without running the secret command.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn mark_secrets_in_request(request: &mut HttpRequest) -> Result<()> {
+    apply_substitution(request, |value| Ok(secrets::mark_secrets(value)))
+}
+
+/// In strict mode, fail when a `{{name}}` placeholder is left after every
+/// substitution instead of sending it as written.
+pub(crate) fn check_undefined_variables(request: &HttpRequest) -> Result<()> {
+    if !request.strict_variables {
+        return Ok(());
+    }
+
+    let mut values = vec![request.url.as_str()];
+    for header in &request.headers {
+        values.push(&header.name);
+        values.push(&header.value);
+    }
+    values.extend(request.body.as_deref());
+    match &request.auth {
+        Some(Auth::Basic { username, password } | Auth::Digest { username, password }) => {
+            values.push(username);
+            values.push(password);
+        }
+        Some(Auth::Bearer { token }) => values.push(token),
+        None => {}
+    }
+
+    let mut undefined: Vec<String> = Vec::new();
+    for name in values.into_iter().flat_map(find_placeholders) {
+        let description = match request
+            .unresolved_variables
+            .iter()
+            .find(|variable| variable.name == name)
+        {
+            Some(variable) => format!("'{}' at line {}", name, variable.line),
+            None => format!("'{}'", name),
+        };
+        if !undefined.contains(&description) {
+            undefined.push(description);
+        }
+    }
+
+    match undefined.as_slice() {
+        [] => Ok(()),
+        [variable] => bail!("undefined variable {}", variable),
+        variables => bail!("undefined variables {}", variables.join(", ")),
+    }
+}
@@ -0,0 +1,428 @@
+use super::http_builders::resolve_body;
+use crate::types::{Auth, HttpRequest};
+use anyhow::{Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose;
+use md5::Md5;
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Resolve the headers to send, applying `@auth` and the IntelliJ-style
+/// `Authorization: Basic <user> <password>` shorthand.
+///
+/// Digest credentials are not sent up front: the executors answer the server's
+/// challenge with [`digest_authorization`] instead. Shared by the blocking and
+/// async executors so authentication lives in one place.
+pub(super) fn resolve_headers(request: &HttpRequest) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = request
+        .headers
+        .iter()
+        .filter_map(|header| {
+            if !header.name.eq_ignore_ascii_case("authorization") {
+                return Some((header.name.clone(), header.value.clone()));
+            }
+            // `@auth` replaces any Authorization header written by hand.
+            if request.auth.is_some() {
+                return None;
+            }
+            match parse_authorization_shorthand(&header.value) {
+                Some(Auth::Basic { username, password }) => Some((
+                    header.name.clone(),
+                    basic_authorization(&username, &password),
+                )),
+                Some(_) => None,
+                None => Some((header.name.clone(), header.value.clone())),
+            }
+        })
+        .collect();
+
+    match &request.auth {
+        Some(Auth::Basic { username, password }) => headers.push((
+            "Authorization".to_string(),
+            basic_authorization(username, password),
+        )),
+        Some(Auth::Bearer { token }) => {
+            headers.push(("Authorization".to_string(), format!("Bearer {token}")))
+        }
+        Some(Auth::Digest { .. }) | None => {}
+    }
+
+    headers
+}
+
+/// The username and password to answer a digest challenge with, from
+/// `@auth digest` or an `Authorization: Digest <user> <password>` header.
+fn digest_credentials(request: &HttpRequest) -> Option<(String, String)> {
+    if let Some(auth) = &request.auth {
+        return match auth {
+            Auth::Digest { username, password } => Some((username.clone(), password.clone())),
+            _ => None,
+        };
+    }
+
+    request
+        .headers
+        .iter()
+        .filter(|header| header.name.eq_ignore_ascii_case("authorization"))
+        .find_map(
+            |header| match parse_authorization_shorthand(&header.value) {
+                Some(Auth::Digest { username, password }) => Some((username, password)),
+                _ => None,
+            },
+        )
+}
+
+/// When a response is a `401` carrying a digest challenge and the request has
+/// digest credentials, returns the `Authorization` header to retry with.
+pub(super) fn digest_retry_authorization(
+    request: &HttpRequest,
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> Result<Option<String>> {
+    if status != reqwest::StatusCode::UNAUTHORIZED {
+        return Ok(None);
+    }
+    let Some((username, password)) = digest_credentials(request) else {
+        return Ok(None);
+    };
+    let challenges = headers
+        .get_all(reqwest::header::WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|value| value.to_str().ok());
+    let Some(challenge) = find_digest_challenge(challenges) else {
+        return Ok(None);
+    };
+
+    let body = resolve_body(request);
+    digest_authorization(
+        challenge,
+        (&username, &password),
+        &request.method,
+        &request.url,
+        body.as_deref(),
+    )
+    .map(Some)
+}
+
+/// Returns the parameters of the first digest challenge among the
+/// `WWW-Authenticate` header values.
+fn find_digest_challenge<'a>(values: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    values.into_iter().find_map(|value| {
+        let value = value.trim_start();
+        let (scheme, params) = value.split_once(char::is_whitespace)?;
+        scheme.eq_ignore_ascii_case("digest").then_some(params)
+    })
+}
+
+/// Build the `Authorization` header that answers an RFC 7616 digest challenge.
+fn digest_authorization(
+    challenge: &str,
+    (username, password): (&str, &str),
+    method: &str,
+    url: &str,
+    body: Option<&str>,
+) -> Result<String> {
+    use rand::RngExt;
+    let mut bytes = [0u8; 8];
+    rand::rng().fill(&mut bytes);
+    digest_authorization_with_cnonce(
+        challenge,
+        (username, password),
+        method,
+        url,
+        body,
+        &hex::encode(bytes),
+    )
+}
+
+fn digest_authorization_with_cnonce(
+    challenge: &str,
+    (username, password): (&str, &str),
+    method: &str,
+    url: &str,
+    body: Option<&str>,
+    cnonce: &str,
+) -> Result<String> {
+    let params = parse_challenge_params(challenge);
+    let realm = params.get("realm").map(String::as_str).unwrap_or_default();
+    let Some(nonce) = params.get("nonce") else {
+        bail!("Digest challenge did not contain a nonce");
+    };
+
+    let algorithm = params
+        .get("algorithm")
+        .map(|algorithm| algorithm.to_ascii_uppercase())
+        .unwrap_or_else(|| "MD5".to_string());
+    let hash: fn(&str) -> String = match algorithm.trim_end_matches("-SESS") {
+        "MD5" => |input| hex::encode(Md5::digest(input.as_bytes())),
+        "SHA-256" => |input| hex::encode(Sha256::digest(input.as_bytes())),
+        _ => bail!("Unsupported digest algorithm '{algorithm}'"),
+    };
+
+    let qop_options: Vec<&str> = params
+        .get("qop")
+        .map(|qop| qop.split(',').map(str::trim).collect())
+        .unwrap_or_default();
+    let qop = ["auth", "auth-int"]
+        .into_iter()
+        .find(|qop| qop_options.contains(qop));
+
+    let url = Url::parse(url)?;
+    let uri = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let nc = "00000001";
+
+    let mut ha1 = hash(&format!("{username}:{realm}:{password}"));
+    if algorithm.ends_with("-SESS") {
+        ha1 = hash(&format!("{ha1}:{nonce}:{cnonce}"));
+    }
+    let ha2 = match qop {
+        Some("auth-int") => hash(&format!(
+            "{}:{uri}:{}",
+            method.to_uppercase(),
+            hash(body.unwrap_or_default())
+        )),
+        _ => hash(&format!("{}:{uri}", method.to_uppercase())),
+    };
+    let response = match qop {
+        Some(qop) => hash(&format!("{ha1}:{nonce}:{nc}:{cnonce}:{qop}:{ha2}")),
+        None => hash(&format!("{ha1}:{nonce}:{ha2}")),
+    };
+
+    let mut authorization = format!(
+        r#"Digest username="{username}", realm="{realm}", nonce="{nonce}", uri="{uri}", algorithm={algorithm}, response="{response}""#
+    );
+    if let Some(qop) = qop {
+        authorization.push_str(&format!(r#", qop={qop}, nc={nc}, cnonce="{cnonce}""#));
+    }
+    if let Some(opaque) = params.get("opaque") {
+        authorization.push_str(&format!(r#", opaque="{opaque}""#));
+    }
+    Ok(authorization)
+}
+
+fn basic_authorization(username: &str, password: &str) -> String {
+    let credentials = general_purpose::STANDARD.encode(format!("{username}:{password}"));
+    format!("Basic {credentials}")
+}
+
+/// IntelliJ-style credentials written directly in an `Authorization` header:
+/// `Basic <user> <password>`, `Basic <user>:<password>` or
+/// `Digest <user> <password>`. Returns `None` for encoded or computed values,
+/// which are sent unchanged.
+fn parse_authorization_shorthand(value: &str) -> Option<Auth> {
+    let (scheme, credentials) = value.trim().split_once(char::is_whitespace)?;
+    let credentials = credentials.trim();
+
+    if scheme.eq_ignore_ascii_case("basic") {
+        let (username, password) = credentials
+            .split_once(char::is_whitespace)
+            .or_else(|| credentials.split_once(':'))?;
+        return Some(Auth::Basic {
+            username: username.to_string(),
+            password: password.trim().to_string(),
+        });
+    }
+
+    if scheme.eq_ignore_ascii_case("digest") && !credentials.contains('=') {
+        let (username, password) = credentials.split_once(char::is_whitespace)?;
+        return Some(Auth::Digest {
+            username: username.to_string(),
+            password: password.trim().to_string(),
+        });
+    }
+
+    None
+}
+
+fn parse_challenge_params(challenge: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    let mut rest = challenge.trim();
+
+    while let Some((key, value)) = rest.split_once('=') {
+        let key = key
+            .trim()
+            .trim_start_matches(',')
+            .trim()
+            .to_ascii_lowercase();
+        let value = value.trim_start();
+
+        let (parsed, remaining) = if let Some(quoted) = value.strip_prefix('"') {
+            let mut parsed = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+            while let Some((index, character)) = chars.next() {
+                match character {
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            parsed.push(escaped);
+                        }
+                    }
+                    '"' => {
+                        end = index + 1;
+                        break;
+                    }
+                    _ => parsed.push(character),
+                }
+            }
+            (parsed, &quoted[end..])
+        } else {
+            let end = value.find(',').unwrap_or(value.len());
+            (value[..end].trim().to_string(), &value[end..])
+        };
+
+        params.insert(key, parsed);
+        rest = remaining.trim_start().trim_start_matches(',');
+    }
+
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn create_test_request(headers: &[(&str, &str)], auth: Option<Auth>) -> HttpRequest {
+        HttpRequest {
+            headers: test_support::headers(headers),
+            auth,
+            ..test_support::request("GET", "https://example.com/dir/index.html")
+        }
+    }
+
+    #[test]
+    fn test_resolve_headers_encodes_basic_shorthand() {
+        let request = create_test_request(&[("Authorization", "Basic admin s3cret")], None);
+        assert_eq!(
+            resolve_headers(&request),
+            vec![(
+                "Authorization".to_string(),
+                "Basic YWRtaW46czNjcmV0".to_string()
+            )]
+        );
+
+        let request = create_test_request(&[("authorization", "Basic admin:s3cret")], None);
+        assert_eq!(resolve_headers(&request)[0].1, "Basic YWRtaW46czNjcmV0");
+    }
+
+    #[test]
+    fn test_resolve_headers_keeps_encoded_credentials() {
+        let request = create_test_request(
+            &[
+                ("Authorization", "Basic YWRtaW46czNjcmV0"),
+                ("Accept", "application/json"),
+            ],
+            None,
+        );
+        assert_eq!(resolve_headers(&request)[0].1, "Basic YWRtaW46czNjcmV0");
+        assert_eq!(resolve_headers(&request)[1].1, "application/json");
+    }
+
+    #[test]
+    fn test_resolve_headers_applies_auth_directive() {
+        let request = create_test_request(
+            &[("Authorization", "Bearer stale")],
+            Some(Auth::Bearer {
+                token: "fresh-token".to_string(),
+            }),
+        );
+        assert_eq!(
+            resolve_headers(&request),
+            vec![(
+                "Authorization".to_string(),
+                "Bearer fresh-token".to_string()
+            )]
+        );
+
+        let request = create_test_request(
+            &[],
+            Some(Auth::Basic {
+                username: "admin".to_string(),
+                password: "s3cret".to_string(),
+            }),
+        );
+        assert_eq!(resolve_headers(&request)[0].1, "Basic YWRtaW46czNjcmV0");
+    }
+
+    #[test]
+    fn test_digest_credentials_are_not_sent_up_front() {
+        let request =
+            create_test_request(&[("Authorization", "Digest Mufasa Circle Of Life")], None);
+        assert!(resolve_headers(&request).is_empty());
+        assert_eq!(
+            digest_credentials(&request),
+            Some(("Mufasa".to_string(), "Circle Of Life".to_string()))
+        );
+
+        let computed =
+            create_test_request(&[("Authorization", r#"Digest username="Mufasa""#)], None);
+        assert_eq!(resolve_headers(&computed).len(), 1);
+        assert!(digest_credentials(&computed).is_none());
+    }
+
+    #[test]
+    fn test_find_digest_challenge() {
+        let values = ["Basic realm=\"api\"", "Digest realm=\"api\", nonce=\"abc\""];
+        assert_eq!(
+            find_digest_challenge(values),
+            Some("realm=\"api\", nonce=\"abc\"")
+        );
+        assert!(find_digest_challenge(["Bearer realm=\"api\""]).is_none());
+    }
+
+    #[test]
+    fn test_digest_authorization_matches_rfc_2617_example() {
+        let challenge = r#"realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#;
+        let authorization = digest_authorization_with_cnonce(
+            challenge,
+            ("Mufasa", "Circle Of Life"),
+            "GET",
+            "http://www.nowhere.org/dir/index.html",
+            None,
+            "0a4f113b",
+        )
+        .unwrap();
+
+        assert!(authorization.contains(r#"response="6629fae49393a05397450978507c4ef1""#));
+        assert!(authorization.contains(r#"uri="/dir/index.html""#));
+        assert!(authorization.contains("qop=auth, nc=00000001"));
+        assert!(authorization.contains(r#"opaque="5ccc069c403ebaf9f0171e9517f40e41""#));
+    }
+
+    #[test]
+    fn test_digest_authorization_supports_sha256() {
+        let challenge = r#"realm="http-auth@example.org", qop="auth", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#;
+        let authorization = digest_authorization_with_cnonce(
+            challenge,
+            ("Mufasa", "Circle of Life"),
+            "GET",
+            "https://example.org/dir/index.html",
+            None,
+            "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+        )
+        .unwrap();
+
+        // Expected response from RFC 7616, section 3.9.1.
+        assert!(authorization.contains(
+            r#"response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1""#
+        ));
+    }
+
+    #[test]
+    fn test_digest_authorization_rejects_unknown_algorithms() {
+        let error = digest_authorization_with_cnonce(
+            r#"realm="api", nonce="abc", algorithm=SHA-512-256"#,
+            ("user", "pass"),
+            "GET",
+            "https://example.com/",
+            None,
+            "cnonce",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("Unsupported digest algorithm"));
+    }
+}
@@ -1,4 +1,6 @@
 #[cfg(not(target_arch = "wasm32"))]
+use super::auth::{digest_retry_authorization, resolve_headers};
+#[cfg(not(target_arch = "wasm32"))]
 use super::http_builders::{ClientConfig, parse_method, resolve_body};
 #[cfg(not(target_arch = "wasm32"))]
 use super::response_processor::{
@@ -6,6 +8,8 @@ use super::response_processor::{
     should_capture_response,
 };
 #[cfg(not(target_arch = "wasm32"))]
+use super::signing::sign_headers;
+#[cfg(not(target_arch = "wasm32"))]
 use crate::telemetry::{ConnectionErrorCategory, track_connection_error};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::types::{HttpRequest, HttpResult};
@@ -91,19 +95,22 @@ pub fn execute_http_request(
 
     let start_time = Instant::now();
 
-    let response = match req_builder.send() {
+    let mut response = match req_builder.send() {
         Ok(resp) => resp,
-        Err(e) => {
-            let duration_ms = start_time.elapsed().as_millis() as u64;
-            let (error_message, error_category) = classify_request_error(&e);
-
-            // Track connection error telemetry
-            track_connection_error(error_category, insecure);
-
-            return Ok(build_error_result(request, error_message, duration_ms));
-        }
+        Err(e) => return Ok(request_error_result(request, &e, insecure, start_time)),
     };
 
+    // Answer a digest challenge once, then report whatever the server says.
+    if let Some(authorization) =
+        digest_retry_authorization(request, response.status(), response.headers())?
+    {
+        let req_builder = build_request(&client, request)?.header("Authorization", authorization);
+        response = match req_builder.send() {
+            Ok(resp) => resp,
+            Err(e) => return Ok(request_error_result(request, &e, insecure, start_time)),
+        };
+    }
+
     let status_code = response.status().as_u16();
     let success = response.status().is_success();
 
@@ -122,6 +129,22 @@ pub fn execute_http_request(
     ))
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn request_error_result(
+    request: &HttpRequest,
+    error: &reqwest::Error,
+    insecure: bool,
+    start_time: Instant,
+) -> HttpResult {
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+    let (error_message, error_category) = classify_request_error(error);
+
+    // Track connection error telemetry
+    track_connection_error(error_category, insecure);
+
+    build_error_result(request, error_message, duration_ms)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn build_client(request: &HttpRequest, insecure: bool) -> Result<Client> {
     let config = ClientConfig::from_request(request, insecure);
@@ -178,11 +201,15 @@ fn build_request(
 ) -> Result<reqwest::blocking::RequestBuilder> {
     let mut req_builder = client.request(parse_method(request)?, &request.url);
 
-    for header in &request.headers {
-        req_builder = req_builder.header(&header.name, &header.value);
+    let body = resolve_body(request);
+    let mut headers = resolve_headers(request);
+    sign_headers(request, &mut headers, body.as_deref())?;
+
+    for (name, value) in headers {
+        req_builder = req_builder.header(name, value);
     }
 
-    if let Some(body) = resolve_body(request) {
+    if let Some(body) = body {
         req_builder = req_builder.body(body);
     }
 
@@ -256,6 +283,7 @@ mod tests {
             conditions: Vec::new(),
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         }
     }
 
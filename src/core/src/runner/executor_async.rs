@@ -1,8 +1,10 @@
+use super::auth::{digest_retry_authorization, resolve_headers};
 use super::http_builders::{parse_method, resolve_body};
 use super::response_processor::{
     build_error_result, build_success_result, extract_headers,
     should_capture_response,
 };
+use super::signing::sign_headers;
 use crate::types::{HttpRequest, HttpResult};
 use anyhow::Result;
 use reqwest::Client;
@@ -24,15 +26,23 @@ pub async fn execute_http_request_async(
 
     let start_time = Instant::now();
 
-    let response = match req_builder.send().await {
+    let mut response = match req_builder.send().await {
         Ok(resp) => resp,
-        Err(e) => {
-            let duration_ms = start_time.elapsed().as_millis() as u64;
-            let error_message = format!("Request failed: {}", e);
-            return Ok(build_error_result(request, &error_message, duration_ms));
-        }
+        Err(e) => return Ok(request_error_result(request, &e, start_time)),
     };
 
+    // Answer a digest challenge once, then report whatever the server says.
+    if let Some(authorization) =
+        digest_retry_authorization(request, response.status(), response.headers())?
+    {
+        let req_builder =
+            build_request_async(&client, request)?.header("Authorization", authorization);
+        response = match req_builder.send().await {
+            Ok(resp) => resp,
+            Err(e) => return Ok(request_error_result(request, &e, start_time)),
+        };
+    }
+
     let status_code = response.status().as_u16();
     let success = response.status().is_success();
 
@@ -52,6 +62,16 @@ pub async fn execute_http_request_async(
     ))
 }
 
+fn request_error_result(
+    request: &HttpRequest,
+    error: &reqwest::Error,
+    start_time: Instant,
+) -> HttpResult {
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+    let error_message = format!("Request failed: {}", error);
+    build_error_result(request, &error_message, duration_ms)
+}
+
 fn build_client_async(request: &HttpRequest, insecure: bool) -> Result<Client> {
     static CLIENT_CACHE: OnceLock<Client> = OnceLock::new();
 
@@ -92,11 +112,15 @@ fn build_client_async(request: &HttpRequest, insecure: bool) -> Result<Client> {
 fn build_request_async(client: &Client, request: &HttpRequest) -> Result<reqwest::RequestBuilder> {
     let mut req_builder = client.request(parse_method(request)?, &request.url);
 
-    for header in &request.headers {
-        req_builder = req_builder.header(&header.name, &header.value);
+    let body = resolve_body(request);
+    let mut headers = resolve_headers(request);
+    sign_headers(request, &mut headers, body.as_deref())?;
+
+    for (name, value) in headers {
+        req_builder = req_builder.header(name, value);
     }
 
-    if let Some(body) = resolve_body(request) {
+    if let Some(body) = body {
         req_builder = req_builder.body(body);
     }
 
@@ -142,6 +166,7 @@ mod tests {
             conditions: Vec::new(),
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         }
     }
 
@@ -61,6 +61,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
             HttpRequest {
                 name: Some("profile".to_string()),
@@ -79,6 +80,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
         ];
 
@@ -127,6 +129,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
             HttpRequest {
                 name: Some("profile".to_string()),
@@ -142,6 +145,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
         ];
 
@@ -188,6 +192,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
             HttpRequest {
                 name: Some("conditional".to_string()),
@@ -208,6 +213,7 @@ mod tests {
                 }],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
         ];
 
@@ -265,6 +271,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             }],
             false,
             0,
@@ -303,6 +310,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             }],
             false,
             0,
@@ -344,6 +352,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             }],
             false,
             0,
@@ -373,6 +382,7 @@ mod tests {
             response_headers: Some(HashMap::new()),
             response_body: body,
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }
     }
 
@@ -408,6 +418,7 @@ mod tests {
                 response_headers: None,
                 response_body: None,
                 assertion_results: vec![],
+                captured_variables: Vec::new(),
             })
         })
     }
@@ -460,6 +471,7 @@ mod tests {
                 response_headers: Some(HashMap::new()),
                 response_body: Some("OK".to_string()),
                 assertion_results: vec![],
+                captured_variables: Vec::new(),
             })
         })
     }
@@ -1,7 +1,10 @@
+mod auth;
 mod executor;
 mod http_builders;
 mod incremental_async;
+mod message_signing;
 mod response_processor;
+mod signing;
 mod url_encoding;
 
 pub use url_encoding::{encode_form_body, needs_form_encoding};
@@ -15,6 +15,7 @@ pub fn build_error_result(
         response_headers: None,
         response_body: None,
         assertion_results: Vec::new(),
+        captured_variables: Vec::new(),
     }
 }
 
@@ -29,7 +30,11 @@ pub fn extract_headers(headers: &reqwest::header::HeaderMap) -> HashMap<String,
 }
 
 pub fn should_capture_response(request: &HttpRequest, verbose: bool) -> bool {
-    verbose || !request.assertions.is_empty() || request.name.is_some()
+    verbose
+        || !request.assertions.is_empty()
+        || request.name.is_some()
+        || request.paginate.is_some()
+        || !request.captures.is_empty()
 }
 
 pub fn build_success_result(
@@ -50,6 +55,7 @@ pub fn build_success_result(
         response_headers,
         response_body,
         assertion_results,
+        captured_variables: Vec::new(),
     }
 }
 
@@ -73,6 +79,7 @@ mod tests {
             conditions: Vec::new(),
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         }
     }
 
@@ -124,6 +131,31 @@ mod tests {
         assert!(should_capture_response(&request, false));
     }
 
+    #[test]
+    fn test_should_capture_response_when_paginating() {
+        let mut request = create_test_request();
+        request.name = None;
+        request.paginate = Some(crate::types::Pagination {
+            next: crate::types::PaginationNext::Link,
+            max_pages: crate::types::DEFAULT_MAX_PAGES,
+            items: None,
+            assert_scope: crate::types::PaginationAssertScope::Page,
+        });
+        assert!(should_capture_response(&request, false));
+    }
+
+    #[test]
+    fn test_should_capture_response_with_captures() {
+        let mut request = create_test_request();
+        request.name = None;
+        request.assertions.clear();
+        request.captures.push(crate::types::Capture {
+            name: "etag".to_string(),
+            source: crate::types::CaptureSource::Header("ETag".to_string()),
+        });
+        assert!(should_capture_response(&request, false));
+    }
+
     #[test]
     fn test_should_not_capture_response() {
         let mut request = create_test_request();
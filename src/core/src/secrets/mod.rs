@@ -0,0 +1,8 @@
+mod provider;
+mod substitution;
+
+pub use provider::{SECRET_COMMAND_ENV_VAR, SecretProvider};
+pub use substitution::{mark_secrets, substitute_secrets};
+
+#[cfg(test)]
+mod tests;
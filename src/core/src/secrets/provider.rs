@@ -0,0 +1,97 @@
+use anyhow::{Context, Result, anyhow, bail};
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::Mutex;
+
+/// Environment variable holding the secret command when none is configured.
+pub const SECRET_COMMAND_ENV_VAR: &str = "HTTPRUNNER_SECRET_COMMAND";
+
+/// Resolves `{{$secret("name")}}` references by running a local command such
+/// as `pass show {name}`.
+///
+/// The command is split into arguments like a shell would, but is not run by
+/// one. `{name}` in an argument is replaced with the secret's name; without
+/// it, the name is passed as the last argument. A provider is created for
+/// each run, which resolves each secret once and shares it between the
+/// requests and files of the run.
+#[derive(Debug, Default)]
+pub struct SecretProvider {
+    command: Option<String>,
+    /// Secrets resolved so far, by name.
+    cache: Mutex<HashMap<String, String>>,
+}
+
+impl SecretProvider {
+    pub fn new(command: Option<String>) -> Self {
+        Self {
+            command,
+            cache: Mutex::default(),
+        }
+    }
+
+    /// The provider for the command in `HTTPRUNNER_SECRET_COMMAND`, if set.
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var(SECRET_COMMAND_ENV_VAR)
+                .ok()
+                .filter(|command| !command.trim().is_empty()),
+        )
+    }
+
+    pub fn secret(&self, name: &str) -> Result<String> {
+        let command = self.command.as_deref().ok_or_else(|| {
+            anyhow!(
+                "No secret command to resolve '{}': pass --secret-command or set {}",
+                name,
+                SECRET_COMMAND_ENV_VAR
+            )
+        })?;
+
+        let mut cache = self.cache.lock().expect("secret cache mutex poisoned");
+        if let Some(secret) = cache.get(name) {
+            return Ok(secret.clone());
+        }
+
+        let secret = run_command(command, name)
+            .with_context(|| format!("Failed to resolve secret '{}'", name))?;
+        cache.insert(name.to_string(), secret.clone());
+        Ok(secret)
+    }
+}
+
+fn run_command(command: &str, name: &str) -> Result<String> {
+    let mut args = shell_words::split(command)
+        .map_err(|_| anyhow!("secret command has an unterminated quote: {}", command))?;
+    if !args.iter().any(|arg| arg.contains("{name}")) {
+        args.push(name.to_string());
+    }
+    let args: Vec<String> = args.iter().map(|arg| arg.replace("{name}", name)).collect();
+    let Some((program, args)) = args.split_first() else {
+        bail!("secret command is empty");
+    };
+
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("failed to run secret command '{}'", program))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "secret command '{}' exited with {}{}",
+            program,
+            output.status,
+            match stderr.trim() {
+                "" => String::new(),
+                stderr => format!(": {}", stderr),
+            }
+        );
+    }
+
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| anyhow!("secret command '{}' printed invalid UTF-8", program))?;
+    let secret = stdout.trim_end_matches(['\r', '\n']);
+    if secret.is_empty() {
+        bail!("secret command '{}' printed nothing", program);
+    }
+    Ok(secret.to_string())
+}
@@ -0,0 +1,59 @@
+use super::SecretProvider;
+use anyhow::Result;
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+static SECRET_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// Replace `{{$secret("name")}}` with secrets from `provider`, adding each
+/// secret used to `resolved` so that output can redact it.
+pub fn substitute_secrets(
+    input: &str,
+    provider: &SecretProvider,
+    resolved: &mut Vec<String>,
+) -> Result<String> {
+    if !input.contains("$secret") {
+        return Ok(input.to_string());
+    }
+
+    let mut error = None;
+    let substituted = secret_regex().replace_all(input, |captures: &Captures| {
+        match provider.secret(&captures[1]) {
+            Ok(secret) => {
+                if !resolved.contains(&secret) {
+                    resolved.push(secret.clone());
+                }
+                secret
+            }
+            Err(err) => {
+                error.get_or_insert(err);
+                String::new()
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(substituted.into_owned()),
+    }
+}
+
+/// Write `{{$secret("name")}}` as `<secret: name>`, without resolving it,
+/// for showing a request before it is sent.
+pub fn mark_secrets(input: &str) -> String {
+    if !input.contains("$secret") {
+        return input.to_string();
+    }
+
+    secret_regex()
+        .replace_all(input, |captures: &Captures| {
+            format!("<secret: {}>", &captures[1])
+        })
+        .into_owned()
+}
+
+fn secret_regex() -> &'static Regex {
+    SECRET_REGEX.get_or_init(|| {
+        Regex::new(r#"\{\{\s*\$secret\(\s*"([^"]+)"\s*\)\s*\}\}"#).expect("valid $secret regex")
+    })
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+#[test]
+fn secret_without_command_fails_clearly() {
+    let error = SecretProvider::new(None).secret("api/key").unwrap_err();
+    assert!(error.to_string().contains("No secret command"));
+    assert!(error.to_string().contains(SECRET_COMMAND_ENV_VAR));
+}
+
+#[test]
+fn substitute_secrets_leaves_other_text_alone() {
+    let provider = SecretProvider::default();
+    let mut resolved = Vec::new();
+    assert_eq!(
+        substitute_secrets("Bearer {{token}}", &provider, &mut resolved).unwrap(),
+        "Bearer {{token}}"
+    );
+    assert!(resolved.is_empty());
+}
+
+#[cfg(unix)]
+mod command {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+    use tempfile::{TempDir, tempdir};
+
+    /// A fake `pass`: prints the secret for `api/key`, fails for any other
+    /// name, and records every call in `calls` next to itself.
+    fn fake_provider() -> (TempDir, PathBuf) {
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("fake-pass");
+        fs::write(
+            &script,
+            r#"#!/bin/sh
+echo "$*" >> "$(dirname "$0")/calls"
+if [ "$2" = "api/key" ]; then
+  echo "s3cret-from-fake-pass"
+else
+  echo "Error: $2 is not in the password store." >&2
+  exit 1
+fi
+"#,
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        (dir, script)
+    }
+
+    fn calls(dir: &Path) -> Vec<String> {
+        fs::read_to_string(dir.join("calls"))
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn secrets_are_resolved_once_per_provider() {
+        let (dir, script) = fake_provider();
+        let command = format!("'{}' show {{name}}", script.display());
+        let provider = SecretProvider::new(Some(command.clone()));
+
+        let header = r#"Bearer {{$secret("api/key")}}"#;
+        let mut resolved = Vec::new();
+        for _ in 0..2 {
+            assert_eq!(
+                substitute_secrets(header, &provider, &mut resolved).unwrap(),
+                "Bearer s3cret-from-fake-pass"
+            );
+        }
+        assert_eq!(resolved, vec!["s3cret-from-fake-pass"]);
+        assert_eq!(calls(dir.path()), vec!["show api/key"]);
+
+        SecretProvider::new(Some(command))
+            .secret("api/key")
+            .unwrap();
+        assert_eq!(calls(dir.path()), vec!["show api/key", "show api/key"]);
+    }
+
+    #[test]
+    fn name_is_appended_without_placeholder() {
+        let (dir, script) = fake_provider();
+        let provider = SecretProvider::new(Some(format!("{} show", script.display())));
+
+        assert_eq!(provider.secret("api/key").unwrap(), "s3cret-from-fake-pass");
+        assert_eq!(calls(dir.path()), vec!["show api/key"]);
+    }
+
+    #[test]
+    fn failing_command_reports_its_error() {
+        let (_dir, script) = fake_provider();
+        let provider = SecretProvider::new(Some(format!("{} show {{name}}", script.display())));
+
+        let error = substitute_secrets(r#"{{$secret("missing")}}"#, &provider, &mut Vec::new())
+            .unwrap_err();
+        let message = format!("{:#}", error);
+        assert!(message.contains("Failed to resolve secret 'missing'"));
+        assert!(message.contains("missing is not in the password store"));
+    }
+}
@@ -0,0 +1,177 @@
+use super::glob::glob_match;
+use crate::parser::{find_placeholders, import_alias};
+use crate::types::{Auth, HttpRequest};
+use crate::variables::referenced_request_names;
+
+/// Selects the requests to run by tag and name, as given with `--tag`,
+/// `--exclude-tag` and `--name`. Patterns may use `*` and `?` wildcards.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestFilter {
+    /// A request must have a tag matching one of these, unless empty
+    pub tags: Vec<String>,
+    /// A request must have no tag matching one of these
+    pub exclude_tags: Vec<String>,
+    /// A request's name must match one of these, unless empty
+    pub names: Vec<String>,
+}
+
+impl RequestFilter {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.exclude_tags.is_empty() && self.names.is_empty()
+    }
+
+    /// Whether `request` is selected itself, regardless of the requests that
+    /// depend on it.
+    pub fn matches(&self, request: &HttpRequest) -> bool {
+        let matches_any =
+            |patterns: &[String], value: &str| patterns.iter().any(|p| glob_match(p, value));
+
+        (self.tags.is_empty() || request.tags.iter().any(|tag| matches_any(&self.tags, tag)))
+            && !request
+                .tags
+                .iter()
+                .any(|tag| matches_any(&self.exclude_tags, tag))
+            && (self.names.is_empty()
+                || request
+                    .name
+                    .as_deref()
+                    .is_some_and(|name| matches_any(&self.names, name)))
+    }
+
+    /// The requests of a file to run, in file order: the selected ones, the
+    /// requests they use the results of, including earlier requests that
+    /// `@capture` a variable they use, and the file's `@setup` and
+    /// `@teardown` requests. Empty when no request of the file is selected.
+    pub fn select(&self, requests: Vec<HttpRequest>) -> Vec<HttpRequest> {
+        if self.is_empty() {
+            return requests;
+        }
+
+        let mut selected: Vec<bool> = requests
+            .iter()
+            .map(|request| request.hook.is_none() && self.matches(request))
+            .collect();
+        if !selected.contains(&true) {
+            return Vec::new();
+        }
+        for (index, request) in requests.iter().enumerate() {
+            selected[index] |= request.hook.is_some();
+        }
+
+        let mut pending: Vec<usize> = (0..requests.len()).filter(|&i| selected[i]).collect();
+        while let Some(index) = pending.pop() {
+            for name in dependencies(&requests[index]) {
+                for (other, request) in requests.iter().enumerate() {
+                    if !selected[other] && request.name.as_deref() == Some(name.as_str()) {
+                        selected[other] = true;
+                        pending.push(other);
+                    }
+                }
+            }
+            let variables = referenced_variables(&requests[index]);
+            for (other, request) in requests[..index].iter().enumerate() {
+                if !selected[other]
+                    && request
+                        .captures
+                        .iter()
+                        .any(|capture| variables.contains(&capture.name.as_str()))
+                {
+                    selected[other] = true;
+                    pending.push(other);
+                }
+            }
+        }
+
+        requests
+            .into_iter()
+            .zip(selected)
+            .filter_map(|(request, selected)| selected.then_some(request))
+            .collect()
+    }
+}
+
+/// The distinct tags of `requests`, sorted, for offering as filters.
+pub fn tags_of(requests: &[HttpRequest]) -> Vec<String> {
+    let mut tags: Vec<String> = requests
+        .iter()
+        .flat_map(|request| request.tags.iter().cloned())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// The names of the requests of the file imported as `alias` whose results
+/// `requests` use, as `<alias>.<request>` or `@dependsOn <file>#<request>`.
+pub(crate) fn imported_request_names(requests: &[HttpRequest], alias: &str) -> Vec<String> {
+    let mut names: Vec<String> = requests
+        .iter()
+        .flat_map(dependencies)
+        .filter_map(|name| match name.split_once('#') {
+            Some((path, request)) => {
+                (import_alias(path) == Some(alias)).then(|| request.to_string())
+            }
+            None => name
+                .strip_prefix(alias)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .map(str::to_string),
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// The names of the requests whose results `request` uses, through
+/// `@dependsOn`, `@if`, `@if-not`, `@foreach` or request variables.
+fn dependencies(request: &HttpRequest) -> Vec<String> {
+    let mut names: Vec<String> = request.depends_on.iter().cloned().collect();
+    names.extend(
+        request
+            .conditions
+            .iter()
+            .map(|condition| condition.request_name.clone()),
+    );
+    names.extend(
+        inputs(request)
+            .into_iter()
+            .flat_map(referenced_request_names),
+    );
+    names
+}
+
+/// The `{{name}}` placeholders of `request`, which may be variables stored
+/// by an earlier request's `@capture`.
+fn referenced_variables(request: &HttpRequest) -> Vec<&str> {
+    inputs(request)
+        .into_iter()
+        .flat_map(find_placeholders)
+        .collect()
+}
+
+/// The texts of `request` that may reference other requests or variables.
+fn inputs(request: &HttpRequest) -> Vec<&str> {
+    let mut inputs = vec![request.url.as_str()];
+    inputs.extend(request.headers.iter().map(|header| header.value.as_str()));
+    inputs.extend(request.body.as_deref());
+    inputs.extend(
+        request
+            .assertions
+            .iter()
+            .map(|assertion| assertion.expected_value.as_str()),
+    );
+    inputs.extend(
+        request
+            .foreach
+            .as_ref()
+            .map(|foreach| foreach.source.as_str()),
+    );
+    match &request.auth {
+        Some(Auth::Basic { username, password } | Auth::Digest { username, password }) => {
+            inputs.extend([username.as_str(), password.as_str()]);
+        }
+        Some(Auth::Bearer { token }) => inputs.push(token),
+        None => {}
+    }
+    inputs
+}
@@ -0,0 +1,9 @@
+mod filter;
+mod glob;
+
+pub(crate) use filter::imported_request_names;
+pub use filter::{RequestFilter, tags_of};
+pub use glob::glob_match;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,190 @@
+use super::*;
+use crate::parser::parse_http_content;
+use crate::types::HttpRequest;
+
+const FILE: &str = r#"# @name login
+POST https://api.example.com/login
+
+###
+# @name list_orders
+# @tag smoke, orders
+GET https://api.example.com/orders
+Authorization: Bearer {{login.response.body.$.token}}
+
+###
+# @name create_order
+# @tag orders, slow
+POST https://api.example.com/orders
+
+###
+# @name get_order
+# @tag orders
+# @dependsOn create_order
+GET https://api.example.com/orders/1
+
+###
+# @name health
+GET https://api.example.com/health
+"#;
+
+fn names(requests: &[HttpRequest]) -> Vec<&str> {
+    requests
+        .iter()
+        .map(|request| request.name.as_deref().unwrap_or_default())
+        .collect()
+}
+
+fn select(filter: RequestFilter) -> Vec<HttpRequest> {
+    filter.select(parse_http_content(FILE, None).unwrap())
+}
+
+#[test]
+fn glob_match_supports_wildcards() {
+    assert!(glob_match("create_order", "create_order"));
+    assert!(glob_match("create_*", "create_order"));
+    assert!(glob_match("*order*", "list_orders"));
+    assert!(glob_match("get_?rder", "get_order"));
+    assert!(glob_match("*", ""));
+    assert!(!glob_match("create_*", "get_order"));
+    assert!(!glob_match("get_?", "get_order"));
+}
+
+#[test]
+fn empty_filter_selects_every_request() {
+    let filter = RequestFilter::default();
+
+    assert!(filter.is_empty());
+    assert_eq!(select(filter).len(), 5);
+}
+
+#[test]
+fn tag_selects_requests_and_their_variable_dependencies() {
+    let filter = RequestFilter {
+        tags: vec!["smoke".to_string()],
+        ..Default::default()
+    };
+
+    assert_eq!(names(&select(filter)), vec!["login", "list_orders"]);
+}
+
+#[test]
+fn exclude_tag_keeps_requests_that_selected_ones_depend_on() {
+    let filter = RequestFilter {
+        tags: vec!["orders".to_string()],
+        exclude_tags: vec!["slow".to_string()],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        names(&select(filter)),
+        vec!["login", "list_orders", "create_order", "get_order"]
+    );
+}
+
+#[test]
+fn tag_selects_earlier_requests_capturing_the_variables_used() {
+    let content = r#"# @capture token = $.token
+POST https://api.example.com/login
+
+###
+# @name create_order
+# @capture orderId = $.id
+POST https://api.example.com/orders
+
+###
+# @name list_orders
+# @tag smoke
+GET https://api.example.com/orders
+Authorization: Bearer {{token}}
+
+###
+# @capture token = $.refreshed
+POST https://api.example.com/refresh
+"#;
+    let filter = RequestFilter {
+        tags: vec!["smoke".to_string()],
+        ..Default::default()
+    };
+
+    let selected = filter.select(parse_http_content(content, None).unwrap());
+
+    let urls: Vec<&str> = selected
+        .iter()
+        .map(|request| request.url.as_str())
+        .collect();
+    assert_eq!(
+        urls,
+        vec![
+            "https://api.example.com/login",
+            "https://api.example.com/orders"
+        ]
+    );
+    assert_eq!(selected[1].name.as_deref(), Some("list_orders"));
+}
+
+#[test]
+fn tag_selects_requests_whose_auth_uses_earlier_results() {
+    let content = "# @name login\nPOST https://api.example.com/login\n\n###\n# @name profile\n# @auth bearer {{login.response.body.$.token}}\nGET https://api.example.com/profile\n\n###\n# @capture password = $.password\n# @name reset\nPOST https://api.example.com/reset\n\n###\n# @name admin\n# @tag smoke\n# @auth basic admin {{password}}\nGET https://api.example.com/admin\n";
+    let requests = parse_http_content(content, None).unwrap();
+
+    let profile = RequestFilter {
+        names: vec!["profile".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&profile.select(requests.clone())),
+        vec!["login", "profile"]
+    );
+
+    let smoke = RequestFilter {
+        tags: vec!["smoke".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(names(&smoke.select(requests)), vec!["reset", "admin"]);
+}
+
+#[test]
+fn imported_request_names_lists_the_requests_used_from_an_import() {
+    let content = "# @import ./auth.http\n\n# @name orders\n# @dependsOn auth.http#login\nGET https://api.example.com/orders\nX-Tenant: {{auth.tenant.response.body.$.id}}\n\n###\n# @name health\nGET https://api.example.com/health?v={{other.ping.response.status}}\n";
+    let requests = parse_http_content(content, None).unwrap();
+
+    assert_eq!(
+        imported_request_names(&requests, "auth"),
+        vec!["login", "tenant"]
+    );
+    assert_eq!(imported_request_names(&requests, "other"), vec!["ping"]);
+}
+
+#[test]
+fn name_patterns_select_requests() {
+    let filter = RequestFilter {
+        names: vec!["health".to_string(), "create_*".to_string()],
+        ..Default::default()
+    };
+
+    assert_eq!(names(&select(filter)), vec!["create_order", "health"]);
+}
+
+#[test]
+fn nothing_selected_skips_setup_and_teardown_requests() {
+    let content = "# @setup\nPOST https://api.example.com/seed\n\n###\n# @name health\nGET https://api.example.com/health\n\n###\n# @teardown\nDELETE https://api.example.com/seed\n";
+    let requests = parse_http_content(content, None).unwrap();
+
+    let none = RequestFilter {
+        tags: vec!["smoke".to_string()],
+        ..Default::default()
+    };
+    assert!(none.select(requests.clone()).is_empty());
+
+    let health = RequestFilter {
+        names: vec!["health".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(health.select(requests).len(), 3);
+}
+
+#[test]
+fn tags_of_lists_distinct_tags_sorted() {
+    let requests = parse_http_content(FILE, None).unwrap();
+    assert_eq!(tags_of(&requests), vec!["orders", "slow", "smoke"]);
+}
@@ -1,4 +1,8 @@
-use crate::types::{Assertion, AssertionType, Condition, HttpRequest};
+use crate::types::{
+    Assertion, AssertionType, Auth, Capture, CaptureSource, Condition, ContentDigest,
+    DEFAULT_MAX_PAGES, DataScope, HttpRequest, Pagination, PaginationAssertScope, PaginationNext,
+    RequestHook, Run, SignatureAlgorithm, SignatureEncoding, Signing,
+};
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
@@ -12,6 +16,14 @@ pub fn serialize_http_request(request: &HttpRequest) -> String {
         output.push_str(&format!("# @name {}\n", name));
     }
 
+    if let Some(hook) = request.hook {
+        output.push_str(&format!("# @{}\n", format_hook(hook)));
+    }
+
+    if !request.tags.is_empty() {
+        output.push_str(&format!("# @tag {}\n", request.tags.join(", ")));
+    }
+
     if let Some(timeout) = request.timeout {
         output.push_str(&format!("# @timeout {}ms\n", timeout));
     }
@@ -41,6 +53,42 @@ pub fn serialize_http_request(request: &HttpRequest) -> String {
         output.push_str(&format!("# @post-delay {}\n", post_delay_ms));
     }
 
+    if let Some(data) = &request.data
+        && data.scope == DataScope::Request
+    {
+        output.push_str(&format!("# @data {}\n", data.path));
+    }
+
+    if let Some(foreach) = &request.foreach {
+        output.push_str(&format!(
+            "# @foreach {} in {}\n",
+            foreach.variable, foreach.source
+        ));
+    }
+
+    if let Some(pagination) = &request.paginate {
+        output.push_str(&format!("# @paginate {}\n", format_pagination(pagination)));
+    }
+
+    if let Some(auth) = &request.auth {
+        output.push_str(&format!("# @auth {}\n", format_auth(auth)));
+    }
+
+    if let Some(sign) = &request.sign {
+        output.push_str(&format!("# @sign {}\n", format_signing(sign)));
+    }
+
+    if let Some(digest) = request.content_digest {
+        output.push_str(&format!(
+            "# @content-digest {}\n",
+            format_content_digest(digest)
+        ));
+    }
+
+    for capture in &request.captures {
+        output.push_str(&format!("# @capture {}\n", format_capture(capture)));
+    }
+
     output.push_str(&format!("{} {}\n", request.method, request.url));
 
     for header in &request.headers {
@@ -63,11 +111,191 @@ pub fn serialize_http_request(request: &HttpRequest) -> String {
 }
 
 pub fn serialize_http_requests(requests: &[HttpRequest]) -> String {
-    requests
+    // The requests of a `run` line are written back as the line itself.
+    let serialized = requests
         .iter()
-        .map(serialize_http_request)
+        .enumerate()
+        .filter_map(|(index, request)| match &request.run {
+            None => Some(serialize_http_request(request)),
+            Some(run) if index == 0 || requests[index - 1].run.as_ref() != Some(run) => {
+                let hook = request
+                    .hook
+                    .map(|hook| format!("# @{}\n", format_hook(hook)))
+                    .unwrap_or_default();
+                Some(format!("###\n{}{}\n", hook, format_run(run)))
+            }
+            Some(_) => None,
+        })
         .collect::<Vec<_>>()
-        .join("\n")
+        .join("\n");
+
+    let file_data = requests
+        .first()
+        .and_then(|request| request.data.as_ref())
+        .filter(|data| data.scope == DataScope::File);
+
+    let serialized = match file_data {
+        Some(data) => format!("# @data {}\n\n{}", data.path, serialized),
+        None => serialized,
+    };
+
+    let serialized = match serialize_imports(requests) {
+        imports if imports.is_empty() => serialized,
+        imports => format!("{}\n{}", imports, serialized),
+    };
+
+    if requests.iter().any(|request| request.strict_variables) {
+        format!("# @strict-variables\n{}", serialized)
+    } else {
+        serialized
+    }
+}
+
+/// The file's `@import` directives, except those implied by a
+/// `@dependsOn <file>#<request>` of one of its requests.
+fn serialize_imports(requests: &[HttpRequest]) -> String {
+    let Some(first) = requests.iter().find(|request| request.run.is_none()) else {
+        return String::new();
+    };
+    first
+        .imports
+        .iter()
+        .filter(|import| {
+            !requests.iter().any(|request| {
+                request
+                    .depends_on
+                    .as_deref()
+                    .and_then(|depends_on| depends_on.split_once('#'))
+                    .is_some_and(|(path, _)| path.trim() == import.path)
+            })
+        })
+        .map(|import| format!("# @import {}\n", import.path))
+        .collect()
+}
+
+fn format_hook(hook: RequestHook) -> &'static str {
+    match hook {
+        RequestHook::Setup => "setup",
+        RequestHook::Teardown => "teardown",
+    }
+}
+
+fn format_run(run: &Run) -> String {
+    let mut line = format!(
+        "run {}{}",
+        run.path.as_deref().unwrap_or_default(),
+        run.request
+            .as_ref()
+            .map(|request| format!("#{}", request))
+            .unwrap_or_default()
+    );
+    if !run.variables.is_empty() {
+        let variables = run
+            .variables
+            .iter()
+            .map(|variable| format!("@{}={}", variable.name, variable.value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        line.push_str(&format!(" ({})", variables));
+    }
+    line
+}
+
+fn format_auth(auth: &Auth) -> String {
+    match auth {
+        Auth::Basic { username, password } => format!("basic {} {}", username, password),
+        Auth::Digest { username, password } => format!("digest {} {}", username, password),
+        Auth::Bearer { token } => format!("bearer {}", token),
+    }
+}
+
+// AWS credentials are read from the environment, so only the scope is written.
+fn format_signing(sign: &Signing) -> String {
+    match sign {
+        Signing::AwsSigV4 {
+            service, region, ..
+        } => format!("aws-sigv4 service={} region={}", service, region),
+        Signing::HttpSignature {
+            label,
+            algorithm,
+            key_id,
+            key,
+            components,
+        } => {
+            let algorithm = match algorithm {
+                SignatureAlgorithm::HmacSha256 => "hmac-sha256",
+                SignatureAlgorithm::Ed25519 => "ed25519",
+            };
+            let mut settings = vec![
+                "http-signature".to_string(),
+                format!("alg={}", algorithm),
+                format!("key={}", key),
+            ];
+            if let Some(key_id) = key_id {
+                settings.push(format!("key-id={}", key_id));
+            }
+            if label != "sig1" {
+                settings.push(format!("label={}", label));
+            }
+            if !components.is_empty() {
+                settings.push(format!("components={}", components.join(",")));
+            }
+            settings.join(" ")
+        }
+        Signing::Hmac {
+            header,
+            key,
+            encoding,
+        } => {
+            let encoding = match encoding {
+                SignatureEncoding::Hex => "hex",
+                SignatureEncoding::Base64 => "base64",
+            };
+            format!(
+                "hmac-sha256 key={} header={} encoding={}",
+                key, header, encoding
+            )
+        }
+    }
+}
+
+fn format_content_digest(digest: ContentDigest) -> &'static str {
+    match digest {
+        ContentDigest::Sha256 => "sha-256",
+        ContentDigest::Sha512 => "sha-512",
+    }
+}
+
+fn format_capture(capture: &Capture) -> String {
+    let source = match &capture.source {
+        CaptureSource::BodyPath(path) => path.clone(),
+        CaptureSource::Header(name) => format!("header {}", name),
+        CaptureSource::Body => "body".to_string(),
+        CaptureSource::Status => "status".to_string(),
+    };
+    format!("{} = {}", capture.name, source)
+}
+
+fn format_pagination(pagination: &Pagination) -> String {
+    let mut settings = vec![match &pagination.next {
+        PaginationNext::Link => "link".to_string(),
+        PaginationNext::Url(path) => format!("next={}", path),
+        PaginationNext::Cursor { path, param } => format!("cursor={} param={}", path, param),
+    }];
+
+    if pagination.max_pages != DEFAULT_MAX_PAGES {
+        settings.push(format!("max={}", pagination.max_pages));
+    }
+
+    if let Some(items) = &pagination.items {
+        settings.push(format!("items={}", items));
+    }
+
+    if pagination.assert_scope == PaginationAssertScope::Aggregate {
+        settings.push("assert=aggregate".to_string());
+    }
+
+    settings.join(" ")
 }
 
 pub fn write_http_file(path: &Path, requests: &[HttpRequest]) -> Result<()> {
@@ -186,6 +414,7 @@ mod tests {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         };
 
         let serialized = serialize_http_request(&request);
@@ -209,6 +438,7 @@ mod tests {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         };
 
         let serialized = serialize_http_request(&request);
@@ -240,6 +470,7 @@ mod tests {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         };
 
         let serialized = serialize_http_request(&request);
@@ -293,6 +524,7 @@ mod tests {
             ],
             pre_delay_ms: Some(250),
             post_delay_ms: Some(750),
+            ..Default::default()
         };
 
         let serialized = serialize_http_request(&request);
@@ -365,6 +597,7 @@ mod tests {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
             HttpRequest {
                 name: Some("admin-dashboard".to_string()),
@@ -399,6 +632,7 @@ mod tests {
                 ],
                 pre_delay_ms: Some(250),
                 post_delay_ms: Some(500),
+                ..Default::default()
             },
         ];
 
@@ -511,3 +745,321 @@ GET https://httpbin.org/get
         let _ = fs::remove_file(&output_file);
     }
 }
+
+#[cfg(test)]
+mod data_tests {
+    use super::*;
+    use crate::parser::parse_http_file;
+    use crate::types::DataScope;
+    use std::fs;
+
+    #[test]
+    fn test_data_directive_serialization_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("users.csv"), "name\nalice\nbob\n").unwrap();
+
+        let request_scoped = temp_dir.path().join("request_scoped.http");
+        fs::write(
+            &request_scoped,
+            "# @name create_user\n# @data ./users.csv\nPOST https://httpbin.org/post\n",
+        )
+        .unwrap();
+
+        let requests = parse_http_file(request_scoped.to_str().unwrap(), None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.contains("# @name create_user\n# @data ./users.csv\n"));
+
+        write_http_file(&request_scoped, &requests).unwrap();
+        let reparsed = parse_http_file(request_scoped.to_str().unwrap(), None).unwrap();
+        let data = reparsed[0].data.as_ref().unwrap();
+        assert_eq!(data.scope, DataScope::Request);
+        assert_eq!(data.rows.len(), 2);
+
+        let file_scoped = temp_dir.path().join("file_scoped.http");
+        fs::write(
+            &file_scoped,
+            "# @data ./users.csv\n\n###\nGET https://httpbin.org/get\n\n###\nGET https://httpbin.org/uuid\n",
+        )
+        .unwrap();
+
+        let requests = parse_http_file(file_scoped.to_str().unwrap(), None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.starts_with("# @data ./users.csv\n\n###\n"));
+        assert_eq!(serialized.matches("@data").count(), 1);
+
+        write_http_file(&file_scoped, &requests).unwrap();
+        let reparsed = parse_http_file(file_scoped.to_str().unwrap(), None).unwrap();
+        assert_eq!(reparsed.len(), 2);
+        assert!(
+            reparsed
+                .iter()
+                .all(|r| r.data.as_ref().unwrap().scope == DataScope::File)
+        );
+    }
+}
+
+#[cfg(test)]
+mod strict_variables_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_strict_variables_directive_serialization_roundtrip() {
+        let content = "# @strict-variables\nGET https://api.example.com/users\n\n###\nGET https://api.example.com/orders\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.starts_with("# @strict-variables\n"));
+        assert_eq!(serialized.matches("@strict-variables").count(), 1);
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        assert_eq!(reparsed.len(), 2);
+        assert!(reparsed.iter().all(|request| request.strict_variables));
+    }
+}
+
+#[cfg(test)]
+mod imports_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_import_directive_serialization_roundtrip() {
+        let content = "# @import ./auth.http\n\n# @dependsOn ./users.http#list_users\nGET https://api.example.com/orders\nAuthorization: Bearer {{auth.login.response.body.$.token}}\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        assert_eq!(requests[0].imports.len(), 2);
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.starts_with("# @import ./auth.http\n"));
+        assert_eq!(serialized.matches("@import").count(), 1);
+        assert!(serialized.contains("# @dependsOn ./users.http#list_users\n"));
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        assert_eq!(reparsed[0].imports, requests[0].imports);
+    }
+}
+
+#[cfg(test)]
+mod run_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_run_line_serialization_roundtrip() {
+        let content = "# @name login\nPOST https://api.example.com/login\n\n###\nrun #login (@user=bob, @password={{secret}})\n\n###\nrun #login\n\n###\nGET https://api.example.com/profile\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        assert_eq!(requests.len(), 4);
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.contains("###\nrun #login (@user=bob, @password={{secret}})\n"));
+        assert_eq!(serialized.matches("run #login").count(), 2);
+        assert_eq!(
+            serialized
+                .matches("POST https://api.example.com/login")
+                .count(),
+            1
+        );
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        assert_eq!(reparsed.len(), 4);
+        assert_eq!(
+            reparsed[1].run.as_ref().unwrap().variables,
+            requests[1].run.as_ref().unwrap().variables
+        );
+    }
+}
+
+#[cfg(test)]
+mod hook_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_setup_and_teardown_serialization_roundtrip() {
+        let content = "# @setup\nPOST https://api.example.com/users\n\n###\nGET https://api.example.com/users/1\n\n###\n# @teardown\nrun #cleanup\n\n###\n# @name cleanup\nDELETE https://api.example.com/users/1\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.starts_with("###\n# @setup\nPOST "));
+        assert!(serialized.contains("###\n# @teardown\nrun #cleanup\n"));
+        assert_eq!(serialized.matches("@teardown").count(), 1);
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        let hooks: Vec<_> = reparsed.iter().map(|request| request.hook).collect();
+        assert_eq!(
+            hooks,
+            vec![
+                Some(RequestHook::Setup),
+                None,
+                Some(RequestHook::Teardown),
+                None
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tag_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_tag_directive_serialization_roundtrip() {
+        let content = "# @name create_order\n# @tag smoke\n# @tag orders, slow\nPOST https://api.example.com/orders\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        assert_eq!(requests[0].tags, vec!["smoke", "orders", "slow"]);
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.contains("# @tag smoke, orders, slow\n"));
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        assert_eq!(reparsed[0].tags, requests[0].tags);
+    }
+}
+
+#[cfg(test)]
+mod foreach_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_foreach_directive_serialization_roundtrip() {
+        let content = "# @name get_item\n# @foreach item in {{list.response.body.$.items}}\nGET https://httpbin.org/anything/{{item.id}}\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.contains("# @foreach item in {{list.response.body.$.items}}\n"));
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        let foreach = reparsed[0].foreach.as_ref().unwrap();
+        assert_eq!(foreach.variable, "item");
+        assert_eq!(foreach.source, "{{list.response.body.$.items}}");
+    }
+}
+
+#[cfg(test)]
+mod paginate_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_paginate_directive_serialization_roundtrip() {
+        let content = "# @name users\n# @paginate cursor=$.meta.next param=after max=3 items=$.data assert=aggregate\nGET https://api.example.com/users\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.contains(
+            "# @paginate cursor=$.meta.next param=after max=3 items=$.data assert=aggregate\n"
+        ));
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        let pagination = reparsed[0].paginate.as_ref().unwrap();
+        assert_eq!(
+            pagination.next,
+            PaginationNext::Cursor {
+                path: "$.meta.next".to_string(),
+                param: "after".to_string(),
+            }
+        );
+        assert_eq!(pagination.max_pages, 3);
+        assert_eq!(pagination.items.as_deref(), Some("$.data"));
+        assert_eq!(pagination.assert_scope, PaginationAssertScope::Aggregate);
+    }
+
+    #[test]
+    fn test_paginate_link_serialization_omits_defaults() {
+        let requests = parse_http_content(
+            "# @paginate link\nGET https://api.example.com/users\n",
+            None,
+        )
+        .unwrap();
+        let serialized = serialize_http_request(&requests[0]);
+        assert!(serialized.contains("# @paginate link\nGET https://api.example.com/users\n"));
+    }
+}
+
+#[cfg(test)]
+mod auth_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_auth_directive_serialization_roundtrip() {
+        let content = "# @auth digest Mufasa Circle Of Life\nGET https://api.example.com/dir\n\n###\n# @auth bearer abc.def\nGET https://api.example.com/me\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.contains("# @auth digest Mufasa Circle Of Life\n"));
+        assert!(serialized.contains("# @auth bearer abc.def\n"));
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        assert_eq!(reparsed[0].auth, requests[0].auth);
+        assert_eq!(
+            reparsed[1].auth,
+            Some(Auth::Bearer {
+                token: "abc.def".to_string()
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod signing_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_sign_directive_serialization_omits_credentials() {
+        let content = "@AWS_ACCESS_KEY_ID=AKIDEXAMPLE\n@AWS_SECRET_ACCESS_KEY=aws-secret-key\n# @sign aws-sigv4 service=s3 region=eu-west-1\nGET https://s3.eu-west-1.amazonaws.com/bucket/key\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        let serialized = serialize_http_request(&requests[0]);
+        assert!(serialized.contains(
+            "# @sign aws-sigv4 service=s3 region=eu-west-1\nGET https://s3.eu-west-1.amazonaws.com/bucket/key\n"
+        ));
+        assert!(!serialized.contains("aws-secret-key"));
+    }
+
+    #[test]
+    fn test_http_signature_and_content_digest_serialization_roundtrip() {
+        let content = "# @sign http-signature alg=ed25519 key=c2VlZA== key-id=partner components=@method,content-digest\n# @content-digest sha-256\nPOST https://api.example.com/orders\n\n###\n# @sign hmac-sha256 key=s3cret header=X-Hub-Signature encoding=base64\nPOST https://api.example.com/hooks\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(serialized.contains(
+            "# @sign http-signature alg=ed25519 key=c2VlZA== key-id=partner components=@method,content-digest\n# @content-digest sha-256\n"
+        ));
+        assert!(
+            serialized.contains(
+                "# @sign hmac-sha256 key=s3cret header=X-Hub-Signature encoding=base64\n"
+            )
+        );
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        assert_eq!(reparsed[0].sign, requests[0].sign);
+        assert_eq!(reparsed[0].content_digest, requests[0].content_digest);
+        assert_eq!(reparsed[1].sign, requests[1].sign);
+    }
+}
+
+#[cfg(test)]
+mod capture_tests {
+    use super::*;
+    use crate::parser::parse_http_content;
+
+    #[test]
+    fn test_capture_directive_serialization_roundtrip() {
+        let content = "# @name login\n# @capture token = $.access_token\n# @capture etag = header ETag\nPOST https://api.example.com/login\n";
+
+        let requests = parse_http_content(content, None).unwrap();
+        let serialized = serialize_http_requests(&requests);
+        assert!(
+            serialized
+                .contains("# @capture token = $.access_token\n# @capture etag = header ETag\n")
+        );
+
+        let reparsed = parse_http_content(&serialized, None).unwrap();
+        assert_eq!(reparsed[0].captures, requests[0].captures);
+        assert_eq!(reparsed[0].captures.len(), 2);
+    }
+}
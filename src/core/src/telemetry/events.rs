@@ -28,6 +28,24 @@ pub(super) fn cli_args_event(args: &CliArgPatterns) -> (&'static str, HashMap<St
     properties.insert("file_count".to_string(), args.file_count.to_string());
     properties.insert("delay".to_string(), args.delay.to_string());
     properties.insert("fail_fast".to_string(), args.fail_fast.to_string());
+    properties.insert(
+        "persist_tokens".to_string(),
+        args.persist_tokens.to_string(),
+    );
+    properties.insert("var".to_string(), args.var.to_string());
+    properties.insert("vars_file".to_string(), args.vars_file.to_string());
+    properties.insert(
+        "strict_variables".to_string(),
+        args.strict_variables.to_string(),
+    );
+    properties.insert(
+        "secret_command".to_string(),
+        args.secret_command.to_string(),
+    );
+    properties.insert("tag".to_string(), args.tag.to_string());
+    properties.insert("exclude_tag".to_string(), args.exclude_tag.to_string());
+    properties.insert("name".to_string(), args.name.to_string());
+    properties.insert("dry_run".to_string(), args.dry_run.to_string());
 
     if let Some(ref format) = args.report_format {
         properties.insert("report_format".to_string(), format.clone());
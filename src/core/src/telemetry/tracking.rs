@@ -381,6 +381,15 @@ pub struct CliArgPatterns {
     pub file_count: usize,
     pub delay: u64,
     pub fail_fast: bool,
+    pub persist_tokens: bool,
+    pub var: bool,
+    pub vars_file: bool,
+    pub strict_variables: bool,
+    pub secret_command: bool,
+    pub tag: bool,
+    pub exclude_tag: bool,
+    pub name: bool,
+    pub dry_run: bool,
 }
 
 pub fn track_cli_args(args: &CliArgPatterns) {
@@ -550,6 +559,15 @@ mod tests {
             file_count: 5,
             delay: 0,
             fail_fast: false,
+            persist_tokens: false,
+            var: false,
+            vars_file: false,
+            strict_variables: false,
+            secret_command: false,
+            tag: false,
+            exclude_tag: false,
+            name: false,
+            dry_run: false,
         };
 
         // Should not panic even if telemetry is not initialized
@@ -703,6 +721,15 @@ mod tests {
             file_count: 100,
             delay: 0,
             fail_fast: false,
+            persist_tokens: false,
+            var: false,
+            vars_file: false,
+            strict_variables: false,
+            secret_command: false,
+            tag: false,
+            exclude_tag: false,
+            name: false,
+            dry_run: false,
         };
 
         track_cli_args(&args);
@@ -727,6 +754,15 @@ mod tests {
             file_count: 0,
             delay: 0,
             fail_fast: false,
+            persist_tokens: false,
+            var: false,
+            vars_file: false,
+            strict_variables: false,
+            secret_command: false,
+            tag: false,
+            exclude_tag: false,
+            name: false,
+            dry_run: false,
         };
 
         track_cli_args(&args);
@@ -854,6 +890,15 @@ mod tests {
             file_count: 42,
             delay: 0,
             fail_fast: false,
+            persist_tokens: false,
+            var: false,
+            vars_file: false,
+            strict_variables: false,
+            secret_command: false,
+            tag: false,
+            exclude_tag: false,
+            name: false,
+            dry_run: false,
         };
 
         let args2 = args1.clone();
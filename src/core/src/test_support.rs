@@ -0,0 +1,44 @@
+use crate::types::{Header, HttpRequest, HttpResult};
+
+/// A request with only its method and URL set.
+pub(crate) fn request(method: &str, url: &str) -> HttpRequest {
+    HttpRequest {
+        method: method.to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn headers(pairs: &[(&str, &str)]) -> Vec<Header> {
+    pairs
+        .iter()
+        .map(|(name, value)| Header {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+        .collect()
+}
+
+/// A successful response with the given status, body and headers.
+pub(crate) fn response(
+    status_code: u16,
+    body: Option<&str>,
+    headers: &[(&str, &str)],
+) -> HttpResult {
+    HttpResult {
+        request_name: None,
+        status_code,
+        success: true,
+        error_message: None,
+        duration_ms: 10,
+        response_headers: Some(
+            headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        ),
+        response_body: body.map(str::to_string),
+        assertion_results: Vec::new(),
+        captured_variables: Vec::new(),
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+/// Credentials applied when a request is sent (from `@auth`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Auth {
+    Basic { username: String, password: String },
+    Digest { username: String, password: String },
+    Bearer { token: String },
+}
+
+impl Auth {
+    /// The values that must never appear in output.
+    pub fn secrets(&self) -> Vec<&str> {
+        match self {
+            Auth::Basic { password, .. } | Auth::Digest { password, .. } => vec![password],
+            Auth::Bearer { token } => vec![token],
+        }
+    }
+}
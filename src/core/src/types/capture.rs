@@ -0,0 +1,16 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Capture {
+    pub name: String,          // Run-scoped variable the value is stored in
+    pub source: CaptureSource, // Part of the response the value comes from
+}
+
+/// Where a `@capture` directive reads its value from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum CaptureSource {
+    BodyPath(String), // JSONPath, `regex(/.../)` or XPath into the response body
+    Header(String),   // Response header, matched case-insensitively
+    Body,             // The whole response body
+    Status,           // The response status code
+}
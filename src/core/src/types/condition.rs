@@ -11,5 +11,5 @@ pub struct Condition {
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum ConditionType {
     Status,               // Check response status code
-    BodyJsonPath(String), // Check JSONPath expression in response body
+    BodyJsonPath(String), // Check a JSONPath, regex or XPath body path in response body
 }
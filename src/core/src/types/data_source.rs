@@ -0,0 +1,15 @@
+use super::variable::Variable;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataSource {
+    pub path: String,             // Dataset path as written in the @data directive
+    pub scope: DataScope,         // Whether the request or the whole file is repeated
+    pub rows: Vec<Vec<Variable>>, // One set of column bindings per dataset row
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum DataScope {
+    Request, // @data placed directly above a request
+    File,    // @data placed in the file header, before the first `###`
+}
@@ -0,0 +1,7 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForEach {
+    pub variable: String, // Loop variable name bound per item (e.g. `item`)
+    pub source: String,   // Reference resolving to a JSON array at run time
+}
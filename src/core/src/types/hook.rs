@@ -0,0 +1,20 @@
+use serde::Serialize;
+use std::fmt;
+
+/// A request marked `@setup` or `@teardown`, which runs before or after the
+/// other requests of its file whether or not they fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestHook {
+    Setup,
+    Teardown,
+}
+
+impl fmt::Display for RequestHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestHook::Setup => write!(f, "Setup"),
+            RequestHook::Teardown => write!(f, "Teardown"),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// A file whose named requests are available to another file as
+/// `<alias>.<request name>` (for `@import`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Import {
+    pub alias: String, // File name without extension, e.g. `auth` for `./auth.http`
+    pub path: String,  // Path as written in the directive
+    pub file: String,  // Path resolved against the importing file's directory
+}
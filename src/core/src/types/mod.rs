@@ -1,15 +1,35 @@
 mod assertion;
+mod auth;
+mod capture;
 mod condition;
 mod context;
+mod data_source;
+mod foreach;
+mod hook;
+mod import;
+mod oauth;
+mod pagination;
 mod request;
 mod request_variable;
 mod result;
+mod run;
+mod signing;
 mod variable;
 
 pub use assertion::{Assertion, AssertionResult, AssertionType};
+pub use auth::Auth;
+pub use capture::{Capture, CaptureSource};
 pub use condition::{Condition, ConditionType};
 pub use context::{HttpFileResults, ProcessorResults, RequestContext};
+pub use data_source::{DataScope, DataSource};
+pub use foreach::ForEach;
+pub use hook::RequestHook;
+pub use import::Import;
+pub use oauth::{OAuthClientAuth, OAuthConfig, OAuthGrant};
+pub use pagination::{DEFAULT_MAX_PAGES, Pagination, PaginationAssertScope, PaginationNext};
 pub use request::{Header, HttpRequest};
 pub use request_variable::{RequestVariable, RequestVariableSource, RequestVariableTarget};
 pub use result::HttpResult;
-pub use variable::Variable;
+pub use run::Run;
+pub use signing::{AwsCredentials, ContentDigest, SignatureAlgorithm, SignatureEncoding, Signing};
+pub use variable::{SourcedVariable, UnresolvedVariable, Variable, VariableSource};
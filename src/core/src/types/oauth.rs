@@ -0,0 +1,27 @@
+/// An OAuth 2.0 configuration from the `Security.Auth` section of an
+/// environment file, referenced as `{{$auth.token("name")}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthConfig {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_auth: OAuthClientAuth,
+    pub scope: Option<String>,
+    pub grant: OAuthGrant,
+}
+
+/// The grant used to obtain a token from the token endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OAuthGrant {
+    ClientCredentials,
+    Password { username: String, password: String },
+    RefreshToken { refresh_token: String },
+}
+
+/// How the client credentials are sent to the token endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OAuthClientAuth {
+    Basic,  // `Authorization: Basic` header (default)
+    InBody, // `client_id` and `client_secret` form parameters
+    None,   // Only `client_id`, for public clients
+}
@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+/// Page limit applied when `@paginate` does not set `max=`.
+pub const DEFAULT_MAX_PAGES: u32 = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Pagination {
+    pub next: PaginationNext,
+    pub max_pages: u32,        // Upper bound on the number of pages fetched
+    pub items: Option<String>, // JSONPath of the array concatenated across pages
+    pub assert_scope: PaginationAssertScope,
+}
+
+/// Where the URL of the next page comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum PaginationNext {
+    Link,        // RFC 8288 `Link: <...>; rel="next"` response header
+    Url(String), // JSONPath to the next page URL in the response body
+    Cursor {
+        path: String,  // JSONPath to the cursor in the response body
+        param: String, // Query parameter the cursor is sent back in
+    },
+}
+
+/// Whether assertions are evaluated on every page or once over the aggregate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PaginationAssertScope {
+    Page,
+    Aggregate,
+}
@@ -1,9 +1,18 @@
 use super::assertion::Assertion;
+use super::auth::Auth;
+use super::capture::Capture;
 use super::condition::Condition;
-use super::variable::Variable;
+use super::data_source::DataSource;
+use super::foreach::ForEach;
+use super::hook::RequestHook;
+use super::import::Import;
+use super::pagination::Pagination;
+use super::run::Run;
+use super::signing::{ContentDigest, Signing};
+use super::variable::{UnresolvedVariable, Variable};
 use serde::Serialize;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct HttpRequest {
     pub name: Option<String>,
     pub method: String,
@@ -19,6 +28,36 @@ pub struct HttpRequest {
     pub conditions: Vec<Condition>,      // Conditions for execution (for @if)
     pub pre_delay_ms: Option<u64>,       // Delay before executing request (for @pre-delay)
     pub post_delay_ms: Option<u64>,      // Delay after executing request (for @post-delay)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<DataSource>, // Dataset rows to repeat the request over (for @data)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreach: Option<ForEach>, // Response array to repeat the request over (for @foreach)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paginate: Option<Pagination>, // Further pages to fetch and aggregate (for @paginate)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Auth>, // Credentials applied when sending (for @auth)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign: Option<Signing>, // Signature added right before sending (for @sign)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_digest: Option<ContentDigest>, // Body digest header to send (for @content-digest)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub captures: Vec<Capture>, // Response values stored as run-scoped variables (for @capture)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<Import>, // Files whose requests this file references (for @import)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run: Option<Run>, // The `run` line this request was executed for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hook: Option<RequestHook>, // Runs before or after the other requests (for @setup/@teardown)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>, // Labels that --tag and --exclude-tag select by (for @tag)
+    #[serde(skip)]
+    pub secrets: Vec<String>, // Values read for $env/$processEnv/$dotenv, redacted from output
+    #[serde(skip)]
+    pub resolved_secrets: Vec<String>, // Values resolved for $secret, redacted even with --include-secrets
+    #[serde(skip)]
+    pub strict_variables: bool, // Fail before sending when a {{variable}} is undefined (for @strict-variables)
+    #[serde(skip)]
+    pub unresolved_variables: Vec<UnresolvedVariable>, // Placeholders left after parsing, with their lines
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -18,4 +18,8 @@ pub enum RequestVariableSource {
 pub enum RequestVariableTarget {
     Body,
     Headers,
+    Status,   // Response status code
+    Duration, // Response time in milliseconds
+    Url,      // Request URL as sent
+    Method,   // Request method
 }
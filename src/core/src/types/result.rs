@@ -1,4 +1,5 @@
 use super::assertion::AssertionResult;
+use super::variable::Variable;
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -12,4 +13,6 @@ pub struct HttpResult {
     pub response_headers: Option<HashMap<String, String>>,
     pub response_body: Option<String>,
     pub assertion_results: Vec<AssertionResult>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub captured_variables: Vec<Variable>, // Values stored by the request's @capture directives
 }
@@ -0,0 +1,12 @@
+use super::variable::Variable;
+use serde::Serialize;
+
+/// A `run #name` or `run ./file.http (@name=value)` line, whose requests are
+/// executed in its place.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Run {
+    pub path: Option<String>, // File as written; `None` for the file the line is in
+    pub request: Option<String>, // Request name after `#`; `None` runs every request
+    pub variables: Vec<Variable>, // Values overriding the file's variables
+    pub line: usize,          // Line of the `run` line in its file
+}
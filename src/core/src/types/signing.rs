@@ -0,0 +1,70 @@
+use serde::Serialize;
+
+/// How a request is signed right before it is sent (from `@sign`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Signing {
+    AwsSigV4 {
+        service: String,
+        region: String,
+        credentials: Option<AwsCredentials>, // None when no credentials were found
+    },
+    /// RFC 9421 HTTP Message Signatures (`Signature-Input` and `Signature`).
+    HttpSignature {
+        label: String, // Signature label, e.g. `sig1`
+        algorithm: SignatureAlgorithm,
+        key_id: Option<String>,
+        key: String,             // HMAC secret or Ed25519 private key
+        components: Vec<String>, // Covered components, e.g. `@method`, `content-digest`
+    },
+    /// HMAC of the body sent in a header, e.g. `X-Signature: <hex>`.
+    Hmac {
+        header: String,
+        key: String,
+        encoding: SignatureEncoding,
+    },
+}
+
+/// AWS credentials used for Signature Version 4.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SignatureAlgorithm {
+    HmacSha256,
+    Ed25519,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SignatureEncoding {
+    Hex,
+    Base64,
+}
+
+/// Digest algorithm of a generated `Content-Digest` header (RFC 9530).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ContentDigest {
+    Sha256,
+    Sha512,
+}
+
+impl Signing {
+    /// The values that must never appear in output.
+    pub fn secrets(&self) -> Vec<&str> {
+        match self {
+            Signing::AwsSigV4 {
+                credentials: Some(credentials),
+                ..
+            } => {
+                let mut secrets = vec![credentials.secret_access_key.as_str()];
+                secrets.extend(credentials.session_token.as_deref());
+                secrets
+            }
+            Signing::AwsSigV4 { .. } => Vec::new(),
+            Signing::HttpSignature { key, .. } | Signing::Hmac { key, .. } => vec![key],
+        }
+    }
+}
@@ -1,7 +1,48 @@
 use serde::Serialize;
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Variable {
     pub name: String,
     pub value: String,
 }
+
+/// Where a variable's value comes from, ordered by precedence: a later
+/// source overrides an earlier one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VariableSource {
+    Environment,
+    File,
+    VarsFile,
+    CommandLine,
+    Run,
+}
+
+impl fmt::Display for VariableSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            VariableSource::Environment => "environment",
+            VariableSource::File => ".http file",
+            VariableSource::VarsFile => "--vars-file",
+            VariableSource::CommandLine => "--var",
+            VariableSource::Run => "run",
+        };
+        f.write_str(source)
+    }
+}
+
+/// A variable together with where its value comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcedVariable {
+    pub name: String,
+    pub value: String,
+    pub source: VariableSource,
+}
+
+/// A `{{name}}` placeholder still unresolved after parsing, and the line of
+/// the `.http` file it appears on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedVariable {
+    pub name: String,
+    pub line: usize,
+}
@@ -0,0 +1,65 @@
+use super::json::extract_json_value;
+use super::xml::extract_xpath_value;
+use anyhow::{Result, anyhow};
+use regex::RegexBuilder;
+
+/// Extract a value from a response body with a body path:
+///
+/// - `*` returns the whole body
+/// - `$.user.email` reads a JSON property
+/// - `regex(/token=(\w+)/)` returns the first capture group, or the whole
+///   match when the pattern has no group
+/// - `//order/id` evaluates an XPath expression against an XML body
+///
+/// Any other path returns the whole body. Shared by request variables, `@if`
+/// conditions and `@capture` directives.
+pub fn extract_body_value(body: &str, path: &str) -> Result<Option<String>> {
+    let path = path.trim();
+    if path == "*" {
+        return Ok(Some(body.to_string()));
+    }
+    if path.starts_with("$.") {
+        return extract_json_value(body, path);
+    }
+    if let Some(pattern) = path
+        .strip_prefix("regex(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return extract_regex_value(body, pattern);
+    }
+    if path.starts_with('/') {
+        return extract_xpath_value(body, path);
+    }
+
+    Ok(Some(body.to_string()))
+}
+
+/// Match a `/pattern/flags` regular expression against `body`. The supported
+/// flags are `i` (case-insensitive), `m` (multi-line) and `s` (dot matches
+/// newlines).
+pub fn extract_regex_value(body: &str, pattern: &str) -> Result<Option<String>> {
+    let invalid = |reason: &str| anyhow!("Invalid regex '{pattern}': {reason}");
+    let (expression, flags) = pattern
+        .strip_prefix('/')
+        .and_then(|rest| rest.rsplit_once('/'))
+        .ok_or_else(|| invalid("expected '/<pattern>/'"))?;
+
+    let mut builder = RegexBuilder::new(expression);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            _ => return Err(invalid(&format!("unknown flag '{flag}'"))),
+        };
+    }
+    let regex = builder.build().map_err(|e| invalid(&e.to_string()))?;
+
+    Ok(regex.captures(body).map(|captures| {
+        captures
+            .get(1)
+            .or_else(|| captures.get(0))
+            .map(|value| value.as_str().to_string())
+            .unwrap_or_default()
+    }))
+}
@@ -0,0 +1,90 @@
+use crate::variables::{extract_body_value, extract_regex_value, extract_xpath_value};
+
+const ORDER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <orders count="2">
+      <order status="shipped">
+        <id>1001</id>
+        <note><![CDATA[fragile & heavy]]></note>
+      </order>
+      <order status="pending">
+        <id>1002</id>
+        <customer>Tom &amp; Jerry</customer>
+      </order>
+    </orders>
+  </soap:Body>
+</soap:Envelope>"#;
+
+#[test]
+fn test_extract_body_value_dispatches_on_path() {
+    assert_eq!(
+        extract_body_value(r#"{"id":7}"#, "$.id").unwrap(),
+        Some("7".to_string())
+    );
+    assert_eq!(
+        extract_body_value("token=abc123; path=/", r"regex(/token=(\w+)/)").unwrap(),
+        Some("abc123".to_string())
+    );
+    assert_eq!(
+        extract_body_value(ORDER_XML, "//order/id").unwrap(),
+        Some("1001".to_string())
+    );
+    assert_eq!(
+        extract_body_value("plain text", "*").unwrap(),
+        Some("plain text".to_string())
+    );
+}
+
+#[test]
+fn test_extract_regex_value_uses_first_group_or_whole_match() {
+    let body = "<input name=\"csrf\" value=\"a1b2\">\nStatus: OK";
+    assert_eq!(
+        extract_regex_value(body, r#"/value="([^"]+)"/"#).unwrap(),
+        Some("a1b2".to_string())
+    );
+    assert_eq!(
+        extract_regex_value(body, r"/status: \w+/i").unwrap(),
+        Some("Status: OK".to_string())
+    );
+    assert_eq!(extract_regex_value(body, r"/missing=(\d+)/").unwrap(), None);
+}
+
+#[test]
+fn test_extract_regex_value_rejects_invalid_patterns() {
+    assert!(extract_regex_value("body", "token=(\\w+)").is_err());
+    assert!(extract_regex_value("body", "/token=(/").is_err());
+    assert!(extract_regex_value("body", "/token/x").is_err());
+}
+
+#[test]
+fn test_extract_xpath_value_selects_elements_and_attributes() {
+    let cases = [
+        ("/Envelope/Body/orders/order/id", Some("1001")),
+        ("/soap:Envelope/soap:Body/orders/@count", Some("2")),
+        ("//order[2]/id", Some("1002")),
+        ("//order[@status='pending']/customer", Some("Tom & Jerry")),
+        ("//order[1]/@status", Some("shipped")),
+        ("//order/note/text()", Some("fragile & heavy")),
+        ("//orders/*[2]/id", Some("1002")),
+        ("//invoice/id", None),
+        ("//order[3]/id", None),
+    ];
+    for (xpath, expected) in cases {
+        assert_eq!(
+            extract_xpath_value(ORDER_XML, xpath).unwrap().as_deref(),
+            expected,
+            "{xpath}"
+        );
+    }
+}
+
+#[test]
+fn test_extract_xpath_value_rejects_invalid_input() {
+    assert!(extract_xpath_value(ORDER_XML, "order/id").is_err());
+    assert!(extract_xpath_value(ORDER_XML, "//order[0]").is_err());
+    assert!(extract_xpath_value(ORDER_XML, "//@status/id").is_err());
+    assert!(extract_xpath_value(ORDER_XML, "//order[position()]").is_err());
+    assert!(extract_xpath_value(r#"{"id":1}"#, "//id").is_err());
+    assert!(extract_xpath_value("<order><id>1</order>", "//id").is_err());
+}
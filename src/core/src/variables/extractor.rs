@@ -1,4 +1,4 @@
-use super::json::extract_json_property;
+use super::body::extract_body_value;
 use crate::types::{RequestContext, RequestVariable, RequestVariableSource, RequestVariableTarget};
 use anyhow::Result;
 
@@ -6,8 +6,10 @@ pub fn extract_request_variable_value(
     request_var: &RequestVariable,
     context: &[RequestContext],
 ) -> Result<Option<String>> {
+    // A request executed again by a `run` line is referenced with its latest result.
     let target_context = context
         .iter()
+        .rev()
         .find(|ctx| ctx.name == request_var.request_name);
 
     if target_context.is_none() {
@@ -48,6 +50,9 @@ fn extract_from_request(
             }
             Ok(None)
         }
+        RequestVariableTarget::Url => Ok(Some(request.url.clone())),
+        RequestVariableTarget::Method => Ok(Some(request.method.clone())),
+        RequestVariableTarget::Status | RequestVariableTarget::Duration => Ok(None),
     }
 }
 
@@ -58,16 +63,7 @@ fn extract_from_response(
     match request_var.target {
         RequestVariableTarget::Body => {
             if let Some(ref body) = result.response_body {
-                if request_var.path == "*" {
-                    return Ok(Some(body.clone()));
-                }
-
-                if request_var.path.starts_with("$.") {
-                    let property = &request_var.path[2..];
-                    return extract_json_property(body, property);
-                }
-
-                Ok(Some(body.clone()))
+                extract_body_value(body, &request_var.path)
             } else {
                 Ok(None)
             }
@@ -82,5 +78,8 @@ fn extract_from_response(
             }
             Ok(None)
         }
+        RequestVariableTarget::Status => Ok(Some(result.status_code.to_string())),
+        RequestVariableTarget::Duration => Ok(Some(result.duration_ms.to_string())),
+        RequestVariableTarget::Url | RequestVariableTarget::Method => Ok(None),
     }
 }
@@ -29,6 +29,7 @@ fn create_test_context() -> Vec<RequestContext> {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("login".to_string()),
@@ -39,6 +40,7 @@ fn create_test_context() -> Vec<RequestContext> {
             response_headers: Some(headers),
             response_body: Some(r#"{"token":"secret123","userId":42}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }]
 }
@@ -172,6 +174,7 @@ fn test_extract_request_variable_value_no_result() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: None,
     }];
@@ -206,6 +209,7 @@ fn test_extract_request_variable_value_missing_response_body() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("test".to_string()),
@@ -216,6 +220,7 @@ fn test_extract_request_variable_value_missing_response_body() {
             response_headers: None,
             response_body: None,
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -249,6 +254,7 @@ fn test_extract_request_variable_value_missing_request_body() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: None,
     }];
@@ -329,6 +335,7 @@ fn test_extract_request_variable_value_request_header_not_found() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let context = vec![RequestContext {
@@ -367,6 +374,7 @@ fn test_extract_request_variable_value_response_body_no_jsonpath() {
         conditions: vec![],
         pre_delay_ms: None,
         post_delay_ms: None,
+        ..Default::default()
     };
 
     let result_data = HttpResult {
@@ -378,6 +386,7 @@ fn test_extract_request_variable_value_response_body_no_jsonpath() {
         response_headers: Some(HashMap::new()),
         response_body: Some("plain text response".to_string()),
         assertion_results: vec![],
+        captured_variables: Vec::new(),
     };
 
     let context = vec![RequestContext {
@@ -399,3 +408,42 @@ fn test_extract_request_variable_value_response_body_no_jsonpath() {
     assert!(extracted.is_ok());
     assert_eq!(extracted.unwrap(), Some("plain text response".to_string()));
 }
+
+#[test]
+fn test_extract_request_variable_value_status_duration_url_and_method() {
+    let context = create_test_context();
+    let cases = [
+        (
+            RequestVariableSource::Response,
+            RequestVariableTarget::Status,
+            "200",
+        ),
+        (
+            RequestVariableSource::Response,
+            RequestVariableTarget::Duration,
+            "100",
+        ),
+        (
+            RequestVariableSource::Request,
+            RequestVariableTarget::Url,
+            "https://api.example.com/login",
+        ),
+        (
+            RequestVariableSource::Request,
+            RequestVariableTarget::Method,
+            "POST",
+        ),
+    ];
+
+    for (source, target, expected) in cases {
+        let request_var = RequestVariable {
+            reference: "{{login}}".to_string(),
+            request_name: "login".to_string(),
+            source,
+            target,
+            path: String::new(),
+        };
+        let result = extract_request_variable_value(&request_var, &context).unwrap();
+        assert_eq!(result.as_deref(), Some(expected));
+    }
+}
@@ -33,6 +33,42 @@ pub fn extract_json_value(json_body: &str, json_path: &str) -> Result<Option<Str
     }
 }
 
+/// Look up a `$.`-prefixed JSONPath in an already parsed JSON document.
+pub fn find_json_path<'a>(root: &'a Value, json_path: &str) -> Result<Option<&'a Value>> {
+    let path = parse_json_path(strip_json_path_prefix(json_path)?)?;
+    find_json_value(root, &path)
+}
+
+/// Replace the value at a `$.`-prefixed JSONPath. Returns `false` when the path
+/// does not exist in `root`.
+///
+/// Used by `@paginate` to splice the items of every page into the first page.
+pub fn replace_json_path(root: &mut Value, json_path: &str, value: Value) -> Result<bool> {
+    let path = parse_json_path(strip_json_path_prefix(json_path)?)?;
+    let mut current = root;
+
+    for segment in &path {
+        let next = match (segment, current) {
+            (JsonPathSegment::Key(key), Value::Object(map)) => map.get_mut(key),
+            (JsonPathSegment::Index(index), Value::Array(items)) => items.get_mut(*index),
+            _ => None,
+        };
+        let Some(next) = next else {
+            return Ok(false);
+        };
+        current = next;
+    }
+
+    *current = value;
+    Ok(true)
+}
+
+fn strip_json_path_prefix(json_path: &str) -> Result<&str> {
+    json_path
+        .strip_prefix("$.")
+        .ok_or_else(|| anyhow!("JSONPath must start with '$.': {json_path}"))
+}
+
 fn parse_json_path(property: &str) -> Result<Vec<JsonPathSegment>> {
     let mut segments = Vec::new();
 
@@ -1,4 +1,4 @@
-use crate::variables::extract_json_value;
+use crate::variables::{extract_json_value, find_json_path, replace_json_path};
 
 #[test]
 fn test_extract_json_value_simple_path() {
@@ -111,3 +111,25 @@ fn test_extract_json_value_object_with_brace_in_string_is_not_truncated() {
     assert!(value.contains(r#""note":"}""#));
     assert!(value.contains(r#""id":1"#));
 }
+
+#[test]
+fn test_find_json_path_returns_nested_values() {
+    let root: serde_json::Value = serde_json::from_str(r#"{"data":[{"id":1}],"meta":{}}"#).unwrap();
+    let items = find_json_path(&root, "$.data").unwrap().unwrap();
+    assert!(items.is_array());
+    assert!(find_json_path(&root, "$.meta.next").unwrap().is_none());
+    assert!(find_json_path(&root, "data").is_err());
+}
+
+#[test]
+fn test_replace_json_path_replaces_existing_values_only() {
+    let mut root: serde_json::Value =
+        serde_json::from_str(r#"{"page":{"data":[1]},"total":1}"#).unwrap();
+    let replaced = replace_json_path(&mut root, "$.page.data", serde_json::json!([1, 2])).unwrap();
+    assert!(replaced);
+    assert_eq!(root["page"]["data"], serde_json::json!([1, 2]));
+
+    let missing = replace_json_path(&mut root, "$.page.items", serde_json::json!([])).unwrap();
+    assert!(!missing);
+    assert!(root["page"].get("items").is_none());
+}
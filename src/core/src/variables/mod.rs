@@ -1,14 +1,20 @@
+mod body;
 mod extractor;
 mod json;
 mod parser;
 mod substitution;
+mod xml;
 
+pub use body::{extract_body_value, extract_regex_value};
 #[allow(unused_imports)]
 pub use extractor::extract_request_variable_value;
-pub use json::{extract_json_property, extract_json_value};
+pub use json::{extract_json_property, extract_json_value, find_json_path, replace_json_path};
 #[allow(unused_imports)]
 pub use parser::parse_request_variable;
-pub use substitution::substitute_request_variables;
+pub use substitution::{
+    looks_like_request_variable, referenced_request_names, substitute_request_variables,
+};
+pub use xml::extract_xpath_value;
 
 #[cfg(test)]
 mod tests;
@@ -18,3 +24,6 @@ mod extractor_tests;
 
 #[cfg(test)]
 mod json_value_tests;
+
+#[cfg(test)]
+mod body_tests;
@@ -7,12 +7,20 @@ pub fn parse_request_variable(reference: &str) -> Result<RequestVariable> {
         cleaned = &reference[2..reference.len() - 2];
     }
 
-    let parts: Vec<&str> = cleaned.split('.').collect();
-    if parts.len() < 4 {
+    let all_parts: Vec<&str> = cleaned.split('.').collect();
+
+    // Requests of an imported file are named `<alias>.<request>`.
+    let name_len = if !is_source(all_parts.get(1)) && is_source(all_parts.get(2)) {
+        2
+    } else {
+        1
+    };
+    if all_parts.len() < name_len + 2 {
         return Err(anyhow!("Invalid request variable format"));
     }
 
-    let request_name = parts[0];
+    let request_name = all_parts[..name_len].join(".");
+    let parts = &all_parts[name_len - 1..];
     let source_str = parts[1];
     let target_str = parts[2];
     let path = parts[3..].join(".");
@@ -26,14 +34,47 @@ pub fn parse_request_variable(reference: &str) -> Result<RequestVariable> {
     let target = match target_str {
         "body" => RequestVariableTarget::Body,
         "headers" => RequestVariableTarget::Headers,
+        "status" => RequestVariableTarget::Status,
+        "duration" => RequestVariableTarget::Duration,
+        "url" => RequestVariableTarget::Url,
+        "method" => RequestVariableTarget::Method,
         _ => return Err(anyhow!("Invalid target: {}", target_str)),
     };
 
+    match target {
+        RequestVariableTarget::Body | RequestVariableTarget::Headers if parts.len() < 4 => {
+            return Err(anyhow!("Invalid request variable format"));
+        }
+        RequestVariableTarget::Status
+        | RequestVariableTarget::Duration
+        | RequestVariableTarget::Url
+        | RequestVariableTarget::Method
+            if parts.len() > 3 =>
+        {
+            return Err(anyhow!("'{target_str}' does not take a path"));
+        }
+        RequestVariableTarget::Status | RequestVariableTarget::Duration
+            if source == RequestVariableSource::Request =>
+        {
+            return Err(anyhow!("'{target_str}' is only available on the response"));
+        }
+        RequestVariableTarget::Url | RequestVariableTarget::Method
+            if source == RequestVariableSource::Response =>
+        {
+            return Err(anyhow!("'{target_str}' is only available on the request"));
+        }
+        _ => {}
+    }
+
     Ok(RequestVariable {
         reference: reference.to_string(),
-        request_name: request_name.to_string(),
+        request_name,
         source,
         target,
         path,
     })
 }
+
+fn is_source(part: Option<&&str>) -> bool {
+    matches!(part, Some(&"request" | &"response"))
+}
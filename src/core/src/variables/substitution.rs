@@ -1,5 +1,6 @@
 use super::extractor::extract_request_variable_value;
 use super::parser::parse_request_variable;
+use crate::parser::find_placeholders;
 use crate::types::RequestContext;
 use anyhow::{Result, anyhow};
 
@@ -48,13 +49,26 @@ pub fn substitute_request_variables(input: &str, context: &[RequestContext]) ->
     Ok(result)
 }
 
-fn looks_like_request_variable(reference: &str) -> bool {
+/// The names of the requests whose request or response `input` references,
+/// such as `login` for `{{login.response.body.$.token}}`.
+pub fn referenced_request_names(input: &str) -> Vec<String> {
+    find_placeholders(input)
+        .into_iter()
+        .filter(|reference| looks_like_request_variable(reference))
+        .filter_map(|reference| parse_request_variable(reference).ok())
+        .map(|variable| variable.request_name)
+        .collect()
+}
+
+/// Whether the `{{…}}` placeholder `reference` is a request variable, such as
+/// `login.response.body.$.token`, rather than a plain variable.
+pub fn looks_like_request_variable(reference: &str) -> bool {
     let cleaned = reference
         .strip_prefix("{{")
         .and_then(|value| value.strip_suffix("}}"))
         .unwrap_or(reference);
 
-    let mut parts = cleaned.split('.');
-    parts.next();
+    let mut parts = cleaned.split('.').skip(1);
     matches!(parts.next(), Some("request" | "response"))
+        || matches!(parts.next(), Some("request" | "response"))
 }
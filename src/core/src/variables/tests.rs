@@ -42,6 +42,53 @@ fn test_parse_request_variable_request_source() {
     assert_eq!(result.path, "*");
 }
 
+#[test]
+fn test_parse_request_variable_of_imported_request() {
+    let result = parse_request_variable("{{auth.login.response.body.$.token}}").unwrap();
+    assert_eq!(result.request_name, "auth.login");
+    assert!(matches!(result.source, RequestVariableSource::Response));
+    assert_eq!(result.path, "$.token");
+
+    let result = parse_request_variable("{{auth.login.response.status}}").unwrap();
+    assert_eq!(result.request_name, "auth.login");
+    assert_eq!(result.target, RequestVariableTarget::Status);
+}
+
+#[test]
+fn test_parse_request_variable_status_duration_url_and_method() {
+    let cases = [
+        ("{{login.response.status}}", RequestVariableTarget::Status),
+        (
+            "{{login.response.duration}}",
+            RequestVariableTarget::Duration,
+        ),
+        ("{{create.request.url}}", RequestVariableTarget::Url),
+        ("{{create.request.method}}", RequestVariableTarget::Method),
+    ];
+    for (reference, target) in cases {
+        let result = parse_request_variable(reference).unwrap();
+        assert_eq!(result.target, target);
+        assert_eq!(result.path, "");
+    }
+}
+
+#[test]
+fn test_parse_request_variable_rejects_misplaced_scalar_targets() {
+    for reference in [
+        "{{login.request.status}}",
+        "{{login.request.duration}}",
+        "{{create.response.url}}",
+        "{{create.response.method}}",
+        "{{login.response.status.code}}",
+        "{{login.response.body}}",
+    ] {
+        assert!(
+            parse_request_variable(reference).is_err(),
+            "{reference} should be rejected"
+        );
+    }
+}
+
 #[test]
 fn test_parse_request_variable_invalid_format() {
     let reference = "{{invalid}}";
@@ -150,6 +197,7 @@ fn test_substitute_request_variables_simple() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("login".to_string()),
@@ -160,6 +208,7 @@ fn test_substitute_request_variables_simple() {
             response_headers: None,
             response_body: Some(r#"{"token":"secret123"}"#.to_string()),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -189,6 +238,7 @@ fn test_substitute_request_variables_multiple() {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
             result: Some(HttpResult {
                 request_name: Some("config".to_string()),
@@ -199,6 +249,7 @@ fn test_substitute_request_variables_multiple() {
                 response_headers: None,
                 response_body: Some(r#"{"host":"api.example.com"}"#.to_string()),
                 assertion_results: vec![],
+                captured_variables: Vec::new(),
             }),
         },
         RequestContext {
@@ -217,6 +268,7 @@ fn test_substitute_request_variables_multiple() {
                 conditions: vec![],
                 pre_delay_ms: None,
                 post_delay_ms: None,
+                ..Default::default()
             },
             result: Some(HttpResult {
                 request_name: Some("user".to_string()),
@@ -227,6 +279,7 @@ fn test_substitute_request_variables_multiple() {
                 response_headers: None,
                 response_body: Some(r#"{"id":"789"}"#.to_string()),
                 assertion_results: vec![],
+                captured_variables: Vec::new(),
             }),
         },
     ];
@@ -272,6 +325,7 @@ fn test_substitute_request_variables_from_headers() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("login".to_string()),
@@ -282,6 +336,7 @@ fn test_substitute_request_variables_from_headers() {
             response_headers: Some(headers),
             response_body: None,
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -310,6 +365,7 @@ fn test_substitute_request_variables_from_request_body() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: None,
     }];
@@ -339,6 +395,7 @@ fn test_substitute_request_variables_nested_json() {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         },
         result: Some(HttpResult {
             request_name: Some("getUser".to_string()),
@@ -351,6 +408,7 @@ fn test_substitute_request_variables_nested_json() {
                 r#"{"profile":{"address":{"city":"New York","zip":"10001"}}}"#.to_string(),
             ),
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }),
     }];
 
@@ -359,6 +417,51 @@ fn test_substitute_request_variables_nested_json() {
     assert_eq!(result, "New York");
 }
 
+#[test]
+fn test_substitute_request_variables_regex_and_xpath() {
+    let context = vec![RequestContext {
+        name: "legacy".to_string(),
+        request: HttpRequest {
+            name: Some("legacy".to_string()),
+            method: "GET".to_string(),
+            url: "https://api.example.com/legacy".to_string(),
+            headers: vec![],
+            body: None,
+            assertions: vec![],
+            variables: vec![],
+            timeout: None,
+            connection_timeout: None,
+            depends_on: None,
+            conditions: vec![],
+            pre_delay_ms: None,
+            post_delay_ms: None,
+            ..Default::default()
+        },
+        result: Some(HttpResult {
+            request_name: Some("legacy".to_string()),
+            status_code: 200,
+            success: true,
+            error_message: None,
+            duration_ms: 100,
+            response_headers: None,
+            response_body: Some(
+                "<response><session>token=abc123</session><order><id>42</id></order></response>"
+                    .to_string(),
+            ),
+            assertion_results: vec![],
+            captured_variables: Vec::new(),
+        }),
+    }];
+
+    let result = substitute_request_variables(
+        r"/orders/{{legacy.response.body.//order/id}}?session={{legacy.response.body.regex(/token=(\w+)/)}}",
+        &context,
+    )
+    .unwrap();
+
+    assert_eq!(result, "/orders/42?session=abc123");
+}
+
 #[test]
 fn test_substitute_preserves_non_request_variables() {
     let input = "Prefix {{token}} Suffix";
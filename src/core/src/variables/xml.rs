@@ -0,0 +1,357 @@
+use anyhow::{Result, anyhow};
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug)]
+struct Step {
+    descendant: bool,
+    test: NodeTest,
+    predicate: Option<Predicate>,
+}
+
+#[derive(Debug)]
+enum NodeTest {
+    Name(String),
+    AnyElement,
+    Text,
+    Attribute(String),
+}
+
+#[derive(Debug)]
+enum Predicate {
+    Position(usize),
+    AttributeEquals(String, String),
+}
+
+/// Extract the string value of the first node selected by an XPath expression
+/// (e.g. `//order/id` or `/envelope/body/item[2]/@sku`).
+///
+/// Supports absolute (`/`) and descendant (`//`) steps, element names, `*`,
+/// `text()`, `@attribute`, and `[n]` or `[@attribute='value']` predicates.
+/// Unprefixed names match elements of any namespace prefix.
+pub fn extract_xpath_value(xml_body: &str, xpath: &str) -> Result<Option<String>> {
+    let steps = parse_xpath(xpath)?;
+    let document = parse_xml(xml_body)?;
+
+    let mut elements = vec![&document];
+    for (index, step) in steps.iter().enumerate() {
+        let is_last = index + 1 == steps.len();
+        match &step.test {
+            NodeTest::Text | NodeTest::Attribute(_) if !is_last => {
+                return Err(anyhow!(
+                    "Invalid XPath '{xpath}': text() and attributes must be the last step"
+                ));
+            }
+            NodeTest::Text => {
+                return Ok(select_parents(&elements, step.descendant)
+                    .into_iter()
+                    .flat_map(|element| element.children.iter())
+                    .find_map(|node| match node {
+                        Node::Text(text) if !text.trim().is_empty() => {
+                            Some(text.trim().to_string())
+                        }
+                        _ => None,
+                    }));
+            }
+            NodeTest::Attribute(name) => {
+                return Ok(select_parents(&elements, step.descendant)
+                    .into_iter()
+                    .find_map(|element| attribute(element, name))
+                    .map(str::to_string));
+            }
+            NodeTest::Name(_) | NodeTest::AnyElement => {
+                elements = select_children(&elements, step);
+            }
+        }
+    }
+
+    Ok(elements.first().map(|element| text_content(element)))
+}
+
+fn select_parents<'a>(elements: &[&'a Element], descendant: bool) -> Vec<&'a Element> {
+    let mut parents = Vec::new();
+    for element in elements {
+        parents.push(*element);
+        if descendant {
+            collect_descendants(element, &mut parents);
+        }
+    }
+    parents
+}
+
+fn collect_descendants<'a>(element: &'a Element, into: &mut Vec<&'a Element>) {
+    for child in child_elements(element) {
+        into.push(child);
+        collect_descendants(child, into);
+    }
+}
+
+fn select_children<'a>(elements: &[&'a Element], step: &Step) -> Vec<&'a Element> {
+    let mut selected = Vec::new();
+    for parent in select_parents(elements, step.descendant) {
+        let matching = child_elements(parent).filter(|child| match &step.test {
+            NodeTest::Name(name) => name_matches(&child.name, name),
+            _ => true,
+        });
+        match &step.predicate {
+            None => selected.extend(matching),
+            Some(Predicate::Position(position)) => {
+                selected.extend(matching.skip(position - 1).take(1));
+            }
+            Some(Predicate::AttributeEquals(name, value)) => selected
+                .extend(matching.filter(|child| attribute(child, name) == Some(value.as_str()))),
+        }
+    }
+    selected
+}
+
+fn child_elements(element: &Element) -> impl Iterator<Item = &Element> {
+    element.children.iter().filter_map(|node| match node {
+        Node::Element(child) => Some(child),
+        Node::Text(_) => None,
+    })
+}
+
+fn name_matches(element_name: &str, name: &str) -> bool {
+    if name.contains(':') {
+        return element_name == name;
+    }
+    let local_name = element_name.rsplit(':').next().unwrap_or(element_name);
+    local_name == name
+}
+
+fn attribute<'a>(element: &'a Element, name: &str) -> Option<&'a str> {
+    element
+        .attributes
+        .iter()
+        .find(|(attribute, _)| name_matches(attribute, name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn text_content(element: &Element) -> String {
+    fn collect(element: &Element, into: &mut String) {
+        for node in &element.children {
+            match node {
+                Node::Element(child) => collect(child, into),
+                Node::Text(text) => into.push_str(text),
+            }
+        }
+    }
+
+    let mut text = String::new();
+    collect(element, &mut text);
+    text.trim().to_string()
+}
+
+fn parse_xpath(xpath: &str) -> Result<Vec<Step>> {
+    let invalid = |reason: &str| anyhow!("Invalid XPath '{xpath}': {reason}");
+    let Some(mut rest) = xpath.trim().strip_prefix('/') else {
+        return Err(invalid("expressions must start with '/'"));
+    };
+
+    let mut steps = Vec::new();
+    loop {
+        let descendant = match rest.strip_prefix('/') {
+            Some(stripped) => {
+                rest = stripped;
+                true
+            }
+            None => false,
+        };
+        let end = step_end(rest);
+        steps.push(parse_step(&rest[..end], descendant).map_err(|e| invalid(&e))?);
+        match rest[end..].strip_prefix('/') {
+            Some(next) => rest = next,
+            None => break,
+        }
+    }
+
+    Ok(steps)
+}
+
+fn step_end(rest: &str) -> usize {
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (index, ch) in rest.char_indices() {
+        match (ch, quote) {
+            ('\'' | '"', None) => quote = Some(ch),
+            (_, Some(open)) if ch == open => quote = None,
+            (_, Some(_)) => {}
+            ('[', None) => depth += 1,
+            (']', None) => depth = depth.saturating_sub(1),
+            ('/', None) if depth == 0 => return index,
+            _ => {}
+        }
+    }
+    rest.len()
+}
+
+fn parse_step(step: &str, descendant: bool) -> std::result::Result<Step, String> {
+    let (test, predicate) = match step.find('[') {
+        Some(open) => {
+            let predicate = step[open + 1..]
+                .strip_suffix(']')
+                .ok_or_else(|| format!("unclosed predicate in '{step}'"))?;
+            (&step[..open], Some(parse_predicate(predicate)?))
+        }
+        None => (step, None),
+    };
+
+    let test = match test {
+        "" => return Err("empty step".to_string()),
+        "*" => NodeTest::AnyElement,
+        "text()" => NodeTest::Text,
+        _ => match test.strip_prefix('@') {
+            Some(name) if is_xml_name(name) => NodeTest::Attribute(name.to_string()),
+            None if is_xml_name(test) => NodeTest::Name(test.to_string()),
+            _ => return Err(format!("unsupported step '{step}'")),
+        },
+    };
+
+    if predicate.is_some() && matches!(test, NodeTest::Text | NodeTest::Attribute(_)) {
+        return Err(format!("predicates are not supported on '{step}'"));
+    }
+
+    Ok(Step {
+        descendant,
+        test,
+        predicate,
+    })
+}
+
+fn parse_predicate(predicate: &str) -> std::result::Result<Predicate, String> {
+    let predicate = predicate.trim();
+    if let Ok(position) = predicate.parse::<usize>() {
+        return match position {
+            0 => Err("positions start at 1".to_string()),
+            _ => Ok(Predicate::Position(position)),
+        };
+    }
+
+    let unsupported = || format!("unsupported predicate '[{predicate}]'");
+    let (name, value) = predicate
+        .strip_prefix('@')
+        .and_then(|rest| rest.split_once('='))
+        .ok_or_else(unsupported)?;
+    let value = value.trim();
+    let value = value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+        .or_else(|| value.strip_prefix('"')?.strip_suffix('"'))
+        .ok_or_else(unsupported)?;
+
+    Ok(Predicate::AttributeEquals(
+        name.trim().to_string(),
+        value.to_string(),
+    ))
+}
+
+fn is_xml_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.' | ':'))
+}
+
+fn parse_xml(xml_body: &str) -> Result<Element> {
+    let invalid = |e: &dyn std::fmt::Display| anyhow!("Invalid XML body: {e}");
+    let mut reader = Reader::from_str(xml_body);
+    let mut stack = vec![Element::default()];
+
+    loop {
+        let event = reader.read_event().map_err(|e| invalid(&e))?;
+        match event {
+            Event::Start(start) => stack.push(start_element(&start)?),
+            Event::Empty(start) => {
+                let element = start_element(&start)?;
+                push_node(&mut stack, Node::Element(element));
+            }
+            Event::End(_) => {
+                if stack.len() < 2 {
+                    return Err(invalid(&"unexpected closing tag"));
+                }
+                if let Some(element) = stack.pop() {
+                    push_node(&mut stack, Node::Element(element));
+                }
+            }
+            Event::Text(text) => {
+                let text = text.decode().map_err(|e| invalid(&e))?;
+                push_text(&mut stack, &text);
+            }
+            Event::CData(data) => {
+                let text = data.decode().map_err(|e| invalid(&e))?;
+                push_text(&mut stack, &text);
+            }
+            Event::GeneralRef(reference) => {
+                let text = match reference.resolve_char_ref().map_err(|e| invalid(&e))? {
+                    Some(ch) => ch.to_string(),
+                    None => {
+                        let name = reference.decode().map_err(|e| invalid(&e))?;
+                        resolve_predefined_entity(&name)
+                            .ok_or_else(|| invalid(&format!("unknown entity '&{name};'")))?
+                            .to_string()
+                    }
+                };
+                push_text(&mut stack, &text);
+            }
+            Event::Eof => break,
+            Event::Comment(_) | Event::Decl(_) | Event::PI(_) | Event::DocType(_) => {}
+        }
+    }
+
+    match stack.pop() {
+        Some(document) if stack.is_empty() && child_elements(&document).next().is_some() => {
+            Ok(document)
+        }
+        Some(_) if !stack.is_empty() => Err(invalid(&"unclosed element")),
+        _ => Err(invalid(&"no root element")),
+    }
+}
+
+fn start_element(start: &BytesStart) -> Result<Element> {
+    let mut attributes = Vec::new();
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|e| anyhow!("Invalid XML body: {e}"))?;
+        let name = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+        let value = attribute
+            .normalized_value(XmlVersion::default())
+            .map_err(|e| anyhow!("Invalid XML body: {e}"))?;
+        attributes.push((name, value.into_owned()));
+    }
+
+    Ok(Element {
+        name: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+        attributes,
+        children: Vec::new(),
+    })
+}
+
+fn push_node(stack: &mut [Element], node: Node) {
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(node);
+    }
+}
+
+fn push_text(stack: &mut [Element], text: &str) {
+    let Some(parent) = stack.last_mut() else {
+        return;
+    };
+    match parent.children.last_mut() {
+        Some(Node::Text(existing)) => existing.push_str(text),
+        _ => parent.children.push(Node::Text(text.to_string())),
+    }
+}
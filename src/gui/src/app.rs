@@ -356,15 +356,17 @@ impl HttpRunnerApp {
         // Load environment editor
         self.environment_editor.load_for_file(file);
 
-        // Try to find and parse http-client.env.json
+        // Tokens may have been obtained with the previous environment's settings
+        #[cfg(not(target_arch = "wasm32"))]
+        httprunner_core::oauth::clear_token_cache();
+
+        // Try to find and parse http-client.env.json and http-client.private.env.json
         if let Some(file_str) = file.to_str()
-            && let Ok(Some(env_file)) =
-                httprunner_core::environment::find_environment_file(file_str)
-            && let Ok(env_config) = httprunner_core::environment::parse_environment_file(&env_file)
+            && let Ok(environments) =
+                httprunner_core::environment::load_environment_names(file_str)
+            && !environments.is_empty()
         {
-            // Extract environment names from the config
-            self.environments = env_config.keys().cloned().collect();
-            self.environments.sort(); // Sort alphabetically for consistent UI
+            self.environments = environments;
             return;
         }
         // No environments found or error occurred
@@ -1,12 +1,21 @@
+use httprunner_core::environment::{
+    EncryptedValues, environment_value_from_str, environment_value_to_string,
+};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Environment editor component for viewing and editing http-client.env.json files
+/// and their http-client.private.env.json counterparts
 pub struct EnvironmentEditor {
     /// The full environment config: env_name -> { var_name -> var_value }
-    config: HashMap<String, HashMap<String, String>>,
+    config: HashMap<String, HashMap<String, Value>>,
+    /// Values from the private environment file, which take precedence
+    private_config: HashMap<String, HashMap<String, Value>>,
     /// Path to the environment file (None on WASM)
     env_file_path: Option<PathBuf>,
+    /// Path to the private environment file (None on WASM)
+    private_file_path: Option<PathBuf>,
     /// Whether config has unsaved changes
     has_changes: bool,
     /// Currently selected environment name for editing
@@ -17,49 +26,84 @@ pub struct EnvironmentEditor {
     new_var_name: String,
     /// New variable value input
     new_var_value: String,
+    /// Whether the new variable goes to the private file
+    new_var_private: bool,
     /// Status message to display
     status_message: Option<String>,
     /// Pending delete environment name (for confirmation)
     pending_delete_env: Option<String>,
+    /// Values stored encrypted, shown masked and encrypted again on save
+    encrypted: EncryptedValues,
 }
 
 impl EnvironmentEditor {
     pub fn new() -> Self {
         Self {
             config: HashMap::new(),
+            private_config: HashMap::new(),
             env_file_path: None,
+            private_file_path: None,
             has_changes: false,
             editing_environment: None,
             new_env_name: String::new(),
             new_var_name: String::new(),
             new_var_value: String::new(),
+            new_var_private: false,
             status_message: None,
             pending_delete_env: None,
+            encrypted: EncryptedValues::default(),
         }
     }
 
     /// Load environments from the env file associated with the given .http file path
     #[cfg(not(target_arch = "wasm32"))]
     pub fn load_for_file(&mut self, http_file: &Path) {
-        if let Some(file_str) = http_file.to_str()
-            && let Ok(Some(env_file)) =
-                httprunner_core::environment::find_environment_file(file_str)
-            && let Ok(config) = httprunner_core::environment::parse_environment_file(&env_file)
-        {
-            self.config = config;
-            self.env_file_path = Some(env_file);
-            self.has_changes = false;
-            self.status_message = None;
-        } else {
-            // No env file found — start fresh, will create on save
-            self.config = HashMap::new();
-            // Default to creating env file next to the .http file
-            if let Some(parent) = http_file.parent() {
-                self.env_file_path = Some(parent.join("http-client.env.json"));
+        use httprunner_core::environment::{
+            EnvironmentFiles, EnvironmentKey, find_environment_files,
+        };
+
+        let files = http_file
+            .to_str()
+            .and_then(|file_str| find_environment_files(file_str).ok().flatten())
+            // No env file found — start fresh, will create on save next to the .http file
+            .or_else(|| http_file.parent().map(EnvironmentFiles::in_dir));
+
+        self.config = HashMap::new();
+        self.private_config = HashMap::new();
+        self.env_file_path = None;
+        self.private_file_path = None;
+        self.status_message = None;
+        self.encrypted = EncryptedValues::default();
+
+        if let Some(files) = files {
+            let key = EnvironmentKey::resolve(files.public.parent()).unwrap_or_else(|e| {
+                self.status_message = Some(format!("Failed to load the environment key: {}", e));
+                None
+            });
+            self.encrypted = EncryptedValues::new(key);
+
+            for (path, config) in [
+                (&files.public, &mut self.config),
+                (&files.private, &mut self.private_config),
+            ] {
+                if !path.exists() {
+                    continue;
+                }
+                match httprunner_core::environment::parse_environment_file(path) {
+                    Ok(mut loaded) => {
+                        self.encrypted.decrypt(&mut loaded);
+                        *config = loaded;
+                    }
+                    Err(e) => {
+                        self.status_message =
+                            Some(format!("Failed to load {}: {}", path.display(), e));
+                    }
+                }
             }
-            self.has_changes = false;
-            self.status_message = None;
+            self.env_file_path = Some(files.public);
+            self.private_file_path = Some(files.private);
         }
+        self.has_changes = false;
     }
 
     /// Load environments from localStorage on WASM
@@ -76,7 +120,7 @@ impl EnvironmentEditor {
             && let Ok(Some(storage)) = window.local_storage()
             && let Ok(Some(json_str)) = storage.get_item("httprunner_env_config")
             && let Ok(config) =
-                serde_json::from_str::<HashMap<String, HashMap<String, String>>>(&json_str)
+                serde_json::from_str::<HashMap<String, HashMap<String, Value>>>(&json_str)
         {
             self.config = config;
         } else {
@@ -90,17 +134,38 @@ impl EnvironmentEditor {
     /// Save the current config
     #[cfg(not(target_arch = "wasm32"))]
     pub fn save(&mut self) {
-        if let Some(ref path) = self.env_file_path {
-            match httprunner_core::environment::save_environment_file(path, &self.config) {
-                Ok(()) => {
-                    self.has_changes = false;
-                    self.status_message = Some("Environment file saved".to_string());
-                }
-                Err(e) => {
-                    self.status_message = Some(format!("Failed to save: {}", e));
-                }
+        let (config, private_config) = match self
+            .encrypted
+            .encrypt(&self.config)
+            .and_then(|config| Ok((config, self.encrypted.encrypt(&self.private_config)?)))
+        {
+            Ok(configs) => configs,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to save: {}", e));
+                return;
             }
+        };
+
+        if let Some(ref path) = self.env_file_path
+            && let Err(e) = httprunner_core::environment::save_environment_file(path, &config)
+        {
+            self.status_message = Some(format!("Failed to save: {}", e));
+            return;
         }
+
+        // Only create the private file once it has something to hold
+        if let Some(ref path) = self.private_file_path
+            && (path.exists() || !private_config.is_empty())
+            && let Err(e) =
+                httprunner_core::environment::save_environment_file(path, &private_config)
+        {
+            self.status_message = Some(format!("Failed to save private file: {}", e));
+            return;
+        }
+
+        httprunner_core::oauth::clear_token_cache();
+        self.has_changes = false;
+        self.status_message = Some("Environment file saved".to_string());
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -126,13 +191,94 @@ impl EnvironmentEditor {
 
     /// Get environment names for use by the app (e.g. populating environment selector)
     pub fn environment_names(&self) -> Vec<String> {
-        let mut names: Vec<String> = self.config.keys().cloned().collect();
+        let mut names = self.all_environment_names();
+        names.retain(|name| name != httprunner_core::environment::SHARED_ENVIRONMENT);
+        names
+    }
+
+    /// All environments in either file, including `$shared`
+    fn all_environment_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .config
+            .keys()
+            .chain(self.private_config.keys())
+            .cloned()
+            .collect();
         names.sort();
+        names.dedup();
         names
     }
 
+    /// Variables of an environment with their effective value and whether
+    /// that value comes from the private file
+    fn environment_variables(&self, env_name: &str) -> Vec<(String, String, bool)> {
+        let mut vars: HashMap<String, (String, bool)> = HashMap::new();
+        for (config, private) in [(&self.config, false), (&self.private_config, true)] {
+            if let Some(env_vars) = config.get(env_name) {
+                for (name, value) in env_vars {
+                    vars.insert(name.clone(), (environment_value_to_string(value), private));
+                }
+            }
+        }
+
+        let mut sorted_vars: Vec<(String, String, bool)> = vars
+            .into_iter()
+            .map(|(name, (value, private))| (name, value, private))
+            .collect();
+        sorted_vars.sort_by(|a, b| a.0.cmp(&b.0));
+        sorted_vars
+    }
+
+    fn config_for(&mut self, private: bool) -> &mut HashMap<String, HashMap<String, Value>> {
+        if private && self.private_file_path.is_some() {
+            &mut self.private_config
+        } else {
+            &mut self.config
+        }
+    }
+
+    /// Store the text entered for a variable, keeping the type of a
+    /// non-string value when the text is still valid JSON
+    fn set_variable(&mut self, env_name: &str, name: String, value: String, private: bool) {
+        let env_vars = self
+            .config_for(private)
+            .entry(env_name.to_string())
+            .or_default();
+        let value = environment_value_from_str(&value, env_vars.get(&name));
+        env_vars.insert(name, value);
+        self.has_changes = true;
+        self.status_message = None;
+    }
+
+    /// Move a variable to the private file, or back to the public file
+    fn move_variable(&mut self, env_name: &str, name: String, to_private: bool) {
+        let Some(value) = self
+            .config_for(!to_private)
+            .get_mut(env_name)
+            .and_then(|env_vars| env_vars.remove(&name))
+        else {
+            return;
+        };
+        self.config_for(to_private)
+            .entry(env_name.to_string())
+            .or_default()
+            .insert(name, value);
+        self.has_changes = true;
+        self.status_message = None;
+    }
+
+    fn remove_variable(&mut self, env_name: &str, name: &str) {
+        for config in [&mut self.config, &mut self.private_config] {
+            if let Some(env_vars) = config.get_mut(env_name) {
+                env_vars.remove(name);
+            }
+        }
+        self.has_changes = true;
+        self.status_message = None;
+    }
+
     /// Get the full config (for WASM env variable resolution)
-    pub fn get_config(&self) -> &HashMap<String, HashMap<String, String>> {
+    pub fn get_config(&self) -> &HashMap<String, HashMap<String, Value>> {
         &self.config
     }
 
@@ -155,6 +301,14 @@ impl EnvironmentEditor {
                     .color(egui::Color32::GRAY),
             );
         }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(ref path) = self.private_file_path {
+            ui.label(
+                egui::RichText::new(format!("Private file: {}", path.display()))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+        }
 
         #[cfg(target_arch = "wasm32")]
         ui.label(
@@ -177,7 +331,7 @@ impl EnvironmentEditor {
             ui.text_edit_singleline(&mut self.new_env_name);
             if ui.button("➕ Add").clicked() && !self.new_env_name.trim().is_empty() {
                 let name = self.new_env_name.trim().to_string();
-                if !self.config.contains_key(&name) {
+                if !self.config.contains_key(&name) && !self.private_config.contains_key(&name) {
                     self.config.insert(name.clone(), HashMap::new());
                     self.editing_environment = Some(name);
                     self.has_changes = true;
@@ -190,7 +344,7 @@ impl EnvironmentEditor {
         ui.separator();
 
         // Environment tabs / selector
-        let env_names = self.environment_names();
+        let env_names = self.all_environment_names();
 
         if env_names.is_empty() {
             ui.label("No environments defined. Add one above.");
@@ -212,7 +366,7 @@ impl EnvironmentEditor {
 
         // Show variables for the selected environment
         if let Some(ref editing_env) = self.editing_environment.clone()
-            && let Some(vars) = self.config.get(editing_env).cloned()
+            && env_names.contains(editing_env)
         {
             ui.horizontal(|ui| {
                 ui.heading(egui::RichText::new(format!("📋 {}", editing_env)).size(16.0));
@@ -222,6 +376,7 @@ impl EnvironmentEditor {
                     ui.colored_label(egui::Color32::RED, "Delete this environment?");
                     if ui.button("Yes").clicked() {
                         self.config.remove(editing_env);
+                        self.private_config.remove(editing_env);
                         self.editing_environment = None;
                         self.pending_delete_env = None;
                         self.has_changes = true;
@@ -239,50 +394,78 @@ impl EnvironmentEditor {
 
             // Variable table
             let mut vars_to_remove: Vec<String> = Vec::new();
-            let mut vars_to_update: Vec<(String, String)> = Vec::new();
+            let mut vars_to_update: Vec<(String, String, bool)> = Vec::new();
+            let mut vars_to_move: Vec<(String, bool)> = Vec::new();
+            let has_private_file = self.private_file_path.is_some();
 
-            let mut sorted_vars: Vec<(String, String)> = vars.into_iter().collect();
-            sorted_vars.sort_by(|a, b| a.0.cmp(&b.0));
+            let sorted_vars = self.environment_variables(editing_env);
 
             egui::Grid::new("env_vars_grid")
                 .striped(true)
-                .num_columns(3)
-                .min_col_width(300.0)
+                .num_columns(4)
+                .min_col_width(100.0)
                 .show(ui, |ui| {
                     ui.strong("Variable");
                     ui.strong("Value");
+                    ui.strong("File");
                     ui.strong("");
                     ui.end_row();
 
-                    for (var_name, var_value) in &sorted_vars {
+                    for (var_name, var_value, private) in &sorted_vars {
                         ui.label(var_name);
 
+                        let encrypted = self.encrypted.is_encrypted(editing_env, var_name);
+                        let locked = self.encrypted.is_locked(editing_env, var_name);
                         let mut value = var_value.clone();
-                        let response =
-                            ui.add(egui::TextEdit::singleline(&mut value).desired_width(300.0));
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut value)
+                                .password(encrypted)
+                                .interactive(!locked)
+                                .desired_width(300.0),
+                        );
                         if response.changed() {
-                            vars_to_update.push((var_name.clone(), value));
+                            vars_to_update.push((var_name.clone(), value, *private));
                         }
 
-                        if ui.button("🗑").clicked() {
-                            vars_to_remove.push(var_name.clone());
+                        let file = if *private { "🔒 private" } else { "public" };
+                        if locked {
+                            ui.label(format!("{} 🔐", file))
+                                .on_hover_text("Encrypted, and no key is available to decrypt it");
+                        } else if encrypted {
+                            ui.label(format!("{} 🔐", file))
+                                .on_hover_text("Encrypted in the file and encrypted again on save");
+                        } else {
+                            ui.label(file);
                         }
+
+                        ui.horizontal(|ui| {
+                            if has_private_file {
+                                let (label, hover) = if *private {
+                                    ("🔓", "Move to the public environment file")
+                                } else {
+                                    ("🔒", "Move to the private environment file")
+                                };
+                                if ui.button(label).on_hover_text(hover).clicked() {
+                                    vars_to_move.push((var_name.clone(), !*private));
+                                }
+                            }
+                            if ui.button("🗑").clicked() {
+                                vars_to_remove.push(var_name.clone());
+                            }
+                        });
                         ui.end_row();
                     }
                 });
 
             // Apply updates
-            if let Some(env_vars) = self.config.get_mut(editing_env) {
-                for (name, value) in vars_to_update {
-                    env_vars.insert(name, value);
-                    self.has_changes = true;
-                    self.status_message = None;
-                }
-                for name in vars_to_remove {
-                    env_vars.remove(&name);
-                    self.has_changes = true;
-                    self.status_message = None;
-                }
+            for (name, value, private) in vars_to_update {
+                self.set_variable(editing_env, name, value, private);
+            }
+            for (name, to_private) in vars_to_move {
+                self.move_variable(editing_env, name, to_private);
+            }
+            for name in vars_to_remove {
+                self.remove_variable(editing_env, &name);
             }
 
             ui.separator();
@@ -293,17 +476,19 @@ impl EnvironmentEditor {
                 ui.text_edit_singleline(&mut self.new_var_name);
                 ui.label("Value:");
                 ui.text_edit_singleline(&mut self.new_var_value);
-                if ui.button("➕ Add Variable").clicked() && !self.new_var_name.trim().is_empty() {
-                    if let Some(env_vars) = self.config.get_mut(editing_env) {
-                        env_vars.insert(
-                            self.new_var_name.trim().to_string(),
-                            self.new_var_value.clone(),
+                if self.private_file_path.is_some() {
+                    ui.checkbox(&mut self.new_var_private, "🔒 Private")
+                        .on_hover_text(
+                            "Store in the private environment file. Secret-looking names such as passwords, tokens and API keys always are.",
                         );
-                        self.has_changes = true;
-                        self.status_message = None;
-                    }
+                }
+                if ui.button("➕ Add Variable").clicked() && !self.new_var_name.trim().is_empty() {
+                    let name = self.new_var_name.trim().to_string();
+                    let private = self.new_var_private
+                        || httprunner_core::environment::is_secret_variable(&name);
+                    let value = std::mem::take(&mut self.new_var_value);
+                    self.set_variable(editing_env, name, value, private);
                     self.new_var_name.clear();
-                    self.new_var_value.clear();
                 }
             });
         }
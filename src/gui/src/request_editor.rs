@@ -16,6 +16,21 @@ pub struct EditableRequest {
     pub conditions: Vec<httprunner_core::types::Condition>,
     pub pre_delay_ms: Option<u64>,
     pub post_delay_ms: Option<u64>,
+    pub data: Option<httprunner_core::types::DataSource>,
+    pub foreach: Option<httprunner_core::types::ForEach>,
+    pub paginate: Option<httprunner_core::types::Pagination>,
+    pub auth: Option<httprunner_core::types::Auth>,
+    pub sign: Option<httprunner_core::types::Signing>,
+    pub content_digest: Option<httprunner_core::types::ContentDigest>,
+    pub captures: Vec<httprunner_core::types::Capture>,
+    pub imports: Vec<httprunner_core::types::Import>,
+    pub run: Option<httprunner_core::types::Run>,
+    pub hook: Option<httprunner_core::types::RequestHook>,
+    pub tags: Vec<String>,
+    pub secrets: Vec<String>,
+    pub resolved_secrets: Vec<String>,
+    pub strict_variables: bool,
+    pub unresolved_variables: Vec<httprunner_core::types::UnresolvedVariable>,
 }
 
 impl Default for EditableRequest {
@@ -34,6 +49,21 @@ impl Default for EditableRequest {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            data: None,
+            foreach: None,
+            paginate: None,
+            auth: None,
+            sign: None,
+            content_digest: None,
+            captures: vec![],
+            imports: vec![],
+            run: None,
+            hook: None,
+            tags: Vec::new(),
+            secrets: vec![],
+            resolved_secrets: vec![],
+            strict_variables: false,
+            unresolved_variables: vec![],
         }
     }
 }
@@ -61,6 +91,21 @@ impl From<&httprunner_core::HttpRequest> for EditableRequest {
             conditions: request.conditions.clone(),
             pre_delay_ms: request.pre_delay_ms,
             post_delay_ms: request.post_delay_ms,
+            data: request.data.clone(),
+            foreach: request.foreach.clone(),
+            paginate: request.paginate.clone(),
+            auth: request.auth.clone(),
+            sign: request.sign.clone(),
+            content_digest: request.content_digest,
+            captures: request.captures.clone(),
+            imports: request.imports.clone(),
+            run: request.run.clone(),
+            hook: request.hook,
+            tags: request.tags.clone(),
+            secrets: request.secrets.clone(),
+            resolved_secrets: request.resolved_secrets.clone(),
+            strict_variables: request.strict_variables,
+            unresolved_variables: request.unresolved_variables.clone(),
         }
     }
 }
@@ -102,6 +147,21 @@ impl EditableRequest {
             conditions: self.conditions.clone(),
             pre_delay_ms: self.pre_delay_ms,
             post_delay_ms: self.post_delay_ms,
+            data: self.data.clone(),
+            foreach: self.foreach.clone(),
+            paginate: self.paginate.clone(),
+            auth: self.auth.clone(),
+            sign: self.sign.clone(),
+            content_digest: self.content_digest,
+            captures: self.captures.clone(),
+            imports: self.imports.clone(),
+            run: self.run.clone(),
+            hook: self.hook,
+            tags: self.tags.clone(),
+            secrets: self.secrets.clone(),
+            resolved_secrets: self.resolved_secrets.clone(),
+            strict_variables: self.strict_variables,
+            unresolved_variables: self.unresolved_variables.clone(),
         }
     }
 
@@ -262,6 +322,7 @@ mod tests {
             }],
             pre_delay_ms: Some(100),
             post_delay_ms: Some(200),
+            ..Default::default()
         };
 
         let editable = EditableRequest::from(&request);
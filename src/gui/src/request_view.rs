@@ -1,4 +1,5 @@
 use crate::request_editor::RequestEditor;
+use httprunner_core::selection::tags_of;
 use std::path::{Path, PathBuf};
 
 pub enum RequestViewAction {
@@ -9,17 +10,20 @@ pub enum RequestViewAction {
 
 pub struct RequestView {
     editor: RequestEditor,
+    tag_filter: Option<String>,
 }
 
 impl RequestView {
     pub fn new() -> Self {
         Self {
             editor: RequestEditor::new(),
+            tag_filter: None,
         }
     }
 
     pub fn load_file(&mut self, path: &Path) {
         self.editor.load_file(path);
+        self.tag_filter = None;
     }
 
     pub fn show(&mut self, ui: &mut egui::Ui, file: &Option<PathBuf>) -> RequestViewAction {
@@ -50,8 +54,30 @@ impl RequestView {
             return action;
         }
 
+        let tags = tags_of(&requests);
+        if !tags.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label("Tag:");
+                egui::ComboBox::from_id_salt("tag_filter_combo")
+                    .selected_text(self.tag_filter.as_deref().unwrap_or("All requests"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.tag_filter, None, "All requests");
+                        for tag in &tags {
+                            ui.selectable_value(&mut self.tag_filter, Some(tag.clone()), tag);
+                        }
+                    });
+            });
+            ui.separator();
+        }
+
         // Show list of requests
         for (idx, request) in requests.iter().enumerate() {
+            if let Some(tag) = &self.tag_filter
+                && !request.tags.contains(tag)
+            {
+                continue;
+            }
+
             let header_text = if let Some(name) = &request.name {
                 format!("{} - {} {}", idx + 1, request.method, name)
             } else {
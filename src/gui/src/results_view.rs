@@ -922,6 +922,7 @@ mod tests {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         }
     }
 
@@ -935,6 +936,7 @@ mod tests {
             response_headers: None,
             response_body: None,
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }
     }
 
@@ -283,14 +283,15 @@ impl App {
         // Load environment editor
         self.environment_editor.load_for_file(file);
 
+        // Tokens may have been obtained with the previous environment's settings
+        httprunner_core::oauth::clear_token_cache();
+
         if let Some(file_str) = file.to_str()
-            && let Ok(Some(env_file)) =
-                httprunner_core::environment::find_environment_file(file_str)
+            && let Ok(Some(_)) = httprunner_core::environment::find_environment_files(file_str)
         {
-            if let Ok(env_config) = httprunner_core::environment::parse_environment_file(&env_file)
+            if let Ok(environments) = httprunner_core::environment::load_environment_names(file_str)
             {
-                self.environments = env_config.keys().cloned().collect();
-                self.environments.sort();
+                self.environments = environments;
                 self.status_message = format!("Loaded {} environments", self.environments.len());
                 return;
             }
@@ -1,4 +1,8 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use httprunner_core::environment::{
+    EncryptedValues, environment_value_from_str, environment_value_to_string,
+};
+use serde_json::Value;
 use std::cell::Cell;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -25,9 +29,13 @@ enum InputMode {
 
 pub struct EnvironmentEditor {
     /// The full environment config: env_name -> { var_name -> var_value }
-    config: HashMap<String, HashMap<String, String>>,
+    config: HashMap<String, HashMap<String, Value>>,
+    /// Values from the private environment file, which take precedence
+    private_config: HashMap<String, HashMap<String, Value>>,
     /// Path to the environment file
     env_file_path: Option<PathBuf>,
+    /// Path to the private environment file
+    private_file_path: Option<PathBuf>,
     /// Whether config has unsaved changes
     has_changes: bool,
     /// Sorted list of environment names
@@ -52,13 +60,17 @@ pub struct EnvironmentEditor {
     scroll_offset: usize,
     /// Visible height of the editor (set during rendering)
     visible_height: Cell<usize>,
+    /// Values stored encrypted, shown masked and encrypted again on save
+    encrypted: EncryptedValues,
 }
 
 impl EnvironmentEditor {
     pub fn new() -> Self {
         Self {
             config: HashMap::new(),
+            private_config: HashMap::new(),
             env_file_path: None,
+            private_file_path: None,
             has_changes: false,
             env_names: Vec::new(),
             selected_env_index: 0,
@@ -71,48 +83,84 @@ impl EnvironmentEditor {
             status_message: None,
             scroll_offset: 0,
             visible_height: Cell::new(0),
+            encrypted: EncryptedValues::default(),
         }
     }
 
     /// Load environments from the env file associated with the given .http file path
     pub fn load_for_file(&mut self, http_file: &Path) {
-        if let Some(file_str) = http_file.to_str()
-            && let Ok(Some(env_file)) =
-                httprunner_core::environment::find_environment_file(file_str)
-            && let Ok(config) = httprunner_core::environment::parse_environment_file(&env_file)
-        {
-            self.config = config;
-            self.env_file_path = Some(env_file);
-        } else {
-            self.config = HashMap::new();
-            if let Some(parent) = http_file.parent() {
-                self.env_file_path = Some(parent.join("http-client.env.json"));
+        use httprunner_core::environment::{
+            EnvironmentFiles, EnvironmentKey, find_environment_files,
+        };
+
+        let files = http_file
+            .to_str()
+            .and_then(|file_str| find_environment_files(file_str).ok().flatten())
+            .or_else(|| http_file.parent().map(EnvironmentFiles::in_dir));
+
+        self.config = HashMap::new();
+        self.private_config = HashMap::new();
+        self.env_file_path = None;
+        self.private_file_path = None;
+        self.status_message = None;
+        self.encrypted = EncryptedValues::default();
+
+        if let Some(files) = files {
+            let key = EnvironmentKey::resolve(files.public.parent()).unwrap_or_else(|e| {
+                self.status_message = Some(format!("Failed to load the environment key: {}", e));
+                None
+            });
+            self.encrypted = EncryptedValues::new(key);
+            for (path, config) in [
+                (&files.public, &mut self.config),
+                (&files.private, &mut self.private_config),
+            ] {
+                if !path.exists() {
+                    continue;
+                }
+                match httprunner_core::environment::parse_environment_file(path) {
+                    Ok(mut loaded) => {
+                        self.encrypted.decrypt(&mut loaded);
+                        *config = loaded;
+                    }
+                    Err(e) => {
+                        self.status_message =
+                            Some(format!("Failed to load {}: {}", path.display(), e));
+                    }
+                }
             }
+            self.env_file_path = Some(files.public);
+            self.private_file_path = Some(files.private);
         }
         self.has_changes = false;
         self.refresh_env_names();
         self.refresh_var_names();
-        self.status_message = None;
     }
 
     fn refresh_env_names(&mut self) {
-        self.env_names = self.config.keys().cloned().collect();
+        self.env_names = self
+            .config
+            .keys()
+            .chain(self.private_config.keys())
+            .cloned()
+            .collect();
         self.env_names.sort();
+        self.env_names.dedup();
         if self.selected_env_index >= self.env_names.len() {
             self.selected_env_index = self.env_names.len().saturating_sub(1);
         }
     }
 
     fn refresh_var_names(&mut self) {
+        self.var_names.clear();
         if let Some(env_name) = self.env_names.get(self.selected_env_index) {
-            if let Some(vars) = self.config.get(env_name) {
-                self.var_names = vars.keys().cloned().collect();
-                self.var_names.sort();
-            } else {
-                self.var_names.clear();
+            for config in [&self.config, &self.private_config] {
+                if let Some(vars) = config.get(env_name) {
+                    self.var_names.extend(vars.keys().cloned());
+                }
             }
-        } else {
-            self.var_names.clear();
+            self.var_names.sort();
+            self.var_names.dedup();
         }
         if self.selected_var_index >= self.var_names.len() {
             self.selected_var_index = self.var_names.len().saturating_sub(1);
@@ -121,22 +169,88 @@ impl EnvironmentEditor {
 
     /// Save the current config to file
     pub fn save(&mut self) {
-        if let Some(ref path) = self.env_file_path {
-            match httprunner_core::environment::save_environment_file(path, &self.config) {
-                Ok(()) => {
-                    self.has_changes = false;
-                    self.status_message = Some("Environment file saved".to_string());
-                }
-                Err(e) => {
-                    self.status_message = Some(format!("Failed to save: {}", e));
-                }
+        let (config, private_config) = match self
+            .encrypted
+            .encrypt(&self.config)
+            .and_then(|config| Ok((config, self.encrypted.encrypt(&self.private_config)?)))
+        {
+            Ok(configs) => configs,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to save: {}", e));
+                return;
             }
+        };
+
+        if let Some(ref path) = self.env_file_path
+            && let Err(e) = httprunner_core::environment::save_environment_file(path, &config)
+        {
+            self.status_message = Some(format!("Failed to save: {}", e));
+            return;
         }
+
+        // Only create the private file once it has something to hold
+        if let Some(ref path) = self.private_file_path
+            && (path.exists() || !private_config.is_empty())
+            && let Err(e) =
+                httprunner_core::environment::save_environment_file(path, &private_config)
+        {
+            self.status_message = Some(format!("Failed to save private file: {}", e));
+            return;
+        }
+
+        httprunner_core::oauth::clear_token_cache();
+        self.has_changes = false;
+        self.status_message = Some("Environment file saved".to_string());
     }
 
-    /// Get environment names
+    /// Get environment names for the environment selector, without `$shared`
     pub fn environment_names(&self) -> Vec<String> {
-        self.env_names.clone()
+        self.env_names
+            .iter()
+            .filter(|name| *name != httprunner_core::environment::SHARED_ENVIRONMENT)
+            .cloned()
+            .collect()
+    }
+
+    fn config_for(&mut self, private: bool) -> &mut HashMap<String, HashMap<String, Value>> {
+        if private && self.private_file_path.is_some() {
+            &mut self.private_config
+        } else {
+            &mut self.config
+        }
+    }
+
+    /// Store the text entered for a variable in the private or the public
+    /// file, keeping the type of a non-string value when the text is still
+    /// valid JSON
+    fn set_variable(&mut self, env_name: &str, name: String, value: String, private: bool) {
+        let vars = self
+            .config_for(private)
+            .entry(env_name.to_string())
+            .or_default();
+        let value = environment_value_from_str(&value, vars.get(&name));
+        self.store_variable(env_name, name, value, private);
+    }
+
+    fn store_variable(&mut self, env_name: &str, name: String, value: Value, private: bool) {
+        self.config_for(private)
+            .entry(env_name.to_string())
+            .or_default()
+            .insert(name, value);
+        self.has_changes = true;
+    }
+
+    /// Remove a variable from both files
+    fn remove_variable(&mut self, env_name: &str, name: &str) -> Option<Value> {
+        let private_value = self
+            .private_config
+            .get_mut(env_name)
+            .and_then(|vars| vars.remove(name));
+        let public_value = self
+            .config
+            .get_mut(env_name)
+            .and_then(|vars| vars.remove(name));
+        private_value.or(public_value)
     }
 
     /// Check if there are unsaved changes
@@ -172,11 +286,37 @@ impl EnvironmentEditor {
         self.selected_var_index
     }
 
-    pub fn get_var_value(&self, env_name: &str, var_name: &str) -> Option<&str> {
-        self.config
+    pub fn get_var_value(&self, env_name: &str, var_name: &str) -> Option<String> {
+        self.private_config
             .get(env_name)
             .and_then(|vars| vars.get(var_name))
-            .map(|s| s.as_str())
+            .or_else(|| {
+                self.config
+                    .get(env_name)
+                    .and_then(|vars| vars.get(var_name))
+            })
+            .map(environment_value_to_string)
+    }
+
+    /// Whether the value of a variable comes from the private file
+    pub fn is_private_var(&self, env_name: &str, var_name: &str) -> bool {
+        self.private_config
+            .get(env_name)
+            .is_some_and(|vars| vars.contains_key(var_name))
+    }
+
+    /// Whether the value of a variable is stored encrypted, so it is shown
+    /// masked
+    pub fn is_encrypted_var(&self, env_name: &str, var_name: &str) -> bool {
+        self.encrypted.is_encrypted(env_name, var_name)
+    }
+
+    /// Whether the input buffer holds a new value for an encrypted variable
+    pub fn is_secret_input(&self) -> bool {
+        self.input_mode == InputMode::EditVariableValue
+            && self
+                .selected_env_name()
+                .is_some_and(|env_name| self.is_encrypted_var(env_name, &self.pending_var_name))
     }
 
     pub fn is_env_list_focused(&self) -> bool {
@@ -199,6 +339,9 @@ impl EnvironmentEditor {
             InputMode::NewVariableName => "New variable name: ",
             InputMode::NewVariableValue => "Variable value: ",
             InputMode::EditVariableName => "Rename variable: ",
+            InputMode::EditVariableValue if self.is_secret_input() => {
+                "New encrypted value (empty keeps it): "
+            }
             InputMode::EditVariableValue => "Edit value: ",
         }
     }
@@ -401,12 +544,35 @@ impl EnvironmentEditor {
                     && let Some(var_name) = self.var_names.get(self.selected_var_index)
                     && let Some(current_value) = self.get_var_value(env_name, var_name)
                 {
-                    self.input_buffer = current_value.to_string();
+                    // Encrypted values are entered anew rather than shown
+                    self.input_buffer = if self.is_encrypted_var(env_name, var_name) {
+                        String::new()
+                    } else {
+                        current_value
+                    };
                     self.pending_var_name = var_name.clone();
                     self.input_mode = InputMode::EditVariableValue;
                     self.focus = EditorFocus::Input;
                 }
             }
+            // Move variable between the public and the private file
+            (KeyCode::Char('p'), KeyModifiers::NONE)
+                if self.focus == EditorFocus::VariableList && self.private_file_path.is_some() =>
+            {
+                if let Some(env_name) = self.env_names.get(self.selected_env_index).cloned()
+                    && let Some(var_name) = self.var_names.get(self.selected_var_index).cloned()
+                {
+                    let to_private = !self.is_private_var(&env_name, &var_name);
+                    if let Some(value) = self.remove_variable(&env_name, &var_name) {
+                        self.store_variable(&env_name, var_name.clone(), value, to_private);
+                        let file = if to_private { "private" } else { "public" };
+                        self.status_message = Some(format!(
+                            "Moved variable '{}' to the {} file",
+                            var_name, file
+                        ));
+                    }
+                }
+            }
             // Rename environment or variable name
             (KeyCode::Char('r'), KeyModifiers::NONE) => match self.focus {
                 EditorFocus::EnvironmentList => {
@@ -434,6 +600,7 @@ impl EnvironmentEditor {
                 EditorFocus::EnvironmentList => {
                     if let Some(env_name) = self.env_names.get(self.selected_env_index).cloned() {
                         self.config.remove(&env_name);
+                        self.private_config.remove(&env_name);
                         self.has_changes = true;
                         self.refresh_env_names();
                         self.refresh_var_names();
@@ -443,9 +610,8 @@ impl EnvironmentEditor {
                 EditorFocus::VariableList => {
                     if let Some(env_name) = self.env_names.get(self.selected_env_index).cloned()
                         && let Some(var_name) = self.var_names.get(self.selected_var_index).cloned()
-                        && let Some(vars) = self.config.get_mut(&env_name)
+                        && self.remove_variable(&env_name, &var_name).is_some()
                     {
-                        vars.remove(&var_name);
                         self.has_changes = true;
                         self.refresh_var_names();
                         self.status_message = Some(format!("Deleted variable '{}'", var_name));
@@ -469,7 +635,10 @@ impl EnvironmentEditor {
                 let value = self.input_buffer.trim().to_string();
                 match self.input_mode {
                     InputMode::NewEnvironment => {
-                        if !value.is_empty() && !self.config.contains_key(&value) {
+                        if !value.is_empty()
+                            && !self.config.contains_key(&value)
+                            && !self.private_config.contains_key(&value)
+                        {
                             self.config.insert(value.clone(), HashMap::new());
                             self.has_changes = true;
                             self.refresh_env_names();
@@ -486,10 +655,15 @@ impl EnvironmentEditor {
                         let old_name = self.pending_var_name.clone();
                         if !value.is_empty()
                             && value != old_name
-                            && !self.config.contains_key(&value)
-                            && let Some(vars) = self.config.remove(&old_name)
+                            && !self.env_names.contains(&value)
+                            && self.env_names.contains(&old_name)
                         {
-                            self.config.insert(value.clone(), vars);
+                            for config in [&mut self.config, &mut self.private_config] {
+                                if let Some(vars) = config.remove(&old_name) {
+                                    config.insert(value.clone(), vars);
+                                }
+                            }
+                            self.encrypted.rename_environment(&old_name, &value);
                             self.has_changes = true;
                             self.refresh_env_names();
                             if let Some(idx) = self.env_names.iter().position(|e| e == &value) {
@@ -512,10 +686,11 @@ impl EnvironmentEditor {
                     }
                     InputMode::NewVariableValue => {
                         if let Some(env_name) = self.env_names.get(self.selected_env_index).cloned()
-                            && let Some(vars) = self.config.get_mut(&env_name)
                         {
-                            vars.insert(self.pending_var_name.clone(), value);
-                            self.has_changes = true;
+                            // Secrets go to the private file; 'p' moves a variable between files
+                            let name = self.pending_var_name.clone();
+                            let private = httprunner_core::environment::is_secret_variable(&name);
+                            self.set_variable(&env_name, name, value, private);
                             self.refresh_var_names();
                             self.status_message =
                                 Some(format!("Added variable '{}'", self.pending_var_name));
@@ -529,10 +704,15 @@ impl EnvironmentEditor {
                             && value != old_name
                             && let Some(env_name) =
                                 self.env_names.get(self.selected_env_index).cloned()
-                            && let Some(vars) = self.config.get_mut(&env_name)
-                            && let Some(old_value) = vars.remove(&old_name)
                         {
-                            vars.insert(value.clone(), old_value);
+                            for config in [&mut self.config, &mut self.private_config] {
+                                if let Some(vars) = config.get_mut(&env_name)
+                                    && let Some(old_value) = vars.remove(&old_name)
+                                {
+                                    vars.insert(value.clone(), old_value);
+                                }
+                            }
+                            self.encrypted.rename_variable(&env_name, &old_name, &value);
                             self.has_changes = true;
                             self.refresh_var_names();
                             if let Some(idx) = self.var_names.iter().position(|v| v == &value) {
@@ -545,11 +725,15 @@ impl EnvironmentEditor {
                         self.focus = EditorFocus::VariableList;
                     }
                     InputMode::EditVariableValue => {
+                        // An empty entry keeps an encrypted value unchanged
                         if let Some(env_name) = self.env_names.get(self.selected_env_index).cloned()
-                            && let Some(vars) = self.config.get_mut(&env_name)
+                            && !(value.is_empty()
+                                && self.is_encrypted_var(&env_name, &self.pending_var_name))
                         {
-                            vars.insert(self.pending_var_name.clone(), value);
-                            self.has_changes = true;
+                            // Update the value in the file it came from
+                            let name = self.pending_var_name.clone();
+                            let private = self.is_private_var(&env_name, &name);
+                            self.set_variable(&env_name, name, value, private);
                             self.status_message =
                                 Some(format!("Updated variable '{}'", self.pending_var_name));
                         }
@@ -573,3 +757,159 @@ impl EnvironmentEditor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(editor: &mut EnvironmentEditor, text: &str) {
+        for c in text.chars() {
+            editor.handle_key_event(key(KeyCode::Char(c)));
+        }
+        editor.handle_key_event(key(KeyCode::Enter));
+    }
+
+    #[test]
+    fn private_file_values_are_shown_and_secrets_are_saved_there() {
+        let dir = std::env::temp_dir().join(format!("httprunner-tui-env-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("http-client.env.json"),
+            r#"{"$shared":{"VERSION":"v1"},"dev":{"HOST":"https://dev","TOKEN":""}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("http-client.private.env.json"),
+            r#"{"dev":{"TOKEN":"real-token"}}"#,
+        )
+        .unwrap();
+
+        let mut editor = EnvironmentEditor::new();
+        editor.load_for_file(&dir.join("request.http"));
+
+        assert_eq!(editor.env_names(), ["$shared", "dev"]);
+        assert_eq!(editor.environment_names(), ["dev"]);
+        editor.handle_key_event(key(KeyCode::Down));
+        assert_eq!(
+            editor.get_var_value("dev", "TOKEN").as_deref(),
+            Some("real-token")
+        );
+        assert!(editor.is_private_var("dev", "TOKEN"));
+        assert!(!editor.is_private_var("dev", "HOST"));
+
+        // New secrets go to the private file
+        editor.handle_key_event(key(KeyCode::Tab));
+        editor.handle_key_event(key(KeyCode::Char('a')));
+        type_text(&mut editor, "ApiKey");
+        type_text(&mut editor, "abc");
+        assert!(editor.is_private_var("dev", "ApiKey"));
+
+        // 'p' moves the selected variable (HOST) to the private file
+        editor.handle_key_event(key(KeyCode::Down));
+        assert_eq!(editor.var_names()[editor.selected_var_index()], "HOST");
+        editor.handle_key_event(key(KeyCode::Char('p')));
+        assert!(editor.is_private_var("dev", "HOST"));
+
+        editor.save();
+        let public = fs::read_to_string(dir.join("http-client.env.json")).unwrap();
+        let private = fs::read_to_string(dir.join("http-client.private.env.json")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!public.contains("ApiKey") && !public.contains("https://dev"));
+        assert!(public.contains("\"TOKEN\": \"\""));
+        assert!(private.contains("\"ApiKey\": \"abc\""));
+        assert!(private.contains("\"HOST\": \"https://dev\""));
+        assert!(private.contains("\"TOKEN\": \"real-token\""));
+    }
+
+    #[test]
+    fn editing_a_typed_value_keeps_its_type() {
+        let dir =
+            std::env::temp_dir().join(format!("httprunner-tui-env-typed-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("http-client.env.json"),
+            r#"{"dev":{"PORT":8080,"TENANT":{"id":5}}}"#,
+        )
+        .unwrap();
+
+        let mut editor = EnvironmentEditor::new();
+        editor.load_for_file(&dir.join("request.http"));
+        assert_eq!(
+            editor.get_var_value("dev", "TENANT").as_deref(),
+            Some(r#"{"id":5}"#)
+        );
+
+        editor.handle_key_event(key(KeyCode::Tab));
+        assert_eq!(editor.var_names()[editor.selected_var_index()], "PORT");
+        editor.handle_key_event(key(KeyCode::Char('e')));
+        for _ in 0..4 {
+            editor.handle_key_event(key(KeyCode::Backspace));
+        }
+        type_text(&mut editor, "9090");
+
+        editor.save();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("http-client.env.json")).unwrap())
+                .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            saved,
+            serde_json::json!({"dev": {"PORT": 9090, "TENANT": {"id": 5}}})
+        );
+    }
+
+    #[test]
+    fn encrypted_values_are_masked_and_encrypted_again_on_save() {
+        use httprunner_core::environment::{ENVIRONMENT_KEY_FILE, EnvironmentKey};
+
+        let dir = std::env::temp_dir().join(format!(
+            "httprunner-tui-env-encrypted-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let secret = EnvironmentKey::generate();
+        secret
+            .write_key_file(&dir.join(ENVIRONMENT_KEY_FILE))
+            .unwrap();
+        let api_key = secret.encrypt("old-key");
+        let password = secret.encrypt("hunter2");
+        fs::write(
+            dir.join("http-client.env.json"),
+            serde_json::json!({"dev": {"apiKey": api_key, "password": password}}).to_string(),
+        )
+        .unwrap();
+
+        let mut editor = EnvironmentEditor::new();
+        editor.load_for_file(&dir.join("request.http"));
+        assert!(editor.is_encrypted_var("dev", "apiKey"));
+        assert_eq!(
+            editor.get_var_value("dev", "apiKey").as_deref(),
+            Some("old-key")
+        );
+
+        // Editing starts from an empty, masked buffer
+        editor.handle_key_event(key(KeyCode::Tab));
+        editor.handle_key_event(key(KeyCode::Char('e')));
+        assert_eq!(editor.input_buffer(), "");
+        assert!(editor.is_secret_input());
+        type_text(&mut editor, "new-key");
+
+        editor.save();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("http-client.env.json")).unwrap())
+                .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let saved_api_key = saved["dev"]["apiKey"].as_str().unwrap();
+        assert_ne!(saved_api_key, api_key);
+        assert_eq!(secret.decrypt(saved_api_key).unwrap(), "new-key");
+        assert_eq!(saved["dev"]["password"], serde_json::json!(password));
+    }
+}
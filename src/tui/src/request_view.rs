@@ -1,10 +1,13 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use httprunner_core::parser::parse_http_file;
+use httprunner_core::selection::tags_of;
 use httprunner_core::types::HttpRequest;
 use std::path::Path;
 
 pub struct RequestView {
+    all_requests: Vec<HttpRequest>,
     requests: Vec<HttpRequest>,
+    tag_filter: Option<String>,
     selected_index: usize,
     run_request: bool,
     error_message: Option<String>,
@@ -13,7 +16,9 @@ pub struct RequestView {
 impl RequestView {
     pub fn new() -> Self {
         Self {
+            all_requests: Vec::new(),
             requests: Vec::new(),
+            tag_filter: None,
             selected_index: 0,
             run_request: false,
             error_message: None,
@@ -25,10 +30,12 @@ impl RequestView {
         if let Some(path_str) = path.to_str() {
             match parse_http_file(path_str, None) {
                 Ok(requests) => {
-                    self.requests = requests;
-                    self.selected_index = 0;
+                    self.all_requests = requests;
+                    self.tag_filter = None;
+                    self.apply_tag_filter();
                 }
                 Err(e) => {
+                    self.all_requests.clear();
                     self.requests.clear();
                     self.error_message = Some(format!("Failed to parse file: {}", e));
                 }
@@ -53,10 +60,45 @@ impl RequestView {
             KeyCode::Enter => {
                 self.run_request = true;
             }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                self.cycle_tag_filter();
+            }
             _ => {}
         }
     }
 
+    /// Show only the requests with the next tag of the file, or all requests
+    /// after the last tag.
+    fn cycle_tag_filter(&mut self) {
+        let tags = tags_of(&self.all_requests);
+        let next = match &self.tag_filter {
+            None => 0,
+            Some(current) => tags
+                .iter()
+                .position(|tag| tag == current)
+                .map_or(0, |i| i + 1),
+        };
+        self.tag_filter = tags.get(next).cloned();
+        self.apply_tag_filter();
+    }
+
+    fn apply_tag_filter(&mut self) {
+        self.requests = match &self.tag_filter {
+            Some(tag) => self
+                .all_requests
+                .iter()
+                .filter(|request| request.tags.contains(tag))
+                .cloned()
+                .collect(),
+            None => self.all_requests.clone(),
+        };
+        self.selected_index = 0;
+    }
+
+    pub fn tag_filter(&self) -> Option<&str> {
+        self.tag_filter.as_deref()
+    }
+
     pub fn requests(&self) -> &[HttpRequest] {
         &self.requests
     }
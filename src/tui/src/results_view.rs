@@ -242,6 +242,7 @@ mod tests {
             conditions: vec![],
             pre_delay_ms: None,
             post_delay_ms: None,
+            ..Default::default()
         }
     }
 
@@ -255,6 +256,7 @@ mod tests {
             response_headers: None,
             response_body: None,
             assertion_results: vec![],
+            captured_variables: Vec::new(),
         }
     }
 
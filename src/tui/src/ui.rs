@@ -235,10 +235,18 @@ fn render_request_view(f: &mut Frame, area: Rect, app: &App) {
         })
         .collect();
 
+    let title = match app.request_view.tag_filter() {
+        Some(tag) => format!(
+            "Requests ({}) tagged {} [T to change tag]",
+            requests.len(),
+            tag
+        ),
+        None => format!("Requests ({}) [R/F5 to run all]", requests.len()),
+    };
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title(format!("Requests ({}) [R/F5 to run all]", requests.len()))
+            .title(title)
             .border_style(border_style),
     );
 
@@ -669,6 +677,8 @@ fn render_environment_editor(f: &mut Frame, area: Rect, app: &App) {
         Span::raw(" Rename | "),
         Span::styled("d", Style::default().fg(Color::Yellow)),
         Span::raw(" Delete | "),
+        Span::styled("p", Style::default().fg(Color::Yellow)),
+        Span::raw(" Private | "),
         Span::styled("Ctrl+S", Style::default().fg(Color::Yellow)),
         Span::raw(" Save"),
     ]));
@@ -749,12 +759,29 @@ fn render_environment_editor(f: &mut Frame, area: Rect, app: &App) {
                         Style::default()
                     };
 
-                    let value = editor.get_var_value(selected_env, var_name).unwrap_or("");
+                    let encrypted = editor.is_encrypted_var(selected_env, var_name);
+                    let value = if encrypted {
+                        "••••••••".to_string()
+                    } else {
+                        editor
+                            .get_var_value(selected_env, var_name)
+                            .unwrap_or_default()
+                    };
+                    let mut file = if editor.is_private_var(selected_env, var_name) {
+                        " [private]"
+                    } else {
+                        ""
+                    }
+                    .to_string();
+                    if encrypted {
+                        file.push_str(" [encrypted]");
+                    }
 
                     lines.push(Line::from(vec![
                         Span::raw(marker),
                         Span::styled(format!("{}: ", var_name), style),
                         Span::styled(value, Style::default().fg(Color::Cyan)),
+                        Span::styled(file, Style::default().fg(Color::Magenta)),
                     ]));
                 }
             }
@@ -766,7 +793,14 @@ fn render_environment_editor(f: &mut Frame, area: Rect, app: &App) {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
             Span::styled(editor.input_prompt(), Style::default().fg(Color::Yellow)),
-            Span::styled(editor.input_buffer(), Style::default().fg(Color::White)),
+            Span::styled(
+                if editor.is_secret_input() {
+                    "•".repeat(editor.input_buffer().chars().count())
+                } else {
+                    editor.input_buffer().to_string()
+                },
+                Style::default().fg(Color::White),
+            ),
             Span::styled("▌", Style::default().fg(Color::White)),
         ]));
     }
@@ -858,7 +892,9 @@ fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
             Span::styled("[/]", Style::default().fg(Color::Yellow)),
             Span::raw(" Delay | "),
             Span::styled("F", Style::default().fg(Color::Yellow)),
-            Span::raw(" Fail-fast"),
+            Span::raw(" Fail-fast | "),
+            Span::styled("T", Style::default().fg(Color::Yellow)),
+            Span::raw(" Tag"),
         ]),
     ];
 